@@ -12,7 +12,9 @@ use drop_staking_base::error::astroport_exchange_handler::{ContractError, Contra
 use drop_staking_base::msg::astroport_exchange_handler::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
 };
-use drop_staking_base::state::astroport_exchange_handler::{Config, CONFIG, SWAP_OPERATIONS};
+use drop_staking_base::state::astroport_exchange_handler::{
+    Config, CONFIG, SWAP_OPERATIONS, SWAP_RECEIVER,
+};
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -79,6 +81,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 fn query_config(deps: Deps, _env: Env) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     let swap_operations = SWAP_OPERATIONS.may_load(deps.storage)?;
+    let swap_receiver = SWAP_RECEIVER.may_load(deps.storage)?;
 
     to_json_binary(&ConfigResponse {
         core_contract: config.core_contract,
@@ -89,6 +92,7 @@ fn query_config(deps: Deps, _env: Env) -> StdResult<Binary> {
         from_denom: config.from_denom,
         min_rewards: config.min_rewards,
         swap_operations,
+        swap_receiver,
     })
 }
 
@@ -129,6 +133,9 @@ pub fn execute(
         ExecuteMsg::UpdateSwapOperations { operations } => {
             exec_update_swap_operations(deps, info, operations)
         }
+        ExecuteMsg::UpdateSwapReceiver { receiver } => {
+            exec_update_swap_receiver(deps, info, receiver)
+        }
     }
 }
 
@@ -203,6 +210,9 @@ fn exec_update_config(
 fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
     let swap_operations = SWAP_OPERATIONS.may_load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
+    let swap_receiver = SWAP_RECEIVER
+        .may_load(deps.storage)?
+        .unwrap_or(config.core_contract);
 
     let from_denom = config.from_denom;
     let balance = deps
@@ -240,7 +250,7 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
             msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
                 operations: swap_operations,
                 minimum_receive: None,
-                to: Some(config.core_contract),
+                to: Some(swap_receiver.to_string()),
                 max_spread: Some(config.max_spread),
             })?,
             funds: vec![balance.clone()],
@@ -263,7 +273,7 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
                 ask_asset_info: None,
                 belief_price: Some(belief_price),
                 max_spread: Some(config.max_spread),
-                to: Some(config.core_contract),
+                to: Some(swap_receiver.to_string()),
             })?,
             funds: vec![balance.clone()],
         });
@@ -302,6 +312,26 @@ fn exec_update_swap_operations(
     Ok(response("update_swap_operations", CONTRACT_NAME, attrs))
 }
 
+fn exec_update_swap_receiver(
+    deps: DepsMut,
+    info: MessageInfo,
+    receiver: Option<String>,
+) -> ContractResult<Response> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let attrs = match receiver {
+        Some(receiver) => {
+            let receiver = deps.api.addr_validate(&receiver)?;
+            SWAP_RECEIVER.save(deps.storage, &receiver.to_string())?;
+            vec![attr("new_swap_receiver", receiver)]
+        }
+        None => {
+            SWAP_RECEIVER.remove(deps.storage);
+            vec![attr("clear_swap_receiver", "1")]
+        }
+    };
+    Ok(response("update_swap_receiver", CONTRACT_NAME, attrs))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
     let version: semver::Version = CONTRACT_VERSION.parse()?;
@@ -307,6 +307,7 @@ fn test_config_query() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             swap_operations: None,
+            swap_receiver: None,
         }
     );
 }
@@ -689,6 +690,7 @@ fn test_config_update() {
             from_denom: "untrn".to_string(),
             min_rewards: Uint128::zero(),
             swap_operations: None,
+            swap_receiver: None,
         }
     );
 }
@@ -759,6 +761,7 @@ fn test_swap_operations_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             swap_operations: Some(operations),
+            swap_receiver: None,
         }
     );
 
@@ -787,6 +790,75 @@ fn test_swap_operations_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             swap_operations: None,
+            swap_receiver: None,
         }
     );
 }
+
+#[test]
+fn test_swap_receiver_update() {
+    let mut app = mock_app();
+
+    let astroport_exchange_handler_code_id = app.store_code(astroport_handler_contract());
+
+    let astroport_handler_contract = instantiate_astroport_handler_contract(
+        &mut app,
+        astroport_exchange_handler_code_id,
+        InstantiateMsg {
+            owner: OWNER_CONTRACT_ADDR.to_string(),
+            price_provider_contract: PRICE_PROVIDER_CONTRACT_ADDR.to_string(),
+            core_contract: CORE_CONTRACT_ADDR.to_string(),
+            cron_address: CRON_ADDR.to_string(),
+            pair_contract: "pair_contract".to_string(),
+            router_contract: "router_contract".to_string(),
+            from_denom: "ueth".to_string(),
+            min_rewards: Uint128::one(),
+            max_spread: Decimal::percent(1),
+        },
+    );
+
+    let _res = app
+        .execute_contract(
+            Addr::unchecked(OWNER_CONTRACT_ADDR),
+            astroport_handler_contract.clone(),
+            &ExecuteMsg::UpdateSwapReceiver {
+                receiver: Some("native_bond_provider".to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(astroport_handler_contract.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        config.swap_receiver,
+        Some("native_bond_provider".to_string())
+    );
+
+    let _res = app
+        .execute_contract(
+            Addr::unchecked(OWNER_CONTRACT_ADDR),
+            astroport_handler_contract.clone(),
+            &ExecuteMsg::UpdateSwapReceiver { receiver: None },
+            &[],
+        )
+        .unwrap();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(astroport_handler_contract.clone(), &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.swap_receiver, None);
+
+    let res = app.execute_contract(
+        Addr::unchecked(SENDER_ADDR),
+        astroport_handler_contract,
+        &ExecuteMsg::UpdateSwapReceiver {
+            receiver: Some("native_bond_provider".to_string()),
+        },
+        &[],
+    );
+    assert!(res.is_err());
+}
@@ -1,32 +1,48 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    attr, ensure, ensure_eq, ensure_ne, to_json_binary, Addr, Attribute, BankQuery, Binary, Coin,
-    CosmosMsg, CustomQuery, Decimal, Deps, DepsMut, Env, MessageInfo, Order, QueryRequest,
-    Response, StdError, StdResult, Uint128, Uint64, WasmMsg,
+    attr, ensure, ensure_eq, ensure_ne, to_json_binary, Addr, Attribute, BankMsg, BankQuery,
+    Binary, Coin, CosmosMsg, CustomQuery, Decimal, Decimal256, Deps, DepsMut, Env, MessageInfo,
+    Order, QueryRequest, Response, StdError, StdResult, Storage, Uint128, Uint256, Uint64, WasmMsg,
 };
 use cw_storage_plus::Bound;
-use drop_helpers::answer::response;
+use drop_helpers::{answer::response, ica::IcaState, pause::PauseInfoResponse};
 use drop_puppeteer_base::{msg::TransferReadyBatchesMsg, peripheral_hook::IBCTransferReason};
 use drop_staking_base::{
     error::core::{ContractError, ContractResult},
     msg::{
         core::{
-            BondCallback, BondHook, ExecuteMsg, FailedBatchResponse, InstantiateMsg,
-            LastPuppeteerResponse, MigrateMsg, QueryMsg,
+            BondCallback, BondHook, ExchangeRateHistoryResponse, ExchangeRateTwapResponse,
+            ExecuteMsg, FailedBatchResponse, FeeLedgerEntriesResponse, InstantiateMsg,
+            LastPuppeteerResponse, LiquidityBufferResponse, MigrateMsg, QueryMsg,
+            SimulateBondResponse, SlashingEventsResponse, UnbondInKindRequestsResponse,
         },
+        distribution::Redelegation,
         token::{
             ConfigResponse as TokenConfigResponse, ExecuteMsg as TokenExecuteMsg,
             QueryMsg as TokenQueryMsg,
         },
+        withdrawal_manager::{
+            QueryMsg as WithdrawalManagerQueryMsg, ReceiveNftMsg as WithdrawalManagerReceiveNftMsg,
+        },
         withdrawal_voucher::ExecuteMsg as VoucherExecuteMsg,
     },
     state::{
+        bond_providers::BondProviderRoute,
         core::{
-            unbond_batches_map, Config, ConfigOptional, ContractState, Pause, UnbondBatch,
-            UnbondBatchStatus, UnbondBatchStatusTimestamps, UnbondBatchesResponse, BOND_HOOKS,
-            BOND_PROVIDERS, CONFIG, EXCHANGE_RATE, FAILED_BATCH_ID, FSM, LAST_ICA_CHANGE_HEIGHT,
-            LAST_IDLE_CALL, LAST_PUPPETEER_RESPONSE, LD_DENOM, MAX_BOND_PROVIDERS, PAUSE,
-            UNBOND_BATCH_ID,
+            unbond_batches_map, Config, ConfigOptional, ContractState, ExchangeRateSnapshot,
+            FeeLedgerEntry, InstantUnbondConfig, InstantUnbondEpoch, LiquidityBufferVoucher,
+            NonNativeRewardsItem, Pause, RebalanceConfig, RewardsFeeConfig, SlashingEvent,
+            UnbondBatch, UnbondBatchStatus, UnbondBatchStatusTimestamps, UnbondBatchesResponse,
+            UnbondInKindConfig, UnbondInKindRequest, UnbondInKindStatus, ValidatorSlashing,
+            BOND_HOOKS, BOND_PROVIDERS, CONFIG, EXCHANGE_RATE, EXCHANGE_RATE_HISTORY,
+            EXCHANGE_RATE_HISTORY_NEXT_ID, FAILED_BATCH_ID, FEE_LEDGER, FEE_LEDGER_ENTRIES, FSM,
+            INSTANT_UNBOND_CONFIG, INSTANT_UNBOND_EPOCH, LAST_ICA_CHANGE_HEIGHT, LAST_IDLE_CALL,
+            LAST_PUPPETEER_RESPONSE, LAST_REBALANCE, LAST_SHARE_RATIOS, LD_DENOM, LIQUIDITY_BUFFER,
+            LIQUIDITY_BUFFER_VOUCHERS, MAX_BOND_PROVIDERS, MAX_EXCHANGE_RATE_HISTORY_SIZE,
+            NON_NATIVE_REWARDS_CONFIG, PAUSE, REBALANCE_CONFIG, REBALANCE_QUEUE,
+            REDELEGATION_COOLDOWNS, REWARDS_FEE_CONFIG, SLASHING_EVENTS, SLASHING_EVENTS_NEXT_ID,
+            UNBOND_BATCH_ID, UNBOND_BATCH_UNDELEGATIONS, UNBOND_IN_KIND_CONFIG,
+            UNBOND_IN_KIND_NEXT_ID, UNBOND_IN_KIND_REQUESTS,
         },
         validatorset::ValidatorInfo,
         withdrawal_voucher::{Metadata, Trait},
@@ -40,6 +56,10 @@ pub type MessageWithFeeResponse<T> = (CosmosMsg<T>, Option<CosmosMsg<T>>);
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const UNBOND_BATCHES_PAGINATION_DEFAULT_LIMIT: Uint64 = Uint64::new(100u64);
+pub const EXCHANGE_RATE_HISTORY_PAGINATION_DEFAULT_LIMIT: u64 = 100;
+pub const FEE_LEDGER_ENTRIES_PAGINATION_DEFAULT_LIMIT: u64 = 100;
+pub const SLASHING_EVENTS_PAGINATION_DEFAULT_LIMIT: u64 = 100;
+pub const UNBOND_IN_KIND_REQUESTS_PAGINATION_DEFAULT_LIMIT: u64 = 100;
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
@@ -81,7 +101,7 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     Ok(match msg {
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?)?,
         QueryMsg::Owner {} => to_json_binary(
@@ -98,6 +118,12 @@ pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResu
             let config = CONFIG.load(deps.storage)?;
             to_json_binary(&query_exchange_rate(deps, &config)?)?
         }
+        QueryMsg::ExchangeRateHistory { start_after, limit } => {
+            to_json_binary(&query_exchange_rate_history(deps, start_after, limit)?)?
+        }
+        QueryMsg::ExchangeRateTwap { window_seconds } => {
+            to_json_binary(&query_exchange_rate_twap(deps, &env, window_seconds)?)?
+        }
         QueryMsg::CurrentUnbondBatch {} => query_current_unbond_batch(deps)?,
         QueryMsg::UnbondBatch { batch_id } => query_unbond_batch(deps, batch_id)?,
         QueryMsg::UnbondBatches { limit, page_key } => query_unbond_batches(deps, limit, page_key)?,
@@ -118,6 +144,74 @@ pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResu
                 .collect::<Vec<String>>(),
         )?,
         QueryMsg::BondProviders {} => to_json_binary(&query_bond_providers(deps)?)?,
+        QueryMsg::BondProviderRoutes {} => to_json_binary(&query_bond_provider_routes(deps)?)?,
+        QueryMsg::SimulateBond { coin } => to_json_binary(&query_simulate_bond(deps, coin)?)?,
+        QueryMsg::RewardsFeeConfig {} => {
+            to_json_binary(&REWARDS_FEE_CONFIG.may_load(deps.storage)?)?
+        }
+        QueryMsg::FeeLedger {} => {
+            to_json_binary(&FEE_LEDGER.may_load(deps.storage)?.unwrap_or_default())?
+        }
+        QueryMsg::FeeLedgerEntries { start_after, limit } => {
+            to_json_binary(&query_fee_ledger_entries(deps, start_after, limit)?)?
+        }
+        QueryMsg::InstantUnbondConfig {} => {
+            to_json_binary(&INSTANT_UNBOND_CONFIG.may_load(deps.storage)?)?
+        }
+        QueryMsg::LiquidityBuffer {} => to_json_binary(&query_liquidity_buffer(deps, &env)?)?,
+        QueryMsg::RebalanceConfig {} => to_json_binary(&REBALANCE_CONFIG.may_load(deps.storage)?)?,
+        QueryMsg::RebalanceQueue {} => {
+            to_json_binary(&REBALANCE_QUEUE.may_load(deps.storage)?.unwrap_or_default())?
+        }
+        QueryMsg::SlashingEvents { start_after, limit } => {
+            to_json_binary(&query_slashing_events(deps, start_after, limit)?)?
+        }
+        QueryMsg::UnbondInKindConfig {} => {
+            to_json_binary(&UNBOND_IN_KIND_CONFIG.may_load(deps.storage)?)?
+        }
+        QueryMsg::UnbondInKindRequests { start_after, limit } => {
+            to_json_binary(&query_unbond_in_kind_requests(deps, start_after, limit)?)?
+        }
+        QueryMsg::RedelegationCooldowns {} => to_json_binary(
+            &REDELEGATION_COOLDOWNS
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        )?,
+        QueryMsg::NonNativeRewardsConfig {} => to_json_binary(
+            &NON_NATIVE_REWARDS_CONFIG
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        )?,
+    })
+}
+
+fn query_liquidity_buffer(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+) -> ContractResult<LiquidityBufferResponse> {
+    let available = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    let pending_refill_dasset = LIQUIDITY_BUFFER_VOUCHERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, voucher)| voucher.dasset_amount))
+        .sum::<StdResult<Uint128>>()?;
+    let (epoch, epoch_limit) = match INSTANT_UNBOND_CONFIG.may_load(deps.storage)? {
+        Some(instant_unbond_config) => (
+            get_instant_unbond_epoch(
+                deps.storage,
+                env.block.time.seconds(),
+                &instant_unbond_config,
+            )?,
+            instant_unbond_config.epoch_limit,
+        ),
+        None => (InstantUnbondEpoch::default(), Uint128::zero()),
+    };
+
+    Ok(LiquidityBufferResponse {
+        available,
+        pending_refill_dasset,
+        epoch_start: epoch.start,
+        epoch_paid_out: epoch.paid_out,
+        epoch_limit,
     })
 }
 
@@ -159,6 +253,35 @@ fn query_bond_providers(deps: Deps<NeutronQuery>) -> ContractResult<Vec<Addr>> {
     Ok(BOND_PROVIDERS.get_all_providers(deps.storage)?)
 }
 
+fn query_bond_provider_routes(
+    deps: Deps<NeutronQuery>,
+) -> ContractResult<Vec<(Addr, BondProviderRoute)>> {
+    Ok(BOND_PROVIDERS
+        .get_all_providers(deps.storage)?
+        .into_iter()
+        .map(|provider| {
+            let route = BOND_PROVIDERS.get_route(deps.storage, provider.clone())?;
+            Ok((provider, route))
+        })
+        .collect::<StdResult<Vec<_>>>()?)
+}
+
+fn query_simulate_bond(
+    deps: Deps<NeutronQuery>,
+    coin: Coin,
+) -> ContractResult<SimulateBondResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let exchange_rate = query_exchange_rate(deps, &config)?;
+    let (bond_provider, issue_amount) =
+        get_bond_provider(deps, &coin, exchange_rate)?.ok_or(ContractError::BondProviderError {
+            message: "No sufficient bond provider found".into(),
+        })?;
+    Ok(SimulateBondResponse {
+        bond_provider,
+        issue_amount,
+    })
+}
+
 fn query_exchange_rate(deps: Deps<NeutronQuery>, config: &Config) -> ContractResult<Decimal> {
     let fsm_state = FSM.get_current_state(deps.storage)?;
     let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, puppeteer_contract);
@@ -168,20 +291,43 @@ fn query_exchange_rate(deps: Deps<NeutronQuery>, config: &Config) -> ContractRes
             .unwrap_or((Decimal::one(), 0))
             .0);
     }
+    Ok(calc_exchange_rate(deps, &addrs.puppeteer_contract)?.exchange_rate)
+}
+
+// exchange rate along with the amounts it was calculated from
+struct ExchangeRateParts {
+    exchange_rate: Decimal,
+    total_bonded: Uint128,
+    dasset_supply: Uint128,
+    numerator: Uint128,   // delegated and in-flight base asset
+    denominator: Uint128, // dAsset supply along with unprocessed unbonds
+}
+
+fn calc_exchange_rate(
+    deps: Deps<NeutronQuery>,
+    puppeteer_contract: &str,
+) -> ContractResult<ExchangeRateParts> {
     let ld_total_supply: cosmwasm_std::SupplyResponse =
         deps.querier.query(&QueryRequest::Bank(BankQuery::Supply {
             denom: LD_DENOM.load(deps.storage)?,
         }))?;
 
-    let mut exchange_rate_denominator = ld_total_supply.amount.amount;
+    let dasset_supply = ld_total_supply.amount.amount;
+    let mut exchange_rate_denominator = dasset_supply;
     if exchange_rate_denominator.is_zero() {
-        return Ok(Decimal::one());
+        return Ok(ExchangeRateParts {
+            exchange_rate: Decimal::one(),
+            total_bonded: Uint128::zero(),
+            dasset_supply,
+            numerator: Uint128::zero(),
+            denominator: Uint128::zero(),
+        });
     }
 
     let delegations_response = deps
         .querier
         .query_wasm_smart::<drop_staking_base::msg::puppeteer::DelegationsResponse>(
-        &addrs.puppeteer_contract,
+        puppeteer_contract,
         &drop_puppeteer_base::msg::QueryMsg::Extension {
             msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Delegations {},
         },
@@ -217,23 +363,252 @@ fn query_exchange_rate(deps: Deps<NeutronQuery>, config: &Config) -> ContractRes
 
     // arithmetic operations order is important here as we don't want to overflow
     let exchange_rate_numerator = delegations_amount + total_async_tokens_amount;
-    if exchange_rate_numerator.is_zero() {
-        return Ok(Decimal::one());
-    }
-    let exchange_rate = Decimal::from_ratio(exchange_rate_numerator, exchange_rate_denominator);
-    Ok(exchange_rate)
+    let exchange_rate = if exchange_rate_numerator.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(exchange_rate_numerator, exchange_rate_denominator)
+    };
+    Ok(ExchangeRateParts {
+        exchange_rate,
+        total_bonded: delegations_amount,
+        dasset_supply,
+        numerator: exchange_rate_numerator,
+        denominator: exchange_rate_denominator,
+    })
 }
 
 fn cache_exchange_rate(
     deps: DepsMut<NeutronQuery>,
-    env: Env,
-    config: &Config,
+    env: &Env,
+    parts: &ExchangeRateParts,
 ) -> ContractResult<()> {
-    let exchange_rate = query_exchange_rate(deps.as_ref(), config)?;
+    let exchange_rate = parts.exchange_rate;
     EXCHANGE_RATE.save(deps.storage, &(exchange_rate, env.block.height))?;
+
+    let snapshot_id = EXCHANGE_RATE_HISTORY_NEXT_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let snapshot = ExchangeRateSnapshot {
+        height: env.block.height,
+        time: env.block.time.seconds(),
+        exchange_rate,
+        total_bonded: parts.total_bonded,
+        dasset_supply: parts.dasset_supply,
+    };
+    EXCHANGE_RATE_HISTORY.save(deps.storage, snapshot_id, &snapshot)?;
+    if snapshot_id >= MAX_EXCHANGE_RATE_HISTORY_SIZE {
+        EXCHANGE_RATE_HISTORY.remove(deps.storage, snapshot_id - MAX_EXCHANGE_RATE_HISTORY_SIZE);
+    }
+    EXCHANGE_RATE_HISTORY_NEXT_ID.save(deps.storage, &(snapshot_id + 1))?;
     Ok(())
 }
 
+// charges the rewards fee on exchange rate growth above the high-water mark by minting dAsset
+// to the fee collector, so that the minted amount is worth exactly the fee, and updates the
+// exchange rate parts to the ones after the mint
+fn get_rewards_fee_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    parts: &mut ExchangeRateParts,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<Option<CosmosMsg<NeutronMsg>>> {
+    let Some(rewards_fee_config) = REWARDS_FEE_CONFIG.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    let mut fee_ledger = FEE_LEDGER.may_load(deps.storage)?.unwrap_or_default();
+    let last_dasset_amount = fee_ledger.last_dasset_amount;
+    fee_ledger.last_dasset_amount = parts.denominator;
+    let high_water_mark = match fee_ledger.high_water_mark {
+        Some(rate) if parts.exchange_rate > rate => rate,
+        // the mark stays where it is after a fall, so recovering back to it isn't charged
+        Some(_) => {
+            FEE_LEDGER.save(deps.storage, &fee_ledger)?;
+            return Ok(None);
+        }
+        None => {
+            fee_ledger.high_water_mark = Some(parts.exchange_rate);
+            FEE_LEDGER.save(deps.storage, &fee_ledger)?;
+            return Ok(None);
+        }
+    };
+
+    // dAsset minted since the last idle tick was issued at a later rate and hasn't earned the growth
+    let earning_dasset = parts.denominator.min(last_dasset_amount);
+    let rewards = earning_dasset * (parts.exchange_rate - high_water_mark);
+    let fee = rewards * rewards_fee_config.fee;
+    let backing = parts.numerator;
+    let fee_dasset_minted = if fee.is_zero() || backing <= fee {
+        Uint128::zero()
+    } else {
+        fee.multiply_ratio(parts.denominator, backing - fee)
+    };
+    if !fee_dasset_minted.is_zero() {
+        parts.dasset_supply += fee_dasset_minted;
+        parts.denominator += fee_dasset_minted;
+        parts.exchange_rate = Decimal::from_ratio(backing, parts.denominator);
+    }
+
+    fee_ledger.cumulative_rewards += rewards;
+    fee_ledger.cumulative_fee += fee;
+    fee_ledger.cumulative_fee_dasset_minted += fee_dasset_minted;
+    fee_ledger.high_water_mark = Some(parts.exchange_rate);
+    fee_ledger.last_dasset_amount = parts.denominator;
+    FEE_LEDGER_ENTRIES.save(
+        deps.storage,
+        fee_ledger.entries_count,
+        &FeeLedgerEntry {
+            height: env.block.height,
+            time: env.block.time.seconds(),
+            exchange_rate: Decimal::from_ratio(backing, parts.denominator - fee_dasset_minted),
+            rewards,
+            fee,
+            fee_dasset_minted,
+        },
+    )?;
+    fee_ledger.entries_count += 1;
+    FEE_LEDGER.save(deps.storage, &fee_ledger)?;
+
+    attrs.push(attr("rewards", rewards));
+    attrs.push(attr("rewards_fee", fee));
+    if fee_dasset_minted.is_zero() {
+        return Ok(None);
+    }
+    attrs.push(attr("rewards_fee_dasset_minted", fee_dasset_minted));
+
+    let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, token_contract);
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.token_contract,
+        msg: to_json_binary(&TokenExecuteMsg::Mint {
+            amount: fee_dasset_minted,
+            receiver: rewards_fee_config.fee_collector.into_string(),
+        })?,
+        funds: vec![],
+    })))
+}
+
+fn query_fee_ledger_entries(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<FeeLedgerEntriesResponse> {
+    let limit = limit
+        .map(|limit| limit as u64)
+        .unwrap_or(FEE_LEDGER_ENTRIES_PAGINATION_DEFAULT_LIMIT) as usize;
+    let entries = FEE_LEDGER_ENTRIES
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(FeeLedgerEntriesResponse { entries })
+}
+
+fn query_slashing_events(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<SlashingEventsResponse> {
+    let limit = limit
+        .map(|limit| limit as u64)
+        .unwrap_or(SLASHING_EVENTS_PAGINATION_DEFAULT_LIMIT) as usize;
+    let events = SLASHING_EVENTS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SlashingEventsResponse { events })
+}
+
+fn query_unbond_in_kind_requests(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<UnbondInKindRequestsResponse> {
+    let limit = limit
+        .map(|limit| limit as u64)
+        .unwrap_or(UNBOND_IN_KIND_REQUESTS_PAGINATION_DEFAULT_LIMIT) as usize;
+    let requests = UNBOND_IN_KIND_REQUESTS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(UnbondInKindRequestsResponse { requests })
+}
+
+fn query_exchange_rate_history(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<ExchangeRateHistoryResponse> {
+    let limit = limit
+        .map(|limit| limit as u64)
+        .unwrap_or(EXCHANGE_RATE_HISTORY_PAGINATION_DEFAULT_LIMIT)
+        .min(MAX_EXCHANGE_RATE_HISTORY_SIZE) as usize;
+    let snapshots = EXCHANGE_RATE_HISTORY
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ExchangeRateHistoryResponse { snapshots })
+}
+
+// every snapshot's rate is considered to hold until the next snapshot (or now for the latest one)
+fn query_exchange_rate_twap(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    window_seconds: u64,
+) -> ContractResult<ExchangeRateTwapResponse> {
+    ensure!(window_seconds > 0, ContractError::InvalidTwapWindow {});
+    let now = env.block.time.seconds();
+    let window_start = now.saturating_sub(window_seconds);
+
+    let mut latest_rate = None;
+    let mut weighted_sum = Decimal::zero();
+    let mut period_end = now;
+    for item in EXCHANGE_RATE_HISTORY.range(deps.storage, None, None, Order::Descending) {
+        let (_, snapshot) = item?;
+        latest_rate.get_or_insert(snapshot.exchange_rate);
+        let period_start = snapshot.time.max(window_start).min(period_end);
+        weighted_sum = weighted_sum.checked_add(
+            snapshot
+                .exchange_rate
+                .checked_mul(Decimal::from_ratio(period_end - period_start, 1u64))?,
+        )?;
+        period_end = period_start;
+        if snapshot.time <= window_start {
+            break;
+        }
+    }
+    let latest_rate = latest_rate.ok_or(ContractError::ExchangeRateHistoryIsEmpty {})?;
+
+    let twap = if period_end < now {
+        weighted_sum / Decimal::from_ratio(now - period_end, 1u64)
+    } else {
+        latest_rate
+    };
+    Ok(ExchangeRateTwapResponse {
+        twap,
+        window_start: period_end,
+        window_end: now,
+    })
+}
+
 fn query_current_unbond_batch(deps: Deps<NeutronQuery>) -> StdResult<Binary> {
     to_json_binary(&UNBOND_BATCH_ID.load(deps.storage)?)
 }
@@ -284,7 +659,21 @@ pub fn execute(
 ) -> ContractResult<Response<NeutronMsg>> {
     match msg {
         ExecuteMsg::Bond { receiver, r#ref } => execute_bond(deps, info, receiver, r#ref),
+        ExecuteMsg::BondFromProvider { amount, receiver } => {
+            execute_bond_from_provider(deps, info, amount, receiver)
+        }
         ExecuteMsg::Unbond {} => execute_unbond(deps, info),
+        ExecuteMsg::InstantUnbond { min_receive } => {
+            execute_instant_unbond(deps, env, info, min_receive)
+        }
+        ExecuteMsg::UnbondInKind { validator } => {
+            execute_unbond_in_kind(deps, env, info, validator)
+        }
+        ExecuteMsg::RetryUnbondInKind { id, share } => {
+            execute_retry_unbond_in_kind(deps, env, info, id, share)
+        }
+        ExecuteMsg::ConfirmUnbondInKind { id } => execute_confirm_unbond_in_kind(deps, info, id),
+        ExecuteMsg::FundLiquidityBuffer {} => execute_fund_liquidity_buffer(deps, info),
         ExecuteMsg::Tick {} => execute_tick(deps, env, info),
         ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, *new_config),
         ExecuteMsg::UpdateOwnership(action) => {
@@ -299,6 +688,9 @@ pub fn execute(
             batch_id,
             unbonded_amount,
         } => execute_process_emergency_batch(deps, info, env, batch_id, unbonded_amount),
+        ExecuteMsg::ResolveEmergencyBatches { batch_ids } => {
+            execute_resolve_emergency_batches(deps, info, env, batch_ids)
+        }
         ExecuteMsg::UpdateWithdrawnAmount {
             batch_id,
             withdrawn_amount,
@@ -312,7 +704,249 @@ pub fn execute(
         ExecuteMsg::RemoveBondProvider {
             bond_provider_address,
         } => execute_remove_bond_provider(deps, info, bond_provider_address),
+        ExecuteMsg::SetBondProviderRoute {
+            bond_provider_address,
+            route,
+        } => execute_set_bond_provider_route(deps, info, bond_provider_address, route),
+        ExecuteMsg::UpdateRewardsFeeConfig { fee, fee_collector } => {
+            execute_update_rewards_fee_config(deps, info, fee, fee_collector)
+        }
+        ExecuteMsg::UpdateUnbondInKindConfig { config } => {
+            execute_update_unbond_in_kind_config(deps, info, config)
+        }
+        ExecuteMsg::UpdateInstantUnbondConfig { config } => {
+            execute_update_instant_unbond_config(deps, info, config)
+        }
+        ExecuteMsg::WithdrawLiquidityBuffer { amount, receiver } => {
+            execute_withdraw_liquidity_buffer(deps, info, amount, receiver)
+        }
+        ExecuteMsg::UpdateRebalanceConfig { config } => {
+            execute_update_rebalance_config(deps, info, config)
+        }
+        ExecuteMsg::UpdateNonNativeRewardsConfig { items } => {
+            execute_update_non_native_rewards_config(deps, info, items)
+        }
+    }
+}
+
+fn execute_update_rewards_fee_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    fee: Decimal,
+    fee_collector: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure!(fee < Decimal::one(), ContractError::InvalidFee {});
+
+    let fee_collector = deps.api.addr_validate(&fee_collector)?;
+    REWARDS_FEE_CONFIG.save(
+        deps.storage,
+        &RewardsFeeConfig {
+            fee,
+            fee_collector: fee_collector.clone(),
+        },
+    )?;
+
+    Ok(response(
+        "execute-update_rewards_fee_config",
+        CONTRACT_NAME,
+        vec![
+            attr("fee", fee.to_string()),
+            attr("fee_collector", fee_collector),
+        ],
+    ))
+}
+
+fn execute_update_instant_unbond_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    config: Option<InstantUnbondConfig>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let attrs = match config {
+        Some(config) => {
+            ensure!(config.fee < Decimal::one(), ContractError::InvalidFee {});
+            INSTANT_UNBOND_CONFIG.save(deps.storage, &config)?;
+            vec![
+                attr("enabled", "true"),
+                attr("fee", config.fee.to_string()),
+                attr("epoch_duration", config.epoch_duration.to_string()),
+                attr("epoch_limit", config.epoch_limit),
+            ]
+        }
+        None => {
+            INSTANT_UNBOND_CONFIG.remove(deps.storage);
+            vec![attr("enabled", "false")]
+        }
+    };
+
+    Ok(response(
+        "execute-update_instant_unbond_config",
+        CONTRACT_NAME,
+        attrs,
+    ))
+}
+
+fn execute_update_unbond_in_kind_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    config: Option<UnbondInKindConfig>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let attrs = match config {
+        Some(config) => {
+            UNBOND_IN_KIND_CONFIG.save(deps.storage, &config)?;
+            vec![
+                attr("enabled", "true"),
+                attr(
+                    "remote_transfer_channel_id",
+                    config.remote_transfer_channel_id,
+                ),
+                attr("transfer_timeout", config.transfer_timeout.to_string()),
+            ]
+        }
+        None => {
+            UNBOND_IN_KIND_CONFIG.remove(deps.storage);
+            vec![attr("enabled", "false")]
+        }
+    };
+
+    Ok(response(
+        "execute-update_unbond_in_kind_config",
+        CONTRACT_NAME,
+        attrs,
+    ))
+}
+
+fn execute_update_rebalance_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    config: Option<RebalanceConfig>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let attrs = match config {
+        Some(config) => {
+            REBALANCE_CONFIG.save(deps.storage, &config)?;
+            vec![
+                attr("enabled", "true"),
+                attr("max_moves", config.max_moves.to_string()),
+                attr("min_interval", config.min_interval.to_string()),
+                attr("min_amount", config.min_amount),
+            ]
+        }
+        None => {
+            REBALANCE_CONFIG.remove(deps.storage);
+            REBALANCE_QUEUE.remove(deps.storage);
+            vec![attr("enabled", "false")]
+        }
+    };
+
+    Ok(response(
+        "execute-update_rebalance_config",
+        CONTRACT_NAME,
+        attrs,
+    ))
+}
+
+fn execute_update_non_native_rewards_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    items: Vec<NonNativeRewardsItem>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut denoms = std::collections::HashSet::new();
+    for item in &items {
+        // the staked denom is handled by the claiming flow and must never leave the ICA here
+        ensure!(
+            item.denom != config.remote_denom && denoms.insert(item.denom.as_str()),
+            ContractError::InvalidNonNativeRewardsDenom {
+                denom: item.denom.to_string()
+            }
+        );
+    }
+
+    let attrs = vec![attr(
+        "denoms",
+        items
+            .iter()
+            .map(|item| item.denom.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+    )];
+    if items.is_empty() {
+        NON_NATIVE_REWARDS_CONFIG.remove(deps.storage);
+    } else {
+        NON_NATIVE_REWARDS_CONFIG.save(deps.storage, &items)?;
     }
+
+    Ok(response(
+        "execute-update_non_native_rewards_config",
+        CONTRACT_NAME,
+        attrs,
+    ))
+}
+
+fn execute_fund_liquidity_buffer(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = cw_utils::must_pay(&info, &config.base_denom)?;
+    let buffer = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    LIQUIDITY_BUFFER.save(deps.storage, &(buffer + amount))?;
+
+    Ok(response(
+        "execute-fund_liquidity_buffer",
+        CONTRACT_NAME,
+        vec![
+            attr("action", "fund_liquidity_buffer"),
+            attr("sender", info.sender),
+            attr("amount", amount),
+        ],
+    ))
+}
+
+fn execute_withdraw_liquidity_buffer(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    amount: Uint128,
+    receiver: Option<String>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let available = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    ensure!(
+        amount <= available,
+        ContractError::LiquidityBufferInsufficient {
+            available,
+            requested: amount,
+        }
+    );
+    LIQUIDITY_BUFFER.save(deps.storage, &(available - amount))?;
+    let receiver = match receiver {
+        Some(receiver) => deps.api.addr_validate(&receiver)?,
+        None => info.sender,
+    };
+
+    Ok(response(
+        "execute-withdraw_liquidity_buffer",
+        CONTRACT_NAME,
+        vec![
+            attr("action", "withdraw_liquidity_buffer"),
+            attr("receiver", &receiver),
+            attr("amount", amount),
+        ],
+    )
+    .add_message(BankMsg::Send {
+        to_address: receiver.into_string(),
+        amount: vec![Coin::new(amount.u128(), config.base_denom)],
+    }))
 }
 
 fn execute_set_bond_hooks(
@@ -390,6 +1024,34 @@ fn execute_remove_bond_provider(
     ))
 }
 
+fn execute_set_bond_provider_route(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    bond_provider_address: String,
+    route: BondProviderRoute,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let bond_provider_address = deps.api.addr_validate(&bond_provider_address)?;
+    BOND_PROVIDERS.set_route(deps.storage, bond_provider_address.clone(), &route)?;
+
+    Ok(response(
+        "execute-set_bond_provider_route",
+        CONTRACT_NAME,
+        vec![
+            attr("bond_provider_address", bond_provider_address),
+            attr("priority", route.priority.to_string()),
+            attr(
+                "async_tokens_cap",
+                route
+                    .async_tokens_cap
+                    .map_or("none".to_string(), |cap| cap.to_string()),
+            ),
+            attr("preferred_denoms", route.preferred_denoms.join(",")),
+        ],
+    ))
+}
+
 fn execute_set_pause(
     deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
@@ -454,27 +1116,115 @@ fn execute_process_emergency_batch(
     ))
 }
 
-fn execute_update_withdrawn_amount(
+fn execute_resolve_emergency_batches(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
     info: MessageInfo,
-    batch_id: u128,
-    withdrawn_amount: Uint128,
+    env: Env,
+    batch_ids: Option<Vec<u128>>,
 ) -> ContractResult<Response<NeutronMsg>> {
     let config = CONFIG.load(deps.storage)?;
-    let addrs =
-        drop_helpers::get_contracts!(deps, config.factory_contract, withdrawal_manager_contract);
-    if info.sender != addrs.withdrawal_manager_contract {
-        return Err(ContractError::Unauthorized {});
+    // funds withdrawn in emergency end up on the emergency address, so it can return them
+    if config.emergency_address.as_deref() != Some(info.sender.as_str()) {
+        cw_ownable::assert_owner(deps.storage, &info.sender)?;
     }
+    let amount = cw_utils::must_pay(&info, &config.base_denom)?;
+    let batch_ids = match batch_ids {
+        Some(batch_ids) => batch_ids,
+        None => unbond_batches_map()
+            .idx
+            .status
+            .prefix(UnbondBatchStatus::WithdrawnEmergency as u8)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+    ensure!(!batch_ids.is_empty(), ContractError::EmptyBatchIds {});
 
-    let mut batch = unbond_batches_map().load(deps.storage, batch_id)?;
-    ensure_eq!(
-        batch.status,
-        UnbondBatchStatus::Withdrawn,
-        ContractError::BatchNotWithdrawn {}
-    );
-    batch.withdrawn_amount = Some(batch.withdrawn_amount.unwrap_or_default() + withdrawn_amount);
+    let mut batches = vec![];
+    for batch_id in batch_ids {
+        ensure!(
+            !batches.iter().any(|(id, _)| *id == batch_id),
+            ContractError::DuplicateBatchId { batch_id }
+        );
+        let batch = unbond_batches_map().load(deps.storage, batch_id)?;
+        ensure_eq!(
+            batch.status,
+            UnbondBatchStatus::WithdrawnEmergency,
+            ContractError::BatchNotWithdrawnEmergency {}
+        );
+        batches.push((batch_id, batch));
+    }
+
+    let total_expected_amount: Uint128 = batches
+        .iter()
+        .map(|(_, batch)| batch.expected_native_asset_amount)
+        .sum();
+    ensure!(
+        total_expected_amount >= amount,
+        ContractError::UnbondedAmountTooHigh {}
+    );
+
+    let mut attrs = vec![
+        attr("action", "resolve_emergency_batches"),
+        attr("amount", amount),
+    ];
+    let mut remaining_amount = amount;
+    let batches_count = batches.len();
+    for (i, (batch_id, mut batch)) in batches.into_iter().enumerate() {
+        // the last batch takes the rounding remainder so the whole payment is accounted for
+        let unbonded_amount = if i + 1 == batches_count {
+            remaining_amount
+        } else {
+            amount.multiply_ratio(batch.expected_native_asset_amount, total_expected_amount)
+        };
+        remaining_amount -= unbonded_amount;
+        let slashing_effect = if batch.expected_native_asset_amount.is_zero() {
+            Decimal::one()
+        } else {
+            Decimal::from_ratio(unbonded_amount, batch.expected_native_asset_amount)
+        };
+        batch.status = UnbondBatchStatus::Withdrawn;
+        batch.unbonded_amount = Some(unbonded_amount);
+        batch.slashing_effect = Some(slashing_effect);
+        batch.status_timestamps.withdrawn = Some(env.block.time.seconds());
+        unbond_batches_map().save(deps.storage, batch_id, &batch)?;
+        attrs.push(attr("batch_id", batch_id.to_string()));
+        attrs.push(attr("unbonded_amount", unbonded_amount));
+        attrs.push(attr("slashing_effect", slashing_effect.to_string()));
+    }
+
+    let addrs =
+        drop_helpers::get_contracts!(deps, config.factory_contract, withdrawal_manager_contract);
+    Ok(
+        response("execute-resolve_emergency_batches", CONTRACT_NAME, attrs).add_message(
+            BankMsg::Send {
+                to_address: addrs.withdrawal_manager_contract,
+                amount: vec![Coin::new(amount.u128(), config.base_denom)],
+            },
+        ),
+    )
+}
+
+fn execute_update_withdrawn_amount(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    batch_id: u128,
+    withdrawn_amount: Uint128,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let addrs =
+        drop_helpers::get_contracts!(deps, config.factory_contract, withdrawal_manager_contract);
+    if info.sender != addrs.withdrawal_manager_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut batch = unbond_batches_map().load(deps.storage, batch_id)?;
+    ensure_eq!(
+        batch.status,
+        UnbondBatchStatus::Withdrawn,
+        ContractError::BatchNotWithdrawn {}
+    );
+    batch.withdrawn_amount = Some(batch.withdrawn_amount.unwrap_or_default() + withdrawn_amount);
     unbond_batches_map().save(deps.storage, batch_id, &batch)?;
 
     Ok(response(
@@ -486,7 +1236,7 @@ fn execute_update_withdrawn_amount(
 
 fn execute_puppeteer_hook(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: drop_puppeteer_base::peripheral_hook::ResponseHookMsg,
 ) -> ContractResult<Response<NeutronMsg>> {
@@ -502,9 +1252,38 @@ fn execute_puppeteer_hook(
         ContractError::Unauthorized {}
     );
 
+    let transaction = match &msg {
+        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(success_msg) => {
+            &success_msg.transaction
+        }
+        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(err_msg) => {
+            &err_msg.transaction
+        }
+    };
+    if matches!(
+        transaction,
+        drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare { .. }
+            | drop_puppeteer_base::peripheral_hook::Transaction::TransferShare { .. }
+    ) {
+        // in-kind exits run beside the FSM, so they must not replace the response it waits for
+        return execute_unbond_in_kind_hook(deps, env, config, msg);
+    }
+
     match msg.clone() {
         drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(success_msg) => {
             LAST_ICA_CHANGE_HEIGHT.save(deps.storage, &success_msg.remote_height)?;
+            // redelegated stake can't be redelegated again until it matures
+            if let drop_puppeteer_base::peripheral_hook::Transaction::Redelegate {
+                validator_to,
+                ..
+            } = success_msg.transaction
+            {
+                REDELEGATION_COOLDOWNS.save(
+                    deps.storage,
+                    &validator_to,
+                    &(env.block.time.seconds() + config.unbonding_period),
+                )?;
+            }
         }
         drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(err_msg) => {
             match err_msg.transaction {
@@ -567,7 +1346,19 @@ fn execute_tick_idle(
     let mut attrs = vec![attr("action", "tick_idle"), attr("knot", "000")];
     let last_idle_call = LAST_IDLE_CALL.load(deps.storage)?;
     let mut messages = vec![];
-    cache_exchange_rate(deps.branch(), env.clone(), config)?;
+    let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, puppeteer_contract);
+    let mut exchange_rate_parts = calc_exchange_rate(deps.as_ref(), &addrs.puppeteer_contract)?;
+    if let Some(rewards_fee_msg) = get_rewards_fee_msg(
+        deps.branch(),
+        &env,
+        config,
+        &mut exchange_rate_parts,
+        &mut attrs,
+    )? {
+        messages.push(rewards_fee_msg);
+    }
+    // the cached rate already accounts for the dAsset minted as the fee
+    cache_exchange_rate(deps.branch(), &env, &exchange_rate_parts)?;
     let addrs = drop_helpers::get_contracts!(
         deps,
         config.factory_contract,
@@ -597,11 +1388,41 @@ fn execute_tick_idle(
 
             messages.push(msg);
 
+            FSM.go_to(deps.storage, ContractState::Peripheral)?;
+        } else if let Some(redelegation) = get_rebalance_redelegation(deps.branch(), &env, config)?
+        {
+            attrs.push(attr("knot", "051")); // rebalance redelegation
+            attrs.push(attr("validator_from", &redelegation.validator_from));
+            attrs.push(attr("validator_to", &redelegation.validator_to));
+            attrs.push(attr("amount", redelegation.amount));
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: addrs.puppeteer_contract.to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Redelegate {
+                    validator_from: redelegation.validator_from,
+                    validator_to: redelegation.validator_to,
+                    amount: redelegation.amount,
+                    reply_to: env.contract.address.to_string(),
+                })?,
+                funds: vec![],
+            }));
+
+            FSM.go_to(deps.storage, ContractState::Peripheral)?;
+        } else if let Some(transfer_msg) =
+            get_non_native_rewards_transfer_msg(deps.as_ref(), &env, config, &mut attrs)?
+        {
+            messages.push(transfer_msg);
+
             FSM.go_to(deps.storage, ContractState::Peripheral)?;
         }
     } else {
         LAST_IDLE_CALL.save(deps.storage, &env.block.time.seconds())?;
         attrs.push(attr("knot", "004"));
+        messages.extend(get_liquidity_buffer_refill_msgs(
+            deps.branch(),
+            config,
+            &mut attrs,
+        )?);
+        messages.extend(get_exchange_rewards_msg(deps.as_ref(), config, &mut attrs)?);
         let unbonding_batches = unbond_batches_map()
             .idx
             .status
@@ -745,6 +1566,14 @@ fn execute_tick_idle(
             }
         );
 
+        detect_slashing(
+            deps.branch(),
+            &env,
+            &delegations_response,
+            &config.remote_denom,
+            &mut attrs,
+        )?;
+
         let validators_map = validators
             .iter()
             .map(|v| (v.valoper_address.clone(), v))
@@ -781,6 +1610,311 @@ fn execute_tick_idle(
     Ok(response("execute-tick_idle", CONTRACT_NAME, attrs).add_messages(messages))
 }
 
+// Moves non-native rewards which grew over their minimum on the ICA to the rewards pump ICA
+// on the host chain. The pump is pushed to the rewards manager once the transfer succeeds.
+fn get_non_native_rewards_transfer_msg(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<Option<CosmosMsg<NeutronMsg>>> {
+    let items = NON_NATIVE_REWARDS_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if items.is_empty() {
+        return Ok(None);
+    }
+    let addrs = drop_helpers::get_contracts!(
+        deps,
+        config.factory_contract,
+        puppeteer_contract,
+        rewards_pump_contract
+    );
+    // the query fails until the puppeteer registers the non-native rewards balances ICQ
+    let Ok(balances) = deps
+        .querier
+        .query_wasm_smart::<drop_staking_base::msg::puppeteer::BalancesResponse>(
+            &addrs.puppeteer_contract,
+            &drop_puppeteer_base::msg::QueryMsg::Extension {
+                msg: drop_staking_base::msg::puppeteer::QueryExtMsg::NonNativeRewardsBalances {},
+            },
+        )
+    else {
+        return Ok(None);
+    };
+    // balances taken before the last ICA change may still hold already transferred rewards
+    if balances.remote_height < LAST_ICA_CHANGE_HEIGHT.load(deps.storage)? {
+        return Ok(None);
+    }
+    let rewards = items
+        .iter()
+        .filter_map(|item| {
+            balances.balances.coins.iter().find(|coin| {
+                coin.denom == item.denom && !coin.amount.is_zero() && coin.amount >= item.min_amount
+            })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if rewards.is_empty() {
+        return Ok(None);
+    }
+    let pump_ica: IcaState = deps.querier.query_wasm_smart(
+        &addrs.rewards_pump_contract,
+        &drop_staking_base::msg::pump::QueryMsg::Ica {},
+    )?;
+    let IcaState::Registered { ica_address, .. } = pump_ica else {
+        return Ok(None);
+    };
+
+    attrs.push(attr("knot", "052"));
+    attrs.push(attr(
+        "non_native_rewards",
+        rewards
+            .iter()
+            .map(|coin| coin.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    ));
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.puppeteer_contract,
+        msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Transfer {
+            items: rewards
+                .into_iter()
+                .map(|coin| (ica_address.to_string(), coin))
+                .collect(),
+            reply_to: env.contract.address.to_string(),
+        })?,
+        funds: vec![],
+    })))
+}
+
+// Hands non-native rewards pumped to the rewards manager over to their exchange handlers.
+// Handlers swap them to the base asset and send it to the native bond provider to be staked.
+fn get_exchange_rewards_msg(
+    deps: Deps<NeutronQuery>,
+    config: &Config,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let items = NON_NATIVE_REWARDS_CONFIG
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if items.is_empty() {
+        return Ok(vec![]);
+    }
+    let addrs =
+        drop_helpers::get_contracts!(deps, config.factory_contract, rewards_manager_contract);
+    let mut denoms = vec![];
+    for item in items {
+        let balance = deps
+            .querier
+            .query_balance(&addrs.rewards_manager_contract, &item.local_denom)?;
+        if !balance.amount.is_zero() {
+            denoms.push(item.local_denom);
+        }
+    }
+    if denoms.is_empty() {
+        return Ok(vec![]);
+    }
+    // paused rewards manager must not block the tick, we'll try again next time
+    let rewards_manager_pause: PauseInfoResponse = deps.querier.query_wasm_smart(
+        &addrs.rewards_manager_contract,
+        &drop_staking_base::msg::rewards_manager::QueryMsg::PauseInfo {},
+    )?;
+    if matches!(rewards_manager_pause, PauseInfoResponse::Paused {}) {
+        attrs.push(attr("exchange_rewards", "postponed"));
+        return Ok(vec![]);
+    }
+
+    attrs.push(attr("exchange_rewards", denoms.join(",")));
+    Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.rewards_manager_contract,
+        msg: to_json_binary(
+            &drop_staking_base::msg::rewards_manager::ExecuteMsg::ExchangeRewards { denoms },
+        )?,
+        funds: vec![],
+    })])
+}
+
+// Pops the next allowed redelegation of the current rebalance round.
+// A new round is planned by the strategy once the previous one is exhausted
+// and `min_interval` has passed. Rounds span several ticks, so every move is
+// capped by what is still delegated to its source validator.
+fn get_rebalance_redelegation(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+) -> ContractResult<Option<Redelegation>> {
+    let rebalance_config = match REBALANCE_CONFIG.may_load(deps.storage)? {
+        Some(rebalance_config) => rebalance_config,
+        None => return Ok(None),
+    };
+    let now = env.block.time.seconds();
+    let mut queue = REBALANCE_QUEUE.may_load(deps.storage)?.unwrap_or_default();
+    if queue.is_empty() {
+        let last_rebalance = LAST_REBALANCE.may_load(deps.storage)?.unwrap_or_default();
+        if now < last_rebalance + rebalance_config.min_interval {
+            return Ok(None);
+        }
+        let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, strategy_contract);
+        queue = deps.querier.query_wasm_smart(
+            addrs.strategy_contract,
+            &drop_staking_base::msg::strategy::QueryMsg::CalcRebalance {
+                max_moves: rebalance_config.max_moves,
+            },
+        )?;
+        queue.reverse();
+        LAST_REBALANCE.save(deps.storage, &now)?;
+    }
+
+    let delegations = if queue.is_empty() {
+        vec![]
+    } else {
+        let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, puppeteer_contract);
+        deps.querier
+            .query_wasm_smart::<drop_staking_base::msg::puppeteer::DelegationsResponse>(
+                addrs.puppeteer_contract,
+                &drop_puppeteer_base::msg::QueryMsg::Extension {
+                    msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Delegations {},
+                },
+            )?
+            .delegations
+            .delegations
+    };
+    let delegated = |validator: &str| -> Uint128 {
+        delegations
+            .iter()
+            .filter(|d| d.validator == validator && d.amount.denom == config.remote_denom)
+            .map(|d| d.amount.amount)
+            .sum()
+    };
+
+    let mut redelegation = None;
+    while let Some(mut item) = queue.pop() {
+        item.amount = item.amount.min(delegated(&item.validator_from));
+        let cooldown = REDELEGATION_COOLDOWNS.may_load(deps.storage, &item.validator_from)?;
+        if item.amount >= rebalance_config.min_amount && cooldown.map_or(true, |until| until <= now)
+        {
+            redelegation = Some(item);
+            break;
+        }
+    }
+    REBALANCE_QUEUE.save(deps.storage, &queue)?;
+
+    Ok(redelegation)
+}
+
+// Compares validators share ratios with the ones seen on the previous tick.
+// Since only slashing lowers tokens per share, any drop is a loss which is
+// recorded as a slashing event. Still unbonding batches lose the slashed share
+// of what they undelegated from every slashed validator.
+fn detect_slashing(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    delegations_response: &drop_staking_base::msg::puppeteer::DelegationsResponse,
+    remote_denom: &str,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<()> {
+    let delegations = delegations_response
+        .delegations
+        .delegations
+        .iter()
+        .filter(|d| d.amount.denom == remote_denom)
+        .collect::<Vec<_>>();
+
+    let mut validators = vec![];
+    let mut total_loss = Uint128::zero();
+    let mut total_stake = Uint128::zero();
+    for delegation in delegations.iter() {
+        total_stake += delegation.amount.amount;
+        let last_share_ratio = LAST_SHARE_RATIOS.may_load(deps.storage, &delegation.validator)?;
+        if let Some(last_share_ratio) = last_share_ratio {
+            if delegation.share_ratio < last_share_ratio && !delegation.share_ratio.is_zero() {
+                let loss =
+                    Uint128::try_from(Uint256::from(delegation.amount.amount).multiply_ratio(
+                        last_share_ratio.atomics() - delegation.share_ratio.atomics(),
+                        delegation.share_ratio.atomics(),
+                    ))?;
+                total_loss += loss;
+                validators.push(ValidatorSlashing {
+                    validator: delegation.validator.to_string(),
+                    slashing_fraction: Decimal256::one()
+                        - delegation.share_ratio / last_share_ratio,
+                    loss,
+                });
+            }
+        }
+    }
+
+    LAST_SHARE_RATIOS.clear(deps.storage);
+    for delegation in delegations {
+        LAST_SHARE_RATIOS.save(deps.storage, &delegation.validator, &delegation.share_ratio)?;
+    }
+
+    if total_loss.is_zero() {
+        return Ok(());
+    }
+
+    let slashing_fraction = Decimal::from_ratio(total_loss, total_stake + total_loss);
+    let mut batch_adjustments = vec![];
+    let unbonding_batches = unbond_batches_map()
+        .idx
+        .status
+        .prefix(UnbondBatchStatus::Unbonding as u8)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, mut batch) in unbonding_batches {
+        let adjustment = match UNBOND_BATCH_UNDELEGATIONS.may_load(deps.storage, id)? {
+            Some(mut undelegations) => {
+                let mut adjustment = Uint128::zero();
+                for (validator, amount) in undelegations.iter_mut() {
+                    let Some(slashing) = validators.iter().find(|v| &v.validator == validator)
+                    else {
+                        continue;
+                    };
+                    let loss = Uint128::try_from(Uint256::from(*amount).multiply_ratio(
+                        slashing.slashing_fraction.atomics(),
+                        Decimal256::one().atomics(),
+                    ))?;
+                    *amount -= loss;
+                    adjustment += loss;
+                }
+                UNBOND_BATCH_UNDELEGATIONS.save(deps.storage, id, &undelegations)?;
+                adjustment.min(batch.expected_native_asset_amount)
+            }
+            // batches undelegated before the breakdown was kept share the loss evenly
+            None => batch.expected_native_asset_amount * slashing_fraction,
+        };
+        if adjustment.is_zero() {
+            continue;
+        }
+        batch.expected_native_asset_amount -= adjustment;
+        unbond_batches_map().save(deps.storage, id, &batch)?;
+        batch_adjustments.push((id, adjustment));
+    }
+
+    let id = SLASHING_EVENTS_NEXT_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    SLASHING_EVENTS.save(
+        deps.storage,
+        id,
+        &SlashingEvent {
+            height: env.block.height,
+            time: env.block.time.seconds(),
+            remote_height: delegations_response.remote_height,
+            validators,
+            total_loss,
+            slashing_fraction,
+            batch_adjustments,
+        },
+    )?;
+    SLASHING_EVENTS_NEXT_ID.save(deps.storage, &(id + 1))?;
+    attrs.push(attr("slashing_event_id", id.to_string()));
+    attrs.push(attr("slashing_loss", total_loss));
+
+    Ok(())
+}
+
 fn execute_tick_peripheral(
     deps: DepsMut<NeutronQuery>,
     _env: Env,
@@ -788,6 +1922,7 @@ fn execute_tick_peripheral(
     config: &Config,
 ) -> ContractResult<Response<NeutronMsg>> {
     let mut attrs = vec![attr("action", "tick_peripheral")];
+    let mut messages = vec![];
     let res = get_received_puppeteer_response(deps.as_ref())?;
     let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, puppeteer_contract);
     if let drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(msg) = res {
@@ -801,6 +1936,22 @@ fn execute_tick_peripheral(
             drop_puppeteer_base::peripheral_hook::Transaction::Stake { .. } => {
                 attrs.push(attr("knot", "039"));
             }
+            drop_puppeteer_base::peripheral_hook::Transaction::Transfer { ref items, .. } => {
+                attrs.push(attr("knot", "053"));
+                // rewards reached the rewards pump ICA, bring them to Neutron
+                let rewards_pump = drop_helpers::get_contracts!(
+                    deps,
+                    config.factory_contract,
+                    rewards_pump_contract
+                );
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: rewards_pump.rewards_pump_contract,
+                    msg: to_json_binary(&drop_staking_base::msg::pump::ExecuteMsg::Push {
+                        coins: items.iter().map(|(_, coin)| coin.clone()).collect(),
+                    })?,
+                    funds: vec![],
+                }));
+            }
             _ => {}
         }
 
@@ -824,7 +1975,7 @@ fn execute_tick_peripheral(
     attrs.push(attr("knot", "000"));
     attrs.push(attr("state", "idle"));
 
-    Ok(response("execute-tick_peripheral", CONTRACT_NAME, attrs))
+    Ok(response("execute-tick_peripheral", CONTRACT_NAME, attrs).add_messages(messages))
 }
 
 fn execute_tick_claiming(
@@ -850,6 +2001,7 @@ fn execute_tick_claiming(
                     if let Some(transfer) = transfer {
                         for id in transfer.batch_ids {
                             let mut batch = unbond_batches_map().load(deps.storage, id)?;
+                            UNBOND_BATCH_UNDELEGATIONS.remove(deps.storage, id);
                             attrs.push(attr("batch_id", id.to_string()));
                             if transfer.emergency {
                                 batch.status = UnbondBatchStatus::WithdrawnEmergency;
@@ -922,10 +2074,13 @@ fn execute_tick_unbonding(
         drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(response) => {
             match response.transaction {
                 drop_puppeteer_base::peripheral_hook::Transaction::Undelegate {
-                    batch_id, ..
+                    batch_id,
+                    items,
+                    ..
                 } => {
                     LAST_PUPPETEER_RESPONSE.remove(deps.storage);
                     attrs.push(attr("batch_id", batch_id.to_string()));
+                    UNBOND_BATCH_UNDELEGATIONS.save(deps.storage, batch_id, &items)?;
                     let mut unbond = unbond_batches_map().load(deps.storage, batch_id)?;
                     unbond.status = UnbondBatchStatus::Unbonding;
                     unbond.status_timestamps.unbonding = Some(env.block.time.seconds());
@@ -939,22 +2094,24 @@ fn execute_tick_unbonding(
                 _ => return Err(ContractError::InvalidTransaction {}),
             }
         }
-        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(response) => match response
-            .transaction
-        {
-            drop_puppeteer_base::peripheral_hook::Transaction::Undelegate { batch_id, .. } => {
-                LAST_PUPPETEER_RESPONSE.remove(deps.storage);
-                attrs.push(attr("batch_id", batch_id.to_string()));
-                let mut unbond = unbond_batches_map().load(deps.storage, batch_id)?;
-                unbond.status = UnbondBatchStatus::UnbondFailed;
-                unbond.status_timestamps.unbond_failed = Some(env.block.time.seconds());
-                unbond_batches_map().save(deps.storage, batch_id, &unbond)?;
-                FAILED_BATCH_ID.save(deps.storage, &batch_id)?;
-                attrs.push(attr("unbonding", "failed"));
-                attrs.push(attr("knot", "031"));
+        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(response) => {
+            match response.transaction {
+                drop_puppeteer_base::peripheral_hook::Transaction::Undelegate {
+                    batch_id, ..
+                } => {
+                    LAST_PUPPETEER_RESPONSE.remove(deps.storage);
+                    attrs.push(attr("batch_id", batch_id.to_string()));
+                    let mut unbond = unbond_batches_map().load(deps.storage, batch_id)?;
+                    unbond.status = UnbondBatchStatus::UnbondFailed;
+                    unbond.status_timestamps.unbond_failed = Some(env.block.time.seconds());
+                    unbond_batches_map().save(deps.storage, batch_id, &unbond)?;
+                    FAILED_BATCH_ID.save(deps.storage, &batch_id)?;
+                    attrs.push(attr("unbonding", "failed"));
+                    attrs.push(attr("knot", "031"));
+                }
+                _ => return Err(ContractError::InvalidTransaction {}),
             }
-            _ => return Err(ContractError::InvalidTransaction {}),
-        },
+        }
     }
     FSM.go_to(deps.storage, ContractState::Idle)?;
     attrs.push(attr("knot", "000"));
@@ -981,88 +2138,154 @@ fn execute_bond(
     let exchange_rate = query_exchange_rate(deps.as_ref(), &config)?;
     attrs.push(attr("exchange_rate", exchange_rate.to_string()));
 
-    let bond_providers = BOND_PROVIDERS.get_all_providers(deps.as_ref().storage)?;
-    let mut bonded = false;
-    for provider in bond_providers {
+    let Some((provider, issue_amount)) =
+        get_bond_provider(deps.as_ref(), &bonded_coin, exchange_rate)?
+    else {
+        return Err(ContractError::BondProviderError {
+            message: "No sufficient bond provider found".into(),
+        });
+    };
+    attrs.push(attr("used_bond_provider", provider.to_string()));
+    attrs.push(attr("issue_amount", issue_amount.to_string()));
+
+    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: provider.to_string(),
+        msg: to_json_binary(&drop_staking_base::msg::bond_provider::ExecuteMsg::Bond {})?,
+        funds: vec![Coin::new(amount.u128(), denom.clone())],
+    }));
+
+    let receiver = receiver.map_or(Ok::<String, ContractError>(info.sender.to_string()), |a| {
+        deps.api.addr_validate(&a)?;
+        Ok(a)
+    })?;
+    attrs.push(attr("receiver", receiver.clone()));
+    if let Some(r#ref) = r#ref.clone() {
+        if !r#ref.is_empty() {
+            attrs.push(attr("ref", r#ref));
+        }
+    }
+    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.token_contract.to_string(),
+        msg: to_json_binary(&TokenExecuteMsg::Mint {
+            amount: issue_amount,
+            receiver,
+        })?,
+        funds: vec![],
+    }));
+
+    let bond_hooks = BOND_HOOKS.load(deps.storage)?;
+    if !bond_hooks.is_empty() {
+        let hook_msg = BondHook {
+            amount: bonded_coin.amount,
+            denom: bonded_coin.denom,
+            sender: info.sender,
+            dasset_minted: issue_amount,
+            r#ref,
+        };
+        for hook in bond_hooks {
+            let msg = WasmMsg::Execute {
+                contract_addr: hook.into_string(),
+                msg: to_json_binary(&BondCallback::BondCallback(hook_msg.clone()))?,
+                funds: vec![],
+            };
+            msgs.push(msg.into());
+        }
+    }
+
+    Ok(response("execute-bond", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+fn execute_bond_from_provider(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    amount: Uint128,
+    receiver: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    if PAUSE.load(deps.storage)?.bond {
+        return Err(drop_helpers::pause::PauseError::Paused {}.into());
+    }
+    ensure!(
+        BOND_PROVIDERS
+            .providers
+            .has(deps.storage, info.sender.clone()),
+        ContractError::Unauthorized {}
+    );
+    deps.api.addr_validate(&receiver)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let addrs = drop_helpers::get_contracts!(deps, config.factory_contract, token_contract);
+    let exchange_rate = query_exchange_rate(deps.as_ref(), &config)?;
+    let issue_amount: Uint128 = deps.querier.query_wasm_smart(
+        info.sender.to_string(),
+        &drop_staking_base::msg::bond_provider::QueryMsg::TokensAmount {
+            coin: Coin::new(amount.u128(), config.base_denom),
+            exchange_rate,
+        },
+    )?;
+
+    let attrs = vec![
+        attr("action", "bond_from_provider"),
+        attr("used_bond_provider", info.sender),
+        attr("exchange_rate", exchange_rate.to_string()),
+        attr("issue_amount", issue_amount.to_string()),
+        attr("receiver", receiver.clone()),
+    ];
+    let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.token_contract,
+        msg: to_json_binary(&TokenExecuteMsg::Mint {
+            amount: issue_amount,
+            receiver,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(response("execute-bond_from_provider", CONTRACT_NAME, attrs).add_message(mint_msg))
+}
+
+// walks bond providers in routing order and returns the first one able to take the coin
+fn get_bond_provider(
+    deps: Deps<NeutronQuery>,
+    coin: &Coin,
+    exchange_rate: Decimal,
+) -> ContractResult<Option<(Addr, Uint128)>> {
+    for (provider, route) in BOND_PROVIDERS.get_routed_providers(deps.storage, &coin.denom)? {
         let can_bond = deps.querier.query_wasm_smart::<bool>(
             provider.to_string(),
             &drop_staking_base::msg::bond_provider::QueryMsg::CanBond {
-                denom: denom.clone(),
+                denom: coin.denom.clone(),
             },
         );
-        if can_bond.unwrap_or(false) {
-            attrs.push(attr("used_bond_provider", provider.to_string()));
-            let issue_amount: Uint128 = deps.querier.query_wasm_smart(
+        if !can_bond.unwrap_or(false) {
+            continue;
+        }
+        if let Some(async_tokens_cap) = route.async_tokens_cap {
+            let async_tokens_amount: Uint128 = deps.querier.query_wasm_smart(
                 provider.to_string(),
-                &drop_staking_base::msg::bond_provider::QueryMsg::TokensAmount {
-                    coin: Coin::new(amount.u128(), denom.clone()),
-                    exchange_rate,
-                },
+                &drop_staking_base::msg::bond_provider::QueryMsg::AsyncTokensAmount {},
             )?;
-            attrs.push(attr("issue_amount", issue_amount.to_string()));
-
-            let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: provider.to_string(),
-                msg: to_json_binary(&drop_staking_base::msg::bond_provider::ExecuteMsg::Bond {})?,
-                funds: vec![Coin::new(amount.u128(), denom.clone())],
-            });
-
-            msgs.push(msg);
-
-            let receiver = receiver.clone().map_or(
-                Ok::<String, ContractError>(info.sender.to_string()),
-                |a| {
-                    deps.api.addr_validate(&a)?;
-                    Ok(a)
+            // the cap is in base denom units while the coin may be e.g. LSM shares,
+            // so its value is taken from the provider at a rate of one
+            let bond_amount: Uint128 = deps.querier.query_wasm_smart(
+                provider.to_string(),
+                &drop_staking_base::msg::bond_provider::QueryMsg::TokensAmount {
+                    coin: coin.clone(),
+                    exchange_rate: Decimal::one(),
                 },
             )?;
-            attrs.push(attr("receiver", receiver.clone()));
-            if let Some(r#ref) = r#ref.clone() {
-                if !r#ref.is_empty() {
-                    attrs.push(attr("ref", r#ref));
-                }
+            if async_tokens_amount.checked_add(bond_amount)? > async_tokens_cap {
+                continue;
             }
-            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: addrs.token_contract.to_string(),
-                msg: to_json_binary(&TokenExecuteMsg::Mint {
-                    amount: issue_amount,
-                    receiver,
-                })?,
-                funds: vec![],
-            }));
-
-            let bond_hooks = BOND_HOOKS.load(deps.storage)?;
-            if !bond_hooks.is_empty() {
-                let hook_msg = BondHook {
-                    amount: bonded_coin.amount,
-                    denom: bonded_coin.denom,
-                    sender: info.sender,
-                    dasset_minted: issue_amount,
-                    r#ref,
-                };
-                for hook in bond_hooks {
-                    let msg = WasmMsg::Execute {
-                        contract_addr: hook.into_string(),
-                        msg: to_json_binary(&BondCallback::BondCallback(hook_msg.clone()))?,
-                        funds: vec![],
-                    };
-                    msgs.push(msg.into());
-                }
-            }
-
-            bonded = true;
-            break;
         }
+        let issue_amount: Uint128 = deps.querier.query_wasm_smart(
+            provider.to_string(),
+            &drop_staking_base::msg::bond_provider::QueryMsg::TokensAmount {
+                coin: coin.clone(),
+                exchange_rate,
+            },
+        )?;
+        return Ok(Some((provider, issue_amount)));
     }
-
-    ensure!(
-        bonded,
-        ContractError::BondProviderError {
-            message: "No sufficient bond provider found".into()
-        }
-    );
-
-    Ok(response("execute-bond", CONTRACT_NAME, attrs).add_messages(msgs))
+    Ok(None)
 }
 
 fn execute_update_config(
@@ -1135,7 +2358,6 @@ fn execute_unbond(
     }
 
     let attrs = vec![attr("action", "unbond")];
-    let unbond_batch_id = UNBOND_BATCH_ID.load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
     let ld_denom = LD_DENOM.load(deps.storage)?;
     let dasset_amount = cw_utils::must_pay(&info, &ld_denom)?;
@@ -1145,10 +2367,603 @@ fn execute_unbond(
         withdrawal_voucher_contract,
         token_contract
     );
-    let mut unbond_batch = unbond_batches_map().load(deps.storage, unbond_batch_id)?;
+    let (_, _, msgs) = add_to_unbond_batch(
+        deps.storage,
+        info.sender.as_str(),
+        dasset_amount,
+        ld_denom,
+        addrs.withdrawal_voucher_contract,
+        addrs.token_contract,
+    )?;
+    Ok(response("execute-unbond", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+fn execute_instant_unbond(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    min_receive: Uint128,
+) -> ContractResult<Response<NeutronMsg>> {
+    if PAUSE.load(deps.storage)?.unbond {
+        return Err(drop_helpers::pause::PauseError::Paused {}.into());
+    }
+
+    let instant_unbond_config = INSTANT_UNBOND_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::InstantUnbondDisabled {})?;
+    let config = CONFIG.load(deps.storage)?;
+    let ld_denom = LD_DENOM.load(deps.storage)?;
+    let dasset_amount = cw_utils::must_pay(&info, &ld_denom)?;
+    let mut attrs = vec![attr("action", "instant_unbond")];
+
+    let exchange_rate = query_exchange_rate(deps.as_ref(), &config)?;
+    attrs.push(attr("exchange_rate", exchange_rate.to_string()));
+    let native_amount = dasset_amount * exchange_rate;
+    let fee = native_amount * instant_unbond_config.fee;
+    let payout = native_amount.checked_sub(fee)?;
+    ensure!(
+        payout >= min_receive,
+        ContractError::InstantUnbondMinReceive {
+            payout,
+            min_receive
+        }
+    );
+
+    let available = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    ensure!(
+        payout <= available,
+        ContractError::LiquidityBufferInsufficient {
+            available,
+            requested: payout,
+        }
+    );
+    LIQUIDITY_BUFFER.save(deps.storage, &(available - payout))?;
+
+    let mut epoch = get_instant_unbond_epoch(
+        deps.storage,
+        env.block.time.seconds(),
+        &instant_unbond_config,
+    )?;
+    let epoch_available = instant_unbond_config
+        .epoch_limit
+        .saturating_sub(epoch.paid_out);
+    ensure!(
+        payout <= epoch_available,
+        ContractError::InstantUnbondEpochLimitExceeded {
+            available: epoch_available,
+            requested: payout,
+        }
+    );
+    epoch.paid_out += payout;
+    INSTANT_UNBOND_EPOCH.save(deps.storage, &epoch)?;
+
+    let addrs = drop_helpers::get_contracts!(
+        deps,
+        config.factory_contract,
+        withdrawal_voucher_contract,
+        token_contract
+    );
+    // the voucher is kept by the core, so the buffer is refilled once the batch is withdrawn
+    let (batch_id, token_id, mut msgs) = add_to_unbond_batch(
+        deps.storage,
+        env.contract.address.as_str(),
+        dasset_amount,
+        ld_denom,
+        addrs.withdrawal_voucher_contract,
+        addrs.token_contract,
+    )?;
+    LIQUIDITY_BUFFER_VOUCHERS.save(
+        deps.storage,
+        &token_id,
+        &LiquidityBufferVoucher {
+            batch_id,
+            dasset_amount,
+        },
+    )?;
+    msgs.push(CosmosMsg::Bank(BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: vec![Coin::new(payout.u128(), config.base_denom)],
+    }));
+
+    attrs.push(attr("batch_id", batch_id.to_string()));
+    attrs.push(attr("dasset_amount", dasset_amount));
+    attrs.push(attr("fee", fee));
+    attrs.push(attr("payout", payout));
+
+    Ok(response("execute-instant_unbond", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+fn execute_unbond_in_kind(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    validator: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    if PAUSE.load(deps.storage)?.unbond {
+        return Err(drop_helpers::pause::PauseError::Paused {}.into());
+    }
+
+    UNBOND_IN_KIND_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::UnbondInKindDisabled {})?;
+    let config = CONFIG.load(deps.storage)?;
+    let ld_denom = LD_DENOM.load(deps.storage)?;
+    let dasset_amount = cw_utils::must_pay(&info, &ld_denom)?;
+    let mut attrs = vec![attr("action", "unbond_in_kind")];
+
+    let exchange_rate = query_exchange_rate(deps.as_ref(), &config)?;
+    attrs.push(attr("exchange_rate", exchange_rate.to_string()));
+    let amount = dasset_amount * exchange_rate;
+
+    let addrs = drop_helpers::get_contracts!(
+        deps,
+        config.factory_contract,
+        puppeteer_contract,
+        validators_set_contract
+    );
+    let validator_info = deps
+        .querier
+        .query_wasm_smart::<drop_staking_base::msg::validatorset::ValidatorResponse>(
+            &addrs.validators_set_contract,
+            &drop_staking_base::msg::validatorset::QueryMsg::Validator {
+                valoper: validator.to_string(),
+            },
+        )?
+        .validator;
+    ensure!(
+        validator_info.is_some(),
+        ContractError::UnbondInKindUnknownValidator {
+            validator: validator.to_string()
+        }
+    );
+    let delegations_response = deps
+        .querier
+        .query_wasm_smart::<drop_staking_base::msg::puppeteer::DelegationsResponse>(
+        &addrs.puppeteer_contract,
+        &drop_puppeteer_base::msg::QueryMsg::Extension {
+            msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Delegations {},
+        },
+    )?;
+    let delegated: Uint128 = delegations_response
+        .delegations
+        .delegations
+        .iter()
+        .filter(|delegation| delegation.validator == validator)
+        .map(|delegation| delegation.amount.amount)
+        .sum();
+    // stake of requests still being tokenized is not reflected in delegations yet
+    let tokenizing: Uint128 = UNBOND_IN_KIND_REQUESTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((_, request))
+                if request.status == UnbondInKindStatus::Tokenizing
+                    && request.validator == validator =>
+            {
+                Some(Ok(request.amount))
+            }
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .sum::<StdResult<Uint128>>()?;
+    ensure!(
+        delegated.saturating_sub(tokenizing) >= amount,
+        ContractError::UnbondInKindInsufficientDelegation {
+            validator: validator.to_string()
+        }
+    );
+
+    let id = UNBOND_IN_KIND_NEXT_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    UNBOND_IN_KIND_NEXT_ID.save(deps.storage, &(id + 1))?;
+    // dAsset is burned only when the delegation is tokenized and returned if it fails
+    UNBOND_IN_KIND_REQUESTS.save(
+        deps.storage,
+        id,
+        &UnbondInKindRequest {
+            receiver: info.sender.clone(),
+            validator: validator.clone(),
+            dasset_amount,
+            amount,
+            share: None,
+            status: UnbondInKindStatus::Tokenizing,
+            transferred_at: None,
+        },
+    )?;
+
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.puppeteer_contract,
+        msg: to_json_binary(
+            &drop_staking_base::msg::puppeteer::ExecuteMsg::TokenizeShare {
+                validator: validator.clone(),
+                amount,
+                request_id: Some(id),
+                reply_to: env.contract.address.to_string(),
+            },
+        )?,
+        funds: vec![],
+    });
+
+    attrs.push(attr("id", id.to_string()));
+    attrs.push(attr("validator", validator));
+    attrs.push(attr("dasset_amount", dasset_amount));
+    attrs.push(attr("amount", amount));
+
+    Ok(response("execute-unbond_in_kind", CONTRACT_NAME, attrs).add_message(msg))
+}
+
+fn execute_retry_unbond_in_kind(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    share: Option<Coin>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let in_kind_config = UNBOND_IN_KIND_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::UnbondInKindDisabled {})?;
+    let mut request = UNBOND_IN_KIND_REQUESTS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::UnbondInKindRequestNotFound { id })?;
+    let config = CONFIG.load(deps.storage)?;
+    let addrs = drop_helpers::get_contracts!(
+        deps,
+        config.factory_contract,
+        token_contract,
+        puppeteer_contract
+    );
+
+    let mut messages = vec![];
+    let share = match (&request.status, request.share.clone(), share) {
+        (UnbondInKindStatus::TransferFailed, Some(share), None) => share,
+        // the delegation was tokenized, but the ack didn't tell the share denom
+        (UnbondInKindStatus::TransferFailed, None, Some(share)) => {
+            cw_ownable::assert_owner(deps.storage, &info.sender)?;
+            ensure!(
+                share.denom.starts_with(&format!("{}/", request.validator)),
+                ContractError::InvalidDenom {}
+            );
+            messages.push(get_unbond_in_kind_burn_msg(
+                deps.storage,
+                addrs.token_contract,
+                &request,
+            )?);
+            request.share = Some(share.clone());
+            share
+        }
+        (UnbondInKindStatus::TransferFailed, None, None) => {
+            return Err(ContractError::UnbondInKindShareRequired { id })
+        }
+        // the transfer may have timed out on the host chain, returning the share to the ICA
+        (UnbondInKindStatus::Transferred, Some(share), None) => {
+            ensure!(
+                info.sender == request.receiver,
+                ContractError::Unauthorized {}
+            );
+            let transferred_at = request.transferred_at.unwrap_or_default();
+            ensure!(
+                env.block.time.seconds() >= transferred_at + in_kind_config.transfer_timeout,
+                ContractError::UnbondInKindNotRetryable { id }
+            );
+            share
+        }
+        _ => return Err(ContractError::UnbondInKindNotRetryable { id }),
+    };
+    request.status = UnbondInKindStatus::Transferring;
+    request.transferred_at = None;
+    UNBOND_IN_KIND_REQUESTS.save(deps.storage, id, &request)?;
+
+    messages.push(get_transfer_share_msg(
+        addrs.puppeteer_contract,
+        &env,
+        &in_kind_config,
+        id,
+        &request,
+        share.clone(),
+    )?);
+
+    Ok(response(
+        "execute-retry_unbond_in_kind",
+        CONTRACT_NAME,
+        [
+            attr("action", "retry_unbond_in_kind"),
+            attr("id", id.to_string()),
+            attr("share", share.to_string()),
+        ],
+    )
+    .add_messages(messages))
+}
+
+fn execute_confirm_unbond_in_kind(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let request = UNBOND_IN_KIND_REQUESTS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::UnbondInKindRequestNotFound { id })?;
+    ensure!(
+        info.sender == request.receiver,
+        ContractError::Unauthorized {}
+    );
+    ensure!(
+        request.status == UnbondInKindStatus::Transferred,
+        ContractError::UnbondInKindNotTransferred { id }
+    );
+    UNBOND_IN_KIND_REQUESTS.remove(deps.storage, id);
+
+    Ok(response(
+        "execute-confirm_unbond_in_kind",
+        CONTRACT_NAME,
+        [
+            attr("action", "confirm_unbond_in_kind"),
+            attr("id", id.to_string()),
+        ],
+    ))
+}
+
+fn execute_unbond_in_kind_hook(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    config: Config,
+    msg: drop_puppeteer_base::peripheral_hook::ResponseHookMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let mut attrs = vec![attr("action", "unbond_in_kind_hook")];
+    let mut messages = vec![];
+    match msg {
+        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(success_msg) => {
+            LAST_ICA_CHANGE_HEIGHT.save(deps.storage, &success_msg.remote_height)?;
+            match success_msg.transaction {
+                drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare {
+                    share,
+                    request_id,
+                    ..
+                } => {
+                    if let Some((id, mut request)) = may_load_unbond_in_kind_request(
+                        deps.storage,
+                        request_id,
+                        UnbondInKindStatus::Tokenizing,
+                    )? {
+                        attrs.push(attr("id", id.to_string()));
+                        // nothing to send without a share, the request waits for a retry
+                        request.status = UnbondInKindStatus::TransferFailed;
+                        // dAsset stays escrowed until the share denom is known
+                        if let Some(share) = share {
+                            let addrs = drop_helpers::get_contracts!(
+                                deps,
+                                config.factory_contract,
+                                token_contract,
+                                puppeteer_contract
+                            );
+                            messages.push(get_unbond_in_kind_burn_msg(
+                                deps.storage,
+                                addrs.token_contract,
+                                &request,
+                            )?);
+                            request.share = Some(share.clone());
+                            attrs.push(attr("share", share.to_string()));
+                            if let Some(in_kind_config) =
+                                UNBOND_IN_KIND_CONFIG.may_load(deps.storage)?
+                            {
+                                messages.push(get_transfer_share_msg(
+                                    addrs.puppeteer_contract,
+                                    &env,
+                                    &in_kind_config,
+                                    id,
+                                    &request,
+                                    share,
+                                )?);
+                                request.status = UnbondInKindStatus::Transferring;
+                            }
+                        }
+                        UNBOND_IN_KIND_REQUESTS.save(deps.storage, id, &request)?;
+                    }
+                }
+                drop_puppeteer_base::peripheral_hook::Transaction::TransferShare {
+                    share,
+                    request_id,
+                    ..
+                } => {
+                    if let Some((id, mut request)) = may_load_unbond_in_kind_request(
+                        deps.storage,
+                        request_id,
+                        UnbondInKindStatus::Transferring,
+                    )? {
+                        attrs.push(attr("id", id.to_string()));
+                        attrs.push(attr("share", share.to_string()));
+                        // the IBC transfer itself is only acked on the host chain,
+                        // so the request stays until the receiver confirms it
+                        request.status = UnbondInKindStatus::Transferred;
+                        request.transferred_at = Some(env.block.time.seconds());
+                        UNBOND_IN_KIND_REQUESTS.save(deps.storage, id, &request)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(err_msg) => {
+            match err_msg.transaction {
+                drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare {
+                    request_id,
+                    ..
+                } => {
+                    if let Some((id, request)) = may_load_unbond_in_kind_request(
+                        deps.storage,
+                        request_id,
+                        UnbondInKindStatus::Tokenizing,
+                    )? {
+                        attrs.push(attr("id", id.to_string()));
+                        attrs.push(attr("refund", request.dasset_amount));
+                        messages.push(CosmosMsg::Bank(BankMsg::Send {
+                            to_address: request.receiver.to_string(),
+                            amount: vec![Coin {
+                                denom: LD_DENOM.load(deps.storage)?,
+                                amount: request.dasset_amount,
+                            }],
+                        }));
+                        UNBOND_IN_KIND_REQUESTS.remove(deps.storage, id);
+                    }
+                }
+                drop_puppeteer_base::peripheral_hook::Transaction::TransferShare {
+                    request_id,
+                    ..
+                } => {
+                    if let Some((id, mut request)) = may_load_unbond_in_kind_request(
+                        deps.storage,
+                        request_id,
+                        UnbondInKindStatus::Transferring,
+                    )? {
+                        attrs.push(attr("id", id.to_string()));
+                        request.status = UnbondInKindStatus::TransferFailed;
+                        UNBOND_IN_KIND_REQUESTS.save(deps.storage, id, &request)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(response("execute-unbond_in_kind_hook", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn may_load_unbond_in_kind_request(
+    storage: &dyn Storage,
+    request_id: Option<u64>,
+    status: UnbondInKindStatus,
+) -> StdResult<Option<(u64, UnbondInKindRequest)>> {
+    let Some(id) = request_id else {
+        return Ok(None);
+    };
+    Ok(UNBOND_IN_KIND_REQUESTS
+        .may_load(storage, id)?
+        .filter(|request| request.status == status)
+        .map(|request| (id, request)))
+}
+
+fn get_unbond_in_kind_burn_msg(
+    storage: &dyn Storage,
+    token_contract: String,
+    request: &UnbondInKindRequest,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token_contract,
+        msg: to_json_binary(&TokenExecuteMsg::Burn {})?,
+        funds: vec![Coin {
+            denom: LD_DENOM.load(storage)?,
+            amount: request.dasset_amount,
+        }],
+    }))
+}
+
+fn get_transfer_share_msg(
+    puppeteer_contract: String,
+    env: &Env,
+    in_kind_config: &UnbondInKindConfig,
+    id: u64,
+    request: &UnbondInKindRequest,
+    share: Coin,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: puppeteer_contract,
+        msg: to_json_binary(
+            &drop_staking_base::msg::puppeteer::ExecuteMsg::TransferShare {
+                share,
+                channel_id: in_kind_config.remote_transfer_channel_id.to_string(),
+                receiver: request.receiver.to_string(),
+                request_id: Some(id),
+                reply_to: env.contract.address.to_string(),
+            },
+        )?,
+        funds: vec![],
+    }))
+}
+
+fn get_instant_unbond_epoch(
+    storage: &dyn Storage,
+    now: u64,
+    instant_unbond_config: &InstantUnbondConfig,
+) -> StdResult<InstantUnbondEpoch> {
+    let epoch = INSTANT_UNBOND_EPOCH.may_load(storage)?.unwrap_or_default();
+    if now >= epoch.start + instant_unbond_config.epoch_duration {
+        return Ok(InstantUnbondEpoch {
+            start: now,
+            paid_out: Uint128::zero(),
+        });
+    }
+    Ok(epoch)
+}
+
+fn get_liquidity_buffer_refill_msgs(
+    deps: DepsMut<NeutronQuery>,
+    config: &Config,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let mut ready_vouchers = vec![];
+    for item in LIQUIDITY_BUFFER_VOUCHERS.range(deps.storage, None, None, Order::Ascending) {
+        let (token_id, voucher) = item?;
+        let batch = unbond_batches_map().load(deps.storage, voucher.batch_id)?;
+        if batch.status == UnbondBatchStatus::Withdrawn {
+            // the same share of the batch the withdrawal manager pays out for the voucher
+            let payout =
+                Decimal::from_ratio(voucher.dasset_amount, batch.total_dasset_amount_to_withdraw)
+                    * batch.unbonded_amount.unwrap_or_default();
+            ready_vouchers.push((token_id, payout));
+        }
+    }
+    if ready_vouchers.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let addrs = drop_helpers::get_contracts!(
+        deps,
+        config.factory_contract,
+        withdrawal_voucher_contract,
+        withdrawal_manager_contract
+    );
+    // paused withdrawal manager must not block the tick, we'll try again next time
+    let withdrawal_manager_pause: PauseInfoResponse = deps.querier.query_wasm_smart(
+        &addrs.withdrawal_manager_contract,
+        &WithdrawalManagerQueryMsg::PauseInfo {},
+    )?;
+    if matches!(withdrawal_manager_pause, PauseInfoResponse::Paused {}) {
+        attrs.push(attr("liquidity_buffer_refill", "postponed"));
+        return Ok(vec![]);
+    }
+
+    let mut messages = vec![];
+    let mut refill = Uint128::zero();
+    for (token_id, payout) in ready_vouchers {
+        LIQUIDITY_BUFFER_VOUCHERS.remove(deps.storage, &token_id);
+        refill += payout;
+        attrs.push(attr("liquidity_buffer_refill", &token_id));
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: addrs.withdrawal_voucher_contract.clone(),
+            msg: to_json_binary(&VoucherExecuteMsg::SendNft {
+                contract: addrs.withdrawal_manager_contract.clone(),
+                token_id,
+                msg: to_json_binary(&WithdrawalManagerReceiveNftMsg::Withdraw { receiver: None })?,
+            })?,
+            funds: vec![],
+        }));
+    }
+    let buffer = LIQUIDITY_BUFFER.may_load(deps.storage)?.unwrap_or_default();
+    LIQUIDITY_BUFFER.save(deps.storage, &(buffer + refill))?;
+    Ok(messages)
+}
+
+fn add_to_unbond_batch(
+    storage: &mut dyn Storage,
+    owner: &str,
+    dasset_amount: Uint128,
+    ld_denom: String,
+    withdrawal_voucher_contract: String,
+    token_contract: String,
+) -> ContractResult<(u128, String, Vec<CosmosMsg<NeutronMsg>>)> {
+    let unbond_batch_id = UNBOND_BATCH_ID.load(storage)?;
+    let mut unbond_batch = unbond_batches_map().load(storage, unbond_batch_id)?;
     unbond_batch.total_unbond_items += 1;
     unbond_batch.total_dasset_amount_to_withdraw += dasset_amount;
-    unbond_batches_map().save(deps.storage, unbond_batch_id, &unbond_batch)?;
+    unbond_batches_map().save(storage, unbond_batch_id, &unbond_batch)?;
 
     let extension = Some(Metadata {
         description: Some("Withdrawal voucher".into()),
@@ -1169,23 +2984,24 @@ fn execute_unbond(
         ]),
     });
 
+    let token_id = unbond_batch_id.to_string()
+        + "_"
+        + owner
+        + "_"
+        + &unbond_batch.total_unbond_items.to_string();
     let msgs = vec![
         CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: addrs.withdrawal_voucher_contract,
+            contract_addr: withdrawal_voucher_contract,
             msg: to_json_binary(&VoucherExecuteMsg::Mint {
-                owner: info.sender.to_string(),
-                token_id: unbond_batch_id.to_string()
-                    + "_"
-                    + info.sender.to_string().as_str()
-                    + "_"
-                    + &unbond_batch.total_unbond_items.to_string(),
+                owner: owner.to_string(),
+                token_id: token_id.clone(),
                 token_uri: None,
                 extension,
             })?,
             funds: vec![],
         }),
         CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: addrs.token_contract,
+            contract_addr: token_contract,
             msg: to_json_binary(&TokenExecuteMsg::Burn {})?,
             funds: vec![Coin {
                 denom: ld_denom,
@@ -1193,7 +3009,7 @@ fn execute_unbond(
             }],
         }),
     ];
-    Ok(response("execute-unbond", CONTRACT_NAME, attrs).add_messages(msgs))
+    Ok((unbond_batch_id, token_id, msgs))
 }
 
 fn check_latest_icq_responses(
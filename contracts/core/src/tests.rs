@@ -1736,6 +1736,84 @@ fn test_bond_with_receiver() {
     );
 }
 
+#[test]
+fn test_bond_from_provider() {
+    let mut deps = mock_dependencies(&[]);
+    mock_state_query(&mut deps);
+    BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+    BOND_PROVIDERS
+        .add(
+            deps.as_mut().storage,
+            Addr::unchecked("deposit_provider_address"),
+        )
+        .unwrap();
+    deps.querier
+        .add_wasm_query_response("deposit_provider_address", |_| {
+            to_json_binary(&Uint128::from(1000u128)).unwrap()
+        });
+    FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+        .unwrap();
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config(1000, 100, 600))
+        .unwrap();
+    LD_DENOM
+        .save(deps.as_mut().storage, &"ld_denom".into())
+        .unwrap();
+    PAUSE
+        .save(deps.as_mut().storage, &Pause::default())
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("deposit_provider_address", &[]),
+        ExecuteMsg::BondFromProvider {
+            amount: Uint128::from(1000u128),
+            receiver: "receiver".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-core-execute-bond_from_provider")
+                    .add_attribute("action", "bond_from_provider")
+                    .add_attribute("used_bond_provider", "deposit_provider_address")
+                    .add_attribute("exchange_rate", "1")
+                    .add_attribute("issue_amount", "1000")
+                    .add_attribute("receiver", "receiver")
+            )
+            .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Mint {
+                    amount: Uint128::from(1000u128),
+                    receiver: "receiver".to_string()
+                })
+                .unwrap(),
+                funds: vec![],
+            }))
+    );
+}
+
+#[test]
+fn test_bond_from_provider_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+    PAUSE
+        .save(deps.as_mut().storage, &Pause::default())
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::BondFromProvider {
+            amount: Uint128::from(1000u128),
+            receiver: "receiver".to_string(),
+        },
+    );
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+}
+
 #[test]
 fn check_failed_batch_query_deserialization() {
     let mut deps = mock_dependencies(&[]);
@@ -2927,3 +3005,2951 @@ mod pause {
         }
     }
 }
+
+mod instant_unbond {
+    use super::*;
+    use drop_staking_base::{
+        msg::core::LiquidityBufferResponse,
+        state::core::{
+            InstantUnbondConfig, InstantUnbondEpoch, LiquidityBufferVoucher, INSTANT_UNBOND_CONFIG,
+            INSTANT_UNBOND_EPOCH, LIQUIDITY_BUFFER, LIQUIDITY_BUFFER_VOUCHERS,
+        },
+    };
+
+    fn setup(
+        buffer: u128,
+        instant_unbond_config: Option<InstantUnbondConfig>,
+    ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        // the core holds more base asset than the buffer, e.g. pending withdrawals
+        let mut deps = mock_dependencies(&[Coin::new(buffer + 10000, "base_denom")]);
+        mock_state_query(&mut deps);
+        {
+            let deps_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+        }
+        LIQUIDITY_BUFFER
+            .save(deps.as_mut().storage, &Uint128::new(buffer))
+            .unwrap();
+        FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+            .unwrap();
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 100, 600))
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        PAUSE
+            .save(deps.as_mut().storage, &Pause::default())
+            .unwrap();
+        UNBOND_BATCH_ID.save(deps.as_mut().storage, &0u128).unwrap();
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                0,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::zero(),
+                    expected_native_asset_amount: Uint128::zero(),
+                    total_unbond_items: 0,
+                    status: UnbondBatchStatus::New,
+                    expected_release_time: 0,
+                    slashing_effect: None,
+                    unbonded_amount: None,
+                    withdrawn_amount: None,
+                    status_timestamps: get_default_unbond_batch_status_timestamps(),
+                },
+            )
+            .unwrap();
+        if let Some(instant_unbond_config) = instant_unbond_config {
+            INSTANT_UNBOND_CONFIG
+                .save(deps.as_mut().storage, &instant_unbond_config)
+                .unwrap();
+        }
+        deps
+    }
+
+    fn default_instant_unbond_config() -> InstantUnbondConfig {
+        InstantUnbondConfig {
+            fee: Decimal::from_ratio(1u128, 100u128),
+            epoch_duration: 100,
+            epoch_limit: Uint128::new(500),
+        }
+    }
+
+    #[test]
+    fn disabled() {
+        let mut deps = setup(1000, None);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::InstantUnbond {
+                min_receive: Uint128::zero(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InstantUnbondDisabled {});
+    }
+
+    #[test]
+    fn update_config_invalid_fee() {
+        let mut deps = setup(1000, None);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateInstantUnbondConfig {
+                config: Some(InstantUnbondConfig {
+                    fee: Decimal::from_ratio(2u128, 1u128),
+                    ..default_instant_unbond_config()
+                }),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFee {});
+    }
+
+    #[test]
+    fn update_config_full_fee() {
+        // a fee taking the whole payout would burn the dAsset for nothing
+        let mut deps = setup(1000, None);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateInstantUnbondConfig {
+                config: Some(InstantUnbondConfig {
+                    fee: Decimal::one(),
+                    ..default_instant_unbond_config()
+                }),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFee {});
+    }
+
+    #[test]
+    fn min_receive_not_reached() {
+        let mut deps = setup(1000, Some(default_instant_unbond_config()));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::InstantUnbond {
+                min_receive: Uint128::new(100),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InstantUnbondMinReceive {
+                payout: Uint128::new(99),
+                min_receive: Uint128::new(100),
+            }
+        );
+    }
+
+    #[test]
+    fn buffer_insufficient() {
+        let mut deps = setup(50, Some(default_instant_unbond_config()));
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::InstantUnbond {
+                min_receive: Uint128::zero(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::LiquidityBufferInsufficient {
+                available: Uint128::new(50),
+                requested: Uint128::new(99),
+            }
+        );
+    }
+
+    #[test]
+    fn epoch_limit_exceeded() {
+        let mut deps = setup(1000, Some(default_instant_unbond_config()));
+        let env = mock_env();
+        INSTANT_UNBOND_EPOCH
+            .save(
+                deps.as_mut().storage,
+                &InstantUnbondEpoch {
+                    start: env.block.time.seconds() - 10,
+                    paid_out: Uint128::new(450),
+                },
+            )
+            .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env,
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::InstantUnbond {
+                min_receive: Uint128::zero(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InstantUnbondEpochLimitExceeded {
+                available: Uint128::new(50),
+                requested: Uint128::new(99),
+            }
+        );
+    }
+
+    #[test]
+    fn success() {
+        let mut deps = setup(1000, Some(default_instant_unbond_config()));
+        let env = mock_env();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::InstantUnbond {
+                min_receive: Uint128::new(99),
+            },
+        )
+        .unwrap();
+        let token_id = format!("0_{}_1", env.contract.address);
+        let extension = Some(drop_staking_base::state::withdrawal_voucher::Metadata {
+            description: Some("Withdrawal voucher".into()),
+            name: "LDV voucher".to_string(),
+            batch_id: "0".to_string(),
+            amount: Uint128::from(100u128),
+            attributes: Some(vec![
+                drop_staking_base::state::withdrawal_voucher::Trait {
+                    display_type: None,
+                    trait_type: "unbond_batch_id".to_string(),
+                    value: "0".to_string(),
+                },
+                drop_staking_base::state::withdrawal_voucher::Trait {
+                    display_type: None,
+                    trait_type: "received_amount".to_string(),
+                    value: "100".to_string(),
+                },
+            ]),
+        });
+        assert_eq!(
+            res,
+            Response::new()
+                .add_event(
+                    Event::new("crates.io:drop-staking__drop-core-execute-instant_unbond")
+                        .add_attribute("action", "instant_unbond")
+                        .add_attribute("exchange_rate", "1")
+                        .add_attribute("batch_id", "0")
+                        .add_attribute("dasset_amount", "100")
+                        .add_attribute("fee", "1")
+                        .add_attribute("payout", "99")
+                )
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "withdrawal_voucher_contract".to_string(),
+                    msg: to_json_binary(
+                        &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::Mint {
+                            token_id: token_id.clone(),
+                            owner: env.contract.address.to_string(),
+                            token_uri: None,
+                            extension,
+                        }
+                    )
+                    .unwrap(),
+                    funds: vec![],
+                }))
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "token_contract".to_string(),
+                    msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Burn {})
+                        .unwrap(),
+                    funds: vec![Coin::new(100u128, "ld_denom")],
+                }))
+                .add_message(CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                    to_address: "some_sender".to_string(),
+                    amount: vec![Coin::new(99u128, "base_denom")],
+                }))
+        );
+        assert_eq!(
+            LIQUIDITY_BUFFER_VOUCHERS
+                .load(deps.as_ref().storage, &token_id)
+                .unwrap(),
+            LiquidityBufferVoucher {
+                batch_id: 0,
+                dasset_amount: Uint128::new(100),
+            }
+        );
+        assert_eq!(
+            INSTANT_UNBOND_EPOCH.load(deps.as_ref().storage).unwrap(),
+            InstantUnbondEpoch {
+                start: env.block.time.seconds(),
+                paid_out: Uint128::new(99),
+            }
+        );
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 0).unwrap();
+        assert_eq!(batch.total_dasset_amount_to_withdraw, Uint128::new(100));
+        assert_eq!(batch.total_unbond_items, 1);
+
+        let buffer: LiquidityBufferResponse =
+            from_json(query(deps.as_ref(), env.clone(), QueryMsg::LiquidityBuffer {}).unwrap())
+                .unwrap();
+        assert_eq!(
+            buffer,
+            LiquidityBufferResponse {
+                available: Uint128::new(901),
+                pending_refill_dasset: Uint128::new(100),
+                epoch_start: env.block.time.seconds(),
+                epoch_paid_out: Uint128::new(99),
+                epoch_limit: Uint128::new(500),
+            }
+        );
+    }
+
+    #[test]
+    fn fund_liquidity_buffer() {
+        let mut deps = setup(1000, None);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(200, "base_denom")]),
+            ExecuteMsg::FundLiquidityBuffer {},
+        )
+        .unwrap();
+        assert_eq!(
+            LIQUIDITY_BUFFER.load(deps.as_ref().storage).unwrap(),
+            Uint128::new(1200)
+        );
+    }
+
+    #[test]
+    fn withdraw_liquidity_buffer_beyond_buffer() {
+        let mut deps = setup(1000, None);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::WithdrawLiquidityBuffer {
+                amount: Uint128::new(1001),
+                receiver: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::LiquidityBufferInsufficient {
+                available: Uint128::new(1000),
+                requested: Uint128::new(1001),
+            }
+        );
+    }
+
+    #[test]
+    fn withdraw_liquidity_buffer() {
+        let mut deps = setup(1000, None);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::WithdrawLiquidityBuffer {
+                amount: Uint128::new(400),
+                receiver: Some("receiver".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "receiver".to_string(),
+                amount: vec![Coin::new(400u128, "base_denom")],
+            }))]
+        );
+        assert_eq!(
+            LIQUIDITY_BUFFER.load(deps.as_ref().storage).unwrap(),
+            Uint128::new(600)
+        );
+    }
+}
+
+mod bond_routing {
+    use super::*;
+    use drop_staking_base::{
+        msg::core::SimulateBondResponse, state::bond_providers::BondProviderRoute,
+    };
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        {
+            let deps_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+        }
+        BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+        for provider in ["1_provider_address", "2_provider_address"] {
+            BOND_PROVIDERS
+                .add(deps.as_mut().storage, Addr::unchecked(provider))
+                .unwrap();
+        }
+        FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+            .unwrap();
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 100, 600))
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        deps
+    }
+
+    #[test]
+    fn set_route_unauthorized() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            ExecuteMsg::SetBondProviderRoute {
+                bond_provider_address: "1_provider_address".to_string(),
+                route: BondProviderRoute::default(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+        );
+    }
+
+    #[test]
+    fn set_route_unknown_provider() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetBondProviderRoute {
+                bond_provider_address: "3_provider_address".to_string(),
+                route: BondProviderRoute::default(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BondProviderNotFound {});
+    }
+
+    #[test]
+    fn simulate_bond_prefers_denom() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetBondProviderRoute {
+                bond_provider_address: "2_provider_address".to_string(),
+                route: BondProviderRoute {
+                    priority: 0,
+                    async_tokens_cap: None,
+                    preferred_denoms: vec!["base_denom".to_string()],
+                },
+            },
+        )
+        .unwrap();
+        deps.querier
+            .add_wasm_query_response("2_provider_address", |_| to_json_binary(&true).unwrap());
+        deps.querier
+            .add_wasm_query_response("2_provider_address", |_| {
+                to_json_binary(&Uint128::from(1000u128)).unwrap()
+            });
+
+        let res: SimulateBondResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateBond {
+                    coin: Coin::new(1000, "base_denom"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            SimulateBondResponse {
+                bond_provider: Addr::unchecked("2_provider_address"),
+                issue_amount: Uint128::from(1000u128),
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_bond_skips_capped_provider() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetBondProviderRoute {
+                bond_provider_address: "1_provider_address".to_string(),
+                route: BondProviderRoute {
+                    priority: 0,
+                    async_tokens_cap: Some(Uint128::from(1500u128)),
+                    preferred_denoms: vec![],
+                },
+            },
+        )
+        .unwrap();
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |_| to_json_binary(&true).unwrap());
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |_| {
+                to_json_binary(&Uint128::from(1000u128)).unwrap()
+            });
+        // value of the bonded coin in base denom
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |_| {
+                to_json_binary(&Uint128::from(600u128)).unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("2_provider_address", |_| to_json_binary(&true).unwrap());
+        deps.querier
+            .add_wasm_query_response("2_provider_address", |_| {
+                to_json_binary(&Uint128::from(900u128)).unwrap()
+            });
+
+        let res: SimulateBondResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateBond {
+                    coin: Coin::new(1000, "base_denom"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            SimulateBondResponse {
+                bond_provider: Addr::unchecked("2_provider_address"),
+                issue_amount: Uint128::from(900u128),
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_bond_caps_provider_in_base_denom() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::SetBondProviderRoute {
+                bond_provider_address: "1_provider_address".to_string(),
+                route: BondProviderRoute {
+                    priority: 0,
+                    async_tokens_cap: Some(Uint128::from(1500u128)),
+                    preferred_denoms: vec![],
+                },
+            },
+        )
+        .unwrap();
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |_| to_json_binary(&true).unwrap());
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |_| {
+                to_json_binary(&Uint128::from(1000u128)).unwrap()
+            });
+        // 1000 shares are worth 400 base denom, which still fits under the cap
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |msg| match from_json(msg).unwrap() {
+                drop_staking_base::msg::bond_provider::QueryMsg::TokensAmount {
+                    coin,
+                    exchange_rate,
+                } => {
+                    assert_eq!(coin, Coin::new(1000, "share_denom"));
+                    assert_eq!(exchange_rate, Decimal::one());
+                    to_json_binary(&Uint128::from(400u128)).unwrap()
+                }
+                _ => unimplemented!(),
+            });
+        deps.querier
+            .add_wasm_query_response("1_provider_address", |_| {
+                to_json_binary(&Uint128::from(380u128)).unwrap()
+            });
+
+        let res: SimulateBondResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SimulateBond {
+                    coin: Coin::new(1000, "share_denom"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            SimulateBondResponse {
+                bond_provider: Addr::unchecked("1_provider_address"),
+                issue_amount: Uint128::from(380u128),
+            }
+        );
+    }
+}
+
+mod exchange_rate_history {
+    use super::*;
+    use drop_staking_base::{
+        msg::core::{ExchangeRateHistoryResponse, ExchangeRateTwapResponse},
+        state::core::{ExchangeRateSnapshot, EXCHANGE_RATE_HISTORY},
+    };
+
+    fn snapshot(time: u64, exchange_rate: Decimal) -> ExchangeRateSnapshot {
+        ExchangeRateSnapshot {
+            height: time / 10,
+            time,
+            exchange_rate,
+            total_bonded: Uint128::new(1000),
+            dasset_supply: Uint128::new(1000),
+        }
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        for (id, (time, rate)) in [(1000u64, 1u64), (1100, 2), (1200, 4)]
+            .into_iter()
+            .enumerate()
+        {
+            EXCHANGE_RATE_HISTORY
+                .save(
+                    deps.as_mut().storage,
+                    id as u64,
+                    &snapshot(time, Decimal::from_ratio(rate, 1u64)),
+                )
+                .unwrap();
+        }
+        deps
+    }
+
+    #[test]
+    fn history_pagination() {
+        let deps = setup();
+        let res: ExchangeRateHistoryResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::ExchangeRateHistory {
+                    start_after: Some(0),
+                    limit: Some(1),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            ExchangeRateHistoryResponse {
+                snapshots: vec![(1, snapshot(1100, Decimal::from_ratio(2u64, 1u64)))],
+            }
+        );
+    }
+
+    #[test]
+    fn twap_empty_history() {
+        let deps = mock_dependencies(&[]);
+        let err = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExchangeRateTwap {
+                window_seconds: 100,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ExchangeRateHistoryIsEmpty {});
+    }
+
+    #[test]
+    fn twap_zero_window() {
+        let deps = setup();
+        let err = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::ExchangeRateTwap { window_seconds: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidTwapWindow {});
+    }
+
+    #[test]
+    fn twap_within_window() {
+        let deps = setup();
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1300);
+        // rate 2 holds for [1150, 1200), rate 4 holds for [1200, 1300)
+        let res: ExchangeRateTwapResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::ExchangeRateTwap {
+                    window_seconds: 150,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            ExchangeRateTwapResponse {
+                twap: Decimal::from_ratio(10u64, 3u64),
+                window_start: 1150,
+                window_end: 1300,
+            }
+        );
+    }
+
+    #[test]
+    fn twap_window_longer_than_history() {
+        let deps = setup();
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1300);
+        let res: ExchangeRateTwapResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                QueryMsg::ExchangeRateTwap {
+                    window_seconds: 10000,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            ExchangeRateTwapResponse {
+                twap: Decimal::from_ratio(7u64, 3u64),
+                window_start: 1000,
+                window_end: 1300,
+            }
+        );
+    }
+}
+
+mod rewards_fee {
+    use super::*;
+    use drop_staking_base::state::core::{
+        FeeLedger, FeeLedgerEntry, RewardsFeeConfig, EXCHANGE_RATE, FEE_LEDGER, FEE_LEDGER_ENTRIES,
+        REWARDS_FEE_CONFIG,
+    };
+
+    #[test]
+    fn update_config_invalid_fee() {
+        let mut deps = mock_dependencies(&[]);
+        {
+            let deps_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+        }
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateRewardsFeeConfig {
+                fee: Decimal::one(),
+                fee_collector: "fee_collector".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidFee {});
+    }
+
+    fn mock_fee_tick(
+        delegated: u128,
+        unbond_dasset: u128,
+        fee_ledger: FeeLedger,
+    ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[Coin::new(1000, "ld_denom")]);
+        mock_state_query(&mut deps);
+        BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+        BOND_PROVIDERS
+            .add(deps.as_mut().storage, Addr::unchecked("native_provider"))
+            .unwrap();
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| {
+                to_json_binary(&Uint128::zero()).unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| to_json_binary(&false).unwrap());
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&BalancesResponse {
+                    balances: Balances { coins: vec![] },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+        for _ in 0..2 {
+            deps.querier
+                .add_wasm_query_response("puppeteer_contract", move |_| {
+                    to_json_binary(&DelegationsResponse {
+                        delegations: Delegations {
+                            delegations: vec![DropDelegation {
+                                delegator: Addr::unchecked("ica_address"),
+                                validator: "valoper_address".to_string(),
+                                amount: Coin {
+                                    denom: "remote_denom".to_string(),
+                                    amount: Uint128::new(delegated),
+                                },
+                                share_ratio: Decimal256::one(),
+                            }],
+                        },
+                        remote_height: 10u64,
+                        local_height: 10u64,
+                        timestamp: Timestamp::from_seconds(90001),
+                    })
+                    .unwrap()
+                });
+        }
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 10, 6000))
+            .unwrap();
+        LAST_ICA_CHANGE_HEIGHT
+            .save(deps.as_mut().storage, &0)
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+            .unwrap();
+        LAST_IDLE_CALL.save(deps.as_mut().storage, &0).unwrap();
+        PAUSE
+            .save(deps.as_mut().storage, &Pause::default())
+            .unwrap();
+        UNBOND_BATCH_ID.save(deps.as_mut().storage, &0u128).unwrap();
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                0,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::new(unbond_dasset),
+                    expected_native_asset_amount: Uint128::zero(),
+                    total_unbond_items: 0,
+                    status: UnbondBatchStatus::New,
+                    expected_release_time: 0,
+                    slashing_effect: None,
+                    unbonded_amount: None,
+                    withdrawn_amount: None,
+                    status_timestamps: get_default_unbond_batch_status_timestamps(),
+                },
+            )
+            .unwrap();
+        REWARDS_FEE_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &RewardsFeeConfig {
+                    fee: Decimal::from_ratio(1u128, 10u128),
+                    fee_collector: Addr::unchecked("fee_collector"),
+                },
+            )
+            .unwrap();
+        FEE_LEDGER.save(deps.as_mut().storage, &fee_ledger).unwrap();
+
+        deps
+    }
+
+    #[test]
+    fn tick_idle_mints_fee() {
+        let mut deps = mock_fee_tick(
+            1200,
+            0,
+            FeeLedger {
+                high_water_mark: Some(Decimal::one()),
+                last_dasset_amount: Uint128::new(1000),
+                ..FeeLedger::default()
+            },
+        );
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Mint {
+                    amount: Uint128::new(16),
+                    receiver: "fee_collector".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        assert_eq!(
+            FEE_LEDGER.load(deps.as_ref().storage).unwrap(),
+            FeeLedger {
+                cumulative_rewards: Uint128::new(200),
+                cumulative_fee: Uint128::new(20),
+                cumulative_fee_dasset_minted: Uint128::new(16),
+                high_water_mark: Some(Decimal::from_ratio(1200u128, 1016u128)),
+                last_dasset_amount: Uint128::new(1016),
+                entries_count: 1,
+            }
+        );
+        assert_eq!(
+            EXCHANGE_RATE.load(deps.as_ref().storage).unwrap(),
+            (Decimal::from_ratio(1200u128, 1016u128), env.block.height)
+        );
+        assert_eq!(
+            FEE_LEDGER_ENTRIES.load(deps.as_ref().storage, 0).unwrap(),
+            FeeLedgerEntry {
+                height: env.block.height,
+                time: 100,
+                exchange_rate: Decimal::from_ratio(12u128, 10u128),
+                rewards: Uint128::new(200),
+                fee: Uint128::new(20),
+                fee_dasset_minted: Uint128::new(16),
+            }
+        );
+    }
+
+    #[test]
+    fn tick_idle_fee_counts_unprocessed_unbonds() {
+        // 1000 dAsset supply along with 200 dAsset waiting in the new batch
+        let mut deps = mock_fee_tick(
+            1320,
+            200,
+            FeeLedger {
+                high_water_mark: Some(Decimal::one()),
+                last_dasset_amount: Uint128::new(1200),
+                ..FeeLedger::default()
+            },
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        // rewards are 120, fee is 12 and 12 * 1200 / (1320 - 12) dAsset is minted
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Mint {
+                    amount: Uint128::new(11),
+                    receiver: "fee_collector".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        let fee_ledger = FEE_LEDGER.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee_ledger.cumulative_rewards, Uint128::new(120));
+        assert_eq!(fee_ledger.last_dasset_amount, Uint128::new(1211));
+        assert_eq!(
+            EXCHANGE_RATE.load(deps.as_ref().storage).unwrap().0,
+            Decimal::from_ratio(1320u128, 1211u128)
+        );
+    }
+
+    #[test]
+    fn tick_idle_fee_skips_dasset_minted_after_checkpoint() {
+        // only 500 dAsset existed at the checkpoint, the rest was bonded at the current rate
+        let mut deps = mock_fee_tick(
+            1200,
+            0,
+            FeeLedger {
+                high_water_mark: Some(Decimal::one()),
+                last_dasset_amount: Uint128::new(500),
+                ..FeeLedger::default()
+            },
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Mint {
+                    amount: Uint128::new(8),
+                    receiver: "fee_collector".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        let fee_ledger = FEE_LEDGER.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee_ledger.cumulative_rewards, Uint128::new(100));
+        assert_eq!(fee_ledger.cumulative_fee, Uint128::new(10));
+    }
+
+    #[test]
+    fn tick_idle_fee_keeps_high_water_mark_on_fall() {
+        let mut deps = mock_fee_tick(
+            900,
+            0,
+            FeeLedger {
+                high_water_mark: Some(Decimal::one()),
+                last_dasset_amount: Uint128::new(1000),
+                ..FeeLedger::default()
+            },
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        let fee_ledger = FEE_LEDGER.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee_ledger.high_water_mark, Some(Decimal::one()));
+        assert_eq!(fee_ledger.cumulative_rewards, Uint128::zero());
+    }
+
+    #[test]
+    fn tick_idle_fee_charges_only_growth_above_high_water_mark() {
+        // the rate fell below 1.1 earlier and has recovered to 1.2 since
+        let mut deps = mock_fee_tick(
+            1200,
+            0,
+            FeeLedger {
+                high_water_mark: Some(Decimal::from_ratio(11u128, 10u128)),
+                last_dasset_amount: Uint128::new(1000),
+                ..FeeLedger::default()
+            },
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        // rewards are 100, fee is 10 and 10 * 1000 / (1200 - 10) dAsset is minted
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Mint {
+                    amount: Uint128::new(8),
+                    receiver: "fee_collector".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        let fee_ledger = FEE_LEDGER.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee_ledger.cumulative_rewards, Uint128::new(100));
+        assert_eq!(
+            fee_ledger.high_water_mark,
+            Some(Decimal::from_ratio(1200u128, 1008u128))
+        );
+    }
+}
+
+mod rebalance {
+    use super::*;
+    use drop_staking_base::{
+        msg::distribution::Redelegation,
+        state::core::{
+            RebalanceConfig, LAST_REBALANCE, REBALANCE_CONFIG, REBALANCE_QUEUE,
+            REDELEGATION_COOLDOWNS,
+        },
+    };
+
+    fn redelegation(validator_from: &str, validator_to: &str, amount: u128) -> Redelegation {
+        Redelegation {
+            validator_from: validator_from.to_string(),
+            validator_to: validator_to.to_string(),
+            amount: Uint128::new(amount),
+        }
+    }
+
+    fn mock_delegations(
+        deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>,
+        delegations: &[(&str, u128)],
+    ) {
+        let delegations = delegations
+            .iter()
+            .map(|(validator, amount)| DropDelegation {
+                delegator: Addr::unchecked("ica_address"),
+                validator: validator.to_string(),
+                amount: Coin {
+                    denom: "remote_denom".to_string(),
+                    amount: Uint128::new(*amount),
+                },
+                share_ratio: Decimal256::one(),
+            })
+            .collect::<Vec<_>>();
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", move |_| {
+                to_json_binary(&DelegationsResponse {
+                    delegations: Delegations {
+                        delegations: delegations.clone(),
+                    },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+    }
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+        BOND_PROVIDERS
+            .add(deps.as_mut().storage, Addr::unchecked("native_provider"))
+            .unwrap();
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&BalancesResponse {
+                    balances: Balances { coins: vec![] },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&DelegationsResponse {
+                    delegations: Delegations {
+                        delegations: vec![],
+                    },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| to_json_binary(&false).unwrap());
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 10, 6000))
+            .unwrap();
+        LAST_ICA_CHANGE_HEIGHT
+            .save(deps.as_mut().storage, &0)
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+            .unwrap();
+        LAST_IDLE_CALL.save(deps.as_mut().storage, &0).unwrap();
+        PAUSE
+            .save(deps.as_mut().storage, &Pause::default())
+            .unwrap();
+        REBALANCE_CONFIG
+            .save(
+                deps.as_mut().storage,
+                &RebalanceConfig {
+                    max_moves: 3,
+                    min_interval: 50,
+                    min_amount: Uint128::new(10),
+                },
+            )
+            .unwrap();
+        deps
+    }
+
+    #[test]
+    fn update_config_unauthorized() {
+        let mut deps = mock_dependencies(&[]);
+        {
+            let deps_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+        }
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("somebody", &[]),
+            ExecuteMsg::UpdateRebalanceConfig { config: None },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+        );
+    }
+
+    #[test]
+    fn tick_idle_plans_and_redelegates() {
+        let mut deps = setup();
+        deps.querier
+            .add_wasm_query_response("strategy_contract", |req| {
+                let req = from_json::<StrategyQueryMsg>(req).unwrap();
+                assert_eq!(req, StrategyQueryMsg::CalcRebalance { max_moves: 3 });
+                to_json_binary(&vec![
+                    redelegation("valoper1", "valoper2", 100),
+                    redelegation("valoper3", "valoper2", 5),
+                    redelegation("valoper4", "valoper2", 50),
+                    redelegation("valoper5", "valoper2", 50),
+                ])
+                .unwrap()
+            });
+        mock_delegations(
+            &mut deps,
+            &[
+                ("valoper1", 1000),
+                ("valoper3", 1000),
+                ("valoper4", 1000),
+                ("valoper5", 1000),
+            ],
+        );
+        REDELEGATION_COOLDOWNS
+            .save(deps.as_mut().storage, "valoper1", &200)
+            .unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "puppeteer_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Redelegate {
+                    validator_from: "valoper4".to_string(),
+                    validator_to: "valoper2".to_string(),
+                    amount: Uint128::new(50),
+                    reply_to: env.contract.address.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Peripheral
+        );
+        assert_eq!(
+            REBALANCE_QUEUE.load(deps.as_ref().storage).unwrap(),
+            vec![redelegation("valoper5", "valoper2", 50)]
+        );
+        assert_eq!(LAST_REBALANCE.load(deps.as_ref().storage).unwrap(), 100);
+    }
+
+    #[test]
+    fn tick_idle_caps_queued_redelegations_by_current_delegations() {
+        let mut deps = setup();
+        // planned on an earlier tick, delegations changed since then
+        REBALANCE_QUEUE
+            .save(
+                deps.as_mut().storage,
+                &vec![
+                    redelegation("valoper6", "valoper2", 50),
+                    redelegation("valoper5", "valoper2", 50),
+                    redelegation("valoper4", "valoper2", 50),
+                ],
+            )
+            .unwrap();
+        mock_delegations(&mut deps, &[("valoper4", 5), ("valoper5", 30)]);
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        // valoper4 has less than min_amount left, valoper5 is capped by its delegation
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "puppeteer_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Redelegate {
+                    validator_from: "valoper5".to_string(),
+                    validator_to: "valoper2".to_string(),
+                    amount: Uint128::new(30),
+                    reply_to: env.contract.address.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        assert_eq!(
+            REBALANCE_QUEUE.load(deps.as_ref().storage).unwrap(),
+            vec![redelegation("valoper6", "valoper2", 50)]
+        );
+        // the round is not planned again while it isn't exhausted
+        assert!(LAST_REBALANCE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn tick_idle_waits_for_min_interval() {
+        let mut deps = setup();
+        LAST_REBALANCE.save(deps.as_mut().storage, &80).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Idle
+        );
+    }
+
+    #[test]
+    fn puppeteer_hook_sets_cooldown() {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 10, 6000))
+            .unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("puppeteer_contract", &[]),
+            ExecuteMsg::PeripheralHook(Box::new(
+                drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                    drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                        local_height: 9u64,
+                        remote_height: 9u64,
+                        transaction:
+                            drop_puppeteer_base::peripheral_hook::Transaction::Redelegate {
+                                interchain_account_id: "ica".to_string(),
+                                validator_from: "valoper1".to_string(),
+                                validator_to: "valoper2".to_string(),
+                                denom: "remote_denom".to_string(),
+                                amount: 100u128,
+                            },
+                    },
+                ),
+            )),
+        )
+        .unwrap();
+        assert_eq!(
+            REDELEGATION_COOLDOWNS
+                .load(deps.as_ref().storage, "valoper2")
+                .unwrap(),
+            160
+        );
+    }
+}
+
+mod slashing_detection {
+    use super::*;
+    use drop_staking_base::{
+        msg::core::SlashingEventsResponse,
+        state::core::{
+            SlashingEvent, ValidatorSlashing, LAST_SHARE_RATIOS, UNBOND_BATCH_UNDELEGATIONS,
+        },
+    };
+
+    fn delegation(validator: &str, share_ratio: Decimal256) -> DropDelegation {
+        DropDelegation {
+            delegator: Addr::unchecked("ica_address"),
+            validator: validator.to_string(),
+            amount: Coin {
+                denom: "remote_denom".to_string(),
+                amount: Uint128::new(100_000),
+            },
+            share_ratio,
+        }
+    }
+
+    fn setup(
+        share_ratio: Decimal256,
+    ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        setup_with_delegations(vec![delegation("valoper_address", share_ratio)])
+    }
+
+    fn setup_with_delegations(
+        delegations: Vec<DropDelegation>,
+    ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&BalancesResponse {
+                    balances: Balances { coins: vec![] },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&DelegationsResponse {
+                    delegations: Delegations {
+                        delegations: vec![],
+                    },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&BalancesResponse {
+                    balances: Balances {
+                        coins: vec![Coin {
+                            denom: "remote_denom".to_string(),
+                            amount: Uint128::new(200),
+                        }],
+                    },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(20),
+                })
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("validators_set_contract", |_| {
+                to_json_binary(&vec![
+                    drop_staking_base::state::validatorset::ValidatorInfo {
+                        valoper_address: "valoper_address".to_string(),
+                        weight: 1,
+                        on_top: Uint128::zero(),
+                        last_processed_remote_height: None,
+                        last_processed_local_height: None,
+                        last_validated_height: None,
+                        last_commission_in_range: None,
+                        uptime: Decimal::one(),
+                        tombstone: false,
+                        jailed_number: None,
+                        init_proposal: None,
+                        total_passed_proposals: 0,
+                        total_voted_proposals: 0,
+                    },
+                ])
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", move |_| {
+                to_json_binary(&DelegationsResponse {
+                    delegations: Delegations {
+                        delegations: delegations.clone(),
+                    },
+                    remote_height: 10u64,
+                    local_height: 12344u64,
+                    timestamp: Timestamp::from_seconds(0),
+                })
+                .unwrap()
+            });
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 100, 6000))
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+            .unwrap();
+        LAST_IDLE_CALL.save(deps.as_mut().storage, &0).unwrap();
+        LAST_ICA_CHANGE_HEIGHT
+            .save(deps.as_mut().storage, &0)
+            .unwrap();
+        UNBOND_BATCH_ID.save(deps.as_mut().storage, &0).unwrap();
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                0,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::from(1000u128),
+                    expected_native_asset_amount: Uint128::from(1000u128),
+                    total_unbond_items: 1,
+                    status: UnbondBatchStatus::Unbonding,
+                    expected_release_time: 9000,
+                    slashing_effect: None,
+                    unbonded_amount: None,
+                    withdrawn_amount: None,
+                    status_timestamps: get_default_unbond_batch_status_timestamps(),
+                },
+            )
+            .unwrap();
+        PAUSE
+            .save(deps.as_mut().storage, &Pause::default())
+            .unwrap();
+        deps
+    }
+
+    fn tick(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>) {
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(10000);
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn first_tick_records_share_ratios() {
+        let mut deps = setup(Decimal256::one());
+        tick(&mut deps);
+
+        assert_eq!(
+            LAST_SHARE_RATIOS
+                .load(deps.as_ref().storage, "valoper_address")
+                .unwrap(),
+            Decimal256::one()
+        );
+        let events: SlashingEventsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SlashingEvents {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(events.events, vec![]);
+    }
+
+    #[test]
+    fn share_ratio_drop_adjusts_unbonding_batches() {
+        let mut deps = setup(Decimal256::one());
+        LAST_SHARE_RATIOS
+            .save(
+                deps.as_mut().storage,
+                "valoper_address",
+                &Decimal256::from_ratio(5u128, 4u128),
+            )
+            .unwrap();
+        tick(&mut deps);
+
+        assert_eq!(
+            unbond_batches_map()
+                .load(deps.as_ref().storage, 0)
+                .unwrap()
+                .expected_native_asset_amount,
+            Uint128::new(800)
+        );
+        let events: SlashingEventsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SlashingEvents {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            events.events,
+            vec![(
+                0,
+                SlashingEvent {
+                    height: mock_env().block.height,
+                    time: 10000,
+                    remote_height: 10,
+                    validators: vec![ValidatorSlashing {
+                        validator: "valoper_address".to_string(),
+                        slashing_fraction: Decimal256::percent(20),
+                        loss: Uint128::new(25_000),
+                    }],
+                    total_loss: Uint128::new(25_000),
+                    slashing_fraction: Decimal::percent(20),
+                    batch_adjustments: vec![(0, Uint128::new(200))],
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn partial_slashing_is_attributed_per_batch() {
+        let mut deps = setup_with_delegations(vec![
+            delegation("valoper_address", Decimal256::one()),
+            delegation("valoper_address_2", Decimal256::one()),
+        ]);
+        LAST_SHARE_RATIOS
+            .save(
+                deps.as_mut().storage,
+                "valoper_address",
+                &Decimal256::from_ratio(5u128, 4u128),
+            )
+            .unwrap();
+        LAST_SHARE_RATIOS
+            .save(
+                deps.as_mut().storage,
+                "valoper_address_2",
+                &Decimal256::one(),
+            )
+            .unwrap();
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                1,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::from(1000u128),
+                    expected_native_asset_amount: Uint128::from(1000u128),
+                    total_unbond_items: 1,
+                    status: UnbondBatchStatus::Unbonding,
+                    expected_release_time: 9000,
+                    slashing_effect: None,
+                    unbonded_amount: None,
+                    withdrawn_amount: None,
+                    status_timestamps: get_default_unbond_batch_status_timestamps(),
+                },
+            )
+            .unwrap();
+        // batch 0 is split between both validators, batch 1 left only the healthy one
+        UNBOND_BATCH_UNDELEGATIONS
+            .save(
+                deps.as_mut().storage,
+                0,
+                &vec![
+                    ("valoper_address".to_string(), Uint128::new(500)),
+                    ("valoper_address_2".to_string(), Uint128::new(500)),
+                ],
+            )
+            .unwrap();
+        UNBOND_BATCH_UNDELEGATIONS
+            .save(
+                deps.as_mut().storage,
+                1,
+                &vec![("valoper_address_2".to_string(), Uint128::new(1000))],
+            )
+            .unwrap();
+        tick(&mut deps);
+
+        assert_eq!(
+            unbond_batches_map()
+                .load(deps.as_ref().storage, 0)
+                .unwrap()
+                .expected_native_asset_amount,
+            Uint128::new(900)
+        );
+        assert_eq!(
+            unbond_batches_map()
+                .load(deps.as_ref().storage, 1)
+                .unwrap()
+                .expected_native_asset_amount,
+            Uint128::new(1000)
+        );
+        assert_eq!(
+            UNBOND_BATCH_UNDELEGATIONS
+                .load(deps.as_ref().storage, 0)
+                .unwrap(),
+            vec![
+                ("valoper_address".to_string(), Uint128::new(400)),
+                ("valoper_address_2".to_string(), Uint128::new(500)),
+            ]
+        );
+        let events: SlashingEventsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::SlashingEvents {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(events.events.len(), 1);
+        assert_eq!(events.events[0].1.total_loss, Uint128::new(25_000));
+        assert_eq!(
+            events.events[0].1.batch_adjustments,
+            vec![(0, Uint128::new(100))]
+        );
+    }
+}
+
+mod resolve_emergency_batches {
+    use super::*;
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        {
+            let deps_as_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_as_mut.storage, deps_as_mut.api, Some("owner"))
+                .unwrap();
+        }
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 100, 6000))
+            .unwrap();
+        for (id, expected_amount, status) in [
+            (1u128, 100u128, UnbondBatchStatus::WithdrawnEmergency),
+            (2u128, 300u128, UnbondBatchStatus::WithdrawnEmergency),
+            (3u128, 100u128, UnbondBatchStatus::WithdrawingEmergency),
+        ] {
+            unbond_batches_map()
+                .save(
+                    deps.as_mut().storage,
+                    id,
+                    &UnbondBatch {
+                        total_dasset_amount_to_withdraw: Uint128::new(expected_amount),
+                        expected_native_asset_amount: Uint128::new(expected_amount),
+                        expected_release_time: 200,
+                        total_unbond_items: 0,
+                        status,
+                        slashing_effect: None,
+                        unbonded_amount: None,
+                        withdrawn_amount: None,
+                        status_timestamps: get_default_unbond_batch_status_timestamps(),
+                    },
+                )
+                .unwrap();
+        }
+        deps
+    }
+
+    fn set_emergency_address(
+        deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>,
+    ) {
+        CONFIG
+            .update(deps.as_mut().storage, |mut config| {
+                config.emergency_address = Some("emergency_address".to_string());
+                cosmwasm_std::StdResult::Ok(config)
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn unauthorized() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches {
+                batch_ids: Some(vec![1, 2]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+        );
+    }
+
+    #[test]
+    fn empty_batch_ids() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches {
+                batch_ids: Some(vec![]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EmptyBatchIds {});
+    }
+
+    #[test]
+    fn duplicate_batch_id() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches {
+                batch_ids: Some(vec![1, 2, 1]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::DuplicateBatchId { batch_id: 1 });
+    }
+
+    #[test]
+    fn not_in_withdrawn_emergency_state() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches {
+                batch_ids: Some(vec![1, 3]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BatchNotWithdrawnEmergency {});
+    }
+
+    #[test]
+    fn amount_too_high() {
+        let mut deps = setup();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[Coin::new(401, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches {
+                batch_ids: Some(vec![1, 2]),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnbondedAmountTooHigh {});
+    }
+
+    #[test]
+    fn split_pro_rata() {
+        let mut deps = setup();
+        let env = mock_env();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("owner", &[Coin::new(301, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches {
+                batch_ids: Some(vec![1, 2]),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "withdrawal_manager_contract".to_string(),
+                amount: vec![Coin::new(301, "base_denom")],
+            }))]
+        );
+        assert_eq!(
+            res.events,
+            vec![
+                Event::new("crates.io:drop-staking__drop-core-execute-resolve_emergency_batches")
+                    .add_attributes(vec![
+                        ("action", "resolve_emergency_batches"),
+                        ("amount", "301"),
+                        ("batch_id", "1"),
+                        ("unbonded_amount", "75"),
+                        ("slashing_effect", "0.75"),
+                        ("batch_id", "2"),
+                        ("unbonded_amount", "226"),
+                        ("slashing_effect", "0.753333333333333333"),
+                    ])
+            ]
+        );
+
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(batch.status, UnbondBatchStatus::Withdrawn);
+        assert_eq!(batch.unbonded_amount, Some(Uint128::new(75)));
+        assert_eq!(batch.slashing_effect, Some(Decimal::percent(75)));
+        assert_eq!(
+            batch.status_timestamps.withdrawn,
+            Some(env.block.time.seconds())
+        );
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 2).unwrap();
+        assert_eq!(batch.status, UnbondBatchStatus::Withdrawn);
+        assert_eq!(batch.unbonded_amount, Some(Uint128::new(226)));
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 3).unwrap();
+        assert_eq!(batch.status, UnbondBatchStatus::WithdrawingEmergency);
+    }
+
+    #[test]
+    fn emergency_address_resolves_all_withdrawn_emergency_batches() {
+        let mut deps = setup();
+        set_emergency_address(&mut deps);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("emergency_address", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches { batch_ids: None },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "withdrawal_manager_contract".to_string(),
+                amount: vec![Coin::new(400, "base_denom")],
+            }))]
+        );
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(batch.unbonded_amount, Some(Uint128::new(100)));
+        assert_eq!(batch.slashing_effect, Some(Decimal::one()));
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 2).unwrap();
+        assert_eq!(batch.unbonded_amount, Some(Uint128::new(300)));
+        let batch = unbond_batches_map().load(deps.as_ref().storage, 3).unwrap();
+        assert_eq!(batch.status, UnbondBatchStatus::WithdrawingEmergency);
+    }
+
+    #[test]
+    fn no_withdrawn_emergency_batches() {
+        let mut deps = setup();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches { batch_ids: None },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[Coin::new(400, "base_denom")]),
+            ExecuteMsg::ResolveEmergencyBatches { batch_ids: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EmptyBatchIds {});
+    }
+}
+
+mod unbond_in_kind {
+    use super::*;
+    use drop_staking_base::{
+        msg::core::UnbondInKindRequestsResponse,
+        state::core::{
+            UnbondInKindConfig, UnbondInKindRequest, UnbondInKindStatus, EXCHANGE_RATE,
+            UNBOND_IN_KIND_CONFIG, UNBOND_IN_KIND_REQUESTS,
+        },
+    };
+
+    fn setup(enabled: bool) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        mock_state_query(&mut deps);
+        {
+            let deps_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+        }
+        // outside of idle the cached exchange rate is used
+        FSM.set_initial_state(deps.as_mut().storage, ContractState::Peripheral)
+            .unwrap();
+        EXCHANGE_RATE
+            .save(
+                deps.as_mut().storage,
+                &(Decimal::from_ratio(3u128, 2u128), 0),
+            )
+            .unwrap();
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 100, 600))
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        PAUSE
+            .save(deps.as_mut().storage, &Pause::default())
+            .unwrap();
+        BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+        if enabled {
+            UNBOND_IN_KIND_CONFIG
+                .save(
+                    deps.as_mut().storage,
+                    &UnbondInKindConfig {
+                        remote_transfer_channel_id: "channel-1".to_string(),
+                        transfer_timeout: 1000,
+                    },
+                )
+                .unwrap();
+        }
+        deps
+    }
+
+    fn request(status: UnbondInKindStatus, share: Option<Coin>) -> UnbondInKindRequest {
+        UnbondInKindRequest {
+            receiver: Addr::unchecked("some_sender"),
+            validator: "valoper1".to_string(),
+            dasset_amount: Uint128::new(100),
+            amount: Uint128::new(150),
+            share,
+            status,
+            transferred_at: None,
+        }
+    }
+
+    fn mock_validator_stake(
+        deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>,
+        known: bool,
+        delegated: u128,
+    ) {
+        deps.querier
+            .add_wasm_query_response("validators_set_contract", move |_| {
+                to_json_binary(&drop_staking_base::msg::validatorset::ValidatorResponse {
+                    validator: known.then(|| {
+                        drop_staking_base::state::validatorset::ValidatorInfo {
+                            valoper_address: "valoper1".to_string(),
+                            weight: 1,
+                            on_top: Uint128::zero(),
+                            last_processed_remote_height: None,
+                            last_processed_local_height: None,
+                            last_validated_height: None,
+                            last_commission_in_range: None,
+                            uptime: Decimal::one(),
+                            tombstone: false,
+                            jailed_number: None,
+                            init_proposal: None,
+                            total_passed_proposals: 0,
+                            total_voted_proposals: 0,
+                        }
+                    }),
+                })
+                .unwrap()
+            });
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", move |_| {
+                to_json_binary(&DelegationsResponse {
+                    delegations: Delegations {
+                        delegations: vec![DropDelegation {
+                            delegator: Addr::unchecked("ica_address"),
+                            validator: "valoper1".to_string(),
+                            amount: Coin::new(delegated, "remote_denom"),
+                            share_ratio: Decimal256::one(),
+                        }],
+                    },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+    }
+
+    fn hook(
+        deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>,
+        msg: drop_puppeteer_base::peripheral_hook::ResponseHookMsg,
+    ) -> Response<neutron_sdk::bindings::msg::NeutronMsg> {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("puppeteer_contract", &[]),
+            ExecuteMsg::PeripheralHook(Box::new(msg)),
+        )
+        .unwrap()
+    }
+
+    fn tokenize_transaction(
+        share: Option<Coin>,
+    ) -> drop_puppeteer_base::peripheral_hook::Transaction {
+        drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare {
+            interchain_account_id: "DROP".to_string(),
+            validator: "valoper1".to_string(),
+            denom: "remote_denom".to_string(),
+            amount: 150u128,
+            share,
+            request_id: Some(0),
+        }
+    }
+
+    fn transfer_transaction() -> drop_puppeteer_base::peripheral_hook::Transaction {
+        drop_puppeteer_base::peripheral_hook::Transaction::TransferShare {
+            interchain_account_id: "DROP".to_string(),
+            share: Coin::new(150, "valoper1/1"),
+            channel_id: "channel-1".to_string(),
+            receiver: "some_sender".to_string(),
+            request_id: Some(0),
+        }
+    }
+
+    #[test]
+    fn disabled() {
+        let mut deps = setup(false);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::UnbondInKind {
+                validator: "valoper1".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnbondInKindDisabled {});
+    }
+
+    #[test]
+    fn update_config_unauthorized() {
+        let mut deps = setup(false);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            ExecuteMsg::UpdateUnbondInKindConfig {
+                config: Some(UnbondInKindConfig {
+                    remote_transfer_channel_id: "channel-1".to_string(),
+                    transfer_timeout: 1000,
+                }),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+        );
+    }
+
+    #[test]
+    fn unknown_validator() {
+        let mut deps = setup(true);
+        mock_validator_stake(&mut deps, false, 1000);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::UnbondInKind {
+                validator: "valoper1".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnbondInKindUnknownValidator {
+                validator: "valoper1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn insufficient_delegation() {
+        let mut deps = setup(true);
+        // 150 of 200 delegated is already being tokenized
+        UNBOND_IN_KIND_REQUESTS
+            .save(
+                deps.as_mut().storage,
+                0,
+                &request(UnbondInKindStatus::Tokenizing, None),
+            )
+            .unwrap();
+        mock_validator_stake(&mut deps, true, 200);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::UnbondInKind {
+                validator: "valoper1".to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnbondInKindInsufficientDelegation {
+                validator: "valoper1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn tokenizes_delegation() {
+        let mut deps = setup(true);
+        mock_validator_stake(&mut deps, true, 150);
+        let env = mock_env();
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("some_sender", &[Coin::new(100, "ld_denom")]),
+            ExecuteMsg::UnbondInKind {
+                validator: "valoper1".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "puppeteer_contract".to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::puppeteer::ExecuteMsg::TokenizeShare {
+                        validator: "valoper1".to_string(),
+                        amount: Uint128::new(150),
+                        request_id: Some(0),
+                        reply_to: env.contract.address.to_string(),
+                    }
+                )
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        let requests: UnbondInKindRequestsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::UnbondInKindRequests {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            requests.requests,
+            vec![(0, request(UnbondInKindStatus::Tokenizing, None))]
+        );
+    }
+
+    #[test]
+    fn tokenize_success_burns_and_transfers_share() {
+        let mut deps = setup(true);
+        UNBOND_IN_KIND_REQUESTS
+            .save(
+                deps.as_mut().storage,
+                0,
+                &request(UnbondInKindStatus::Tokenizing, None),
+            )
+            .unwrap();
+        LAST_PUPPETEER_RESPONSE
+            .save(
+                deps.as_mut().storage,
+                &drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                    drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                        local_height: 1,
+                        remote_height: 1,
+                        transaction: drop_puppeteer_base::peripheral_hook::Transaction::Stake {
+                            amount: Uint128::new(1),
+                        },
+                    },
+                ),
+            )
+            .unwrap();
+        let res = hook(
+            &mut deps,
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                    local_height: 10,
+                    remote_height: 20,
+                    transaction: tokenize_transaction(Some(Coin::new(150, "valoper1/1"))),
+                },
+            ),
+        );
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "token_contract".to_string(),
+                    msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Burn {})
+                        .unwrap(),
+                    funds: vec![Coin::new(100, "ld_denom")],
+                })),
+                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "puppeteer_contract".to_string(),
+                    msg: to_json_binary(
+                        &drop_staking_base::msg::puppeteer::ExecuteMsg::TransferShare {
+                            share: Coin::new(150, "valoper1/1"),
+                            channel_id: "channel-1".to_string(),
+                            receiver: "some_sender".to_string(),
+                            request_id: Some(0),
+                            reply_to: mock_env().contract.address.to_string(),
+                        }
+                    )
+                    .unwrap(),
+                    funds: vec![],
+                })),
+            ]
+        );
+        assert_eq!(
+            UNBOND_IN_KIND_REQUESTS
+                .load(deps.as_ref().storage, 0)
+                .unwrap(),
+            request(
+                UnbondInKindStatus::Transferring,
+                Some(Coin::new(150, "valoper1/1"))
+            )
+        );
+        assert_eq!(
+            LAST_ICA_CHANGE_HEIGHT.load(deps.as_ref().storage).unwrap(),
+            20
+        );
+        // the response awaited by the FSM is kept
+        assert!(matches!(
+            LAST_PUPPETEER_RESPONSE.load(deps.as_ref().storage).unwrap(),
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                    transaction: drop_puppeteer_base::peripheral_hook::Transaction::Stake { .. },
+                    ..
+                }
+            )
+        ));
+    }
+
+    #[test]
+    fn tokenize_error_refunds_dasset() {
+        let mut deps = setup(true);
+        UNBOND_IN_KIND_REQUESTS
+            .save(
+                deps.as_mut().storage,
+                0,
+                &request(UnbondInKindStatus::Tokenizing, None),
+            )
+            .unwrap();
+        let res = hook(
+            &mut deps,
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
+                drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
+                    transaction: tokenize_transaction(None),
+                    details: "insufficient delegation".to_string(),
+                },
+            ),
+        );
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "some_sender".to_string(),
+                amount: vec![Coin::new(100, "ld_denom")],
+            }))]
+        );
+        assert!(UNBOND_IN_KIND_REQUESTS
+            .may_load(deps.as_ref().storage, 0)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn transfer_failure_and_retry() {
+        let mut deps = setup(true);
+        UNBOND_IN_KIND_REQUESTS
+            .save(
+                deps.as_mut().storage,
+                0,
+                &request(
+                    UnbondInKindStatus::Transferring,
+                    Some(Coin::new(150, "valoper1/1")),
+                ),
+            )
+            .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RetryUnbondInKind { id: 0, share: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnbondInKindNotRetryable { id: 0 });
+
+        hook(
+            &mut deps,
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
+                drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
+                    transaction: transfer_transaction(),
+                    details: "timeout".to_string(),
+                },
+            ),
+        );
+        assert_eq!(
+            UNBOND_IN_KIND_REQUESTS
+                .load(deps.as_ref().storage, 0)
+                .unwrap()
+                .status,
+            UnbondInKindStatus::TransferFailed
+        );
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RetryUnbondInKind { id: 0, share: None },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "puppeteer_contract".to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::puppeteer::ExecuteMsg::TransferShare {
+                        share: Coin::new(150, "valoper1/1"),
+                        channel_id: "channel-1".to_string(),
+                        receiver: "some_sender".to_string(),
+                        request_id: Some(0),
+                        reply_to: mock_env().contract.address.to_string(),
+                    }
+                )
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+
+        mock_state_query(&mut deps);
+        hook(
+            &mut deps,
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                    local_height: 10,
+                    remote_height: 20,
+                    transaction: transfer_transaction(),
+                },
+            ),
+        );
+        let request = UNBOND_IN_KIND_REQUESTS
+            .load(deps.as_ref().storage, 0)
+            .unwrap();
+        assert_eq!(request.status, UnbondInKindStatus::Transferred);
+        assert_eq!(
+            request.transferred_at,
+            Some(mock_env().block.time.seconds())
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::ConfirmUnbondInKind { id: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_sender", &[]),
+            ExecuteMsg::ConfirmUnbondInKind { id: 0 },
+        )
+        .unwrap();
+        assert!(UNBOND_IN_KIND_REQUESTS
+            .may_load(deps.as_ref().storage, 0)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn tokenize_success_without_share_keeps_dasset() {
+        let mut deps = setup(true);
+        UNBOND_IN_KIND_REQUESTS
+            .save(
+                deps.as_mut().storage,
+                0,
+                &request(UnbondInKindStatus::Tokenizing, None),
+            )
+            .unwrap();
+        let res = hook(
+            &mut deps,
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                    local_height: 10,
+                    remote_height: 20,
+                    transaction: tokenize_transaction(None),
+                },
+            ),
+        );
+        assert_eq!(res.messages, vec![]);
+        assert_eq!(
+            UNBOND_IN_KIND_REQUESTS
+                .load(deps.as_ref().storage, 0)
+                .unwrap(),
+            request(UnbondInKindStatus::TransferFailed, None)
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RetryUnbondInKind { id: 0, share: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnbondInKindShareRequired { id: 0 });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RetryUnbondInKind {
+                id: 0,
+                share: Some(Coin::new(150, "valoper1/1")),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::RetryUnbondInKind {
+                id: 0,
+                share: Some(Coin::new(150, "valoper2/1")),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidDenom {});
+
+        // the dAsset is burned only once the share is known
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::RetryUnbondInKind {
+                id: 0,
+                share: Some(Coin::new(150, "valoper1/1")),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "token_contract".to_string(),
+                    msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Burn {})
+                        .unwrap(),
+                    funds: vec![Coin::new(100, "ld_denom")],
+                })),
+                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "puppeteer_contract".to_string(),
+                    msg: to_json_binary(
+                        &drop_staking_base::msg::puppeteer::ExecuteMsg::TransferShare {
+                            share: Coin::new(150, "valoper1/1"),
+                            channel_id: "channel-1".to_string(),
+                            receiver: "some_sender".to_string(),
+                            request_id: Some(0),
+                            reply_to: mock_env().contract.address.to_string(),
+                        }
+                    )
+                    .unwrap(),
+                    funds: vec![],
+                })),
+            ]
+        );
+        assert_eq!(
+            UNBOND_IN_KIND_REQUESTS
+                .load(deps.as_ref().storage, 0)
+                .unwrap(),
+            request(
+                UnbondInKindStatus::Transferring,
+                Some(Coin::new(150, "valoper1/1"))
+            )
+        );
+    }
+
+    #[test]
+    fn receiver_resends_unconfirmed_transfer() {
+        let mut deps = setup(true);
+        let mut transferred = request(
+            UnbondInKindStatus::Transferred,
+            Some(Coin::new(150, "valoper1/1")),
+        );
+        transferred.transferred_at = Some(mock_env().block.time.seconds());
+        UNBOND_IN_KIND_REQUESTS
+            .save(deps.as_mut().storage, 0, &transferred)
+            .unwrap();
+
+        let mut env = mock_env();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("some_sender", &[]),
+            ExecuteMsg::RetryUnbondInKind { id: 0, share: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnbondInKindNotRetryable { id: 0 });
+
+        env.block.time = env.block.time.plus_seconds(1000);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RetryUnbondInKind { id: 0, share: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("some_sender", &[]),
+            ExecuteMsg::RetryUnbondInKind { id: 0, share: None },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            UNBOND_IN_KIND_REQUESTS
+                .load(deps.as_ref().storage, 0)
+                .unwrap(),
+            request(
+                UnbondInKindStatus::Transferring,
+                Some(Coin::new(150, "valoper1/1"))
+            )
+        );
+    }
+
+    #[test]
+    fn hooks_match_requests_by_id() {
+        let mut deps = setup(true);
+        for id in 0..2 {
+            UNBOND_IN_KIND_REQUESTS
+                .save(
+                    deps.as_mut().storage,
+                    id,
+                    &request(UnbondInKindStatus::Tokenizing, None),
+                )
+                .unwrap();
+        }
+        hook(
+            &mut deps,
+            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
+                drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
+                    transaction: drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare {
+                        interchain_account_id: "DROP".to_string(),
+                        validator: "valoper1".to_string(),
+                        denom: "remote_denom".to_string(),
+                        amount: 150u128,
+                        share: None,
+                        request_id: Some(1),
+                    },
+                    details: "insufficient delegation".to_string(),
+                },
+            ),
+        );
+        assert!(UNBOND_IN_KIND_REQUESTS
+            .may_load(deps.as_ref().storage, 1)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            UNBOND_IN_KIND_REQUESTS
+                .load(deps.as_ref().storage, 0)
+                .unwrap(),
+            request(UnbondInKindStatus::Tokenizing, None)
+        );
+    }
+}
+
+mod non_native_rewards {
+    use super::*;
+    use cosmwasm_std::Binary;
+    use drop_helpers::ica::IcaState;
+    use drop_staking_base::state::core::{NonNativeRewardsItem, NON_NATIVE_REWARDS_CONFIG};
+
+    fn items() -> Vec<NonNativeRewardsItem> {
+        vec![
+            NonNativeRewardsItem {
+                denom: "ufoo".to_string(),
+                local_denom: "ibc/foo".to_string(),
+                min_amount: Uint128::new(50),
+            },
+            NonNativeRewardsItem {
+                denom: "ubar".to_string(),
+                local_denom: "ibc/bar".to_string(),
+                min_amount: Uint128::new(10),
+            },
+        ]
+    }
+
+    fn balances_response(coins: Vec<Coin>, remote_height: u64) -> Binary {
+        to_json_binary(&BalancesResponse {
+            balances: Balances { coins },
+            remote_height,
+            local_height: 10u64,
+            timestamp: Timestamp::from_seconds(90001),
+        })
+        .unwrap()
+    }
+
+    fn setup(
+        state: ContractState,
+    ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+        let mut deps = mock_dependencies(&[]);
+        mock_state_query(&mut deps);
+        {
+            let deps_mut = deps.as_mut();
+            cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+        }
+        BOND_PROVIDERS.init(deps.as_mut().storage).unwrap();
+        BOND_PROVIDERS
+            .add(deps.as_mut().storage, Addr::unchecked("native_provider"))
+            .unwrap();
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| balances_response(vec![], 10));
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                to_json_binary(&DelegationsResponse {
+                    delegations: Delegations {
+                        delegations: vec![],
+                    },
+                    remote_height: 10u64,
+                    local_height: 10u64,
+                    timestamp: Timestamp::from_seconds(90001),
+                })
+                .unwrap()
+            });
+        CONFIG
+            .save(deps.as_mut().storage, &get_default_config(1000, 10, 6000))
+            .unwrap();
+        LAST_ICA_CHANGE_HEIGHT
+            .save(deps.as_mut().storage, &0)
+            .unwrap();
+        LD_DENOM
+            .save(deps.as_mut().storage, &"ld_denom".into())
+            .unwrap();
+        FSM.set_initial_state(deps.as_mut().storage, state).unwrap();
+        LAST_IDLE_CALL.save(deps.as_mut().storage, &0).unwrap();
+        PAUSE
+            .save(deps.as_mut().storage, &Pause::default())
+            .unwrap();
+        NON_NATIVE_REWARDS_CONFIG
+            .save(deps.as_mut().storage, &items())
+            .unwrap();
+        deps
+    }
+
+    #[test]
+    fn update_config_rejects_staked_denom() {
+        let mut deps = setup(ContractState::Idle);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateNonNativeRewardsConfig {
+                items: vec![NonNativeRewardsItem {
+                    denom: "remote_denom".to_string(),
+                    local_denom: "ibc/remote".to_string(),
+                    min_amount: Uint128::new(10),
+                }],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidNonNativeRewardsDenom {
+                denom: "remote_denom".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn update_config_rejects_duplicates() {
+        let mut deps = setup(ContractState::Idle);
+        let mut items = items();
+        items.push(items[0].clone());
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateNonNativeRewardsConfig { items },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidNonNativeRewardsDenom {
+                denom: "ufoo".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn update_config() {
+        let mut deps = setup(ContractState::Idle);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateNonNativeRewardsConfig {
+                items: items()[..1].to_vec(),
+            },
+        )
+        .unwrap();
+        let config: Vec<NonNativeRewardsItem> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::NonNativeRewardsConfig {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config, items()[..1].to_vec());
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateNonNativeRewardsConfig { items: vec![] },
+        )
+        .unwrap();
+        assert_eq!(
+            NON_NATIVE_REWARDS_CONFIG
+                .may_load(deps.as_ref().storage)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn tick_idle_transfers_rewards_to_pump() {
+        let mut deps = setup(ContractState::Idle);
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| to_json_binary(&false).unwrap());
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                balances_response(
+                    vec![
+                        Coin::new(100, "ufoo"),
+                        Coin::new(5, "ubar"),
+                        Coin::new(1000, "ubaz"),
+                    ],
+                    10,
+                )
+            });
+        deps.querier
+            .add_wasm_query_response("rewards_pump_contract", |_| {
+                to_json_binary(&IcaState::Registered {
+                    ica_address: "pump_ica".to_string(),
+                    port_id: "port".to_string(),
+                    channel_id: "channel".to_string(),
+                })
+                .unwrap()
+            });
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "puppeteer_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Transfer {
+                    items: vec![("pump_ica".to_string(), Coin::new(100, "ufoo"))],
+                    reply_to: env.contract.address.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Peripheral
+        );
+    }
+
+    #[test]
+    fn tick_idle_keeps_rewards_below_min() {
+        let mut deps = setup(ContractState::Idle);
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| to_json_binary(&false).unwrap());
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                balances_response(vec![Coin::new(49, "ufoo")], 10)
+            });
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Idle
+        );
+    }
+
+    #[test]
+    fn tick_idle_skips_outdated_rewards_balances() {
+        let mut deps = setup(ContractState::Idle);
+        LAST_ICA_CHANGE_HEIGHT
+            .save(deps.as_mut().storage, &10)
+            .unwrap();
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| to_json_binary(&false).unwrap());
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| {
+                balances_response(vec![Coin::new(100, "ufoo")], 9)
+            });
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Idle
+        );
+    }
+
+    #[test]
+    fn tick_idle_skips_unregistered_rewards_balances() {
+        let mut deps = setup(ContractState::Idle);
+        deps.querier
+            .add_wasm_query_response("native_provider", |_| to_json_binary(&false).unwrap());
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(100);
+        let res = execute(
+            deps.as_mut(),
+            env,
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Idle
+        );
+    }
+
+    #[test]
+    fn tick_peripheral_pushes_rewards() {
+        let mut deps = setup(ContractState::Peripheral);
+        deps.querier
+            .add_wasm_query_response("puppeteer_contract", |_| balances_response(vec![], 10));
+        LAST_PUPPETEER_RESPONSE
+            .save(
+                deps.as_mut().storage,
+                &drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                    drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                        local_height: 9u64,
+                        remote_height: 9u64,
+                        transaction: drop_puppeteer_base::peripheral_hook::Transaction::Transfer {
+                            interchain_account_id: "ica".to_string(),
+                            items: vec![("pump_ica".to_string(), Coin::new(100, "ufoo"))],
+                        },
+                    },
+                ),
+            )
+            .unwrap();
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::Tick {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "rewards_pump_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::pump::ExecuteMsg::Push {
+                    coins: vec![Coin::new(100, "ufoo")],
+                })
+                .unwrap(),
+                funds: vec![],
+            }))]
+        );
+        assert_eq!(
+            FSM.get_current_state(deps.as_ref().storage).unwrap(),
+            ContractState::Idle
+        );
+    }
+}
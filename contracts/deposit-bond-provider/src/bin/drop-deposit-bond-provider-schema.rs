@@ -0,0 +1,13 @@
+use cosmwasm_schema::write_api;
+use drop_staking_base::msg::deposit_bond_provider::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
+    }
+}
@@ -0,0 +1,1057 @@
+use cosmos_sdk_proto::cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as ProtoCoin};
+use cosmwasm_std::{
+    attr, ensure, ensure_eq, from_json, to_json_binary, Attribute, Binary, Coin, CosmosMsg,
+    Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Order, Reply, Response, StdError, StdResult,
+    SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
+use cw_ownable::{get_ownership, update_ownership};
+use cw_storage_plus::Bound;
+use drop_helpers::answer::{attr_coin, response};
+use drop_helpers::get_contracts;
+use drop_helpers::ibc_client_state::query_client_state;
+use drop_helpers::ibc_fee::query_ibc_fee;
+use drop_helpers::ica::IcaState;
+use drop_helpers::interchain::prepare_any_msg;
+use drop_puppeteer_base::msg::OpenAckVersion;
+use drop_puppeteer_base::peripheral_hook::{ReceiverExecuteMsg, ResponseHookMsg, Transaction};
+use drop_puppeteer_base::r#trait::PuppeteerReconstruct;
+use drop_staking_base::error::deposit_bond_provider::{ContractError, ContractResult};
+use drop_staking_base::msg::core::LastPuppeteerResponse;
+use drop_staking_base::msg::deposit_bond_provider::{
+    DepositAccountResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Receiver,
+};
+use drop_staking_base::msg::mirror::FungibleTokenPacketData;
+use drop_staking_base::msg::puppeteer::MultiBalances;
+use drop_staking_base::state::deposit_bond_provider::{
+    deposit_ica_id, Config, ConfigOptional, DepositAccount, DepositReceiver, ReplyMsg, TxState,
+    TxStateStatus, CONFIG, DEPOSIT_ACCOUNTS, DEPOSIT_ICA_ID_PREFIX, LAST_PUPPETEER_RESPONSE,
+    NEXT_DEPOSIT_ACCOUNT_ID, NON_STAKED_BALANCE, TX_STATE,
+};
+use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
+use neutron_sdk::bindings::query::NeutronQuery;
+use neutron_sdk::interchain_queries::queries::get_raw_interchain_query_result;
+use neutron_sdk::sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, SudoMsg};
+
+const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const LOCAL_DENOM: &str = "untrn";
+const TRANSFER_PORT: &str = "transfer";
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn instantiate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(msg.owner.as_ref()))?;
+
+    let factory_contract = deps.api.addr_validate(&msg.factory_contract)?;
+
+    let config = &Config {
+        factory_contract: factory_contract.clone(),
+        base_denom: msg.base_denom.to_string(),
+        connection_id: msg.connection_id.to_string(),
+        sdk_version: msg.sdk_version.to_string(),
+        update_period: msg.update_period,
+        timeout: msg.timeout,
+        min_deposit: msg.min_deposit,
+        min_stake_amount: msg.min_stake_amount,
+        dasset_transfer_channel_id: msg.dasset_transfer_channel_id.to_string(),
+    };
+    CONFIG.save(deps.storage, config)?;
+
+    NON_STAKED_BALANCE.save(deps.storage, &Uint128::zero())?;
+    TX_STATE.save(deps.storage, &TxState::default())?;
+    NEXT_DEPOSIT_ACCOUNT_ID.save(deps.storage, &0)?;
+
+    Ok(response(
+        "instantiate",
+        CONTRACT_NAME,
+        [
+            attr("factory_contract", factory_contract.into_string()),
+            attr("base_denom", msg.base_denom),
+            attr("connection_id", msg.connection_id),
+            attr("sdk_version", msg.sdk_version),
+            attr("update_period", msg.update_period.to_string()),
+            attr("timeout", msg.timeout.to_string()),
+            attr("min_deposit", msg.min_deposit),
+            attr("min_stake_amount", msg.min_stake_amount),
+            attr("dasset_transfer_channel_id", msg.dasset_transfer_channel_id),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
+        QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
+        QueryMsg::DepositAccount { id } => Ok(to_json_binary(&DepositAccountResponse {
+            id,
+            account: load_account(deps, id)?,
+        })?),
+        QueryMsg::DepositAccounts { start_after, limit } => {
+            query_deposit_accounts(deps, start_after, limit)
+        }
+        // deposits never come through the core bond, they are minted with BondFromProvider
+        QueryMsg::CanBond { .. } => Ok(to_json_binary(&false)?),
+        QueryMsg::CanProcessOnIdle {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(to_json_binary(&query_can_process_on_idle(deps, &config)?)?)
+        }
+        QueryMsg::TokensAmount {
+            coin,
+            exchange_rate,
+        } => query_token_amount(deps, coin, exchange_rate),
+        QueryMsg::AsyncTokensAmount {} => {
+            Ok(to_json_binary(&NON_STAKED_BALANCE.load(deps.storage)?)?)
+        }
+        QueryMsg::NonStakedBalance {} => {
+            Ok(to_json_binary(&NON_STAKED_BALANCE.load(deps.storage)?)?)
+        }
+        QueryMsg::TxState {} => Ok(to_json_binary(&TX_STATE.load(deps.storage)?)?),
+        QueryMsg::LastPuppeteerResponse {} => Ok(to_json_binary(&LastPuppeteerResponse {
+            response: LAST_PUPPETEER_RESPONSE.may_load(deps.storage)?,
+        })?),
+        QueryMsg::CanBeRemoved {} => query_can_be_removed(deps),
+    }
+}
+
+fn query_deposit_accounts(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let accounts = DEPOSIT_ACCOUNTS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(id, account)| DepositAccountResponse { id, account }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(to_json_binary(&accounts)?)
+}
+
+fn query_can_be_removed(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+    let has_pending_deposits = DEPOSIT_ACCOUNTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .any(|(_, account)| account.in_flight.is_some() || !account.unminted.is_zero());
+    let result = !has_pending_deposits
+        && NON_STAKED_BALANCE.load(deps.storage)?.is_zero()
+        && TX_STATE.load(deps.storage)?.status == TxStateStatus::Idle;
+    Ok(to_json_binary(&result)?)
+}
+
+fn query_can_process_on_idle(deps: Deps<NeutronQuery>, config: &Config) -> ContractResult<bool> {
+    let tx_state = TX_STATE.load(deps.storage)?;
+    ensure!(
+        tx_state.status == TxStateStatus::Idle,
+        ContractError::InvalidState {
+            reason: "tx_state is not idle".to_string()
+        }
+    );
+
+    let non_staked_balance = NON_STAKED_BALANCE.load(deps.storage)?;
+    ensure!(
+        non_staked_balance >= config.min_stake_amount && !non_staked_balance.is_zero(),
+        ContractError::NotEnoughToProcessIdle {
+            min_stake_amount: config.min_stake_amount,
+            non_staked_balance,
+        }
+    );
+
+    Ok(true)
+}
+
+fn query_token_amount(
+    deps: Deps<NeutronQuery>,
+    coin: Coin,
+    exchange_rate: Decimal,
+) -> ContractResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_eq!(
+        coin.denom,
+        config.base_denom,
+        ContractError::InvalidDenom {}
+    );
+
+    let issue_amount = coin.amount * (Decimal::one() / exchange_rate);
+    Ok(to_json_binary(&issue_amount)?)
+}
+
+fn load_account(deps: Deps<NeutronQuery>, id: u64) -> ContractResult<DepositAccount> {
+    DEPOSIT_ACCOUNTS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::DepositAccountNotFound { id })
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(Response::new())
+        }
+        ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::RegisterDepositAddress { receiver } => {
+            execute_register_deposit_address(deps, info, receiver)
+        }
+        ExecuteMsg::ReopenDepositAddress { id } => execute_reopen_deposit_address(deps, info, id),
+        ExecuteMsg::ProcessDeposit { id } => execute_process_deposit(deps, info, id),
+        ExecuteMsg::RetryMint { id } => execute_retry_mint(deps, env, info, id),
+        ExecuteMsg::RetryTransfer { id } => execute_retry_transfer(deps, env, info, id),
+        ExecuteMsg::Bond {} => Err(ContractError::MessageIsNotSupported {}),
+        ExecuteMsg::ProcessOnIdle {} => execute_process_on_idle(deps, env, info),
+        ExecuteMsg::PeripheralHook(msg) => execute_puppeteer_hook(deps, info, *msg),
+    }
+}
+
+fn execute_update_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    new_config: ConfigOptional,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut state = CONFIG.load(deps.storage)?;
+    let mut attrs: Vec<Attribute> = Vec::new();
+
+    if let Some(factory_contract) = new_config.factory_contract {
+        state.factory_contract = deps.api.addr_validate(factory_contract.as_ref())?;
+        attrs.push(attr("factory_contract", factory_contract))
+    }
+
+    if let Some(base_denom) = new_config.base_denom {
+        state.base_denom = base_denom.to_string();
+        attrs.push(attr("base_denom", base_denom));
+    }
+
+    if let Some(connection_id) = new_config.connection_id {
+        state.connection_id = connection_id.to_string();
+        attrs.push(attr("connection_id", connection_id));
+    }
+
+    if let Some(sdk_version) = new_config.sdk_version {
+        state.sdk_version = sdk_version.to_string();
+        attrs.push(attr("sdk_version", sdk_version));
+    }
+
+    if let Some(update_period) = new_config.update_period {
+        state.update_period = update_period;
+        attrs.push(attr("update_period", update_period.to_string()));
+    }
+
+    if let Some(timeout) = new_config.timeout {
+        state.timeout = timeout;
+        attrs.push(attr("timeout", timeout.to_string()));
+    }
+
+    if let Some(min_deposit) = new_config.min_deposit {
+        state.min_deposit = min_deposit;
+        attrs.push(attr("min_deposit", min_deposit));
+    }
+
+    if let Some(min_stake_amount) = new_config.min_stake_amount {
+        state.min_stake_amount = min_stake_amount;
+        attrs.push(attr("min_stake_amount", min_stake_amount));
+    }
+
+    if let Some(dasset_transfer_channel_id) = new_config.dasset_transfer_channel_id {
+        state.dasset_transfer_channel_id = dasset_transfer_channel_id.to_string();
+        attrs.push(attr(
+            "dasset_transfer_channel_id",
+            dasset_transfer_channel_id,
+        ));
+    }
+
+    CONFIG.save(deps.storage, &state)?;
+
+    Ok(response("update_config", CONTRACT_NAME, attrs))
+}
+
+fn execute_register_deposit_address(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    receiver: Receiver,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let receiver = match receiver {
+        Receiver::Local(address) => DepositReceiver::Local {
+            address: deps.api.addr_validate(&address)?,
+        },
+        Receiver::Remote(address) => {
+            ensure!(
+                !address.is_empty(),
+                StdError::generic_err("remote receiver is empty")
+            );
+            DepositReceiver::Remote { address }
+        }
+    };
+
+    let id = NEXT_DEPOSIT_ACCOUNT_ID.load(deps.storage)?;
+    // the account id has to fit into the lower bits of reply ids
+    ensure!(
+        id <= u32::MAX as u64,
+        ContractError::TooManyDepositAccounts {}
+    );
+    NEXT_DEPOSIT_ACCOUNT_ID.save(deps.storage, &(id + 1))?;
+
+    DEPOSIT_ACCOUNTS.save(
+        deps.storage,
+        id,
+        &DepositAccount {
+            owner: info.sender.clone(),
+            receiver: receiver.clone(),
+            ica: IcaState::InProgress,
+            balance_query_id: None,
+            in_flight: None,
+            unminted: Uint128::zero(),
+            undelivered: None,
+            processed_remote_height: 0,
+        },
+    )?;
+
+    let receiver_address = match receiver {
+        DepositReceiver::Local { address } => address.to_string(),
+        DepositReceiver::Remote { address } => address,
+    };
+
+    Ok(response(
+        "register_deposit_address",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr("owner", info.sender.to_string()),
+            attr("receiver", receiver_address),
+        ],
+    )
+    .add_message(register_ica_msg(&config, id, info.funds)))
+}
+
+fn execute_reopen_deposit_address(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    ensure!(
+        account.ica == IcaState::Timeout,
+        ContractError::DepositAddressNotClosed { id }
+    );
+    account.ica = IcaState::InProgress;
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    Ok(response(
+        "reopen_deposit_address",
+        CONTRACT_NAME,
+        [attr("id", id.to_string())],
+    )
+    .add_message(register_ica_msg(&config, id, info.funds)))
+}
+
+fn register_ica_msg(config: &Config, id: u64, register_fee: Vec<Coin>) -> NeutronMsg {
+    NeutronMsg::register_interchain_account(
+        config.connection_id.to_string(),
+        deposit_ica_id(id),
+        (!register_fee.is_empty()).then_some(register_fee),
+    )
+}
+
+fn execute_process_deposit(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    let IcaState::Registered { ica_address, .. } = account.ica.clone() else {
+        return Err(ContractError::DepositAddressNotRegistered { id });
+    };
+    ensure!(
+        account.in_flight.is_none() && account.unminted.is_zero(),
+        ContractError::DepositAccountBusy { id }
+    );
+    let query_id = account
+        .balance_query_id
+        .ok_or(ContractError::DepositAddressNotRegistered { id })?;
+
+    let query_result = get_raw_interchain_query_result(deps.as_ref(), query_id)?.result;
+    // the balance has to be observed after the previous deposit left the address
+    ensure!(
+        query_result.height > account.processed_remote_height,
+        ContractError::DepositBalanceNotUpdated { id }
+    );
+    let balances = MultiBalances::reconstruct(&query_result.kv_results, &config.sdk_version, None)?;
+    let deposit = balances
+        .coins
+        .into_iter()
+        .find(|coin| coin.denom == config.base_denom)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+    ensure!(
+        !deposit.is_zero() && deposit >= config.min_deposit,
+        ContractError::DepositTooSmall {
+            min_deposit: config.min_deposit,
+            deposit,
+        }
+    );
+
+    let addrs = get_contracts!(deps, config.factory_contract, puppeteer_contract);
+    let puppeteer_ica: IcaState = deps.querier.query_wasm_smart(
+        addrs.puppeteer_contract,
+        &drop_puppeteer_base::msg::QueryMsg::<Empty>::Ica {},
+    )?;
+    let IcaState::Registered {
+        ica_address: puppeteer_ica_address,
+        ..
+    } = puppeteer_ica
+    else {
+        return Err(ContractError::IcaNotRegistered {});
+    };
+
+    // the dAsset transfer to a remote receiver is paid for upfront as well
+    let fee = ensure_ibc_fee_paid(
+        deps.as_ref(),
+        &info,
+        match account.receiver {
+            DepositReceiver::Local { .. } => 1,
+            DepositReceiver::Remote { .. } => 2,
+        },
+    )?;
+
+    let send_msg = MsgSend {
+        from_address: ica_address,
+        to_address: puppeteer_ica_address.to_string(),
+        amount: vec![ProtoCoin {
+            denom: config.base_denom.to_string(),
+            amount: deposit.to_string(),
+        }],
+    };
+    let submit_tx_msg = NeutronMsg::submit_tx(
+        config.connection_id.to_string(),
+        deposit_ica_id(id),
+        vec![prepare_any_msg(send_msg, "/cosmos.bank.v1beta1.MsgSend")?],
+        "".to_string(),
+        config.timeout,
+        fee,
+    );
+
+    account.in_flight = Some(deposit);
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    Ok(response(
+        "process_deposit",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr_coin("deposit", deposit, config.base_denom),
+            attr("receiver", puppeteer_ica_address),
+        ],
+    )
+    .add_message(submit_tx_msg))
+}
+
+fn execute_retry_mint(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let account = load_account(deps.as_ref(), id)?;
+    ensure!(
+        account.in_flight.is_none() && !account.unminted.is_zero(),
+        ContractError::NothingToRetry { id }
+    );
+    if let DepositReceiver::Remote { .. } = account.receiver {
+        ensure_ibc_fee_paid(deps.as_ref(), &info, 1)?;
+    }
+
+    Ok(response(
+        "retry_mint",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr_coin("amount", account.unminted, config.base_denom.to_string()),
+        ],
+    )
+    .add_submessage(get_mint_msg(deps, &env, &config, id, &account)?))
+}
+
+fn execute_retry_transfer(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    let DepositReceiver::Remote { address } = account.receiver.clone() else {
+        return Err(ContractError::NothingToRetry { id });
+    };
+    let coin = account
+        .undelivered
+        .take()
+        .ok_or(ContractError::NothingToRetry { id })?;
+    ensure_ibc_fee_paid(deps.as_ref(), &info, 1)?;
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    Ok(response(
+        "retry_transfer",
+        CONTRACT_NAME,
+        [attr("id", id.to_string()), attr("amount", coin.to_string())],
+    )
+    .add_message(get_transfer_msg(
+        deps.as_ref(),
+        &env,
+        &config,
+        id,
+        coin,
+        address,
+    )?))
+}
+
+// IBC messages of permissionless calls are paid by the caller, not from the contract balance
+fn ensure_ibc_fee_paid(
+    deps: Deps<NeutronQuery>,
+    info: &MessageInfo,
+    messages: u128,
+) -> ContractResult<IbcFee> {
+    let fee = query_ibc_fee(deps, LOCAL_DENOM)?;
+    let required = fee
+        .recv_fee
+        .iter()
+        .chain(fee.ack_fee.iter())
+        .chain(fee.timeout_fee.iter())
+        .map(|coin| coin.amount)
+        .sum::<Uint128>()
+        * Uint128::from(messages);
+    let paid = cw_utils::may_pay(info, LOCAL_DENOM)?;
+    ensure!(
+        paid >= required,
+        ContractError::IbcFeeNotPaid { required, paid }
+    );
+    Ok(fee)
+}
+
+fn get_mint_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    id: u64,
+    account: &DepositAccount,
+) -> ContractResult<SubMsg<NeutronMsg>> {
+    let addrs = get_contracts!(deps, config.factory_contract, core_contract);
+    // dAssets for remote receivers are minted to the provider and sent over IBC afterwards
+    let receiver = match &account.receiver {
+        DepositReceiver::Local { address } => address.to_string(),
+        DepositReceiver::Remote { .. } => env.contract.address.to_string(),
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.core_contract,
+        msg: to_json_binary(
+            &drop_staking_base::msg::core::ExecuteMsg::BondFromProvider {
+                amount: account.unminted,
+                receiver,
+            },
+        )?,
+        funds: vec![],
+    });
+    Ok(SubMsg::reply_always(
+        msg,
+        ReplyMsg::Mint { account_id: id }.to_reply_id(),
+    ))
+}
+
+fn get_transfer_msg(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    id: u64,
+    token: Coin,
+    receiver: String,
+) -> ContractResult<NeutronMsg> {
+    Ok(NeutronMsg::IbcTransfer {
+        source_port: TRANSFER_PORT.to_string(),
+        source_channel: config.dasset_transfer_channel_id.to_string(),
+        token,
+        sender: env.contract.address.to_string(),
+        receiver,
+        timeout_height: RequestPacketTimeoutHeight {
+            revision_number: None,
+            revision_height: None,
+        },
+        timeout_timestamp: env.block.time.plus_seconds(config.timeout).nanos(),
+        memo: id.to_string(),
+        fee: query_ibc_fee(deps, LOCAL_DENOM)?,
+    })
+}
+
+fn execute_process_on_idle(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let addrs = get_contracts!(
+        deps,
+        config.factory_contract,
+        core_contract,
+        strategy_contract,
+        puppeteer_contract
+    );
+
+    ensure_eq!(
+        info.sender,
+        addrs.core_contract,
+        ContractError::Unauthorized {}
+    );
+
+    query_can_process_on_idle(deps.as_ref(), &config)?;
+
+    let non_staked_balance = NON_STAKED_BALANCE.load(deps.storage)?;
+    let to_delegate: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
+        &addrs.strategy_contract,
+        &drop_staking_base::msg::strategy::QueryMsg::CalcDeposit {
+            deposit: non_staked_balance,
+        },
+    )?;
+    let puppeteer_delegation_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.puppeteer_contract.to_string(),
+        msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Delegate {
+            items: to_delegate,
+            reply_to: env.contract.address.to_string(),
+        })?,
+        funds: vec![],
+    });
+
+    TX_STATE.save(
+        deps.storage,
+        &TxState {
+            status: TxStateStatus::InProgress,
+            transaction: Some(Transaction::Stake {
+                amount: non_staked_balance,
+            }),
+        },
+    )?;
+
+    Ok(response(
+        "process_on_idle",
+        CONTRACT_NAME,
+        [attr_coin(
+            "stake",
+            non_staked_balance,
+            config.base_denom.to_string(),
+        )],
+    )
+    .add_submessage(SubMsg::reply_always(
+        puppeteer_delegation_msg,
+        ReplyMsg::Bond.to_reply_id(),
+    )))
+}
+
+fn execute_puppeteer_hook(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    msg: ResponseHookMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let addrs = get_contracts!(
+        deps,
+        config.factory_contract,
+        core_contract,
+        puppeteer_contract
+    );
+
+    ensure_eq!(
+        info.sender,
+        addrs.puppeteer_contract,
+        ContractError::Unauthorized {}
+    );
+
+    let tx_state = TX_STATE.load(deps.storage)?;
+    ensure!(
+        tx_state.status == TxStateStatus::WaitingForAck,
+        ContractError::InvalidState {
+            reason: "tx_state is not WaitingForAck".to_string()
+        }
+    );
+
+    match msg.clone() {
+        ResponseHookMsg::Success(success_msg) => {
+            if let Transaction::Stake { amount } = success_msg.transaction {
+                NON_STAKED_BALANCE
+                    .update(deps.storage, |balance| StdResult::Ok(balance - amount))?;
+                TX_STATE.save(deps.storage, &TxState::default())?;
+            }
+        }
+        ResponseHookMsg::Error(error_msg) => {
+            if let Transaction::Stake { .. } = error_msg.transaction {
+                TX_STATE.save(deps.storage, &TxState::default())?;
+            }
+        }
+    }
+
+    LAST_PUPPETEER_RESPONSE.save(deps.storage, &msg)?;
+
+    let hook_message = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.core_contract.to_string(),
+        msg: to_json_binary(&ReceiverExecuteMsg::PeripheralHook(msg))?,
+        funds: vec![],
+    });
+
+    Ok(response(
+        "execute-puppeteer_hook",
+        CONTRACT_NAME,
+        vec![attr("action", "puppeteer_hook")],
+    )
+    .add_submessage(SubMsg::reply_on_error(
+        hook_message,
+        ReplyMsg::PuppeteerHookForward.to_reply_id(),
+    )))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: Reply,
+) -> ContractResult<Response<NeutronMsg>> {
+    match ReplyMsg::from_reply_id(msg.id) {
+        ReplyMsg::Bond => {
+            if let SubMsgResult::Err(err) = msg.result {
+                return Err(ContractError::PuppeteerError { message: err });
+            }
+            TX_STATE.update(deps.storage, |mut tx_state| {
+                tx_state.status = TxStateStatus::WaitingForAck;
+                StdResult::Ok(tx_state)
+            })?;
+            Ok(Response::new())
+        }
+        ReplyMsg::PuppeteerHookForward => Err(ContractError::PuppeteerError {
+            message: msg.result.unwrap_err(),
+        }),
+        ReplyMsg::RegisterBalanceQuery { account_id } => {
+            let query_id = drop_helpers::reply::get_query_id(msg.result)?;
+            let mut account = load_account(deps.as_ref(), account_id)?;
+            account.balance_query_id = Some(query_id);
+            DEPOSIT_ACCOUNTS.save(deps.storage, account_id, &account)?;
+            Ok(response(
+                "register_balance_query",
+                CONTRACT_NAME,
+                [
+                    attr("id", account_id.to_string()),
+                    attr("query_id", query_id.to_string()),
+                ],
+            ))
+        }
+        ReplyMsg::Mint { account_id } => mint_reply(deps, env, account_id, msg.result),
+    }
+}
+
+fn mint_reply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    id: u64,
+    result: SubMsgResult,
+) -> ContractResult<Response<NeutronMsg>> {
+    let res = match result {
+        SubMsgResult::Ok(res) => res,
+        // the deposit stays unminted until RetryMint succeeds
+        SubMsgResult::Err(err) => {
+            return Ok(response(
+                "mint_failed",
+                CONTRACT_NAME,
+                [attr("id", id.to_string()), attr("error", err)],
+            ))
+        }
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    let amount = account.unminted;
+    account.unminted = Uint128::zero();
+    // the deposit is accounted only now, so it doesn't affect the exchange rate it was minted at
+    NON_STAKED_BALANCE.update(deps.storage, |balance| StdResult::Ok(balance + amount))?;
+
+    let mut attrs = vec![
+        attr("id", id.to_string()),
+        attr_coin("amount", amount, config.base_denom.to_string()),
+    ];
+    let mut msgs = vec![];
+    if let DepositReceiver::Remote { address } = account.receiver.clone() {
+        let minted = res
+            .events
+            .iter()
+            .find(|event| event.ty == "tf_mint")
+            .and_then(|event| event.attributes.iter().find(|a| a.key == "amount"))
+            .ok_or(ContractError::NoTokensMinted)?
+            .value
+            .parse::<Coin>()
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        attrs.push(attr("minted", minted.to_string()));
+        // failing here would revert the mint together with the deposit response
+        // and leave the deposit in flight, so the dAsset waits for RetryTransfer instead
+        match get_transfer_msg(deps.as_ref(), &env, &config, id, minted.clone(), address) {
+            Ok(msg) => msgs.push(msg),
+            Err(err) => {
+                attrs.push(attr("transfer_error", err.to_string()));
+                account.undelivered = Some(match account.undelivered.take() {
+                    Some(coin) => Coin::new((coin.amount + minted.amount).u128(), coin.denom),
+                    None => minted,
+                });
+            }
+        }
+    }
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    Ok(response("mint", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn sudo(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: SudoMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    deps.api.debug(&format!(
+        "WASMDEBUG: sudo call: {:?} block: {:?}",
+        msg, env.block
+    ));
+    match msg {
+        SudoMsg::OpenAck {
+            port_id,
+            channel_id,
+            counterparty_version,
+            ..
+        } => sudo_open_ack(deps, port_id, channel_id, counterparty_version),
+        SudoMsg::Response { request, .. } if is_transfer(&request) => {
+            let id = get_id_from_request_memo(&request)?;
+            Ok(response(
+                "sudo-transfer_response",
+                CONTRACT_NAME,
+                [attr("id", id.to_string())],
+            ))
+        }
+        SudoMsg::Response { request, .. } => sudo_deposit_response(deps, env, request),
+        SudoMsg::Error { request, .. } | SudoMsg::Timeout { request } if is_transfer(&request) => {
+            sudo_transfer_failed(deps, request)
+        }
+        SudoMsg::Error { request, details } => sudo_deposit_failed(deps, request, details, false),
+        SudoMsg::Timeout { request } => {
+            sudo_deposit_failed(deps, request, "Timeout".to_string(), true)
+        }
+        _ => Err(ContractError::MessageIsNotSupported {}),
+    }
+}
+
+fn is_transfer(request: &RequestPacket) -> bool {
+    request.source_port.as_deref() == Some(TRANSFER_PORT)
+}
+
+// ICA ports look like icacontroller-<contract address>.<interchain account id>
+fn get_id_from_port(port_id: &str) -> ContractResult<u64> {
+    port_id
+        .rsplit_once('.')
+        .and_then(|(_, ica_id)| ica_id.strip_prefix(DEPOSIT_ICA_ID_PREFIX))
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| ContractError::InvalidPortId {
+            port_id: port_id.to_string(),
+        })
+}
+
+fn get_id_from_request_memo(request: &RequestPacket) -> ContractResult<u64> {
+    let data: FungibleTokenPacketData = from_json(
+        request
+            .data
+            .as_ref()
+            .ok_or_else(|| StdError::generic_err("request data not found"))?,
+    )?;
+    data.memo
+        .parse()
+        .map_err(|_| StdError::generic_err("invalid memo").into())
+}
+
+fn sudo_open_ack(
+    deps: DepsMut<NeutronQuery>,
+    port_id: String,
+    channel_id: String,
+    counterparty_version: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let id = get_id_from_port(&port_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    let version: OpenAckVersion = serde_json_wasm::from_str(&counterparty_version)
+        .map_err(|_| StdError::generic_err("can't parse version"))?;
+
+    account.ica = IcaState::Registered {
+        ica_address: version.address.to_string(),
+        port_id,
+        channel_id,
+    };
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    let mut submsgs = vec![];
+    // a reopened account keeps its address and thus its balance query
+    if account.balance_query_id.is_none() {
+        submsgs.push(SubMsg::reply_on_success(
+            drop_helpers::icq::new_multiple_balances_query_msg(
+                config.connection_id,
+                version.address.to_string(),
+                vec![config.base_denom],
+                config.update_period,
+            )?,
+            ReplyMsg::RegisterBalanceQuery { account_id: id }.to_reply_id(),
+        ));
+    }
+
+    Ok(response(
+        "sudo-open_ack",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr("deposit_address", version.address),
+        ],
+    )
+    .add_submessages(submsgs))
+}
+
+fn sudo_deposit_response(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    request: RequestPacket,
+) -> ContractResult<Response<NeutronMsg>> {
+    let port_id = request
+        .source_port
+        .ok_or_else(|| StdError::generic_err("source_port not found"))?;
+    let channel_id = request
+        .source_channel
+        .ok_or_else(|| StdError::generic_err("source_channel not found"))?;
+    let id = get_id_from_port(&port_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    let amount = account
+        .in_flight
+        .take()
+        .ok_or(ContractError::InvalidState {
+            reason: "no deposit in flight".to_string(),
+        })?;
+
+    let remote_height = query_client_state(&deps.as_ref(), channel_id, port_id)?
+        .identified_client_state
+        .ok_or_else(|| StdError::generic_err("IBC client state identified_client_state not found"))?
+        .client_state
+        .latest_height
+        .ok_or_else(|| StdError::generic_err("IBC client state latest_height not found"))?
+        .revision_height;
+
+    account.unminted += amount;
+    account.processed_remote_height = remote_height.u64();
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    let res = response(
+        "sudo-deposit_response",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr_coin("amount", amount, config.base_denom.to_string()),
+            attr("remote_height", remote_height.to_string()),
+        ],
+    );
+    // the deposit has already left the deposit address, so it stays unminted until
+    // RetryMint instead of failing the response and being left in flight
+    Ok(match get_mint_msg(deps, &env, &config, id, &account) {
+        Ok(msg) => res.add_submessage(msg),
+        Err(err) => res.add_attribute("mint_error", err.to_string()),
+    })
+}
+
+fn sudo_deposit_failed(
+    deps: DepsMut<NeutronQuery>,
+    request: RequestPacket,
+    details: String,
+    timeout: bool,
+) -> ContractResult<Response<NeutronMsg>> {
+    let port_id = request
+        .source_port
+        .ok_or_else(|| StdError::generic_err("source_port not found"))?;
+    let id = get_id_from_port(&port_id)?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    // the deposit is still on the deposit address and is picked up by the next ProcessDeposit
+    account.in_flight = None;
+    if timeout {
+        // ordered ICA channels get closed on timeout
+        account.ica = IcaState::Timeout;
+    }
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    Ok(response(
+        "sudo-deposit_failed",
+        CONTRACT_NAME,
+        [attr("id", id.to_string()), attr("details", details)],
+    ))
+}
+
+fn sudo_transfer_failed(
+    deps: DepsMut<NeutronQuery>,
+    request: RequestPacket,
+) -> ContractResult<Response<NeutronMsg>> {
+    let id = get_id_from_request_memo(&request)?;
+    let data: FungibleTokenPacketData = from_json(
+        request
+            .data
+            .as_ref()
+            .ok_or_else(|| StdError::generic_err("request data not found"))?,
+    )?;
+    let amount: Uint128 = data.amount.parse()?;
+    let mut account = load_account(deps.as_ref(), id)?;
+    let undelivered = match account.undelivered.take() {
+        Some(coin) => Coin::new((coin.amount + amount).u128(), coin.denom),
+        None => Coin::new(amount.u128(), data.denom),
+    };
+    account.undelivered = Some(undelivered.clone());
+    DEPOSIT_ACCOUNTS.save(deps.storage, id, &account)?;
+
+    Ok(response(
+        "sudo-transfer_failed",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr("undelivered", undelivered.to_string()),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn migrate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let version: semver::Version = CONTRACT_VERSION.parse()?;
+    let storage_version: semver::Version =
+        cw2::get_contract_version(deps.storage)?.version.parse()?;
+
+    if storage_version < version {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new())
+}
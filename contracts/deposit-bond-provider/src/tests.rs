@@ -0,0 +1,901 @@
+use cosmos_sdk_proto::cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as ProtoCoin};
+use cosmwasm_std::{
+    attr, coin, coins, from_json,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Event, OwnedDeps, Reply, SubMsg,
+    SubMsgResponse, SubMsgResult, Uint128, Uint64, WasmMsg,
+};
+use drop_helpers::{
+    ibc_client_state::{
+        ChannelClientStateResponse, ClientState, Fraction, Height, IdentifiedClientState,
+    },
+    ica::IcaState,
+    interchain::prepare_any_msg,
+    testing::{mock_dependencies, mock_state_query, WasmMockQuerier},
+};
+use drop_staking_base::{
+    error::deposit_bond_provider::ContractError,
+    msg::{
+        deposit_bond_provider::{ExecuteMsg, InstantiateMsg, QueryMsg, Receiver},
+        mirror::FungibleTokenPacketData,
+    },
+    state::deposit_bond_provider::{
+        Config, DepositAccount, DepositReceiver, ReplyMsg, CONFIG, DEPOSIT_ACCOUNTS,
+        NEXT_DEPOSIT_ACCOUNT_ID, NON_STAKED_BALANCE, TX_STATE,
+    },
+};
+use neutron_sdk::{
+    bindings::{
+        msg::{IbcFee, MsgRegisterInterchainQueryResponse, NeutronMsg},
+        query::{NeutronQuery, QueryRegisteredQueryResultResponse},
+        types::{InterchainQueryResult, StorageValue},
+    },
+    interchain_queries::v045::helpers::create_account_denom_balance_key,
+    query::min_ibc_fee::MinIbcFeeResponse,
+    sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, SudoMsg},
+};
+
+const DEPOSIT_PORT: &str = "icacontroller-cosmos2contract.deposit0";
+
+fn get_default_config() -> Config {
+    Config {
+        factory_contract: Addr::unchecked("factory_contract"),
+        base_denom: "base_denom".to_string(),
+        connection_id: "connection_id".to_string(),
+        sdk_version: "0.47.10".to_string(),
+        update_period: 100,
+        timeout: 100,
+        min_deposit: Uint128::from(100u128),
+        min_stake_amount: Uint128::from(100u128),
+        dasset_transfer_channel_id: "channel-1".to_string(),
+    }
+}
+
+fn get_default_account(receiver: DepositReceiver) -> DepositAccount {
+    DepositAccount {
+        owner: Addr::unchecked("owner"),
+        receiver,
+        ica: IcaState::Registered {
+            ica_address: "deposit_ica".to_string(),
+            port_id: DEPOSIT_PORT.to_string(),
+            channel_id: "channel-10".to_string(),
+        },
+        balance_query_id: Some(5),
+        in_flight: None,
+        unminted: Uint128::zero(),
+        undelivered: None,
+        processed_remote_height: 0,
+    }
+}
+
+fn setup(
+    receiver: DepositReceiver,
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &Default::default())
+        .unwrap();
+    NEXT_DEPOSIT_ACCOUNT_ID
+        .save(deps.as_mut().storage, &1)
+        .unwrap();
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &get_default_account(receiver))
+        .unwrap();
+    deps
+}
+
+fn local_receiver() -> DepositReceiver {
+    DepositReceiver::Local {
+        address: Addr::unchecked("receiver"),
+    }
+}
+
+fn remote_receiver() -> DepositReceiver {
+    DepositReceiver::Remote {
+        address: "cosmos1receiver".to_string(),
+    }
+}
+
+fn mock_ibc_fee(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>) {
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "untrn"),
+                timeout_fee: coins(200, "untrn"),
+            },
+        })
+        .unwrap()
+    });
+}
+
+fn mock_deposit_balance(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>,
+    amount: u128,
+    height: u64,
+) {
+    deps.querier.add_custom_query_response(move |_| {
+        let key = create_account_denom_balance_key(b"deposit_ica".to_vec(), "base_denom").unwrap();
+        to_json_binary(&QueryRegisteredQueryResultResponse {
+            result: InterchainQueryResult {
+                kv_results: vec![StorageValue {
+                    storage_prefix: "bank".to_string(),
+                    key: Binary::from(key),
+                    value: Binary::from(amount.to_string().as_bytes()),
+                }],
+                height,
+                revision: 0,
+            },
+        })
+        .unwrap()
+    });
+}
+
+fn mock_client_state(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery>) {
+    deps.querier.add_stargate_query_response(
+        "/ibc.core.channel.v1.Query/ChannelClientState",
+        |_| {
+            to_json_binary(&ChannelClientStateResponse {
+                identified_client_state: Some(IdentifiedClientState {
+                    client_id: "07-tendermint-0".to_string(),
+                    client_state: ClientState {
+                        chain_id: "test-1".to_string(),
+                        type_url: "type_url".to_string(),
+                        trust_level: Fraction {
+                            numerator: Uint64::from(1u64),
+                            denominator: Uint64::from(3u64),
+                        },
+                        trusting_period: Some("1000".to_string()),
+                        unbonding_period: Some("1500".to_string()),
+                        max_clock_drift: Some("1000".to_string()),
+                        frozen_height: None,
+                        latest_height: Some(Height {
+                            revision_number: Uint64::from(0u64),
+                            revision_height: Uint64::from(54321u64),
+                        }),
+                        proof_specs: vec![],
+                        upgrade_path: vec![],
+                        allow_update_after_expiry: true,
+                        allow_update_after_misbehaviour: true,
+                    },
+                }),
+                proof: None,
+                proof_height: Height {
+                    revision_number: Uint64::from(0u64),
+                    revision_height: Uint64::from(33333u64),
+                },
+            })
+            .unwrap()
+        },
+    );
+}
+
+fn transfer_request(memo: &str) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(1),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("channel-1".to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: Some(
+            to_json_binary(&FungibleTokenPacketData {
+                denom: "ld_denom".to_string(),
+                amount: "90".to_string(),
+                sender: "cosmos2contract".to_string(),
+                receiver: "cosmos1receiver".to_string(),
+                memo: memo.to_string(),
+            })
+            .unwrap(),
+        ),
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+fn deposit_request() -> RequestPacket {
+    RequestPacket {
+        sequence: Some(1),
+        source_port: Some(DEPOSIT_PORT.to_string()),
+        source_channel: Some("channel-10".to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+#[test]
+fn instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    let response = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            factory_contract: "factory_contract".to_string(),
+            base_denom: "base_denom".to_string(),
+            connection_id: "connection_id".to_string(),
+            sdk_version: "0.47.10".to_string(),
+            update_period: 100,
+            timeout: 100,
+            min_deposit: Uint128::from(100u128),
+            min_stake_amount: Uint128::from(100u128),
+            dasset_transfer_channel_id: "channel-1".to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap(),
+        get_default_config()
+    );
+    assert_eq!(
+        NEXT_DEPOSIT_ACCOUNT_ID.load(deps.as_ref().storage).unwrap(),
+        0
+    );
+    assert_eq!(response.messages.len(), 0);
+}
+
+#[test]
+fn register_deposit_address() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    NEXT_DEPOSIT_ACCOUNT_ID
+        .save(deps.as_mut().storage, &0)
+        .unwrap();
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(1000, "untrn")),
+        ExecuteMsg::RegisterDepositAddress {
+            receiver: Receiver::Remote("cosmos1receiver".to_string()),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(NeutronMsg::register_interchain_account(
+            "connection_id".to_string(),
+            "deposit0".to_string(),
+            Some(coins(1000, "untrn")),
+        ))]
+    );
+    assert_eq!(
+        response.events,
+        vec![Event::new(
+            "crates.io:drop-staking__drop-deposit-bond-provider-register_deposit_address"
+        )
+        .add_attributes([
+            attr("id", "0"),
+            attr("owner", "user"),
+            attr("receiver", "cosmos1receiver"),
+        ])]
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.ica, IcaState::InProgress);
+    assert_eq!(account.receiver, remote_receiver());
+    assert_eq!(
+        NEXT_DEPOSIT_ACCOUNT_ID.load(deps.as_ref().storage).unwrap(),
+        1
+    );
+}
+
+#[test]
+fn sudo_open_ack_registers_balance_query() {
+    let mut deps = setup(local_receiver());
+    let mut account = get_default_account(local_receiver());
+    account.ica = IcaState::InProgress;
+    account.balance_query_id = None;
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    let response = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::OpenAck {
+            port_id: DEPOSIT_PORT.to_string(),
+            channel_id: "channel-10".to_string(),
+            counterparty_channel_id: "channel-20".to_string(),
+            counterparty_version: r#"{"version":"ics27-1","controller_connection_id":"connection-0","host_connection_id":"connection-0","address":"cosmos1hdga6p84cpc6gulk9ruxy5w0vpfx9dv83ku59r","encoding":"proto3","tx_type":"sdk_multi_msg"}"#.to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_on_success(
+            drop_helpers::icq::new_multiple_balances_query_msg(
+                "connection_id".to_string(),
+                "cosmos1hdga6p84cpc6gulk9ruxy5w0vpfx9dv83ku59r".to_string(),
+                vec!["base_denom".to_string()],
+                100,
+            )
+            .unwrap(),
+            ReplyMsg::RegisterBalanceQuery { account_id: 0 }.to_reply_id(),
+        )]
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(
+        account.ica,
+        IcaState::Registered {
+            ica_address: "cosmos1hdga6p84cpc6gulk9ruxy5w0vpfx9dv83ku59r".to_string(),
+            port_id: DEPOSIT_PORT.to_string(),
+            channel_id: "channel-10".to_string(),
+        }
+    );
+
+    crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::RegisterBalanceQuery { account_id: 0 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(to_json_binary(&MsgRegisterInterchainQueryResponse { id: 7 }).unwrap()),
+            }),
+        },
+    )
+    .unwrap();
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.balance_query_id, Some(7));
+}
+
+#[test]
+fn process_deposit() {
+    let mut deps = setup(local_receiver());
+    mock_state_query(&mut deps);
+    mock_deposit_balance(&mut deps, 1000, 10);
+    mock_ibc_fee(&mut deps);
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&IcaState::Registered {
+                ica_address: "puppeteer_ica".to_string(),
+                port_id: "port_id".to_string(),
+                channel_id: "channel_id".to_string(),
+            })
+            .unwrap()
+        });
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &coins(300, "untrn")),
+        ExecuteMsg::ProcessDeposit { id: 0 },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(NeutronMsg::submit_tx(
+            "connection_id".to_string(),
+            "deposit0".to_string(),
+            vec![prepare_any_msg(
+                MsgSend {
+                    from_address: "deposit_ica".to_string(),
+                    to_address: "puppeteer_ica".to_string(),
+                    amount: vec![ProtoCoin {
+                        denom: "base_denom".to_string(),
+                        amount: "1000".to_string(),
+                    }],
+                },
+                "/cosmos.bank.v1beta1.MsgSend",
+            )
+            .unwrap()],
+            "".to_string(),
+            100,
+            IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "untrn"),
+                timeout_fee: coins(200, "untrn"),
+            },
+        ))]
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.in_flight, Some(Uint128::from(1000u128)));
+}
+
+#[test]
+fn process_deposit_fee_not_paid() {
+    let mut deps = setup(remote_receiver());
+    mock_state_query(&mut deps);
+    mock_deposit_balance(&mut deps, 1000, 10);
+    mock_ibc_fee(&mut deps);
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&IcaState::Registered {
+                ica_address: "puppeteer_ica".to_string(),
+                port_id: "port_id".to_string(),
+                channel_id: "channel_id".to_string(),
+            })
+            .unwrap()
+        });
+
+    // the dAsset transfer to a remote receiver has to be paid for as well
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &coins(300, "untrn")),
+        ExecuteMsg::ProcessDeposit { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::IbcFeeNotPaid {
+            required: Uint128::from(600u128),
+            paid: Uint128::from(300u128),
+        }
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.in_flight, None);
+}
+
+#[test]
+fn process_deposit_stale_balance() {
+    let mut deps = setup(local_receiver());
+    let mut account = get_default_account(local_receiver());
+    account.processed_remote_height = 10;
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+    mock_deposit_balance(&mut deps, 1000, 10);
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ProcessDeposit { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::DepositBalanceNotUpdated { id: 0 });
+}
+
+#[test]
+fn process_deposit_too_small() {
+    let mut deps = setup(local_receiver());
+    mock_deposit_balance(&mut deps, 99, 10);
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ProcessDeposit { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::DepositTooSmall {
+            min_deposit: Uint128::from(100u128),
+            deposit: Uint128::from(99u128),
+        }
+    );
+}
+
+#[test]
+fn process_deposit_busy() {
+    let mut deps = setup(local_receiver());
+    let mut account = get_default_account(local_receiver());
+    account.in_flight = Some(Uint128::from(1000u128));
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ProcessDeposit { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::DepositAccountBusy { id: 0 });
+}
+
+#[test]
+fn sudo_deposit_response_mints() {
+    let mut deps = setup(local_receiver());
+    mock_state_query(&mut deps);
+    mock_client_state(&mut deps);
+    let mut account = get_default_account(local_receiver());
+    account.in_flight = Some(Uint128::from(1000u128));
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    let response = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Response {
+            request: deposit_request(),
+            data: Binary::default(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "core_contract".to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::core::ExecuteMsg::BondFromProvider {
+                        amount: Uint128::from(1000u128),
+                        receiver: "receiver".to_string(),
+                    }
+                )
+                .unwrap(),
+                funds: vec![],
+            }),
+            ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+        )]
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.in_flight, None);
+    assert_eq!(account.unminted, Uint128::from(1000u128));
+    assert_eq!(account.processed_remote_height, 54321);
+}
+
+#[test]
+fn sudo_deposit_timeout_closes_address() {
+    let mut deps = setup(local_receiver());
+    let mut account = get_default_account(local_receiver());
+    account.in_flight = Some(Uint128::from(1000u128));
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: deposit_request(),
+        },
+    )
+    .unwrap();
+
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.in_flight, None);
+    assert_eq!(account.ica, IcaState::Timeout);
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &coins(1000, "untrn")),
+        ExecuteMsg::ReopenDepositAddress { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(NeutronMsg::register_interchain_account(
+            "connection_id".to_string(),
+            "deposit0".to_string(),
+            Some(coins(1000, "untrn")),
+        ))]
+    );
+}
+
+#[test]
+fn mint_reply_local() {
+    let mut deps = setup(local_receiver());
+    let mut account = get_default_account(local_receiver());
+    account.unminted = Uint128::from(1000u128);
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    assert!(response.messages.is_empty());
+    assert_eq!(
+        NON_STAKED_BALANCE.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(1000u128)
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.unminted, Uint128::zero());
+}
+
+#[test]
+fn mint_reply_remote_sends_dasset() {
+    let mut deps = setup(remote_receiver());
+    mock_ibc_fee(&mut deps);
+    let mut account = get_default_account(remote_receiver());
+    account.unminted = Uint128::from(1000u128);
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("tf_mint").add_attribute("amount", "900ld_denom")],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(NeutronMsg::IbcTransfer {
+            source_port: "transfer".to_string(),
+            source_channel: "channel-1".to_string(),
+            token: coin(900, "ld_denom"),
+            sender: "cosmos2contract".to_string(),
+            receiver: "cosmos1receiver".to_string(),
+            timeout_height: RequestPacketTimeoutHeight {
+                revision_number: None,
+                revision_height: None,
+            },
+            timeout_timestamp: mock_env().block.time.plus_seconds(100).nanos(),
+            memo: "0".to_string(),
+            fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "untrn"),
+                timeout_fee: coins(200, "untrn"),
+            },
+        })]
+    );
+    assert_eq!(
+        NON_STAKED_BALANCE.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(1000u128)
+    );
+}
+
+#[test]
+fn mint_reply_error_keeps_deposit_unminted() {
+    let mut deps = setup(local_receiver());
+    let mut account = get_default_account(local_receiver());
+    account.unminted = Uint128::from(1000u128);
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+            result: SubMsgResult::Err("paused".to_string()),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        NON_STAKED_BALANCE.load(deps.as_ref().storage).unwrap(),
+        Uint128::zero()
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.unminted, Uint128::from(1000u128));
+}
+
+#[test]
+fn sudo_deposit_response_mint_error_keeps_deposit_for_retry() {
+    let mut deps = setup(local_receiver());
+    mock_state_query(&mut deps);
+    mock_client_state(&mut deps);
+    let mut account = get_default_account(local_receiver());
+    account.in_flight = Some(Uint128::from(1000u128));
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Response {
+            request: deposit_request(),
+            data: Binary::default(),
+        },
+    )
+    .unwrap();
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+            result: SubMsgResult::Err("paused".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-deposit-bond-provider-mint_failed")
+                .add_attributes(vec![attr("id", "0"), attr("error", "paused")])
+        ]
+    );
+
+    // the deposit is not in flight anymore, so the account is not stuck
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.in_flight, None);
+    assert_eq!(account.unminted, Uint128::from(1000u128));
+
+    mock_state_query(&mut deps);
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::RetryMint { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "core_contract".to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::core::ExecuteMsg::BondFromProvider {
+                        amount: Uint128::from(1000u128),
+                        receiver: "receiver".to_string(),
+                    }
+                )
+                .unwrap(),
+                funds: vec![],
+            }),
+            ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+        )]
+    );
+}
+
+#[test]
+fn retry_mint_remote_fee_not_paid() {
+    let mut deps = setup(remote_receiver());
+    mock_ibc_fee(&mut deps);
+    let mut account = get_default_account(remote_receiver());
+    account.unminted = Uint128::from(1000u128);
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::RetryMint { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::IbcFeeNotPaid {
+            required: Uint128::from(300u128),
+            paid: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn mint_reply_remote_transfer_error_keeps_dasset_for_retry() {
+    let mut deps = setup(remote_receiver());
+    let mut account = get_default_account(remote_receiver());
+    account.unminted = Uint128::from(1000u128);
+    DEPOSIT_ACCOUNTS
+        .save(deps.as_mut().storage, 0, &account)
+        .unwrap();
+
+    // the IBC fee query is not mocked, so the transfer can't be built
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Mint { account_id: 0 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("tf_mint").add_attribute("amount", "900ld_denom")],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    assert!(response.messages.is_empty());
+    assert_eq!(
+        NON_STAKED_BALANCE.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(1000u128)
+    );
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.unminted, Uint128::zero());
+    assert_eq!(account.undelivered, Some(coin(900, "ld_denom")));
+}
+
+#[test]
+fn sudo_transfer_failed_keeps_dasset_for_retry() {
+    let mut deps = setup(remote_receiver());
+
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: transfer_request("0"),
+        },
+    )
+    .unwrap();
+
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.undelivered, Some(coin(90, "ld_denom")));
+    // the ICA of the deposit address is not affected by a failed dAsset transfer
+    assert_ne!(account.ica, IcaState::Timeout);
+
+    mock_ibc_fee(&mut deps);
+    mock_ibc_fee(&mut deps);
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &coins(300, "untrn")),
+        ExecuteMsg::RetryTransfer { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(response.messages.len(), 1);
+    let account = DEPOSIT_ACCOUNTS.load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(account.undelivered, None);
+}
+
+#[test]
+fn query_tokens_amount() {
+    let deps = setup(local_receiver());
+
+    let issue_amount: Uint128 = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokensAmount {
+                coin: coin(1000, "base_denom"),
+                exchange_rate: Decimal::from_ratio(5u128, 4u128),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(issue_amount, Uint128::from(800u128));
+
+    let can_bond: bool = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CanBond {
+                denom: "base_denom".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!can_bond);
+}
+
+#[test]
+fn process_on_idle_unauthorized() {
+    let mut deps = setup(local_receiver());
+    mock_state_query(&mut deps);
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_core", &[]),
+        ExecuteMsg::ProcessOnIdle {},
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::Unauthorized {});
+}
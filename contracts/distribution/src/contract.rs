@@ -2,7 +2,7 @@ use cosmwasm_std::{
     to_json_binary, Attribute, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Uint128,
 };
 use drop_helpers::answer::response;
-use drop_staking_base::msg::distribution::Delegation;
+use drop_staking_base::msg::distribution::{Delegation, Redelegation};
 use drop_staking_base::{
     error::distribution::{ContractError, ContractResult},
     msg::distribution::{Delegations, InstantiateMsg, MigrateMsg, QueryMsg},
@@ -40,7 +40,72 @@ pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             withdraw,
             delegations,
         } => Ok(to_json_binary(&calc_withdraw(withdraw, delegations)?)?),
+        QueryMsg::CalcRebalance {
+            delegations,
+            max_moves,
+        } => Ok(to_json_binary(&calc_rebalance(delegations, max_moves)?)?),
+    }
+}
+
+/// Calculates redelegations moving the current stake towards the ideal distribution.
+/// Biggest surpluses are matched with biggest deficits, at most `max_moves` are returned.
+pub fn calc_rebalance(
+    delegations: Delegations,
+    max_moves: u32,
+) -> ContractResult<Vec<Redelegation>> {
+    if delegations.total_stake.is_zero() || delegations.total_weight == 0 || max_moves == 0 {
+        return Ok(vec![]);
+    }
+
+    let empty_delegations = Delegations {
+        total_stake: Uint128::zero(),
+        delegations: delegations
+            .delegations
+            .iter()
+            .map(|d| Delegation {
+                stake: Uint128::zero(),
+                ..d.clone()
+            })
+            .collect(),
+        ..delegations.clone()
+    };
+    let ideal: HashMap<String, Uint128> = calc_deposit(delegations.total_stake, empty_delegations)?
+        .into_iter()
+        .collect();
+
+    let mut surpluses = vec![];
+    let mut deficits = vec![];
+    for d in delegations.delegations {
+        let ideal_stake = ideal.get(&d.valoper_address).copied().unwrap_or_default();
+        if d.stake > ideal_stake {
+            surpluses.push((d.valoper_address, d.stake - ideal_stake));
+        } else if d.stake < ideal_stake {
+            deficits.push((d.valoper_address, ideal_stake - d.stake));
+        }
     }
+    surpluses.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    deficits.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut redelegations = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < surpluses.len() && j < deficits.len() && redelegations.len() < max_moves as usize {
+        let amount = surpluses[i].1.min(deficits[j].1);
+        redelegations.push(Redelegation {
+            validator_from: surpluses[i].0.clone(),
+            validator_to: deficits[j].0.clone(),
+            amount,
+        });
+        surpluses[i].1 -= amount;
+        deficits[j].1 -= amount;
+        if surpluses[i].1.is_zero() {
+            i += 1;
+        }
+        if deficits[j].1.is_zero() {
+            j += 1;
+        }
+    }
+
+    Ok(redelegations)
 }
 
 /// Calculates the ideal withdrawal of stake among the given withdraw amount.
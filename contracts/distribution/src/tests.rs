@@ -1,11 +1,11 @@
-use super::contract::{calc_deposit, calc_withdraw};
+use super::contract::{calc_deposit, calc_rebalance, calc_withdraw};
 use cosmwasm_std::{
     testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage},
     to_json_binary, Empty, OwnedDeps, Querier, Uint128,
 };
 use drop_staking_base::{
     error::distribution::ContractError,
-    msg::distribution::{Delegation, Delegations, QueryMsg},
+    msg::distribution::{Delegation, Delegations, QueryMsg, Redelegation},
 };
 use std::marker::PhantomData;
 
@@ -438,3 +438,77 @@ fn withdraw_from_two_of_two_mixed_on_top_and_normal() {
     let distribution = calc_withdraw(withdraw, delegations).unwrap();
     assert_distributions_eq(distribution, &[("1", 40), ("2", 100)]);
 }
+
+fn make_redelegations(redelegations: &[(&str, &str, u128)]) -> Vec<Redelegation> {
+    redelegations
+        .iter()
+        .map(|r| Redelegation {
+            validator_from: r.0.to_string(),
+            validator_to: r.1.to_string(),
+            amount: Uint128::new(r.2),
+        })
+        .collect()
+}
+
+#[test]
+fn query_rebalance_calculation() {
+    let deps = mock_dependencies::<MockQuerier>();
+
+    let response = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::CalcRebalance {
+            delegations: make_delegations(&[("1", 100, 0, 10), ("2", 0, 0, 10)]),
+            max_moves: 5,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        response,
+        to_json_binary(&make_redelegations(&[("1", "2", 50)])).unwrap()
+    );
+}
+
+#[test]
+fn calc_rebalance_already_balanced() {
+    let delegations = make_delegations(&[("1", 50, 0, 10), ("2", 50, 0, 10)]);
+
+    let redelegations = calc_rebalance(delegations, 5).unwrap();
+    assert_eq!(redelegations, vec![]);
+}
+
+#[test]
+fn calc_rebalance_empty_stake() {
+    let delegations = make_delegations(&[("1", 0, 0, 10), ("2", 0, 0, 10)]);
+
+    let redelegations = calc_rebalance(delegations, 5).unwrap();
+    assert_eq!(redelegations, vec![]);
+}
+
+#[test]
+fn calc_rebalance_one_to_many() {
+    let delegations = make_delegations(&[("1", 90, 0, 10), ("2", 0, 0, 10), ("3", 0, 0, 10)]);
+
+    let redelegations = calc_rebalance(delegations, 5).unwrap();
+    assert_eq!(
+        redelegations,
+        make_redelegations(&[("1", "2", 30), ("1", "3", 30)])
+    );
+}
+
+#[test]
+fn calc_rebalance_max_moves() {
+    let delegations = make_delegations(&[("1", 90, 0, 10), ("2", 0, 0, 10), ("3", 0, 0, 10)]);
+
+    let redelegations = calc_rebalance(delegations, 1).unwrap();
+    assert_eq!(redelegations, make_redelegations(&[("1", "2", 30)]));
+}
+
+#[test]
+fn calc_rebalance_respects_on_top() {
+    let delegations = make_delegations(&[("1", 100, 0, 10), ("2", 0, 40, 10)]);
+
+    let redelegations = calc_rebalance(delegations, 5).unwrap();
+    assert_eq!(redelegations, make_redelegations(&[("1", "2", 70)]));
+}
@@ -1,24 +1,28 @@
 use std::collections::HashMap;
 
 use cosmwasm_std::{
-    attr, instantiate2_address, to_json_binary, Binary, CodeInfoResponse, CosmosMsg, Deps, DepsMut,
-    Env, HexBinary, MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    attr, instantiate2_address, to_json_binary, Binary, CodeInfoResponse, CosmosMsg, CustomQuery,
+    Deps, DepsMut, Empty, Env, HexBinary, MessageInfo, Order, Response, StdResult, Uint128,
+    WasmMsg,
 };
 use drop_helpers::answer::response;
+use drop_helpers::pause::PauseInfoResponse as ContractPauseInfo;
 use drop_helpers::phonebook::{
     CORE_CONTRACT, DISTRIBUTION_CONTRACT, LSM_SHARE_BOND_PROVIDER_CONTRACT,
     NATIVE_BOND_PROVIDER_CONTRACT, PUPPETEER_CONTRACT, REWARDS_MANAGER_CONTRACT,
     REWARDS_PUMP_CONTRACT, SPLITTER_CONTRACT, STRATEGY_CONTRACT, TOKEN_CONTRACT,
     VALIDATORS_SET_CONTRACT, WITHDRAWAL_MANAGER_CONTRACT, WITHDRAWAL_VOUCHER_CONTRACT,
 };
-use drop_staking_base::error::factory::ContractResult;
+use drop_staking_base::error::factory::{ContractError, ContractResult};
 use drop_staking_base::state::splitter::Config as SplitterConfig;
 use drop_staking_base::{
     msg::factory::{
         ExecuteMsg, InstantiateMsg, MigrateMsg, ProxyMsg, QueryMsg, UpdateConfigMsg,
         ValidatorSetMsg,
     },
-    state::factory::STATE,
+    state::factory::{
+        CodeIds, DeployedContract, Deployment, PauseInfoResponse, DEPLOYMENTS, STATE,
+    },
 };
 use drop_staking_base::{
     msg::{
@@ -41,7 +45,7 @@ use drop_staking_base::{
         },
         withdrawal_voucher::InstantiateMsg as WithdrawalVoucherInstantiateMsg,
     },
-    state::pump::PumpTimeout,
+    state::{core::Pause as CorePause, pump::PumpTimeout},
 };
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
@@ -54,7 +58,7 @@ const PERCENT_PRECISION: Uint128 = Uint128::new(10000u128); // allows to achieve
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
@@ -257,6 +261,7 @@ pub fn instantiate(
         REWARDS_PUMP_CONTRACT,
         &rewards_pump_contract.clone(),
     )?;
+    record_deployment(deps.branch(), &env, &msg.code_ids)?;
 
     let msgs = vec![
         CosmosMsg::Wasm(WasmMsg::Instantiate2 {
@@ -463,6 +468,7 @@ pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResu
     match msg {
         QueryMsg::State {} => query_state(deps),
         QueryMsg::PauseInfo {} => query_pause_info(deps),
+        QueryMsg::Deployments {} => query_deployments(deps),
         QueryMsg::Ownership {} => {
             let ownership = cw_ownable::get_ownership(deps.storage)?;
             Ok(to_json_binary(&ownership)?)
@@ -480,12 +486,24 @@ fn query_state(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
     Ok(to_json_binary(&out)?)
 }
 
+fn query_deployments(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+    let deployments = DEPLOYMENTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, deployment)| deployment))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(to_json_binary(&deployments)?)
+}
+
 fn query_pause_info(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+    to_json_binary(&get_pause_info(deps)?).map_err(From::from)
+}
+
+fn get_pause_info<C: CustomQuery>(deps: Deps<C>) -> ContractResult<PauseInfoResponse> {
     let core_contract = STATE.load(deps.storage, CORE_CONTRACT)?;
     let withdrawal_manager_contract = STATE.load(deps.storage, WITHDRAWAL_MANAGER_CONTRACT)?;
     let rewards_manager_contract = STATE.load(deps.storage, REWARDS_MANAGER_CONTRACT)?;
 
-    to_json_binary(&drop_staking_base::state::factory::PauseInfoResponse {
+    Ok(PauseInfoResponse {
         core: deps
             .querier
             .query_wasm_smart(core_contract, &CoreQueryMsg::Pause {})?,
@@ -497,7 +515,15 @@ fn query_pause_info(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
             .querier
             .query_wasm_smart(rewards_manager_contract, &RewardsQueryMsg::PauseInfo {})?,
     })
-    .map_err(From::from)
+}
+
+/// Pause state of a system where nothing is paused
+fn unpaused_info() -> PauseInfoResponse {
+    PauseInfoResponse {
+        core: CorePause::default(),
+        withdrawal_manager: ContractPauseInfo::Unpaused {},
+        rewards_manager: ContractPauseInfo::Unpaused {},
+    }
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
@@ -519,6 +545,10 @@ pub fn execute(
         ExecuteMsg::UpdateConfig(msg) => execute_update_config(deps, env, info, *msg),
         ExecuteMsg::Proxy(msg) => execute_proxy_msg(deps, env, info, msg),
         ExecuteMsg::AdminExecute { msgs } => execute_admin_execute(deps, env, info, msgs),
+        ExecuteMsg::MigrateAll {
+            code_ids,
+            migrate_msgs,
+        } => execute_migrate_all(deps, env, info, code_ids, migrate_msgs),
         ExecuteMsg::Pause {} => exec_pause(deps, info),
         ExecuteMsg::Unpause {} => exec_unpause(deps, info),
     }
@@ -526,67 +556,204 @@ pub fn execute(
 
 fn exec_pause(deps: DepsMut, info: MessageInfo) -> ContractResult<Response<NeutronMsg>> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    let core_contract = STATE.load(deps.storage, CORE_CONTRACT)?;
-    let withdrawal_manager_contract = STATE.load(deps.storage, WITHDRAWAL_MANAGER_CONTRACT)?;
-    let rewards_manager_contract = STATE.load(deps.storage, REWARDS_MANAGER_CONTRACT)?;
-
     let attrs = vec![attr("action", "pause")];
-    let messages = vec![
-        get_proxied_message(
-            core_contract.to_string(),
-            drop_staking_base::msg::core::ExecuteMsg::SetPause(
-                drop_staking_base::state::core::Pause {
-                    tick: true,
-                    bond: false,
-                    unbond: false,
-                },
-            ),
+    let messages = get_pause_messages(deps.as_ref(), &unpaused_info())?;
+    Ok(response("execute-pause", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn exec_unpause(deps: DepsMut, info: MessageInfo) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let attrs = vec![attr("action", "unpause")];
+    let messages = get_unpause_messages(deps.as_ref(), &unpaused_info())?;
+    Ok(response("execute-unpause", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+/// Pauses every contract that is not paused in `current`, core keeps its
+/// bond and unbond flags
+fn get_pause_messages(
+    deps: Deps,
+    current: &PauseInfoResponse,
+) -> ContractResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let mut messages = vec![];
+    if !current.core.tick {
+        messages.push(get_proxied_message(
+            STATE.load(deps.storage, CORE_CONTRACT)?.to_string(),
+            drop_staking_base::msg::core::ExecuteMsg::SetPause(CorePause {
+                tick: true,
+                ..current.core.clone()
+            }),
             vec![],
-        )?,
-        get_proxied_message(
-            withdrawal_manager_contract.to_string(),
+        )?);
+    }
+    if matches!(current.withdrawal_manager, ContractPauseInfo::Unpaused {}) {
+        messages.push(get_proxied_message(
+            STATE
+                .load(deps.storage, WITHDRAWAL_MANAGER_CONTRACT)?
+                .to_string(),
             drop_staking_base::msg::withdrawal_manager::ExecuteMsg::Pause {},
             vec![],
-        )?,
-        get_proxied_message(
-            rewards_manager_contract.to_string(),
+        )?);
+    }
+    if matches!(current.rewards_manager, ContractPauseInfo::Unpaused {}) {
+        messages.push(get_proxied_message(
+            STATE
+                .load(deps.storage, REWARDS_MANAGER_CONTRACT)?
+                .to_string(),
             drop_staking_base::msg::rewards_manager::ExecuteMsg::Pause {},
             vec![],
-        )?,
-    ];
-    Ok(response("execute-pause", CONTRACT_NAME, attrs).add_messages(messages))
+        )?);
+    }
+    Ok(messages)
 }
 
-fn exec_unpause(deps: DepsMut, info: MessageInfo) -> ContractResult<Response<NeutronMsg>> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    let core_contract = STATE.load(deps.storage, CORE_CONTRACT)?;
-    let withdrawal_manager_contract = STATE.load(deps.storage, WITHDRAWAL_MANAGER_CONTRACT)?;
-    let rewards_manager_contract = STATE.load(deps.storage, REWARDS_MANAGER_CONTRACT)?;
-    let attrs = vec![attr("action", "unpause")];
-    let messages = vec![
-        get_proxied_message(
-            core_contract.to_string(),
-            drop_staking_base::msg::core::ExecuteMsg::SetPause(
-                drop_staking_base::state::core::Pause {
-                    tick: false,
-                    bond: false,
-                    unbond: false,
-                },
-            ),
+/// Brings every contract that is not paused in `restore` back to that state,
+/// contracts paused in `restore` are left paused
+fn get_unpause_messages(
+    deps: Deps,
+    restore: &PauseInfoResponse,
+) -> ContractResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let mut messages = vec![];
+    if !restore.core.tick {
+        messages.push(get_proxied_message(
+            STATE.load(deps.storage, CORE_CONTRACT)?.to_string(),
+            drop_staking_base::msg::core::ExecuteMsg::SetPause(restore.core.clone()),
             vec![],
-        )?,
-        get_proxied_message(
-            rewards_manager_contract.to_string(),
+        )?);
+    }
+    if matches!(restore.rewards_manager, ContractPauseInfo::Unpaused {}) {
+        messages.push(get_proxied_message(
+            STATE
+                .load(deps.storage, REWARDS_MANAGER_CONTRACT)?
+                .to_string(),
             drop_staking_base::msg::rewards_manager::ExecuteMsg::Unpause {},
             vec![],
-        )?,
-        get_proxied_message(
-            withdrawal_manager_contract.to_string(),
+        )?);
+    }
+    if matches!(restore.withdrawal_manager, ContractPauseInfo::Unpaused {}) {
+        messages.push(get_proxied_message(
+            STATE
+                .load(deps.storage, WITHDRAWAL_MANAGER_CONTRACT)?
+                .to_string(),
             drop_staking_base::msg::withdrawal_manager::ExecuteMsg::Unpause {},
             vec![],
-        )?,
+        )?);
+    }
+    Ok(messages)
+}
+
+fn execute_migrate_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    code_ids: CodeIds,
+    migrate_msgs: HashMap<String, Binary>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let contracts = contracts_in_deployment_order(&code_ids);
+    if let Some(name) = migrate_msgs
+        .keys()
+        .find(|name| !contracts.iter().any(|(contract, _)| contract == name))
+    {
+        return Err(ContractError::ContractAddressNotFound {
+            name: name.to_string(),
+        });
+    }
+
+    // contracts paused by the owner are left paused after the upgrade
+    let pause_info = get_pause_info(deps.as_ref())?;
+
+    let mut messages = get_pause_messages(deps.as_ref(), &pause_info)?;
+    for (name, code_id) in contracts {
+        let contract_addr = STATE.load(deps.storage, name)?;
+        let msg = match migrate_msgs.get(name) {
+            Some(msg) => msg.clone(),
+            None => to_json_binary(&Empty {})?,
+        };
+        messages.push(CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: contract_addr.to_string(),
+            new_code_id: code_id,
+            msg,
+        }));
+    }
+    messages.extend(get_unpause_messages(deps.as_ref(), &pause_info)?);
+
+    let version = record_deployment(deps, &env, &code_ids)?;
+    let attrs = vec![
+        attr("action", "migrate-all"),
+        attr("deployment_version", version.to_string()),
+        attr("core_paused", pause_info.core.tick.to_string()),
+        attr(
+            "withdrawal_manager_paused",
+            matches!(pause_info.withdrawal_manager, ContractPauseInfo::Paused {}).to_string(),
+        ),
+        attr(
+            "rewards_manager_paused",
+            matches!(pause_info.rewards_manager, ContractPauseInfo::Paused {}).to_string(),
+        ),
     ];
-    Ok(response("execute-unpause", CONTRACT_NAME, attrs).add_messages(messages))
+    Ok(response("execute-migrate-all", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+/// Child contracts in the order they are instantiated, so that every contract
+/// is migrated after the ones it depends on
+fn contracts_in_deployment_order(code_ids: &CodeIds) -> Vec<(&'static str, u64)> {
+    vec![
+        (TOKEN_CONTRACT, code_ids.token_code_id),
+        (VALIDATORS_SET_CONTRACT, code_ids.validators_set_code_id),
+        (DISTRIBUTION_CONTRACT, code_ids.distribution_code_id),
+        (PUPPETEER_CONTRACT, code_ids.puppeteer_code_id),
+        (STRATEGY_CONTRACT, code_ids.strategy_code_id),
+        (CORE_CONTRACT, code_ids.core_code_id),
+        (
+            WITHDRAWAL_VOUCHER_CONTRACT,
+            code_ids.withdrawal_voucher_code_id,
+        ),
+        (
+            WITHDRAWAL_MANAGER_CONTRACT,
+            code_ids.withdrawal_manager_code_id,
+        ),
+        (REWARDS_MANAGER_CONTRACT, code_ids.rewards_manager_code_id),
+        (SPLITTER_CONTRACT, code_ids.splitter_code_id),
+        (REWARDS_PUMP_CONTRACT, code_ids.rewards_pump_code_id),
+        (
+            LSM_SHARE_BOND_PROVIDER_CONTRACT,
+            code_ids.lsm_share_bond_provider_code_id,
+        ),
+        (
+            NATIVE_BOND_PROVIDER_CONTRACT,
+            code_ids.native_bond_provider_code_id,
+        ),
+    ]
+}
+
+fn record_deployment(deps: DepsMut, env: &Env, code_ids: &CodeIds) -> ContractResult<u64> {
+    let version = DEPLOYMENTS
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(1, |last_version| last_version + 1);
+    let contracts = contracts_in_deployment_order(code_ids)
+        .into_iter()
+        .map(|(name, code_id)| {
+            Ok(DeployedContract {
+                name: name.to_string(),
+                address: STATE.load(deps.storage, name)?,
+                code_id,
+                checksum: get_code_checksum(deps.as_ref(), code_id)?,
+            })
+        })
+        .collect::<ContractResult<Vec<_>>>()?;
+    DEPLOYMENTS.save(
+        deps.storage,
+        version,
+        &Deployment {
+            version,
+            height: env.block.height,
+            timestamp: env.block.time,
+            contracts,
+        },
+    )?;
+    Ok(version)
 }
 
 fn execute_admin_execute(
@@ -692,6 +692,7 @@ fn test_update_config_validators_set_unauthorized() {
         stats_contract: Some("validator_stats_contract".to_string()),
         provider_proposals_contract: Some("provider_proposals_contract1".to_string()),
         val_ref_contract: Some("val_ref_contract1".to_string()),
+        puppeteer_contract: None,
     };
     let res = execute(
         deps.as_mut().into_empty(),
@@ -721,6 +722,7 @@ fn test_update_config_validators_set() {
         stats_contract: Some("validator_stats_contract".to_string()),
         provider_proposals_contract: Some("provider_proposals_contract1".to_string()),
         val_ref_contract: Some("val_ref_contract1".to_string()),
+        puppeteer_contract: None,
     };
     let res = execute(
         deps.as_mut().into_empty(),
@@ -1305,3 +1307,362 @@ fn test_transfer_ownership() {
         }
     );
 }
+
+fn get_code_ids() -> CodeIds {
+    CodeIds {
+        token_code_id: 101,
+        core_code_id: 102,
+        puppeteer_code_id: 103,
+        withdrawal_voucher_code_id: 105,
+        withdrawal_manager_code_id: 106,
+        strategy_code_id: 107,
+        validators_set_code_id: 108,
+        distribution_code_id: 109,
+        rewards_manager_code_id: 110,
+        rewards_pump_code_id: 111,
+        splitter_code_id: 112,
+        lsm_share_bond_provider_code_id: 113,
+        native_bond_provider_code_id: 114,
+    }
+}
+
+fn mock_code_info(querier: &mut drop_helpers::testing::WasmMockQuerier) {
+    querier.add_stargate_query_response(
+        "/cosmos.wasm.v1.Query/QueryCodeRequest",
+        |data| -> cosmwasm_std::Binary {
+            let mut y = vec![0; 32];
+            y[..data.len()].copy_from_slice(data);
+            to_json_binary(&cosmwasm_std::CodeInfoResponse::new(
+                from_json(data).unwrap(),
+                "creator".to_string(),
+                cosmwasm_std::HexBinary::from(y.as_slice()),
+            ))
+            .unwrap()
+        },
+    );
+}
+
+#[test]
+fn test_migrate_all_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    let _ = cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    set_default_factory_state(deps.as_mut());
+    let res = execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        mock_info("not_an_owner", &[]),
+        ExecuteMsg::MigrateAll {
+            code_ids: get_code_ids(),
+            migrate_msgs: HashMap::new(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        drop_staking_base::error::factory::ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        )
+    );
+}
+
+#[test]
+fn test_migrate_all_unknown_contract() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    let _ = cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    set_default_factory_state(deps.as_mut());
+    let res = execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::MigrateAll {
+            code_ids: get_code_ids(),
+            migrate_msgs: HashMap::from([(
+                "unknown_contract".to_string(),
+                to_json_binary(&cosmwasm_std::Empty {}).unwrap(),
+            )]),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        drop_staking_base::error::factory::ContractError::ContractAddressNotFound {
+            name: "unknown_contract".to_string()
+        }
+    );
+}
+
+fn mock_pause_info(
+    querier: &mut drop_helpers::testing::WasmMockQuerier,
+    core: CorePause,
+    withdrawal_manager: drop_helpers::pause::PauseInfoResponse,
+    rewards_manager: drop_helpers::pause::PauseInfoResponse,
+) {
+    querier.add_wasm_query_response("core_contract", move |_| to_json_binary(&core).unwrap());
+    querier.add_wasm_query_response("withdrawal_manager_contract", move |_| {
+        to_json_binary(&withdrawal_manager).unwrap()
+    });
+    querier.add_wasm_query_response("rewards_manager_contract", move |_| {
+        to_json_binary(&rewards_manager).unwrap()
+    });
+}
+
+#[test]
+fn test_migrate_all() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    let _ = cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    set_default_factory_state(deps.as_mut());
+    mock_code_info(&mut deps.querier);
+    mock_pause_info(
+        &mut deps.querier,
+        CorePause {
+            tick: false,
+            bond: true,
+            unbond: false,
+        },
+        drop_helpers::pause::PauseInfoResponse::Unpaused {},
+        drop_helpers::pause::PauseInfoResponse::Unpaused {},
+    );
+    let core_migrate_msg = to_json_binary(&"core migrate msg").unwrap();
+    let res = execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::MigrateAll {
+            code_ids: get_code_ids(),
+            migrate_msgs: HashMap::from([(CORE_CONTRACT.to_string(), core_migrate_msg.clone())]),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![
+            cosmwasm_std::Event::new("crates.io:drop-staking__drop-factory-execute-migrate-all")
+                .add_attributes(vec![
+                    attr("action", "migrate-all"),
+                    attr("deployment_version", "1"),
+                    attr("core_paused", "false"),
+                    attr("withdrawal_manager_paused", "false"),
+                    attr("rewards_manager_paused", "false"),
+                ])
+        ]
+    );
+    assert_eq!(res.messages.len(), 19);
+    assert_eq!(
+        res.messages[0].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: "core_contract".to_string(),
+            msg: to_json_binary(&CoreExecuteMsg::SetPause(CorePause {
+                tick: true,
+                bond: true,
+                unbond: false,
+            }))
+            .unwrap(),
+            funds: vec![]
+        })
+    );
+    assert_eq!(
+        res.messages[3].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Migrate {
+            contract_addr: "token_contract".to_string(),
+            new_code_id: 101,
+            msg: to_json_binary(&cosmwasm_std::Empty {}).unwrap(),
+        })
+    );
+    assert_eq!(
+        res.messages[8].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Migrate {
+            contract_addr: "core_contract".to_string(),
+            new_code_id: 102,
+            msg: core_migrate_msg,
+        })
+    );
+    assert_eq!(
+        res.messages[15].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Migrate {
+            contract_addr: "native_bond_provider_contract".to_string(),
+            new_code_id: 114,
+            msg: to_json_binary(&cosmwasm_std::Empty {}).unwrap(),
+        })
+    );
+    // the core pause flags set before the upgrade are restored
+    assert_eq!(
+        res.messages[16].msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: "core_contract".to_string(),
+            msg: to_json_binary(&CoreExecuteMsg::SetPause(CorePause {
+                tick: false,
+                bond: true,
+                unbond: false,
+            }))
+            .unwrap(),
+            funds: vec![]
+        })
+    );
+
+    let deployments: Vec<drop_staking_base::state::factory::Deployment> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Deployments {}).unwrap()).unwrap();
+    assert_eq!(deployments.len(), 1);
+    assert_eq!(deployments[0].version, 1);
+    assert_eq!(deployments[0].height, mock_env().block.height);
+    assert_eq!(deployments[0].contracts.len(), 13);
+    let mut checksum = vec![0; 32];
+    checksum[..3].copy_from_slice(b"101");
+    assert_eq!(
+        deployments[0].contracts[0],
+        drop_staking_base::state::factory::DeployedContract {
+            name: TOKEN_CONTRACT.to_string(),
+            address: Addr::unchecked("token_contract"),
+            code_id: 101,
+            checksum: cosmwasm_std::HexBinary::from(checksum.as_slice()),
+        }
+    );
+}
+
+#[test]
+fn test_migrate_all_already_paused() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    let _ = cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    set_default_factory_state(deps.as_mut());
+    mock_code_info(&mut deps.querier);
+    for _ in 0..2 {
+        mock_pause_info(
+            &mut deps.querier,
+            CorePause {
+                tick: true,
+                bond: false,
+                unbond: false,
+            },
+            drop_helpers::pause::PauseInfoResponse::Paused {},
+            drop_helpers::pause::PauseInfoResponse::Paused {},
+        );
+    }
+    for _ in 0..2 {
+        let res = execute(
+            deps.as_mut().into_empty(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::MigrateAll {
+                code_ids: get_code_ids(),
+                migrate_msgs: HashMap::new(),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 13);
+        assert!(res.messages.iter().all(|msg| matches!(
+            msg.msg,
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Migrate { .. })
+        )));
+    }
+
+    let deployments: Vec<drop_staking_base::state::factory::Deployment> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Deployments {}).unwrap()).unwrap();
+    assert_eq!(
+        deployments
+            .iter()
+            .map(|deployment| deployment.version)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn test_migrate_all_partially_paused() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    let _ = cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    set_default_factory_state(deps.as_mut());
+    mock_code_info(&mut deps.querier);
+    mock_pause_info(
+        &mut deps.querier,
+        CorePause {
+            tick: false,
+            bond: false,
+            unbond: true,
+        },
+        drop_helpers::pause::PauseInfoResponse::Paused {},
+        drop_helpers::pause::PauseInfoResponse::Unpaused {},
+    );
+    let res = execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::MigrateAll {
+            code_ids: get_code_ids(),
+            migrate_msgs: HashMap::new(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![
+            cosmwasm_std::Event::new("crates.io:drop-staking__drop-factory-execute-migrate-all")
+                .add_attributes(vec![
+                    attr("action", "migrate-all"),
+                    attr("deployment_version", "1"),
+                    attr("core_paused", "false"),
+                    attr("withdrawal_manager_paused", "true"),
+                    attr("rewards_manager_paused", "false"),
+                ])
+        ]
+    );
+    // the withdrawal manager is neither paused nor unpaused by the upgrade
+    assert_eq!(res.messages.len(), 17);
+    assert_eq!(
+        res.messages[..2]
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "core_contract".to_string(),
+                msg: to_json_binary(&CoreExecuteMsg::SetPause(CorePause {
+                    tick: true,
+                    bond: false,
+                    unbond: true,
+                }))
+                .unwrap(),
+                funds: vec![]
+            }),
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "rewards_manager_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::rewards_manager::ExecuteMsg::Pause {})
+                    .unwrap(),
+                funds: vec![]
+            }),
+        ]
+    );
+    assert!(res.messages[2..15].iter().all(|msg| matches!(
+        msg.msg,
+        cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Migrate { .. })
+    )));
+    assert_eq!(
+        res.messages[15..]
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "core_contract".to_string(),
+                msg: to_json_binary(&CoreExecuteMsg::SetPause(CorePause {
+                    tick: false,
+                    bond: false,
+                    unbond: true,
+                }))
+                .unwrap(),
+                funds: vec![]
+            }),
+            cosmwasm_std::CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "rewards_manager_contract".to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::rewards_manager::ExecuteMsg::Unpause {}
+                )
+                .unwrap(),
+                funds: vec![]
+            }),
+        ]
+    );
+}
@@ -180,6 +180,7 @@ fn execute_tokenize_share(
             &drop_staking_base::msg::puppeteer::ExecuteMsg::TokenizeShare {
                 validator,
                 amount,
+                request_id: None,
                 reply_to: env.contract.address.to_string(),
             },
         )?,
@@ -0,0 +1,10 @@
+use cosmwasm_schema::write_api;
+use drop_staking_base::msg::ibc_hook_bonder::{ExecuteMsg, InstantiateMsg, QueryMsg};
+
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg
+    }
+}
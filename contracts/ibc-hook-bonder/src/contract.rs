@@ -0,0 +1,451 @@
+use cosmwasm_std::{
+    attr, ensure, from_json, to_json_binary, Attribute, BankMsg, Binary, Coin, Deps, DepsMut, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, SubMsgResult, Uint128,
+    WasmMsg,
+};
+use cw_ownable::update_ownership;
+use cw_storage_plus::Bound;
+use drop_helpers::answer::response;
+use drop_helpers::ibc_fee::query_ibc_fee;
+use drop_staking_base::error::ibc_hook_bonder::{ContractError, ContractResult};
+use drop_staking_base::msg::ibc_hook_bonder::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use drop_staking_base::msg::mirror::FungibleTokenPacketData;
+use drop_staking_base::state::ibc_hook_bonder::{
+    Config, ConfigOptional, Forward, HookBond, ReplyMsg, CONFIG, FORWARDS_IN_FLIGHT, HOOK_BONDS,
+    HOOK_BOND_COUNTER,
+};
+use neutron_sdk::bindings::msg::{MsgIbcTransferResponse, NeutronMsg};
+use neutron_sdk::bindings::query::NeutronQuery;
+use neutron_sdk::sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, TransferSudoMsg};
+use std::str::FromStr;
+
+const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const LOCAL_DENOM: &str = "untrn";
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn instantiate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let owner = msg.owner.unwrap_or(info.sender.to_string());
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(owner.as_str()))?;
+    ensure!(msg.ibc_timeout > 0, ContractError::InvalidIbcTimeout);
+    let core_contract = deps.api.addr_validate(&msg.core_contract)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            core_contract: core_contract.clone(),
+            ibc_timeout: msg.ibc_timeout,
+        },
+    )?;
+    HOOK_BOND_COUNTER.save(deps.storage, &0)?;
+    Ok(response(
+        "instantiate",
+        CONTRACT_NAME,
+        [
+            attr("owner", owner),
+            attr("core_contract", core_contract),
+            attr("ibc_timeout", msg.ibc_timeout.to_string()),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
+        QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
+        QueryMsg::HookBond { id } => Ok(to_json_binary(&HOOK_BONDS.load(deps.storage, id)?)?),
+        QueryMsg::HookBonds { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+            let bonds = HOOK_BONDS
+                .range(
+                    deps.storage,
+                    start_after.map(Bound::exclusive),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            Ok(to_json_binary(&bonds)?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(Response::new())
+        }
+        ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::Bond {
+            receiver,
+            forward,
+            fallback,
+            r#ref,
+        } => execute_bond(deps, info, receiver, forward, fallback, r#ref),
+    }
+}
+
+fn execute_update_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    new_config: ConfigOptional,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut attrs: Vec<Attribute> = Vec::new();
+    if let Some(core_contract) = new_config.core_contract {
+        config.core_contract = deps.api.addr_validate(core_contract.as_ref())?;
+        attrs.push(attr("core_contract", core_contract));
+    }
+    if let Some(ibc_timeout) = new_config.ibc_timeout {
+        ensure!(ibc_timeout > 0, ContractError::InvalidIbcTimeout);
+        config.ibc_timeout = ibc_timeout;
+        attrs.push(attr("ibc_timeout", ibc_timeout.to_string()));
+    }
+    CONFIG.save(deps.storage, &config)?;
+    Ok(response("update_config", CONTRACT_NAME, attrs))
+}
+
+/// Called by ibc-hooks with the transferred base asset attached, so the sender
+/// is the intermediate hooks address and not the remote user
+fn execute_bond(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    receiver: Option<String>,
+    forward: Option<Forward>,
+    fallback: String,
+    r#ref: Option<String>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let fallback = deps.api.addr_validate(&fallback)?;
+    let receiver = receiver
+        .map(|receiver| deps.api.addr_validate(&receiver))
+        .transpose()?;
+    ensure!(
+        receiver.is_some() != forward.is_some(),
+        ContractError::InvalidDestination
+    );
+    if let Some(forward) = &forward {
+        ensure!(
+            !forward.receiver.is_empty() && !forward.channel.is_empty(),
+            ContractError::InvalidDestination
+        );
+    }
+    // the forwarding transfer is paid by the caller, its IBC fee comes along with the deposit
+    let (fee, deposit, fee_refund) = match &forward {
+        None => (vec![], cw_utils::one_coin(&info)?, Uint128::zero()),
+        Some(_) => {
+            let (fee, deposit): (Vec<Coin>, Vec<Coin>) = info
+                .funds
+                .iter()
+                .cloned()
+                .partition(|coin| coin.denom == LOCAL_DENOM);
+            let deposit = match deposit.as_slice() {
+                [] => return Err(cw_utils::PaymentError::NoFunds {}.into()),
+                [deposit] => deposit.clone(),
+                _ => return Err(cw_utils::PaymentError::MultipleDenoms {}.into()),
+            };
+            let ibc_fee = query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?;
+            let required: Uint128 = ibc_fee
+                .recv_fee
+                .iter()
+                .chain(ibc_fee.ack_fee.iter())
+                .chain(ibc_fee.timeout_fee.iter())
+                .map(|coin| coin.amount)
+                .sum();
+            let paid: Uint128 = fee.iter().map(|coin| coin.amount).sum();
+            ensure!(
+                paid >= required,
+                ContractError::IbcFeeNotPaid { required, paid }
+            );
+            // only the required fee is kept for the forwarding transfer
+            let fee = if required.is_zero() {
+                vec![]
+            } else {
+                vec![Coin::new(required.u128(), LOCAL_DENOM)]
+            };
+            (fee, deposit, paid - required)
+        }
+    };
+
+    let id = HOOK_BOND_COUNTER.load(deps.storage)? + 1;
+    ensure!(id <= u32::MAX as u64, ContractError::IdOverflow { id });
+    HOOK_BOND_COUNTER.save(deps.storage, &id)?;
+    HOOK_BONDS.save(
+        deps.storage,
+        id,
+        &HookBond {
+            fallback: fallback.clone(),
+            deposit: deposit.clone(),
+            fee,
+            receiver: receiver.clone(),
+            forward: forward.clone(),
+            minted: None,
+        },
+    )?;
+
+    let mut attrs = vec![
+        attr("id", id.to_string()),
+        attr("deposit", deposit.to_string()),
+        attr("fallback", fallback.to_string()),
+        attr(
+            "receiver",
+            receiver.as_ref().map(|x| x.to_string()).unwrap_or_default(),
+        ),
+        attr(
+            "forward_receiver",
+            forward.map(|x| x.receiver).unwrap_or_default(),
+        ),
+    ];
+    let mut msgs = vec![];
+    if !fee_refund.is_zero() {
+        attrs.push(attr("fee_refund", format!("{}{}", fee_refund, LOCAL_DENOM)));
+        msgs.push(BankMsg::Send {
+            to_address: fallback.to_string(),
+            amount: vec![Coin::new(fee_refund.u128(), LOCAL_DENOM)],
+        });
+    }
+    // dAsset to be forwarded is minted to this contract first
+    let bond_msg = SubMsg::reply_always(
+        WasmMsg::Execute {
+            contract_addr: config.core_contract.to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::Bond {
+                receiver: receiver.map(|x| x.to_string()),
+                r#ref,
+            })?,
+            funds: vec![deposit],
+        },
+        ReplyMsg::Bond { id }.to_reply_id(),
+    );
+    Ok(response("bond", CONTRACT_NAME, attrs)
+        .add_submessage(bond_msg)
+        .add_messages(msgs))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: Reply,
+) -> ContractResult<Response<NeutronMsg>> {
+    match ReplyMsg::from_reply_id(msg.id) {
+        ReplyMsg::Bond { id } => bond_reply(deps, env, id, msg.result),
+        ReplyMsg::Forward { id } => forward_reply(deps, id, msg.result),
+    }
+}
+
+fn bond_reply(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    id: u64,
+    result: SubMsgResult,
+) -> ContractResult<Response<NeutronMsg>> {
+    let mut hook_bond = HOOK_BONDS.load(deps.storage, id)?;
+    let res = match result {
+        SubMsgResult::Ok(res) => res,
+        // funds attached to the failed bond are back on the contract,
+        // the forwarding fee is not spent either
+        SubMsgResult::Err(err) => {
+            HOOK_BONDS.remove(deps.storage, id);
+            return Ok(response(
+                "bond_failed",
+                CONTRACT_NAME,
+                [
+                    attr("id", id.to_string()),
+                    attr("error", err),
+                    attr("refund", hook_bond.deposit.to_string()),
+                    attr("fallback", hook_bond.fallback.to_string()),
+                ],
+            )
+            .add_message(BankMsg::Send {
+                to_address: hook_bond.fallback.to_string(),
+                amount: [vec![hook_bond.deposit], hook_bond.fee].concat(),
+            }));
+        }
+    };
+
+    let Some(forward) = hook_bond.forward.clone() else {
+        HOOK_BONDS.remove(deps.storage, id);
+        return Ok(response(
+            "bond_finalized",
+            CONTRACT_NAME,
+            [attr("id", id.to_string())],
+        ));
+    };
+
+    let minted = Coin::from_str(
+        &res.events
+            .iter()
+            .find(|x| x.ty == "tf_mint")
+            .and_then(|x| x.attributes.iter().find(|a| a.key == "amount"))
+            .ok_or(ContractError::NoTokensMinted)?
+            .value,
+    )?;
+    hook_bond.minted = Some(minted.clone());
+    HOOK_BONDS.save(deps.storage, id, &hook_bond)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let transfer_msg = NeutronMsg::IbcTransfer {
+        source_port: forward.port.to_string(),
+        source_channel: forward.channel.to_string(),
+        token: minted.clone(),
+        sender: env.contract.address.to_string(),
+        receiver: forward.receiver.to_string(),
+        timeout_height: RequestPacketTimeoutHeight {
+            revision_number: None,
+            revision_height: None,
+        },
+        timeout_timestamp: env
+            .block
+            .time
+            .plus_seconds(forward.timeout.unwrap_or(config.ibc_timeout))
+            .nanos(),
+        memo: forward.next.unwrap_or_default(),
+        fee: query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?,
+    };
+    Ok(response(
+        "forward",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr("minted", minted.to_string()),
+            attr("channel", forward.channel),
+            attr("receiver", forward.receiver),
+        ],
+    )
+    .add_submessage(SubMsg::reply_on_success(
+        transfer_msg,
+        ReplyMsg::Forward { id }.to_reply_id(),
+    )))
+}
+
+fn forward_reply(
+    deps: DepsMut<NeutronQuery>,
+    id: u64,
+    result: SubMsgResult,
+) -> ContractResult<Response<NeutronMsg>> {
+    let data = result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .ok_or_else(|| StdError::generic_err("no transfer response data"))?;
+    let transfer: MsgIbcTransferResponse = from_json(data)?;
+    // the memo belongs to the next hop, so acks are matched by the packet sequence
+    FORWARDS_IN_FLIGHT.save(deps.storage, (&transfer.channel, transfer.sequence_id), &id)?;
+    Ok(response(
+        "forward_sent",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr("channel", transfer.channel),
+            attr("sequence", transfer.sequence_id.to_string()),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn sudo(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    msg: TransferSudoMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    match msg {
+        TransferSudoMsg::Response { request, .. } => {
+            let (id, _) = take_forward_in_flight(deps, &request)?;
+            Ok(response(
+                "sudo_response",
+                CONTRACT_NAME,
+                [attr("id", id.to_string())],
+            ))
+        }
+        TransferSudoMsg::Error { request, details } => sudo_forward_failed(deps, request, details),
+        TransferSudoMsg::Timeout { request } => {
+            sudo_forward_failed(deps, request, "timeout".to_string())
+        }
+    }
+}
+
+fn take_forward_in_flight(
+    deps: DepsMut<NeutronQuery>,
+    request: &RequestPacket,
+) -> ContractResult<(u64, HookBond)> {
+    let channel = request
+        .source_channel
+        .clone()
+        .ok_or_else(|| StdError::generic_err("source_channel not found"))?;
+    let sequence = request
+        .sequence
+        .ok_or_else(|| StdError::generic_err("sequence not found"))?;
+    let id = FORWARDS_IN_FLIGHT
+        .may_load(deps.storage, (&channel, sequence))?
+        .ok_or(ContractError::UnknownTransfer {
+            channel: channel.to_string(),
+            sequence,
+        })?;
+    let hook_bond = HOOK_BONDS.load(deps.storage, id)?;
+    FORWARDS_IN_FLIGHT.remove(deps.storage, (&channel, sequence));
+    HOOK_BONDS.remove(deps.storage, id);
+    Ok((id, hook_bond))
+}
+
+fn sudo_forward_failed(
+    deps: DepsMut<NeutronQuery>,
+    request: RequestPacket,
+    details: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let (id, hook_bond) = take_forward_in_flight(deps, &request)?;
+    // the refunded dAsset is exactly what the failed packet carried
+    let data: FungibleTokenPacketData = from_json(
+        request
+            .data
+            .ok_or_else(|| StdError::generic_err("request data not found"))?,
+    )?;
+    let refund = Coin {
+        denom: data.denom,
+        amount: Uint128::from_str(&data.amount)?,
+    };
+    Ok(response(
+        "sudo_forward_failed",
+        CONTRACT_NAME,
+        [
+            attr("id", id.to_string()),
+            attr("details", details),
+            attr("refund", refund.to_string()),
+            attr("fallback", hook_bond.fallback.to_string()),
+        ],
+    )
+    .add_message(BankMsg::Send {
+        to_address: hook_bond.fallback.to_string(),
+        amount: vec![refund],
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn migrate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let version: semver::Version = CONTRACT_VERSION.parse()?;
+    let storage_version: semver::Version =
+        cw2::get_contract_version(deps.storage)?.version.parse()?;
+    if storage_version < version {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+    Ok(Response::new())
+}
@@ -0,0 +1,715 @@
+use cosmwasm_std::{
+    attr, coin, coins, from_json,
+    testing::{mock_env, mock_info},
+    to_json_binary, Addr, BankMsg, CosmosMsg, CustomQuery, DepsMut, Event, Reply, Response, SubMsg,
+    SubMsgResponse, SubMsgResult, WasmMsg,
+};
+use drop_helpers::testing::mock_dependencies;
+use drop_staking_base::{
+    error::ibc_hook_bonder::ContractError,
+    msg::ibc_hook_bonder::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::ibc_hook_bonder::{
+        Config, Forward, HookBond, ReplyMsg, CONFIG, FORWARDS_IN_FLIGHT, HOOK_BONDS,
+        HOOK_BOND_COUNTER,
+    },
+};
+use neutron_sdk::{
+    bindings::msg::{IbcFee, MsgIbcTransferResponse, NeutronMsg},
+    query::min_ibc_fee::MinIbcFeeResponse,
+    sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, TransferSudoMsg},
+};
+
+fn base_init<T>(deps: DepsMut<T>)
+where
+    T: CustomQuery,
+{
+    CONFIG
+        .save(
+            deps.storage,
+            &Config {
+                core_contract: Addr::unchecked("core"),
+                ibc_timeout: 10,
+            },
+        )
+        .unwrap();
+    HOOK_BOND_COUNTER.save(deps.storage, &0).unwrap();
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some("owner")).unwrap();
+}
+
+fn forward() -> Forward {
+    Forward {
+        receiver: "cosmos1receiver".to_string(),
+        port: "transfer".to_string(),
+        channel: "channel-1".to_string(),
+        timeout: None,
+        next: Some("{\"forward\":{\"receiver\":\"osmo1receiver\"}}".to_string()),
+    }
+}
+
+fn hook_bond(forward: Option<Forward>, minted: Option<cosmwasm_std::Coin>) -> HookBond {
+    HookBond {
+        fallback: Addr::unchecked("fallback"),
+        deposit: coin(1000, "base_denom"),
+        fee: match forward {
+            Some(_) => coins(300, "untrn"),
+            None => vec![],
+        },
+        receiver: match forward {
+            Some(_) => None,
+            None => Some(Addr::unchecked("receiver")),
+        },
+        forward,
+        minted,
+    }
+}
+
+fn forward_request() -> RequestPacket {
+    RequestPacket {
+        sequence: Some(7),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("channel-1".to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: Some(
+            to_json_binary(&drop_staking_base::msg::mirror::FungibleTokenPacketData {
+                denom: "ld_denom".to_string(),
+                amount: "900".to_string(),
+                sender: "cosmos2contract".to_string(),
+                receiver: "cosmos1receiver".to_string(),
+                memo: String::new(),
+            })
+            .unwrap(),
+        ),
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+fn get_standard_fees() -> IbcFee {
+    IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(100, "untrn"),
+        timeout_fee: coins(200, "untrn"),
+    }
+}
+
+#[test]
+fn test_instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    let response = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &[]),
+        InstantiateMsg {
+            owner: Some("owner".to_string()),
+            core_contract: "core".to_string(),
+            ibc_timeout: 10,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::new().add_event(
+            Event::new("crates.io:drop-staking__drop-ibc-hook-bonder-instantiate").add_attributes(
+                vec![
+                    attr("action", "instantiate"),
+                    attr("owner", "owner"),
+                    attr("core_contract", "core"),
+                    attr("ibc_timeout", "10"),
+                ]
+            )
+        )
+    );
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap(),
+        Config {
+            core_contract: Addr::unchecked("core"),
+            ibc_timeout: 10,
+        }
+    );
+    assert_eq!(HOOK_BOND_COUNTER.load(&deps.storage).unwrap(), 0);
+    let owner = cw_ownable::get_ownership(&deps.storage).unwrap();
+    assert_eq!(owner.owner, Some(Addr::unchecked("owner")));
+}
+
+#[test]
+fn test_instantiate_zero_timeout() {
+    let mut deps = mock_dependencies(&[]);
+    let error = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &[]),
+        InstantiateMsg {
+            owner: None,
+            core_contract: "core".to_string(),
+            ibc_timeout: 0,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::InvalidIbcTimeout);
+}
+
+#[test]
+fn update_config_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_config: drop_staking_base::state::ibc_hook_bonder::ConfigOptional {
+                core_contract: None,
+                ibc_timeout: Some(20),
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+    );
+}
+
+#[test]
+fn bond_local() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("hooks_sender", &[coin(1000, "base_denom")]),
+        ExecuteMsg::Bond {
+            receiver: Some("receiver".to_string()),
+            forward: None,
+            fallback: "fallback".to_string(),
+            r#ref: Some("ref".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::new()
+            .add_submessage(SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: "core".to_string(),
+                    msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::Bond {
+                        receiver: Some("receiver".to_string()),
+                        r#ref: Some("ref".to_string()),
+                    })
+                    .unwrap(),
+                    funds: vec![coin(1000, "base_denom")],
+                },
+                ReplyMsg::Bond { id: 1 }.to_reply_id(),
+            ))
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-ibc-hook-bonder-bond").add_attributes(
+                    vec![
+                        attr("action", "bond"),
+                        attr("id", "1"),
+                        attr("deposit", "1000base_denom"),
+                        attr("fallback", "fallback"),
+                        attr("receiver", "receiver"),
+                        attr("forward_receiver", ""),
+                    ]
+                )
+            )
+    );
+    assert_eq!(HOOK_BOND_COUNTER.load(&deps.storage).unwrap(), 1);
+    assert_eq!(
+        HOOK_BONDS.load(&deps.storage, 1).unwrap(),
+        hook_bond(None, None)
+    );
+}
+
+#[test]
+fn bond_forward() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(deps.as_mut());
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "hooks_sender",
+            &[coin(1000, "base_denom"), coin(300, "untrn")],
+        ),
+        ExecuteMsg::Bond {
+            receiver: None,
+            forward: Some(forward()),
+            fallback: "fallback".to_string(),
+            r#ref: None,
+        },
+    )
+    .unwrap();
+    // only the base asset is bonded, the fee stays for the forwarding transfer
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: "core".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::Bond {
+                    receiver: None,
+                    r#ref: None,
+                })
+                .unwrap(),
+                funds: vec![coin(1000, "base_denom")],
+            },
+            ReplyMsg::Bond { id: 1 }.to_reply_id(),
+        )]
+    );
+    assert_eq!(
+        HOOK_BONDS.load(&deps.storage, 1).unwrap(),
+        hook_bond(Some(forward()), None)
+    );
+}
+
+#[test]
+fn bond_forward_fee_not_paid() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(deps.as_mut());
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("hooks_sender", &[coin(1000, "base_denom")]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            forward: Some(forward()),
+            fallback: "fallback".to_string(),
+            r#ref: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::IbcFeeNotPaid {
+            required: 300u128.into(),
+            paid: 0u128.into(),
+        }
+    );
+    assert_eq!(HOOK_BOND_COUNTER.load(&deps.storage).unwrap(), 0);
+}
+
+#[test]
+fn bond_forward_refunds_excess_fee() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(deps.as_mut());
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "hooks_sender",
+            &[coin(1000, "base_denom"), coin(500, "untrn")],
+        ),
+        ExecuteMsg::Bond {
+            receiver: None,
+            forward: Some(forward()),
+            fallback: "fallback".to_string(),
+            r#ref: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages[1],
+        SubMsg::new(BankMsg::Send {
+            to_address: "fallback".to_string(),
+            amount: coins(200, "untrn"),
+        })
+    );
+    assert_eq!(
+        response.events[0].attributes.last().unwrap(),
+        attr("fee_refund", "200untrn")
+    );
+    // the bond failure refund returns the required fee only
+    assert_eq!(
+        HOOK_BONDS.load(&deps.storage, 1).unwrap(),
+        hook_bond(Some(forward()), None)
+    );
+}
+
+#[test]
+fn bond_id_overflow() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    HOOK_BOND_COUNTER
+        .save(deps.as_mut().storage, &(u32::MAX as u64))
+        .unwrap();
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("hooks_sender", &[coin(1000, "base_denom")]),
+        ExecuteMsg::Bond {
+            receiver: Some("receiver".to_string()),
+            forward: None,
+            fallback: "fallback".to_string(),
+            r#ref: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::IdOverflow {
+            id: u32::MAX as u64 + 1
+        }
+    );
+    assert_eq!(
+        HOOK_BOND_COUNTER.load(&deps.storage).unwrap(),
+        u32::MAX as u64
+    );
+}
+
+#[test]
+fn bond_invalid_destination() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    for (receiver, forward) in [
+        (None, None),
+        (Some("receiver".to_string()), Some(forward())),
+    ] {
+        let error = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("hooks_sender", &[coin(1000, "base_denom")]),
+            ExecuteMsg::Bond {
+                receiver,
+                forward,
+                fallback: "fallback".to_string(),
+                r#ref: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error, ContractError::InvalidDestination);
+    }
+}
+
+#[test]
+fn bond_no_funds() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("hooks_sender", &[]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            forward: Some(forward()),
+            fallback: "fallback".to_string(),
+            r#ref: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::PaymentError(cw_utils::PaymentError::NoFunds {})
+    );
+}
+
+#[test]
+fn bond_reply_error_refunds_fallback() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    HOOK_BONDS
+        .save(deps.as_mut().storage, 1, &hook_bond(Some(forward()), None))
+        .unwrap();
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Bond { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Err("bond is paused".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "fallback".to_string(),
+                amount: vec![coin(1000, "base_denom"), coin(300, "untrn")],
+            })
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-ibc-hook-bonder-bond_failed")
+                    .add_attributes(vec![
+                        attr("action", "bond_failed"),
+                        attr("id", "1"),
+                        attr("error", "bond is paused"),
+                        attr("refund", "1000base_denom"),
+                        attr("fallback", "fallback"),
+                    ])
+            )
+    );
+    assert!(HOOK_BONDS.may_load(&deps.storage, 1).unwrap().is_none());
+}
+
+#[test]
+fn bond_reply_local() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    HOOK_BONDS
+        .save(deps.as_mut().storage, 1, &hook_bond(None, None))
+        .unwrap();
+    crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Bond { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert!(HOOK_BONDS.may_load(&deps.storage, 1).unwrap().is_none());
+}
+
+#[test]
+fn bond_reply_forward() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(deps.as_mut());
+    HOOK_BONDS
+        .save(deps.as_mut().storage, 1, &hook_bond(Some(forward()), None))
+        .unwrap();
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Bond { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("tf_mint").add_attribute("amount", "900ld_denom")],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::new()
+            .add_submessage(SubMsg::reply_on_success(
+                CosmosMsg::Custom(NeutronMsg::IbcTransfer {
+                    source_port: "transfer".to_string(),
+                    source_channel: "channel-1".to_string(),
+                    token: coin(900, "ld_denom"),
+                    sender: "cosmos2contract".to_string(),
+                    receiver: "cosmos1receiver".to_string(),
+                    timeout_height: RequestPacketTimeoutHeight {
+                        revision_number: None,
+                        revision_height: None,
+                    },
+                    timeout_timestamp: 1571797429879305533,
+                    memo: "{\"forward\":{\"receiver\":\"osmo1receiver\"}}".to_string(),
+                    fee: get_standard_fees(),
+                }),
+                ReplyMsg::Forward { id: 1 }.to_reply_id(),
+            ))
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-ibc-hook-bonder-forward").add_attributes(
+                    vec![
+                        attr("action", "forward"),
+                        attr("id", "1"),
+                        attr("minted", "900ld_denom"),
+                        attr("channel", "channel-1"),
+                        attr("receiver", "cosmos1receiver"),
+                    ]
+                )
+            )
+    );
+    assert_eq!(
+        HOOK_BONDS.load(&deps.storage, 1).unwrap(),
+        hook_bond(Some(forward()), Some(coin(900, "ld_denom")))
+    );
+}
+
+#[test]
+fn bond_reply_forward_no_mint() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    HOOK_BONDS
+        .save(deps.as_mut().storage, 1, &hook_bond(Some(forward()), None))
+        .unwrap();
+    let error = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Bond { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::NoTokensMinted);
+}
+
+#[test]
+fn forward_reply() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Forward { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(
+                    to_json_binary(&MsgIbcTransferResponse {
+                        sequence_id: 7,
+                        channel: "channel-1".to_string(),
+                    })
+                    .unwrap(),
+                ),
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        FORWARDS_IN_FLIGHT
+            .load(&deps.storage, ("channel-1", 7))
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn sudo_response() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    HOOK_BONDS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &hook_bond(Some(forward()), Some(coin(900, "ld_denom"))),
+        )
+        .unwrap();
+    FORWARDS_IN_FLIGHT
+        .save(deps.as_mut().storage, ("channel-1", 7), &1)
+        .unwrap();
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Response {
+            request: forward_request(),
+            data: cosmwasm_std::Binary::default(),
+        },
+    )
+    .unwrap();
+    assert!(HOOK_BONDS.may_load(&deps.storage, 1).unwrap().is_none());
+    assert!(FORWARDS_IN_FLIGHT
+        .may_load(&deps.storage, ("channel-1", 7))
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn sudo_timeout_refunds_fallback() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    HOOK_BONDS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &hook_bond(Some(forward()), Some(coin(900, "ld_denom"))),
+        )
+        .unwrap();
+    FORWARDS_IN_FLIGHT
+        .save(deps.as_mut().storage, ("channel-1", 7), &1)
+        .unwrap();
+    let response = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Timeout {
+            request: forward_request(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::new()
+            .add_message(BankMsg::Send {
+                to_address: "fallback".to_string(),
+                amount: coins(900, "ld_denom"),
+            })
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-ibc-hook-bonder-sudo_forward_failed")
+                    .add_attributes(vec![
+                        attr("action", "sudo_forward_failed"),
+                        attr("id", "1"),
+                        attr("details", "timeout"),
+                        attr("refund", "900ld_denom"),
+                        attr("fallback", "fallback"),
+                    ])
+            )
+    );
+    assert!(HOOK_BONDS.may_load(&deps.storage, 1).unwrap().is_none());
+}
+
+#[test]
+fn sudo_unknown_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let error = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Error {
+            request: forward_request(),
+            details: "error".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::UnknownTransfer {
+            channel: "channel-1".to_string(),
+            sequence: 7,
+        }
+    );
+}
+
+#[test]
+fn query_hook_bonds() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    for id in 1..=3 {
+        HOOK_BONDS
+            .save(deps.as_mut().storage, id, &hook_bond(None, None))
+            .unwrap();
+    }
+    let bonds: Vec<(u64, HookBond)> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::HookBonds {
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(bonds, vec![(2, hook_bond(None, None))]);
+}
@@ -0,0 +1,361 @@
+use astroport::asset::AssetInfo;
+use astroport::router::{ExecuteMsg as RouterExecuteMsg, SwapOperation};
+use cosmwasm_std::{
+    attr, ensure, to_json_binary, Attribute, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdResult, Uint128, WasmMsg,
+};
+use cw_ownable::{get_ownership, update_ownership};
+use drop_helpers::answer::{attr_coin, response};
+use drop_helpers::get_contracts;
+use drop_staking_base::error::lst_swap_bond_provider::{ContractError, ContractResult};
+use drop_staking_base::msg::lst_swap_bond_provider::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use drop_staking_base::state::lst_swap_bond_provider::{
+    Config, ConfigOptional, CONFIG, SWAP_ROUTES,
+};
+use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_sdk::bindings::query::NeutronQuery;
+
+const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const LOCAL_DENOM: &str = "untrn";
+// dAssets are minted at the quote minus the spread, a wider spread would shortchange bonders
+pub const MAX_SPREAD_LIMIT: Decimal = Decimal::percent(1);
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn instantiate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(msg.owner.as_ref()))?;
+
+    validate_max_spread(msg.max_spread)?;
+
+    let config = &Config {
+        factory_contract: deps.api.addr_validate(&msg.factory_contract)?,
+        base_denom: msg.base_denom.to_string(),
+        router_contract: deps.api.addr_validate(&msg.router_contract)?,
+        price_provider_contract: deps.api.addr_validate(&msg.price_provider_contract)?,
+        max_spread: msg.max_spread,
+    };
+    CONFIG.save(deps.storage, config)?;
+
+    Ok(response(
+        "instantiate",
+        CONTRACT_NAME,
+        [
+            attr("factory_contract", msg.factory_contract),
+            attr("base_denom", msg.base_denom),
+            attr("router_contract", msg.router_contract),
+            attr("price_provider_contract", msg.price_provider_contract),
+            attr("max_spread", msg.max_spread.to_string()),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
+        QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
+        QueryMsg::SwapRoutes {} => query_swap_routes(deps),
+        QueryMsg::CanBond { denom } => Ok(to_json_binary(&SWAP_ROUTES.has(deps.storage, &denom))?),
+        // swapped funds are handed over to the native bond provider right away,
+        // so there is never anything to process on idle
+        QueryMsg::CanProcessOnIdle {} => Ok(to_json_binary(&false)?),
+        QueryMsg::TokensAmount {
+            coin,
+            exchange_rate,
+        } => query_token_amount(deps, coin, exchange_rate),
+        QueryMsg::AsyncTokensAmount {} => Ok(to_json_binary(&Uint128::zero())?),
+        QueryMsg::CanBeRemoved {} => query_can_be_removed(deps, env),
+    }
+}
+
+fn query_swap_routes(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+    let routes = SWAP_ROUTES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Vec<SwapOperation>)>>>()?;
+    Ok(to_json_binary(&routes)?)
+}
+
+fn query_can_be_removed(deps: Deps<NeutronQuery>, env: Env) -> ContractResult<Binary> {
+    let all_balances = deps.querier.query_all_balances(env.contract.address)?;
+    let result = all_balances
+        .into_iter()
+        .all(|coin| coin.denom == *LOCAL_DENOM.to_string());
+    Ok(to_json_binary(&result)?)
+}
+
+fn query_token_amount(
+    deps: Deps<NeutronQuery>,
+    coin: Coin,
+    exchange_rate: Decimal,
+) -> ContractResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !SWAP_ROUTES.has(deps.storage, &coin.denom) {
+        return Err(ContractError::InvalidDenom {});
+    }
+
+    // only the minimum the swap is guaranteed to return is minted for, so a worse
+    // swap output than the quote can't dilute dAsset holders
+    let price = query_price(deps, &config, &coin.denom)?;
+    let issue_amount = coin.amount * (guaranteed_price(&config, price) / exchange_rate);
+
+    Ok(to_json_binary(&issue_amount)?)
+}
+
+// price of the LST denominated in the base denom
+fn query_price(deps: Deps<NeutronQuery>, config: &Config, denom: &str) -> ContractResult<Decimal> {
+    deps.querier
+        .query_wasm_smart(
+            config.price_provider_contract.to_string(),
+            &drop_staking_base::msg::price_provider::QueryMsg::Price {
+                denom: denom.to_string(),
+            },
+        )
+        .map_err(|e| ContractError::AssetPriceQueryFailed {
+            details: e.to_string(),
+        })
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(Response::new())
+        }
+        ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::SetSwapRoute { denom, operations } => {
+            execute_set_swap_route(deps, info, denom, operations)
+        }
+        ExecuteMsg::RemoveSwapRoute { denom } => execute_remove_swap_route(deps, info, denom),
+        ExecuteMsg::Bond {} => execute_bond(deps, info),
+        ExecuteMsg::ProcessOnIdle {} => Err(ContractError::MessageIsNotSupported {}),
+    }
+}
+
+fn execute_update_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    new_config: ConfigOptional,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut state = CONFIG.load(deps.storage)?;
+    let mut attrs: Vec<Attribute> = Vec::new();
+
+    if let Some(factory_contract) = new_config.factory_contract {
+        state.factory_contract = deps.api.addr_validate(factory_contract.as_ref())?;
+        attrs.push(attr("factory_contract", factory_contract))
+    }
+
+    if let Some(base_denom) = new_config.base_denom {
+        state.base_denom = base_denom.to_string();
+        attrs.push(attr("base_denom", base_denom));
+    }
+
+    if let Some(router_contract) = new_config.router_contract {
+        state.router_contract = deps.api.addr_validate(router_contract.as_ref())?;
+        attrs.push(attr("router_contract", router_contract))
+    }
+
+    if let Some(price_provider_contract) = new_config.price_provider_contract {
+        state.price_provider_contract = deps.api.addr_validate(price_provider_contract.as_ref())?;
+        attrs.push(attr("price_provider_contract", price_provider_contract))
+    }
+
+    if let Some(max_spread) = new_config.max_spread {
+        validate_max_spread(max_spread)?;
+        state.max_spread = max_spread;
+        attrs.push(attr("max_spread", max_spread.to_string()));
+    }
+
+    CONFIG.save(deps.storage, &state)?;
+
+    Ok(response("update_config", CONTRACT_NAME, attrs))
+}
+
+fn execute_set_swap_route(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    denom: String,
+    operations: Vec<SwapOperation>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_swap_route(&denom, &config.base_denom, &operations)?;
+    SWAP_ROUTES.save(deps.storage, &denom, &operations)?;
+
+    Ok(response(
+        "set_swap_route",
+        CONTRACT_NAME,
+        [
+            attr("denom", denom),
+            attr("operations", operations.len().to_string()),
+        ],
+    ))
+}
+
+fn execute_remove_swap_route(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    denom: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    ensure!(
+        SWAP_ROUTES.has(deps.storage, &denom),
+        ContractError::InvalidDenom {}
+    );
+    SWAP_ROUTES.remove(deps.storage, &denom);
+
+    Ok(response(
+        "remove_swap_route",
+        CONTRACT_NAME,
+        [attr("denom", denom)],
+    ))
+}
+
+fn execute_bond(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> ContractResult<Response<NeutronMsg>> {
+    let bonded_coin = cw_utils::one_coin(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let operations = SWAP_ROUTES
+        .may_load(deps.storage, &bonded_coin.denom)?
+        .ok_or(ContractError::InvalidDenom {})?;
+    let addrs = get_contracts!(deps, config.factory_contract, native_bond_provider_contract);
+
+    // dAssets are minted by the core against the same minimum receive,
+    // anything the swap returns above it goes to the pool
+    let price = query_price(deps.as_ref(), &config, &bonded_coin.denom)?;
+    let minimum_receive = bonded_coin.amount * guaranteed_price(&config, price);
+
+    let swap_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.router_contract.to_string(),
+        msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive: Some(minimum_receive),
+            to: Some(addrs.native_bond_provider_contract.clone()),
+            max_spread: Some(config.max_spread),
+        })?,
+        funds: vec![bonded_coin.clone()],
+    });
+
+    Ok(response(
+        "bond",
+        CONTRACT_NAME,
+        [
+            attr_coin(
+                "received_funds",
+                bonded_coin.amount.to_string(),
+                bonded_coin.denom,
+            ),
+            attr("price", price.to_string()),
+            attr_coin("minimum_receive", minimum_receive, config.base_denom),
+            attr("receiver", addrs.native_bond_provider_contract),
+        ],
+    )
+    .add_message(swap_msg))
+}
+
+fn guaranteed_price(config: &Config, price: Decimal) -> Decimal {
+    price * (Decimal::one() - config.max_spread)
+}
+
+fn validate_max_spread(max_spread: Decimal) -> ContractResult<()> {
+    if max_spread.is_zero() {
+        return Err(ContractError::ZeroMaxSpread {});
+    }
+    if max_spread > MAX_SPREAD_LIMIT {
+        return Err(ContractError::MaxSpreadTooBig {});
+    }
+    Ok(())
+}
+
+fn swap_operation_denoms(operation: &SwapOperation) -> (String, String) {
+    let asset_denom = |info: &AssetInfo| match info {
+        AssetInfo::NativeToken { denom } => denom.to_string(),
+        AssetInfo::Token { contract_addr } => contract_addr.to_string(),
+    };
+    match operation {
+        SwapOperation::NativeSwap {
+            offer_denom,
+            ask_denom,
+        } => (offer_denom.to_string(), ask_denom.to_string()),
+        SwapOperation::AstroSwap {
+            offer_asset_info,
+            ask_asset_info,
+        } => (asset_denom(offer_asset_info), asset_denom(ask_asset_info)),
+    }
+}
+
+// the route has to start with the accepted LST, end with the base denom
+// and every hop has to continue from the previous one
+fn validate_swap_route(
+    denom: &str,
+    base_denom: &str,
+    operations: &[SwapOperation],
+) -> ContractResult<()> {
+    let invalid = |reason: &str| ContractError::InvalidSwapRoute {
+        denom: denom.to_string(),
+        reason: reason.to_string(),
+    };
+    ensure!(
+        denom != base_denom,
+        invalid("base denom can not be swapped")
+    );
+    ensure!(!operations.is_empty(), invalid("route is empty"));
+
+    let mut current_denom = denom.to_string();
+    for operation in operations {
+        let (offer_denom, ask_denom) = swap_operation_denoms(operation);
+        ensure!(
+            offer_denom == current_denom,
+            invalid(&format!(
+                "expected offer denom {current_denom}, got {offer_denom}"
+            ))
+        );
+        current_denom = ask_denom;
+    }
+    ensure!(
+        current_denom == base_denom,
+        invalid(&format!(
+            "route ends with {current_denom} instead of {base_denom}"
+        ))
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn migrate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let version: semver::Version = CONTRACT_VERSION.parse()?;
+    let storage_version: semver::Version =
+        cw2::get_contract_version(deps.storage)?.version.parse()?;
+
+    if storage_version < version {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new())
+}
@@ -0,0 +1,442 @@
+use astroport::asset::AssetInfo;
+use astroport::router::{ExecuteMsg as RouterExecuteMsg, SwapOperation};
+use cosmwasm_std::{
+    attr, coin, from_json,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    to_json_binary, Addr, CosmosMsg, Decimal, Event, OwnedDeps, Uint128, WasmMsg,
+};
+use cw_utils::PaymentError;
+use drop_helpers::testing::{mock_dependencies, mock_state_query, WasmMockQuerier};
+use drop_staking_base::error::lst_swap_bond_provider::ContractError;
+use drop_staking_base::msg::lst_swap_bond_provider::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use drop_staking_base::state::lst_swap_bond_provider::{
+    Config, ConfigOptional, CONFIG, SWAP_ROUTES,
+};
+use neutron_sdk::bindings::query::NeutronQuery;
+
+fn get_default_config() -> Config {
+    Config {
+        factory_contract: Addr::unchecked("factory_contract"),
+        base_denom: "base_denom".to_string(),
+        router_contract: Addr::unchecked("router_contract"),
+        price_provider_contract: Addr::unchecked("price_provider_contract"),
+        max_spread: Decimal::percent(1),
+    }
+}
+
+fn get_default_route() -> Vec<SwapOperation> {
+    vec![
+        SwapOperation::AstroSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: "lst_denom".to_string(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "untrn".to_string(),
+            },
+        },
+        SwapOperation::AstroSwap {
+            offer_asset_info: AssetInfo::NativeToken {
+                denom: "untrn".to_string(),
+            },
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "base_denom".to_string(),
+            },
+        },
+    ]
+}
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier, NeutronQuery> {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    SWAP_ROUTES
+        .save(deps.as_mut().storage, "lst_denom", &get_default_route())
+        .unwrap();
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    deps
+}
+
+#[test]
+fn instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    let response = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            factory_contract: "factory_contract".to_string(),
+            base_denom: "base_denom".to_string(),
+            router_contract: "router_contract".to_string(),
+            price_provider_contract: "price_provider_contract".to_string(),
+            max_spread: Decimal::percent(1),
+        },
+    )
+    .unwrap();
+
+    let config = CONFIG.load(deps.as_ref().storage).unwrap();
+    assert_eq!(config, get_default_config());
+
+    assert_eq!(response.messages.len(), 0);
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-lst-swap-bond-provider-instantiate")
+                .add_attributes([
+                    attr("factory_contract", "factory_contract"),
+                    attr("base_denom", "base_denom"),
+                    attr("router_contract", "router_contract"),
+                    attr("price_provider_contract", "price_provider_contract"),
+                    attr("max_spread", "0.01"),
+                ])
+        ]
+    );
+}
+
+#[test]
+fn instantiate_max_spread_too_big() {
+    let mut deps = mock_dependencies(&[]);
+    let error = crate::contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            factory_contract: "factory_contract".to_string(),
+            base_denom: "base_denom".to_string(),
+            router_contract: "router_contract".to_string(),
+            price_provider_contract: "price_provider_contract".to_string(),
+            max_spread: Decimal::permille(11),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::MaxSpreadTooBig);
+}
+
+#[test]
+fn update_config_zero_max_spread() {
+    let mut deps = setup();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_config: ConfigOptional {
+                factory_contract: None,
+                base_denom: None,
+                router_contract: None,
+                price_provider_contract: None,
+                max_spread: Some(Decimal::zero()),
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::ZeroMaxSpread);
+}
+
+#[test]
+fn update_config_wrong_owner() {
+    let mut deps = setup();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_config: ConfigOptional {
+                factory_contract: None,
+                base_denom: None,
+                router_contract: Some(Addr::unchecked("new_router")),
+                price_provider_contract: None,
+                max_spread: None,
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner)
+    );
+}
+
+#[test]
+fn set_swap_route_ok() {
+    let mut deps = setup();
+
+    let route = vec![SwapOperation::NativeSwap {
+        offer_denom: "other_lst".to_string(),
+        ask_denom: "base_denom".to_string(),
+    }];
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetSwapRoute {
+            denom: "other_lst".to_string(),
+            operations: route.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-lst-swap-bond-provider-set_swap_route")
+                .add_attributes([attr("denom", "other_lst"), attr("operations", "1")])
+        ]
+    );
+
+    let routes: Vec<(String, Vec<SwapOperation>)> = from_json(
+        crate::contract::query(deps.as_ref(), mock_env(), QueryMsg::SwapRoutes {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        routes,
+        vec![
+            ("lst_denom".to_string(), get_default_route()),
+            ("other_lst".to_string(), route),
+        ]
+    );
+}
+
+#[test]
+fn set_swap_route_wrong_target() {
+    let mut deps = setup();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetSwapRoute {
+            denom: "other_lst".to_string(),
+            operations: vec![SwapOperation::NativeSwap {
+                offer_denom: "other_lst".to_string(),
+                ask_denom: "untrn".to_string(),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::InvalidSwapRoute {
+            denom: "other_lst".to_string(),
+            reason: "route ends with untrn instead of base_denom".to_string(),
+        }
+    );
+}
+
+#[test]
+fn set_swap_route_broken_chain() {
+    let mut deps = setup();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetSwapRoute {
+            denom: "other_lst".to_string(),
+            operations: vec![
+                SwapOperation::NativeSwap {
+                    offer_denom: "other_lst".to_string(),
+                    ask_denom: "untrn".to_string(),
+                },
+                SwapOperation::NativeSwap {
+                    offer_denom: "uatom".to_string(),
+                    ask_denom: "base_denom".to_string(),
+                },
+            ],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::InvalidSwapRoute {
+            denom: "other_lst".to_string(),
+            reason: "expected offer denom untrn, got uatom".to_string(),
+        }
+    );
+}
+
+#[test]
+fn remove_swap_route() {
+    let mut deps = setup();
+
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveSwapRoute {
+            denom: "lst_denom".to_string(),
+        },
+    )
+    .unwrap();
+
+    let can_bond: bool = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CanBond {
+                denom: "lst_denom".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!can_bond);
+}
+
+#[test]
+fn query_can_bond() {
+    let deps = setup();
+
+    for (denom, expected) in [("lst_denom", true), ("base_denom", false)] {
+        let can_bond: bool = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::CanBond {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(can_bond, expected);
+    }
+}
+
+#[test]
+fn query_tokens_amount() {
+    let mut deps = setup();
+    deps.querier
+        .add_wasm_query_response("price_provider_contract", |_| {
+            to_json_binary(&Decimal::from_ratio(12u128, 10u128)).unwrap()
+        });
+
+    let issue_amount: Uint128 = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokensAmount {
+                coin: coin(1000, "lst_denom"),
+                exchange_rate: Decimal::from_ratio(3u128, 2u128),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    // 1000 * 1.2 * (1 - 0.01) / 1.5
+    assert_eq!(issue_amount, Uint128::new(792));
+}
+
+#[test]
+fn query_tokens_amount_invalid_denom() {
+    let deps = setup();
+
+    let error = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TokensAmount {
+            coin: coin(1000, "base_denom"),
+            exchange_rate: Decimal::one(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::InvalidDenom {});
+}
+
+#[test]
+fn bond_ok() {
+    let mut deps = setup();
+    mock_state_query(&mut deps);
+    deps.querier
+        .add_wasm_query_response("price_provider_contract", |_| {
+            to_json_binary(&Decimal::from_ratio(12u128, 10u128)).unwrap()
+        });
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core_contract", &[coin(1000, "lst_denom")]),
+        ExecuteMsg::Bond {},
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router_contract".to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: get_default_route(),
+                minimum_receive: Some(Uint128::new(1188)),
+                to: Some("native_bond_provider_contract".to_string()),
+                max_spread: Some(Decimal::percent(1)),
+            })
+            .unwrap(),
+            funds: vec![coin(1000, "lst_denom")],
+        })
+    );
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-lst-swap-bond-provider-bond").add_attributes(
+                [
+                    attr("received_funds", "1000lst_denom"),
+                    attr("price", "1.2"),
+                    attr("minimum_receive", "1188base_denom"),
+                    attr("receiver", "native_bond_provider_contract"),
+                ]
+            )
+        ]
+    );
+}
+
+#[test]
+fn bond_unknown_denom() {
+    let mut deps = setup();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core_contract", &[coin(1000, "base_denom")]),
+        ExecuteMsg::Bond {},
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::InvalidDenom {});
+}
+
+#[test]
+fn bond_no_funds() {
+    let mut deps = setup();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core_contract", &[]),
+        ExecuteMsg::Bond {},
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::PaymentError(PaymentError::NoFunds {}));
+}
+
+#[test]
+fn process_on_idle_not_supported() {
+    let mut deps = setup();
+
+    let can_process: bool = from_json(
+        crate::contract::query(deps.as_ref(), mock_env(), QueryMsg::CanProcessOnIdle {}).unwrap(),
+    )
+    .unwrap();
+    assert!(!can_process);
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core_contract", &[]),
+        ExecuteMsg::ProcessOnIdle {},
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::MessageIsNotSupported {});
+}
@@ -1,13 +1,17 @@
 use cosmwasm_std::{
     attr, ensure, ensure_eq, from_json, to_json_binary, BankMsg, Binary, Coin, CosmosMsg, Deps,
-    DepsMut, Env, IbcQuery, MessageInfo, Order, Reply, Response, SubMsg, WasmMsg,
+    DepsMut, Env, IbcQuery, MessageInfo, Order, Reply, Response, StdResult, SubMsg, SubMsgResponse,
+    SubMsgResult, WasmMsg,
 };
 use cw_ownable::update_ownership;
+use cw_storage_plus::Bound;
 use drop_helpers::answer::response;
 use drop_helpers::ibc_fee::query_ibc_fee;
 use drop_staking_base::msg::mirror::{ExecuteMsg, FungibleTokenPacketData};
 use drop_staking_base::state::mirror::{
-    BondItem, BondState, Config, ConfigOptional, ReturnType, BONDS, CONFIG, COUNTER,
+    bonds_map, unbonds_map, BondItem, BondState, Config, ConfigOptional, ReplyMsg, RetryPolicy,
+    ReturnType, UnbondItem, UnbondState, CONFIG, COUNTER, RETRY_POLICY, UNBOND_COUNTER,
+    UNBOND_MEMO_PREFIX,
 };
 use drop_staking_base::{
     error::mirror::{ContractError, ContractResult},
@@ -24,6 +28,8 @@ use std::{env, vec};
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const LOCAL_DENOM: &str = "untrn";
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
@@ -47,6 +53,10 @@ pub fn instantiate(
         },
     )?;
     COUNTER.save(deps.storage, &0)?;
+    UNBOND_COUNTER.save(deps.storage, &0)?;
+    let retry_policy = msg.retry_policy.unwrap_or_default();
+    validate_retry_policy(&retry_policy)?;
+    RETRY_POLICY.save(deps.storage, &retry_policy)?;
     let attrs = vec![attr("action", "instantiate"), attr("owner", owner)];
     Ok(response("instantiate", CONTRACT_NAME, attrs))
 }
@@ -58,11 +68,50 @@ pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResu
         QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
         QueryMsg::One { id } => query_one(deps, id),
         QueryMsg::All { start_after, limit } => query_all(deps, start_after, limit),
+        QueryMsg::BondsByReceiver {
+            receiver,
+            start_after,
+            limit,
+        } => query_bonds_by_receiver(deps, receiver, start_after, limit),
+        QueryMsg::FailedBonds { start_after, limit } => {
+            query_failed_bonds(deps, start_after, limit)
+        }
+        QueryMsg::OneUnbond { id } => Ok(to_json_binary(&unbonds_map().load(deps.storage, id)?)?),
+        QueryMsg::AllUnbonds { start_after, limit } => query_all_unbonds(deps, start_after, limit),
+        QueryMsg::UnbondsByReceiver {
+            receiver,
+            start_after,
+            limit,
+        } => query_unbonds_by_receiver(deps, receiver, start_after, limit),
+        QueryMsg::FailedUnbonds { start_after, limit } => {
+            query_failed_unbonds(deps, start_after, limit)
+        }
+        QueryMsg::RetryPolicy {} => Ok(to_json_binary(&load_retry_policy(deps)?)?),
     }
 }
 
+fn load_retry_policy(deps: Deps<NeutronQuery>) -> ContractResult<RetryPolicy> {
+    Ok(RETRY_POLICY.may_load(deps.storage)?.unwrap_or_default())
+}
+
+fn validate_retry_policy(retry_policy: &RetryPolicy) -> ContractResult<()> {
+    ensure!(
+        retry_policy.max_retries > 0,
+        ContractError::InvalidRetryPolicy {
+            reason: "max_retries must be positive".to_string()
+        }
+    );
+    ensure!(
+        retry_policy.backoff <= retry_policy.max_backoff,
+        ContractError::InvalidRetryPolicy {
+            reason: "backoff exceeds max_backoff".to_string()
+        }
+    );
+    Ok(())
+}
+
 pub fn query_one(deps: Deps<NeutronQuery>, id: u64) -> ContractResult<Binary> {
-    let bond = BONDS.load(deps.storage, id)?;
+    let bond = bonds_map().load(deps.storage, id)?;
     to_json_binary(&bond).map_err(ContractError::Std)
 }
 
@@ -72,7 +121,7 @@ pub fn query_all(
     limit: Option<u32>,
 ) -> ContractResult<Binary> {
     let limit = limit.map(|x| x as usize).unwrap_or(usize::MAX);
-    let bonds = BONDS
+    let bonds = bonds_map()
         .range(
             deps.storage,
             start_after.map(|x| cw_storage_plus::Bound::Inclusive((x, PhantomData))),
@@ -85,6 +134,110 @@ pub fn query_all(
     to_json_binary(&bonds).map_err(ContractError::Std)
 }
 
+pub fn query_bonds_by_receiver(
+    deps: Deps<NeutronQuery>,
+    receiver: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<Binary> {
+    let limit = limit.map(|x| x as usize).unwrap_or(usize::MAX);
+    let bonds = bonds_map()
+        .idx
+        .receiver
+        .prefix(receiver)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&bonds).map_err(ContractError::Std)
+}
+
+pub fn query_failed_bonds(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<Binary> {
+    let limit = limit.map(|x| x as usize).unwrap_or(usize::MAX);
+    let bonds = bonds_map()
+        .idx
+        .failed
+        .prefix(1)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&bonds).map_err(ContractError::Std)
+}
+
+pub fn query_all_unbonds(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<Binary> {
+    let limit = limit.map(|x| x as usize).unwrap_or(usize::MAX);
+    let unbonds = unbonds_map()
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&unbonds).map_err(ContractError::Std)
+}
+
+pub fn query_unbonds_by_receiver(
+    deps: Deps<NeutronQuery>,
+    receiver: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<Binary> {
+    let limit = limit.map(|x| x as usize).unwrap_or(usize::MAX);
+    let unbonds = unbonds_map()
+        .idx
+        .receiver
+        .prefix(receiver)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&unbonds).map_err(ContractError::Std)
+}
+
+pub fn query_failed_unbonds(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<Binary> {
+    let limit = limit.map(|x| x as usize).unwrap_or(usize::MAX);
+    let unbonds = unbonds_map()
+        .idx
+        .failed
+        .prefix(1)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&unbonds).map_err(ContractError::Std)
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn execute(
     deps: DepsMut<NeutronQuery>,
@@ -102,7 +255,13 @@ pub fn execute(
             update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
             Ok(Response::new())
         }
+        ExecuteMsg::Unbond { receiver, backup } => execute_unbond(deps, info, receiver, backup),
+        ExecuteMsg::Withdraw { items } => execute_withdraw(deps, items),
+        ExecuteMsg::ChangeUnbondReturnType { id, return_type } => {
+            execute_change_unbond_return_type(deps, info, id, return_type)
+        }
         ExecuteMsg::Complete { items } => execute_complete(deps, env, info, items),
+        ExecuteMsg::RetryFailed { limit } => execute_retry_failed(deps, env, limit),
         ExecuteMsg::ChangeReturnType { id, return_type } => {
             execute_change_return_type(deps, env, info, id, return_type)
         }
@@ -140,6 +299,16 @@ pub fn execute_update_config(
         attrs.push(attr("prefix", &prefix));
         config.prefix = prefix;
     }
+    if let Some(retry_policy) = new_config.retry_policy {
+        validate_retry_policy(&retry_policy)?;
+        attrs.push(attr("retry_backoff", retry_policy.backoff.to_string()));
+        attrs.push(attr(
+            "retry_max_backoff",
+            retry_policy.max_backoff.to_string(),
+        ));
+        attrs.push(attr("max_retries", retry_policy.max_retries.to_string()));
+        RETRY_POLICY.save(deps.storage, &retry_policy)?;
+    }
     {
         if let Some(source_port) = new_config.source_port {
             attrs.push(attr("source_port", &source_port));
@@ -171,7 +340,7 @@ pub fn execute_change_return_type(
     id: u64,
     return_type: ReturnType,
 ) -> ContractResult<Response<NeutronMsg>> {
-    let mut bond = BONDS.load(deps.storage, id)?;
+    let mut bond = bonds_map().load(deps.storage, id)?;
     ensure!(
         bond.state == BondState::Bonded,
         ContractError::WrongBondState {
@@ -183,7 +352,7 @@ pub fn execute_change_return_type(
 
     ensure_eq!(info.sender, backup, ContractError::Unauthorized);
     bond.return_type = return_type.clone();
-    BONDS.save(deps.storage, id, &bond)?;
+    bonds_map().save(deps.storage, id, &bond)?;
     let attrs = vec![
         attr("action", "change_return_type"),
         attr("id", id.to_string()),
@@ -202,14 +371,17 @@ pub fn execute_update_bond(
     return_type: ReturnType,
 ) -> ContractResult<Response<NeutronMsg>> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    let mut bond = BONDS.load(deps.storage, id)?;
+    let mut bond = bonds_map().load(deps.storage, id)?;
     bond.receiver = receiver.clone();
     bond.backup = backup
         .clone()
         .map(|a| deps.api.addr_validate(&a))
         .transpose()?;
     bond.return_type = return_type.clone();
-    BONDS.save(deps.storage, id, &bond)?;
+    // a corrected bond starts over without waiting for the backoff
+    bond.retries = 0;
+    bond.retry_at = None;
+    bonds_map().save(deps.storage, id, &bond)?;
     let attrs = vec![
         attr("action", "update_bond"),
         attr("id", id.to_string()),
@@ -241,11 +413,12 @@ pub fn execute_bond(
         .as_ref()
         .map(|addr| deps.api.addr_validate(addr))
         .transpose()?;
-    ensure!(receiver.starts_with(&prefix), ContractError::InvalidPrefix);
-    bech32::decode(&receiver).map_err(|_| ContractError::WrongReceiverAddress)?;
+    validate_receiver(&receiver, &prefix)?;
     let coin = cw_utils::one_coin(&info)?;
     let counter = COUNTER.load(deps.storage)?;
     let id = counter + 1;
+    // the id has to fit into the lower bits of reply ids
+    ensure!(id <= u32::MAX as u64, ContractError::IdOverflow { id });
     COUNTER.save(deps.storage, &id)?;
     let attrs = vec![
         attr("action", "bond"),
@@ -257,7 +430,7 @@ pub fn execute_bond(
             backup.as_ref().map(|x| x.to_string()).unwrap_or_default(),
         ),
     ];
-    BONDS.save(
+    bonds_map().save(
         deps.storage,
         id,
         &BondItem {
@@ -267,6 +440,8 @@ pub fn execute_bond(
             amount: coin.amount,
             return_type: drop_staking_base::state::mirror::ReturnType::default(),
             state: drop_staking_base::state::mirror::BondState::default(),
+            retries: 0,
+            retry_at: None,
         },
     )?;
     let msg = SubMsg::reply_on_success(
@@ -278,27 +453,167 @@ pub fn execute_bond(
             })?,
             funds: vec![coin],
         },
-        id,
+        ReplyMsg::Bond { id }.to_reply_id(),
     );
     Ok(response("bond", CONTRACT_NAME, attrs).add_submessage(msg))
 }
 
-pub fn execute_complete(
+fn validate_receiver(receiver: &str, prefix: &str) -> ContractResult<()> {
+    ensure!(receiver.starts_with(prefix), ContractError::InvalidPrefix);
+    bech32::decode(receiver).map_err(|_| ContractError::WrongReceiverAddress)?;
+    Ok(())
+}
+
+/// Unbonds dAsset sent from the remote chain, the withdrawal voucher stays with
+/// the mirror until the unbonding is released and the base asset can be returned
+pub fn execute_unbond(
     deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    receiver: String,
+    backup: Option<String>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let Config {
+        core_contract,
+        prefix,
+        ..
+    } = CONFIG.load(deps.storage)?;
+    let backup = backup
+        .as_ref()
+        .map(|addr| deps.api.addr_validate(addr))
+        .transpose()?;
+    validate_receiver(&receiver, &prefix)?;
+    let coin = cw_utils::one_coin(&info)?;
+    let id = UNBOND_COUNTER.may_load(deps.storage)?.unwrap_or_default() + 1;
+    // the id has to fit into the lower bits of reply ids
+    ensure!(id <= u32::MAX as u64, ContractError::IdOverflow { id });
+    UNBOND_COUNTER.save(deps.storage, &id)?;
+    let attrs = vec![
+        attr("action", "unbond"),
+        attr("id", id.to_string()),
+        attr("receiver", receiver.to_string()),
+        attr("amount", coin.to_string()),
+        attr(
+            "backup",
+            backup.as_ref().map(|x| x.to_string()).unwrap_or_default(),
+        ),
+    ];
+    unbonds_map().save(
+        deps.storage,
+        id,
+        &UnbondItem {
+            receiver,
+            backup,
+            amount: coin.amount,
+            token_id: None,
+            received: None,
+            return_type: ReturnType::default(),
+            state: UnbondState::default(),
+            retries: 0,
+            retry_at: None,
+        },
+    )?;
+    let msg = SubMsg::reply_on_success(
+        WasmMsg::Execute {
+            contract_addr: core_contract,
+            msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::Unbond {})?,
+            funds: vec![coin],
+        },
+        ReplyMsg::Unbond { id }.to_reply_id(),
+    );
+    Ok(response("unbond", CONTRACT_NAME, attrs).add_submessage(msg))
+}
+
+/// Permissionless, fails as a whole if any of the unbondings is not released yet
+pub fn execute_withdraw(
+    deps: DepsMut<NeutronQuery>,
+    items: Vec<u64>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let Config { core_contract, .. } = CONFIG.load(deps.storage)?;
+    let core_config: drop_staking_base::state::core::Config = deps.querier.query_wasm_smart(
+        &core_contract,
+        &drop_staking_base::msg::core::QueryMsg::Config {},
+    )?;
+    let addrs = drop_helpers::get_contracts!(
+        deps,
+        core_config.factory_contract,
+        withdrawal_voucher_contract,
+        withdrawal_manager_contract
+    );
+    let mut msgs = vec![];
+    let mut attrs = vec![attr("action", "withdraw")];
+    for id in items {
+        let unbond = unbonds_map().load(deps.storage, id)?;
+        ensure_eq!(
+            unbond.state,
+            UnbondState::Unbonding,
+            ContractError::WrongBondState {
+                expected: UnbondState::Unbonding.to_string(),
+                got: unbond.state.to_string(),
+            }
+        );
+        let token_id = unbond.token_id.unwrap(); // at this point unwrap is safe as the voucher is minted already
+        attrs.push(attr("id", id.to_string()));
+        attrs.push(attr("token_id", &token_id));
+        msgs.push(SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: addrs.withdrawal_voucher_contract.to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::SendNft {
+                        contract: addrs.withdrawal_manager_contract.to_string(),
+                        token_id,
+                        msg: to_json_binary(
+                            &drop_staking_base::msg::withdrawal_manager::ReceiveNftMsg::Withdraw {
+                                receiver: None,
+                            },
+                        )?,
+                    },
+                )?,
+                funds: vec![],
+            },
+            ReplyMsg::Withdraw { id }.to_reply_id(),
+        ));
+    }
+    Ok(response("withdraw", CONTRACT_NAME, attrs).add_submessages(msgs))
+}
+
+pub fn execute_change_unbond_return_type(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    id: u64,
+    return_type: ReturnType,
+) -> ContractResult<Response<NeutronMsg>> {
+    let mut unbond = unbonds_map().load(deps.storage, id)?;
+    ensure!(
+        unbond.state == UnbondState::Unbonding || unbond.state == UnbondState::Withdrawn,
+        ContractError::WrongBondState {
+            expected: UnbondState::Unbonding.to_string(),
+            got: unbond.state.to_string(),
+        }
+    );
+    let backup = unbond.backup.clone().ok_or(ContractError::BackupIsNotSet)?;
+
+    ensure_eq!(info.sender, backup, ContractError::Unauthorized);
+    unbond.return_type = return_type.clone();
+    unbonds_map().save(deps.storage, id, &unbond)?;
+    let attrs = vec![
+        attr("action", "change_unbond_return_type"),
+        attr("id", id.to_string()),
+        attr("return_type", return_type.to_string()),
+    ];
+    Ok(response("change_unbond_return_type", CONTRACT_NAME, attrs))
+}
+
+pub fn execute_complete(
+    mut deps: DepsMut<NeutronQuery>,
     env: Env,
     _info: MessageInfo,
     items: Vec<u64>,
 ) -> ContractResult<Response<NeutronMsg>> {
     let mut msgs = vec![];
     let mut attrs = vec![attr("action", "complete")];
-    let Config {
-        source_port,
-        source_channel,
-        ibc_timeout,
-        ..
-    } = CONFIG.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
     for id in items {
-        let mut bond = BONDS.load(deps.storage, id)?;
+        let bond = bonds_map().load(deps.storage, id)?;
         attrs.push(attr("id", id.to_string()));
         attrs.push(attr("return_type", bond.state.to_string()));
         attrs.push(attr("coin", bond.received.clone().unwrap().to_string())); // at this point unwrap is safe as bond is finalized already
@@ -310,55 +625,176 @@ pub fn execute_complete(
                 got: bond.state.to_string(),
             }
         );
-        match bond.return_type {
-            ReturnType::Remote => {
-                bond.state = BondState::Sent;
-                BONDS.save(deps.storage, id, &bond)?;
-                msgs.push(CosmosMsg::Custom(NeutronMsg::IbcTransfer {
-                    source_port: source_port.clone(),
-                    source_channel: source_channel.clone(),
-                    token: bond.received.unwrap(), // at this point unwrap is safe as bond is finalized already
-                    sender: env.contract.address.to_string(),
-                    receiver: bond.receiver,
-                    timeout_height: RequestPacketTimeoutHeight {
-                        revision_number: None,
-                        revision_height: None,
-                    },
-                    timeout_timestamp: env.block.time.plus_seconds(ibc_timeout).nanos(),
-                    memo: id.to_string(),
-                    fee: query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?,
-                }));
-            }
-            ReturnType::Local => {
-                if let Some(backup) = bond.backup {
-                    BONDS.remove(deps.storage, id);
-                    msgs.push(CosmosMsg::Bank(BankMsg::Send {
-                        to_address: backup.to_string(),
-                        amount: vec![bond.received.unwrap()],
-                    }));
-                }
-            }
+        if let Some(retry_at) = bond.retry_at {
+            ensure!(
+                env.block.time.seconds() >= retry_at,
+                ContractError::RetryTooEarly { id, retry_at }
+            );
         }
+        msgs.extend(get_return_msg(deps.branch(), &env, &config, id, bond)?);
     }
     Ok(response("complete", CONTRACT_NAME, attrs).add_messages(msgs))
 }
 
+/// Permissionless, so anyone (e.g. a keeper bot) can push failed bonds and
+/// unbonds out once their backoff has passed
+pub fn execute_retry_failed(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    limit: Option<u32>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let due = bonds_map()
+        .idx
+        .failed
+        .prefix(1)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, bond)| bond.retry_at.unwrap_or_default() <= now)
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let due_unbonds = unbonds_map()
+        .idx
+        .failed
+        .prefix(1)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, unbond)| {
+                unbond.retry_at.unwrap_or_default() <= now
+            })
+        })
+        .take(limit.saturating_sub(due.len()))
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut msgs = vec![];
+    let mut attrs = vec![attr("action", "retry_failed")];
+    for (id, bond) in due {
+        attrs.push(attr("id", id.to_string()));
+        attrs.push(attr("retries", bond.retries.to_string()));
+        msgs.extend(get_return_msg(deps.branch(), &env, &config, id, bond)?);
+    }
+    for (id, unbond) in due_unbonds {
+        attrs.push(attr("unbond_id", id.to_string()));
+        attrs.push(attr("retries", unbond.retries.to_string()));
+        msgs.extend(get_unbond_return_msg(
+            deps.branch(),
+            &env,
+            &config,
+            id,
+            unbond,
+        )?);
+    }
+    Ok(response("retry_failed", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+fn get_return_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    id: u64,
+    mut bond: BondItem,
+) -> ContractResult<Option<CosmosMsg<NeutronMsg>>> {
+    match bond.return_type {
+        ReturnType::Remote => {
+            let token = bond.received.clone().unwrap(); // at this point unwrap is safe as bond is finalized already
+            let receiver = bond.receiver.to_string();
+            bond.state = BondState::Sent;
+            bonds_map().save(deps.storage, id, &bond)?;
+            Ok(Some(CosmosMsg::Custom(NeutronMsg::IbcTransfer {
+                source_port: config.source_port.clone(),
+                source_channel: config.source_channel.clone(),
+                token,
+                sender: env.contract.address.to_string(),
+                receiver,
+                timeout_height: RequestPacketTimeoutHeight {
+                    revision_number: None,
+                    revision_height: None,
+                },
+                timeout_timestamp: env.block.time.plus_seconds(config.ibc_timeout).nanos(),
+                memo: id.to_string(),
+                fee: query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?,
+            })))
+        }
+        ReturnType::Local => match bond.backup {
+            Some(backup) => {
+                bonds_map().remove(deps.storage, id)?;
+                Ok(Some(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: backup.to_string(),
+                    amount: vec![bond.received.unwrap()],
+                })))
+            }
+            None => Ok(None),
+        },
+    }
+}
+
+fn get_unbond_return_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    id: u64,
+    mut unbond: UnbondItem,
+) -> ContractResult<Option<CosmosMsg<NeutronMsg>>> {
+    let token = unbond.received.clone().unwrap(); // at this point unwrap is safe as voucher is withdrawn already
+    match (&unbond.return_type, &unbond.backup) {
+        (ReturnType::Remote, _) => {
+            let receiver = unbond.receiver.to_string();
+            unbond.state = UnbondState::Sent;
+            unbonds_map().save(deps.storage, id, &unbond)?;
+            Ok(Some(CosmosMsg::Custom(NeutronMsg::IbcTransfer {
+                source_port: config.source_port.clone(),
+                source_channel: config.source_channel.clone(),
+                token,
+                sender: env.contract.address.to_string(),
+                receiver,
+                timeout_height: RequestPacketTimeoutHeight {
+                    revision_number: None,
+                    revision_height: None,
+                },
+                timeout_timestamp: env.block.time.plus_seconds(config.ibc_timeout).nanos(),
+                memo: format!("{UNBOND_MEMO_PREFIX}{id}"),
+                fee: query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?,
+            })))
+        }
+        (ReturnType::Local, Some(backup)) => {
+            let to_address = backup.to_string();
+            unbonds_map().remove(deps.storage, id)?;
+            Ok(Some(CosmosMsg::Bank(BankMsg::Send {
+                to_address,
+                amount: vec![token],
+            })))
+        }
+        (ReturnType::Local, None) => {
+            unbonds_map().save(deps.storage, id, &unbond)?;
+            Ok(None)
+        }
+    }
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn reply(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     msg: Reply,
 ) -> ContractResult<Response<NeutronMsg>> {
-    finalize_bond(deps, msg)
+    match ReplyMsg::from_reply_id(msg.id) {
+        ReplyMsg::Bond { id } => finalize_bond(deps, id, msg.result),
+        ReplyMsg::Unbond { id } => finalize_unbond(deps, id, msg.result),
+        ReplyMsg::Withdraw { id } => finalize_withdraw(deps, env, id, msg.result),
+    }
 }
 
 pub fn finalize_bond(
     deps: DepsMut<NeutronQuery>,
-    msg: Reply,
+    id: u64,
+    result: SubMsgResult,
 ) -> ContractResult<Response<NeutronMsg>> {
-    match msg.result {
+    match result {
         cosmwasm_std::SubMsgResult::Ok(res) => {
-            let mut bond = BONDS.load(deps.storage, msg.id)?;
+            let mut bond = bonds_map().load(deps.storage, id)?;
             bond.state = BondState::Bonded;
             // get token factory mint event
             let tf_mint_event = res
@@ -376,10 +812,10 @@ pub fn finalize_bond(
                     .value,
             )?;
             bond.received = Some(coin);
-            BONDS.save(deps.storage, msg.id, &bond)?;
+            bonds_map().save(deps.storage, id, &bond)?;
             let attrs = vec![
                 attr("action", "finalize_bond"),
-                attr("id", msg.id.to_string()),
+                attr("id", id.to_string()),
                 attr("state", bond.state.to_string()),
             ];
             Ok(response("finalize_bond", CONTRACT_NAME, attrs))
@@ -388,56 +824,199 @@ pub fn finalize_bond(
     }
 }
 
+pub fn finalize_unbond(
+    deps: DepsMut<NeutronQuery>,
+    id: u64,
+    result: SubMsgResult,
+) -> ContractResult<Response<NeutronMsg>> {
+    let res = result.unwrap(); // as there is only SubMsg::reply_on_success()
+    let mut unbond = unbonds_map().load(deps.storage, id)?;
+    // get withdrawal voucher mint event
+    let token_id = res
+        .events
+        .iter()
+        .filter(|x| x.ty == "wasm")
+        .filter(|x| {
+            x.attributes
+                .iter()
+                .any(|a| a.key == "action" && a.value == "mint")
+        })
+        .find_map(|x| x.attributes.iter().find(|a| a.key == "token_id"))
+        .ok_or(ContractError::NoVoucherMinted)?
+        .value
+        .to_string();
+    unbond.token_id = Some(token_id.to_string());
+    unbond.state = UnbondState::Unbonding;
+    unbonds_map().save(deps.storage, id, &unbond)?;
+    let attrs = vec![
+        attr("action", "finalize_unbond"),
+        attr("id", id.to_string()),
+        attr("token_id", token_id),
+        attr("state", unbond.state.to_string()),
+    ];
+    Ok(response("finalize_unbond", CONTRACT_NAME, attrs))
+}
+
+pub fn finalize_withdraw(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    id: u64,
+    result: SubMsgResult,
+) -> ContractResult<Response<NeutronMsg>> {
+    let res = result.unwrap(); // as there is only SubMsg::reply_on_success()
+    let config = CONFIG.load(deps.storage)?;
+    let mut unbond = unbonds_map().load(deps.storage, id)?;
+    let received = get_received_coin(&res, env.contract.address.as_str())?;
+    unbond.received = Some(received.clone());
+    unbond.state = UnbondState::Withdrawn;
+    let attrs = vec![
+        attr("action", "finalize_withdraw"),
+        attr("id", id.to_string()),
+        attr("coin", received.to_string()),
+        attr("return_type", unbond.return_type.to_string()),
+    ];
+    let msgs = get_unbond_return_msg(deps.branch(), &env, &config, id, unbond)?;
+    Ok(response("finalize_withdraw", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+// the withdrawal manager pays the voucher out to its sender, which is the mirror
+fn get_received_coin(res: &SubMsgResponse, recipient: &str) -> ContractResult<Coin> {
+    let amount = res
+        .events
+        .iter()
+        .filter(|x| x.ty == "transfer")
+        .filter(|x| {
+            x.attributes
+                .iter()
+                .any(|a| a.key == "recipient" && a.value == recipient)
+        })
+        .find_map(|x| x.attributes.iter().find(|a| a.key == "amount"))
+        .ok_or(ContractError::NothingWithdrawn)?;
+    Ok(Coin::from_str(&amount.value)?)
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn sudo(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     msg: TransferSudoMsg,
 ) -> ContractResult<Response<NeutronMsg>> {
     match msg {
         TransferSudoMsg::Response { request, data } => sudo_response(deps, request, data),
-        TransferSudoMsg::Error { request, details } => sudo_error(deps, request, details),
-        TransferSudoMsg::Timeout { request } => sudo_timeout(deps, request),
+        TransferSudoMsg::Error { request, details } => sudo_error(deps, env, request, details),
+        TransferSudoMsg::Timeout { request } => sudo_timeout(deps, env, request),
     }
 }
 
 fn handle_sudo_error(
     err_type: &str,
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     req: RequestPacket,
 ) -> ContractResult<Response<NeutronMsg>> {
-    let id = get_id_from_request_memo(&req)?;
+    let transfer = get_transfer_from_request_memo(&req)?;
     deps.api.debug(
         format!(
-            "WASMDEBUG: {}: ack received: {:?} id: {:?}",
-            err_type, req, id
+            "WASMDEBUG: {}: ack received: {:?} transfer: {:?}",
+            err_type, req, transfer
         )
         .as_str(),
     );
-    let mut bond = BONDS.load(deps.storage, id)?;
+    let retry_policy = load_retry_policy(deps.as_ref())?;
+    let id = match transfer {
+        ReturnTransfer::Bond(id) => id,
+        ReturnTransfer::Unbond(id) => {
+            return handle_unbond_return_error(err_type, deps, env, id, retry_policy)
+        }
+    };
+    let mut bond = bonds_map().load(deps.storage, id)?;
     bond.state = BondState::Bonded;
-    BONDS.save(deps.storage, id, &bond)?;
-    let attrs = vec![
+    bond.retries += 1;
+    let mut attrs = vec![
         attr("action", err_type),
         attr("id", id.to_string()),
-        attr("state", bond.state.to_string()),
+        attr("retries", bond.retries.to_string()),
     ];
+    if bond.retries >= retry_policy.max_retries {
+        if let Some(backup) = bond.backup.clone() {
+            // the receiver seems unreachable, the refunded tokens go to the backup address
+            bonds_map().remove(deps.storage, id)?;
+            attrs.push(attr("fallback", backup.to_string()));
+            return Ok(
+                response(err_type, CONTRACT_NAME, attrs).add_message(BankMsg::Send {
+                    to_address: backup.to_string(),
+                    amount: vec![bond.received.unwrap()], // at this point unwrap is safe as bond is finalized already
+                }),
+            );
+        }
+    }
+    let retry_at = env
+        .block
+        .time
+        .seconds()
+        .saturating_add(retry_policy.retry_delay(bond.retries));
+    bond.retry_at = Some(retry_at);
+    bonds_map().save(deps.storage, id, &bond)?;
+    attrs.push(attr("state", bond.state.to_string()));
+    attrs.push(attr("retry_at", retry_at.to_string()));
+    Ok(response(err_type, CONTRACT_NAME, attrs))
+}
+
+fn handle_unbond_return_error(
+    err_type: &str,
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    id: u64,
+    retry_policy: RetryPolicy,
+) -> ContractResult<Response<NeutronMsg>> {
+    let mut unbond = unbonds_map().load(deps.storage, id)?;
+    unbond.state = UnbondState::Withdrawn;
+    unbond.retries += 1;
+    let mut attrs = vec![
+        attr("action", err_type),
+        attr("unbond_id", id.to_string()),
+        attr("retries", unbond.retries.to_string()),
+    ];
+    if unbond.retries >= retry_policy.max_retries {
+        if let Some(backup) = unbond.backup.clone() {
+            // the receiver seems unreachable, the refunded tokens go to the backup address
+            unbonds_map().remove(deps.storage, id)?;
+            attrs.push(attr("fallback", backup.to_string()));
+            return Ok(
+                response(err_type, CONTRACT_NAME, attrs).add_message(BankMsg::Send {
+                    to_address: backup.to_string(),
+                    amount: vec![unbond.received.unwrap()], // at this point unwrap is safe as voucher is withdrawn already
+                }),
+            );
+        }
+    }
+    let retry_at = env
+        .block
+        .time
+        .seconds()
+        .saturating_add(retry_policy.retry_delay(unbond.retries));
+    unbond.retry_at = Some(retry_at);
+    unbonds_map().save(deps.storage, id, &unbond)?;
+    attrs.push(attr("state", unbond.state.to_string()));
+    attrs.push(attr("retry_at", retry_at.to_string()));
     Ok(response(err_type, CONTRACT_NAME, attrs))
 }
 
 fn sudo_error(
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     req: RequestPacket,
     _data: String,
 ) -> ContractResult<Response<NeutronMsg>> {
-    handle_sudo_error("sudo_error", deps, req)
+    handle_sudo_error("sudo_error", deps, env, req)
 }
 
 fn sudo_timeout(
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     req: RequestPacket,
 ) -> ContractResult<Response<NeutronMsg>> {
-    handle_sudo_error("sudo_timeout", deps, req)
+    handle_sudo_error("sudo_timeout", deps, env, req)
 }
 
 fn sudo_response(
@@ -446,19 +1025,28 @@ fn sudo_response(
     data: Binary,
 ) -> ContractResult<Response<NeutronMsg>> {
     let request_data: FungibleTokenPacketData = from_json(req.data.clone().unwrap())?; // must present as there is only IBC transfer for this contract
-    let id: u64 = get_id_from_request_memo(&req)?;
+    let transfer = get_transfer_from_request_memo(&req)?;
     deps.api.debug(
         format!(
-            "WASMDEBUG: sudo_response: sudo received: {:?} {:?} {:?} id: {:?}",
-            req, data, request_data, id
+            "WASMDEBUG: sudo_response: sudo received: {:?} {:?} {:?} transfer: {:?}",
+            req, data, request_data, transfer
         )
         .as_str(),
     );
-    BONDS.remove(deps.storage, id);
+    let id_attr = match transfer {
+        ReturnTransfer::Bond(id) => {
+            bonds_map().remove(deps.storage, id)?;
+            attr("id", id.to_string())
+        }
+        ReturnTransfer::Unbond(id) => {
+            unbonds_map().remove(deps.storage, id)?;
+            attr("unbond_id", id.to_string())
+        }
+    };
     let attrs = vec![
         attr("action", "sudo_response"),
         attr("request", format!("{:?}", req)),
-        attr("id", id.to_string()),
+        id_attr,
     ];
     Ok(response("sudo_response", CONTRACT_NAME, attrs))
 }
@@ -474,12 +1062,36 @@ pub fn migrate(
         cw2::get_contract_version(deps.storage)?.version.parse()?;
     if storage_version < version {
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        // bonds stored before the indexes existed have to be indexed
+        let bonds = bonds_map()
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (id, bond) in bonds {
+            bonds_map().replace(deps.storage, id, Some(&bond), None)?;
+        }
+        if UNBOND_COUNTER.may_load(deps.storage)?.is_none() {
+            UNBOND_COUNTER.save(deps.storage, &0)?;
+        }
+        if RETRY_POLICY.may_load(deps.storage)?.is_none() {
+            RETRY_POLICY.save(deps.storage, &RetryPolicy::default())?;
+        }
     }
     Ok(Response::new())
 }
 
-fn get_id_from_request_memo(req: &RequestPacket) -> ContractResult<u64> {
+#[derive(Debug)]
+enum ReturnTransfer {
+    Bond(u64),
+    Unbond(u64),
+}
+
+fn get_transfer_from_request_memo(req: &RequestPacket) -> ContractResult<ReturnTransfer> {
     let request_data: FungibleTokenPacketData = from_json(req.data.clone().unwrap())?; // must present as there is only IBC transfer for this contract
-    let id: u64 = request_data.memo.parse().unwrap(); // can be safelly unwrapped as it is serialized in execute_complete
-    Ok(id)
+
+    // memos can be safely unwrapped as they are serialized by the contract itself
+    let transfer = match request_data.memo.strip_prefix(UNBOND_MEMO_PREFIX) {
+        Some(id) => ReturnTransfer::Unbond(id.parse().unwrap()),
+        None => ReturnTransfer::Bond(request_data.memo.parse().unwrap()),
+    };
+    Ok(transfer)
 }
@@ -1,18 +1,18 @@
 use cosmwasm_std::{
-    attr, coin,
+    attr, coin, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, CosmosMsg, CustomQuery, DepsMut, IbcChannel, IbcEndpoint, IbcOrder,
-    Uint128, WasmMsg,
+    to_json_binary, Addr, CosmosMsg, CustomQuery, DepsMut, Event, IbcChannel, IbcEndpoint,
+    IbcOrder, Reply, SubMsg, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
 };
-use drop_helpers::testing::mock_dependencies;
+use drop_helpers::testing::{mock_dependencies, mock_state_query};
 use drop_staking_base::{
     error::mirror::ContractError,
-    state::mirror::{Config, BONDS, CONFIG, COUNTER},
+    state::mirror::{bonds_map, unbonds_map, Config, ReplyMsg, CONFIG, COUNTER},
 };
 use neutron_sdk::{
     bindings::msg::{IbcFee, NeutronMsg},
     query::min_ibc_fee::MinIbcFeeResponse,
-    sudo::msg::RequestPacketTimeoutHeight,
+    sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, TransferSudoMsg},
 };
 
 fn base_init<T>(deps: DepsMut<T>)
@@ -45,6 +45,7 @@ fn test_instantiate() {
             ibc_timeout: 10,
             owner: Some("owner".to_string()),
             prefix: "prefix".to_string(),
+            retry_policy: None,
         },
     )
     .unwrap();
@@ -86,6 +87,7 @@ fn test_instantiate_wo_owner() {
             ibc_timeout: 10,
             owner: None,
             prefix: "prefix".to_string(),
+            retry_policy: None,
         },
     )
     .unwrap();
@@ -126,6 +128,7 @@ fn update_config_ibc_timeout_out_of_range() {
                 source_channel: Some("new_source_channel".to_string()),
                 ibc_timeout: Some(u64::MAX),
                 prefix: Some("new_prefix".to_string()),
+                retry_policy: None,
             },
         },
     )
@@ -148,6 +151,7 @@ fn update_config_source_channel_not_found() {
                 source_channel: Some("new_source_channel".to_string()),
                 ibc_timeout: Some(20),
                 prefix: Some("new_prefix".to_string()),
+                retry_policy: None,
             },
         },
     )
@@ -201,6 +205,7 @@ fn update_config() {
                 source_channel: Some("channel-0".to_string()),
                 ibc_timeout: Some(20),
                 prefix: Some("new_prefix".to_string()),
+                retry_policy: None,
             },
         },
     )
@@ -279,6 +284,34 @@ fn bond_no_funds() {
     );
 }
 
+#[test]
+fn bond_id_overflow() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    COUNTER
+        .save(deps.as_mut().storage, &(u32::MAX as u64))
+        .unwrap();
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &[coin(1000, "mytoken")]),
+        drop_staking_base::msg::mirror::ExecuteMsg::Bond {
+            receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j"
+                .to_string(),
+            r#ref: None,
+            backup: None,
+        },
+    );
+    assert_eq!(
+        response,
+        Err(
+            drop_staking_base::error::mirror::ContractError::IdOverflow {
+                id: u32::MAX as u64 + 1
+            }
+        )
+    );
+}
+
 #[test]
 fn bond() {
     let mut deps = mock_dependencies(&[]);
@@ -297,7 +330,7 @@ fn bond() {
     .unwrap();
     let counter = COUNTER.load(&deps.storage).unwrap();
     assert_eq!(counter, 1);
-    let bond = BONDS.load(&deps.storage, 1).unwrap();
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
     assert_eq!(
         bond,
         drop_staking_base::state::mirror::BondItem {
@@ -308,6 +341,8 @@ fn bond() {
             received: None,
             return_type: drop_staking_base::state::mirror::ReturnType::Remote,
             state: drop_staking_base::state::mirror::BondState::Initiated,
+            retries: 0,
+            retry_at: None,
         }
     );
     assert_eq!(
@@ -351,7 +386,7 @@ fn complete_remote() {
         .unwrap()
     });
     base_init(deps.as_mut());
-    BONDS
+    bonds_map()
         .save(
             deps.as_mut().storage,
             1,
@@ -363,6 +398,8 @@ fn complete_remote() {
                 received: Some(coin(1000, "ld_denom")),
                 return_type: drop_staking_base::state::mirror::ReturnType::Remote,
                 state: drop_staking_base::state::mirror::BondState::Bonded,
+                retries: 0,
+                retry_at: None,
             },
         )
         .unwrap();
@@ -373,7 +410,7 @@ fn complete_remote() {
         drop_staking_base::msg::mirror::ExecuteMsg::Complete { items: vec![1] },
     )
     .unwrap();
-    let bond = BONDS.load(&deps.storage, 1).unwrap();
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
     assert_eq!(
         bond,
         drop_staking_base::state::mirror::BondItem {
@@ -384,6 +421,8 @@ fn complete_remote() {
             received: Some(coin(1000, "ld_denom")),
             return_type: drop_staking_base::state::mirror::ReturnType::Remote,
             state: drop_staking_base::state::mirror::BondState::Sent,
+            retries: 0,
+            retry_at: None,
         }
     );
     assert_eq!(
@@ -430,7 +469,7 @@ fn complete_local() {
         .unwrap()
     });
     base_init(deps.as_mut());
-    BONDS
+    bonds_map()
         .save(
             deps.as_mut().storage,
             1,
@@ -442,6 +481,8 @@ fn complete_local() {
                 received: Some(coin(1000, "ld_denom")),
                 return_type: drop_staking_base::state::mirror::ReturnType::Local,
                 state: drop_staking_base::state::mirror::BondState::Bonded,
+                retries: 0,
+                retry_at: None,
             },
         )
         .unwrap();
@@ -452,7 +493,7 @@ fn complete_local() {
         drop_staking_base::msg::mirror::ExecuteMsg::Complete { items: vec![1] },
     )
     .unwrap();
-    let bond = BONDS.load(&deps.storage, 1);
+    let bond = bonds_map().load(&deps.storage, 1);
     assert!(bond.is_err());
     assert_eq!(
         response,
@@ -481,7 +522,7 @@ fn complete_local() {
 fn change_return_type() {
     let mut deps = mock_dependencies(&[]);
     base_init(deps.as_mut());
-    BONDS
+    bonds_map()
         .save(
             deps.as_mut().storage,
             1,
@@ -493,6 +534,8 @@ fn change_return_type() {
                 received: Some(coin(1000, "ld_denom")),
                 return_type: drop_staking_base::state::mirror::ReturnType::Remote,
                 state: drop_staking_base::state::mirror::BondState::Bonded,
+                retries: 0,
+                retry_at: None,
             },
         )
         .unwrap();
@@ -521,7 +564,7 @@ fn change_return_type() {
     )
     .unwrap();
 
-    let bond = BONDS.load(&deps.storage, 1).unwrap();
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
     assert_eq!(
         bond,
         drop_staking_base::state::mirror::BondItem {
@@ -532,6 +575,8 @@ fn change_return_type() {
             received: Some(coin(1000, "ld_denom")),
             return_type: drop_staking_base::state::mirror::ReturnType::Local,
             state: drop_staking_base::state::mirror::BondState::Bonded,
+            retries: 0,
+            retry_at: None,
         }
     );
     assert_eq!(
@@ -553,7 +598,7 @@ fn change_return_type() {
 fn update_bond() {
     let mut deps = mock_dependencies(&[]);
     base_init(deps.as_mut());
-    BONDS
+    bonds_map()
         .save(
             deps.as_mut().storage,
             1,
@@ -564,6 +609,8 @@ fn update_bond() {
                 received: None,
                 return_type: drop_staking_base::state::mirror::ReturnType::Remote,
                 state: drop_staking_base::state::mirror::BondState::Initiated,
+                retries: 0,
+                retry_at: None,
             },
         )
         .unwrap();
@@ -600,7 +647,7 @@ fn update_bond() {
     )
     .unwrap();
 
-    let bond = BONDS.load(&deps.storage, 1).unwrap();
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
     assert_eq!(
         bond,
         drop_staking_base::state::mirror::BondItem {
@@ -610,6 +657,8 @@ fn update_bond() {
             received: None,
             return_type: drop_staking_base::state::mirror::ReturnType::Local,
             state: drop_staking_base::state::mirror::BondState::Initiated,
+            retries: 0,
+            retry_at: None,
         }
     );
     assert_eq!(
@@ -633,7 +682,7 @@ fn update_bond() {
 fn query_one() {
     let mut deps = mock_dependencies(&[]);
     base_init(deps.as_mut());
-    BONDS
+    bonds_map()
         .save(
             deps.as_mut().storage,
             1,
@@ -645,6 +694,8 @@ fn query_one() {
                 received: None,
                 return_type: drop_staking_base::state::mirror::ReturnType::Remote,
                 state: drop_staking_base::state::mirror::BondState::Initiated,
+                retries: 0,
+                retry_at: None,
             },
         )
         .unwrap();
@@ -664,6 +715,8 @@ fn query_one() {
             received: None,
             return_type: drop_staking_base::state::mirror::ReturnType::Remote,
             state: drop_staking_base::state::mirror::BondState::Initiated,
+            retries: 0,
+            retry_at: None,
         })
         .unwrap()
     );
@@ -673,7 +726,7 @@ fn query_one() {
 fn query_all() {
     let mut deps = mock_dependencies(&[]);
     base_init(deps.as_mut());
-    BONDS
+    bonds_map()
         .save(
             deps.as_mut().storage,
             1,
@@ -685,6 +738,8 @@ fn query_all() {
                 received: None,
                 return_type: drop_staking_base::state::mirror::ReturnType::Remote,
                 state: drop_staking_base::state::mirror::BondState::Initiated,
+                retries: 0,
+                retry_at: None,
             },
         )
         .unwrap();
@@ -709,12 +764,672 @@ fn query_all() {
                 received: None,
                 return_type: drop_staking_base::state::mirror::ReturnType::Remote,
                 state: drop_staking_base::state::mirror::BondState::Initiated,
+                retries: 0,
+                retry_at: None,
             }
         )])
         .unwrap()
     );
 }
 
+fn sent_bond(backup: Option<&str>, retries: u32) -> drop_staking_base::state::mirror::BondItem {
+    drop_staking_base::state::mirror::BondItem {
+        receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j".to_string(),
+        backup: backup.map(Addr::unchecked),
+        amount: Uint128::new(1000),
+        received: Some(coin(1000, "ld_denom")),
+        return_type: drop_staking_base::state::mirror::ReturnType::Remote,
+        state: drop_staking_base::state::mirror::BondState::Sent,
+        retries,
+        retry_at: None,
+    }
+}
+
+fn transfer_request(memo: &str) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(1),
+        source_port: Some("source_port".to_string()),
+        source_channel: Some("source_channel".to_string()),
+        destination_port: None,
+        destination_channel: None,
+        data: Some(
+            to_json_binary(&drop_staking_base::msg::mirror::FungibleTokenPacketData {
+                denom: "ld_denom".to_string(),
+                amount: "1000".to_string(),
+                sender: "cosmos2contract".to_string(),
+                receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j"
+                    .to_string(),
+                memo: memo.to_string(),
+            })
+            .unwrap(),
+        ),
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+#[test]
+fn sudo_timeout_schedules_retry() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    bonds_map()
+        .save(deps.as_mut().storage, 1, &sent_bond(None, 0))
+        .unwrap();
+    let response = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Timeout {
+            request: transfer_request("1"),
+        },
+    )
+    .unwrap();
+    let retry_at = mock_env().block.time.seconds() + 60;
+    assert_eq!(
+        response,
+        cosmwasm_std::Response::new().add_event(
+            cosmwasm_std::Event::new("crates.io:drop-staking__drop-mirror-sudo_timeout")
+                .add_attributes(vec![
+                    attr("action", "sudo_timeout"),
+                    attr("id", "1"),
+                    attr("retries", "1"),
+                    attr("state", "Bonded"),
+                    attr("retry_at", retry_at.to_string()),
+                ])
+        )
+    );
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        bond.state,
+        drop_staking_base::state::mirror::BondState::Bonded
+    );
+    assert_eq!(bond.retries, 1);
+    assert_eq!(bond.retry_at, Some(retry_at));
+
+    let failed: Vec<(u64, drop_staking_base::state::mirror::BondItem)> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            drop_staking_base::msg::mirror::QueryMsg::FailedBonds {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(failed, vec![(1, bond)]);
+}
+
+#[test]
+fn sudo_error_backoff_grows() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    bonds_map()
+        .save(deps.as_mut().storage, 1, &sent_bond(None, 2))
+        .unwrap();
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Error {
+            request: transfer_request("1"),
+            details: "error".to_string(),
+        },
+    )
+    .unwrap();
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
+    assert_eq!(bond.retries, 3);
+    assert_eq!(bond.retry_at, Some(mock_env().block.time.seconds() + 240));
+}
+
+#[test]
+fn complete_before_backoff() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let retry_at = mock_env().block.time.seconds() + 60;
+    let mut bond = sent_bond(None, 1);
+    bond.state = drop_staking_base::state::mirror::BondState::Bonded;
+    bond.retry_at = Some(retry_at);
+    bonds_map().save(deps.as_mut().storage, 1, &bond).unwrap();
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::mirror::ExecuteMsg::Complete { items: vec![1] },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::RetryTooEarly { id: 1, retry_at });
+}
+
+#[test]
+fn retry_failed() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(deps.as_mut());
+    let mut env = mock_env();
+    let mut due = sent_bond(None, 1);
+    due.state = drop_staking_base::state::mirror::BondState::Bonded;
+    due.retry_at = Some(env.block.time.seconds() + 60);
+    bonds_map().save(deps.as_mut().storage, 1, &due).unwrap();
+    let mut not_due = due.clone();
+    not_due.retry_at = Some(env.block.time.seconds() + 120);
+    bonds_map()
+        .save(deps.as_mut().storage, 2, &not_due)
+        .unwrap();
+    bonds_map()
+        .save(deps.as_mut().storage, 3, &sent_bond(None, 0))
+        .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(60);
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::mirror::ExecuteMsg::RetryFailed { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        cosmwasm_std::Response::new()
+            .add_message(CosmosMsg::Custom(NeutronMsg::IbcTransfer {
+                source_port: "source_port".to_string(),
+                source_channel: "source_channel".to_string(),
+                token: coin(1000, "ld_denom"),
+                sender: "cosmos2contract".to_string(),
+                receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j"
+                    .to_string(),
+                timeout_height: RequestPacketTimeoutHeight {
+                    revision_number: None,
+                    revision_height: None
+                },
+                timeout_timestamp: env.block.time.plus_seconds(10).nanos(),
+                memo: "1".to_string(),
+                fee: get_standard_fees(),
+            }))
+            .add_event(
+                cosmwasm_std::Event::new("crates.io:drop-staking__drop-mirror-retry_failed")
+                    .add_attributes(vec![
+                        attr("action", "retry_failed"),
+                        attr("id", "1"),
+                        attr("retries", "1"),
+                    ])
+            )
+    );
+    let bond = bonds_map().load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        bond.state,
+        drop_staking_base::state::mirror::BondState::Sent
+    );
+    // retries are kept, so the next failure backs off further
+    assert_eq!(bond.retries, 1);
+}
+
+#[test]
+fn retry_failed_default_limit() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let mut failed = sent_bond(Some("backup"), 1);
+    failed.state = drop_staking_base::state::mirror::BondState::Bonded;
+    failed.return_type = drop_staking_base::state::mirror::ReturnType::Local;
+    for id in 1..=31 {
+        bonds_map()
+            .save(deps.as_mut().storage, id, &failed)
+            .unwrap();
+    }
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::mirror::ExecuteMsg::RetryFailed { limit: None },
+    )
+    .unwrap();
+    assert_eq!(response.messages.len(), 30);
+    assert!(!bonds_map().has(&deps.storage, 30));
+    assert!(bonds_map().has(&deps.storage, 31));
+}
+
+#[test]
+fn retry_failed_skips_local_without_backup() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let mut stuck = sent_bond(None, 1);
+    stuck.state = drop_staking_base::state::mirror::BondState::Bonded;
+    stuck.return_type = drop_staking_base::state::mirror::ReturnType::Local;
+    bonds_map().save(deps.as_mut().storage, 1, &stuck).unwrap();
+    let mut failed = stuck.clone();
+    failed.backup = Some(Addr::unchecked("backup"));
+    bonds_map().save(deps.as_mut().storage, 2, &failed).unwrap();
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::mirror::ExecuteMsg::RetryFailed { limit: Some(1) },
+    )
+    .unwrap();
+    // the bond which can't be returned doesn't take the only slot
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(cosmwasm_std::BankMsg::Send {
+            to_address: "backup".to_string(),
+            amount: vec![coin(1000, "ld_denom")],
+        })]
+    );
+    assert_eq!(bonds_map().load(&deps.storage, 1).unwrap(), stuck);
+    let failed_ids = bonds_map()
+        .idx
+        .failed
+        .prefix(1)
+        .keys(&deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<cosmwasm_std::StdResult<Vec<_>>>()
+        .unwrap();
+    assert!(failed_ids.is_empty());
+}
+
+#[test]
+fn sudo_timeout_falls_back_to_backup() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    bonds_map()
+        .save(deps.as_mut().storage, 1, &sent_bond(Some("backup"), 4))
+        .unwrap();
+    let response = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Timeout {
+            request: transfer_request("1"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        cosmwasm_std::Response::new()
+            .add_message(cosmwasm_std::BankMsg::Send {
+                to_address: "backup".to_string(),
+                amount: vec![coin(1000, "ld_denom")],
+            })
+            .add_event(
+                cosmwasm_std::Event::new("crates.io:drop-staking__drop-mirror-sudo_timeout")
+                    .add_attributes(vec![
+                        attr("action", "sudo_timeout"),
+                        attr("id", "1"),
+                        attr("retries", "5"),
+                        attr("fallback", "backup"),
+                    ])
+            )
+    );
+    assert!(bonds_map().load(&deps.storage, 1).is_err());
+}
+
+#[test]
+fn query_bonds_by_receiver() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    bonds_map()
+        .save(deps.as_mut().storage, 1, &sent_bond(None, 0))
+        .unwrap();
+    let mut other = sent_bond(None, 0);
+    other.receiver = "prefix1other".to_string();
+    bonds_map().save(deps.as_mut().storage, 2, &other).unwrap();
+    bonds_map()
+        .save(deps.as_mut().storage, 3, &sent_bond(None, 0))
+        .unwrap();
+    let bonds: Vec<(u64, drop_staking_base::state::mirror::BondItem)> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            drop_staking_base::msg::mirror::QueryMsg::BondsByReceiver {
+                receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j"
+                    .to_string(),
+                start_after: Some(1),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(bonds, vec![(3, sent_bond(None, 0))]);
+}
+
+fn unbond_item(
+    state: drop_staking_base::state::mirror::UnbondState,
+) -> drop_staking_base::state::mirror::UnbondItem {
+    drop_staking_base::state::mirror::UnbondItem {
+        receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j".to_string(),
+        backup: Some(Addr::unchecked("backup")),
+        amount: Uint128::new(1000),
+        token_id: Some("0_cosmos2contract_1".to_string()),
+        received: None,
+        return_type: drop_staking_base::state::mirror::ReturnType::Remote,
+        state,
+        retries: 0,
+        retry_at: None,
+    }
+}
+
+#[test]
+fn unbond() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &[coin(1000, "ld_denom")]),
+        drop_staking_base::msg::mirror::ExecuteMsg::Unbond {
+            receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j"
+                .to_string(),
+            backup: Some("backup".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: "core".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::Unbond {}).unwrap(),
+                funds: vec![coin(1000, "ld_denom")],
+            },
+            ReplyMsg::Unbond { id: 1 }.to_reply_id(),
+        )]
+    );
+    let mut expected = unbond_item(drop_staking_base::state::mirror::UnbondState::Initiated);
+    expected.token_id = None;
+    assert_eq!(unbonds_map().load(&deps.storage, 1).unwrap(), expected);
+
+    crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Unbond { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("wasm").add_attributes(vec![
+                    attr("_contract_address", "withdrawal_voucher_contract"),
+                    attr("action", "mint"),
+                    attr("minter", "core"),
+                    attr("owner", "cosmos2contract"),
+                    attr("token_id", "0_cosmos2contract_1"),
+                ])],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        unbonds_map().load(&deps.storage, 1).unwrap(),
+        unbond_item(drop_staking_base::state::mirror::UnbondState::Unbonding)
+    );
+}
+
+#[test]
+fn unbond_wrong_receiver() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("sender", &[coin(1000, "ld_denom")]),
+        drop_staking_base::msg::mirror::ExecuteMsg::Unbond {
+            receiver: "other1receiver".to_string(),
+            backup: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::InvalidPrefix);
+}
+
+#[test]
+fn withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    mock_state_query(&mut deps);
+    deps.querier.add_wasm_query_response("core", |_| {
+        to_json_binary(&drop_staking_base::state::core::Config {
+            factory_contract: Addr::unchecked("factory_contract"),
+            base_denom: "base_denom".to_string(),
+            remote_denom: "remote_denom".to_string(),
+            idle_min_interval: 60,
+            unbonding_period: 60,
+            unbonding_safe_period: 10,
+            unbond_batch_switch_time: 6000,
+            pump_ica_address: None,
+            transfer_channel_id: "transfer_channel".to_string(),
+            emergency_address: None,
+            icq_update_delay: 5,
+        })
+        .unwrap()
+    });
+    unbonds_map()
+        .save(
+            deps.as_mut().storage,
+            1,
+            &unbond_item(drop_staking_base::state::mirror::UnbondState::Unbonding),
+        )
+        .unwrap();
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::mirror::ExecuteMsg::Withdraw { items: vec![1] },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: "withdrawal_voucher_contract".to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::SendNft {
+                        contract: "withdrawal_manager_contract".to_string(),
+                        token_id: "0_cosmos2contract_1".to_string(),
+                        msg: to_json_binary(
+                            &drop_staking_base::msg::withdrawal_manager::ReceiveNftMsg::Withdraw {
+                                receiver: None,
+                            },
+                        )
+                        .unwrap(),
+                    },
+                )
+                .unwrap(),
+                funds: vec![],
+            },
+            ReplyMsg::Withdraw { id: 1 }.to_reply_id(),
+        )]
+    );
+}
+
+#[test]
+fn withdraw_wrong_state() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    mock_state_query(&mut deps);
+    deps.querier.add_wasm_query_response("core", |_| {
+        to_json_binary(&drop_staking_base::state::core::Config {
+            factory_contract: Addr::unchecked("factory_contract"),
+            base_denom: "base_denom".to_string(),
+            remote_denom: "remote_denom".to_string(),
+            idle_min_interval: 60,
+            unbonding_period: 60,
+            unbonding_safe_period: 10,
+            unbond_batch_switch_time: 6000,
+            pump_ica_address: None,
+            transfer_channel_id: "transfer_channel".to_string(),
+            emergency_address: None,
+            icq_update_delay: 5,
+        })
+        .unwrap()
+    });
+    unbonds_map()
+        .save(
+            deps.as_mut().storage,
+            1,
+            &unbond_item(drop_staking_base::state::mirror::UnbondState::Sent),
+        )
+        .unwrap();
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::mirror::ExecuteMsg::Withdraw { items: vec![1] },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::WrongBondState {
+            expected: "Unbonding".to_string(),
+            got: "Sent".to_string(),
+        }
+    );
+}
+
+#[test]
+fn finalize_withdraw_remote() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(deps.as_mut());
+    unbonds_map()
+        .save(
+            deps.as_mut().storage,
+            1,
+            &unbond_item(drop_staking_base::state::mirror::UnbondState::Unbonding),
+        )
+        .unwrap();
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Withdraw { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("transfer").add_attributes(vec![
+                    attr("recipient", "cosmos2contract"),
+                    attr("sender", "withdrawal_manager_contract"),
+                    attr("amount", "1100base_denom"),
+                ])],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(CosmosMsg::Custom(NeutronMsg::IbcTransfer {
+            source_port: "source_port".to_string(),
+            source_channel: "source_channel".to_string(),
+            token: coin(1100, "base_denom"),
+            sender: "cosmos2contract".to_string(),
+            receiver: "prefix10yaps46wgmzrsslmeqpc9wxpssu7zuw4rrfv8d5rv8pudt8m88446jgnu2j"
+                .to_string(),
+            timeout_height: RequestPacketTimeoutHeight {
+                revision_number: None,
+                revision_height: None
+            },
+            timeout_timestamp: mock_env().block.time.plus_seconds(10).nanos(),
+            memo: "unbond-1".to_string(),
+            fee: get_standard_fees(),
+        }))]
+    );
+    let unbond = unbonds_map().load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        unbond.state,
+        drop_staking_base::state::mirror::UnbondState::Sent
+    );
+    assert_eq!(unbond.received, Some(coin(1100, "base_denom")));
+}
+
+#[test]
+fn finalize_withdraw_local() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let mut unbond = unbond_item(drop_staking_base::state::mirror::UnbondState::Unbonding);
+    unbond.return_type = drop_staking_base::state::mirror::ReturnType::Local;
+    unbonds_map()
+        .save(deps.as_mut().storage, 1, &unbond)
+        .unwrap();
+    let response = crate::contract::reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: ReplyMsg::Withdraw { id: 1 }.to_reply_id(),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![Event::new("transfer").add_attributes(vec![
+                    attr("recipient", "cosmos2contract"),
+                    attr("sender", "withdrawal_manager_contract"),
+                    attr("amount", "1100base_denom"),
+                ])],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(cosmwasm_std::BankMsg::Send {
+            to_address: "backup".to_string(),
+            amount: vec![coin(1100, "base_denom")],
+        })]
+    );
+    assert!(unbonds_map().load(&deps.storage, 1).is_err());
+}
+
+#[test]
+fn sudo_unbond_return() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let mut unbond = unbond_item(drop_staking_base::state::mirror::UnbondState::Sent);
+    unbond.received = Some(coin(1100, "base_denom"));
+    unbonds_map()
+        .save(deps.as_mut().storage, 1, &unbond)
+        .unwrap();
+    bonds_map()
+        .save(deps.as_mut().storage, 1, &sent_bond(None, 0))
+        .unwrap();
+
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Timeout {
+            request: transfer_request("unbond-1"),
+        },
+    )
+    .unwrap();
+    let failed = unbonds_map().load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        failed.state,
+        drop_staking_base::state::mirror::UnbondState::Withdrawn
+    );
+    assert_eq!(failed.retries, 1);
+    assert_eq!(failed.retry_at, Some(mock_env().block.time.seconds() + 60));
+    // the bond with the same id is not affected
+    assert_eq!(
+        bonds_map().load(&deps.storage, 1).unwrap(),
+        sent_bond(None, 0)
+    );
+
+    unbonds_map()
+        .save(deps.as_mut().storage, 1, &unbond)
+        .unwrap();
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        TransferSudoMsg::Response {
+            request: transfer_request("unbond-1"),
+            data: cosmwasm_std::Binary::default(),
+        },
+    )
+    .unwrap();
+    assert!(unbonds_map().load(&deps.storage, 1).is_err());
+    assert!(bonds_map().load(&deps.storage, 1).is_ok());
+}
+
 fn get_standard_fees() -> IbcFee {
     IbcFee {
         recv_fee: vec![],
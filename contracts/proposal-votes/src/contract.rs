@@ -23,6 +23,8 @@ use neutron_sdk::sudo::msg::SudoMsg;
 use std::collections::HashSet;
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+// the contract was published under this name while it was a proof of concept
+const LEGACY_CONTRACT_NAME: &str = "crates.io:drop-staking__drop-proposal-votes-poc";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
@@ -377,10 +379,10 @@ pub fn migrate(
     _msg: MigrateMsg,
 ) -> ContractResult<Response<NeutronMsg>> {
     let version: semver::Version = CONTRACT_VERSION.parse()?;
-    let storage_version: semver::Version =
-        cw2::get_contract_version(deps.storage)?.version.parse()?;
+    let contract_version = cw2::get_contract_version(deps.storage)?;
+    let storage_version: semver::Version = contract_version.version.parse()?;
 
-    if storage_version < version {
+    if storage_version < version || contract_version.contract == LEGACY_CONTRACT_NAME {
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
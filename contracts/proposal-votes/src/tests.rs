@@ -50,14 +50,13 @@ fn instantiate() {
     assert_eq!(
         response.events,
         vec![
-            Event::new("crates.io:drop-staking__drop-proposal-votes-poc-instantiate")
-                .add_attributes([
-                    attr("connection_id", "connection-0"),
-                    attr("port_id", "transfer"),
-                    attr("update_period", "100"),
-                    attr("core_address", "core"),
-                    attr("provider_proposals_address", "provider_proposals")
-                ])
+            Event::new("crates.io:drop-staking__drop-proposal-votes-instantiate").add_attributes([
+                attr("connection_id", "connection-0"),
+                attr("port_id", "transfer"),
+                attr("update_period", "100"),
+                attr("core_address", "core"),
+                attr("provider_proposals_address", "provider_proposals")
+            ])
         ]
     );
     assert!(response.attributes.is_empty());
@@ -333,4 +332,30 @@ fn update_active_proposals_ok() {
     assert_eq!(active_proposals, vec![1, 2]);
 }
 
+#[test]
+fn migrate_from_poc() {
+    let mut deps = mock_dependencies::<MockQuerier>();
+    cw2::set_contract_version(
+        deps.as_mut().storage,
+        "crates.io:drop-staking__drop-proposal-votes-poc",
+        "1.0.0",
+    )
+    .unwrap();
+
+    crate::contract::migrate(
+        deps.as_mut(),
+        mock_env(),
+        drop_staking_base::msg::proposal_votes::MigrateMsg {},
+    )
+    .unwrap();
+
+    assert_eq!(
+        cw2::get_contract_version(deps.as_ref().storage).unwrap(),
+        cw2::ContractVersion {
+            contract: "crates.io:drop-staking__drop-proposal-votes".to_string(),
+            version: "1.0.0".to_string(),
+        }
+    );
+}
+
 // TODO: Add more tests
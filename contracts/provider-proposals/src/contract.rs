@@ -1,9 +1,10 @@
-use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{ProposalStatus, VoteOption};
 use cosmwasm_std::{
     attr, ensure_eq, to_json_binary, Attribute, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
 };
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
 use crate::error::{ContractError, ContractResult};
 use drop_helpers::answer::response;
@@ -14,8 +15,8 @@ use drop_staking_base::msg::provider_proposals::{
 };
 use drop_staking_base::msg::validatorset::ExecuteMsg as ValidatorSetExecuteMsg;
 use drop_staking_base::state::provider_proposals::{
-    Config, ConfigOptional, Metrics, ProposalInfo, CONFIG, PROPOSALS, PROPOSALS_REPLY_ID,
-    PROPOSALS_VOTES, QUERY_ID,
+    Config, ConfigOptional, Metrics, ParticipationResponse, ProposalInfo, ValidatorParticipation,
+    CONFIG, FINISHED_PROPOSALS, PROPOSALS, PROPOSALS_REPLY_ID, PROPOSALS_VOTES, QUERY_ID, VOTES,
 };
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_sdk::bindings::query::{NeutronQuery, QueryRegisteredQueryResultResponse};
@@ -28,9 +29,13 @@ use neutron_sdk::interchain_queries::v045::types::{GovernmentProposal, Proposal,
 use neutron_sdk::sudo::msg::SudoMsg;
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+// the contract was published under this name while it was a proof of concept
+const LEGACY_CONTRACT_NAME: &str = "crates.io:drop-staking__drop-provider-proposals-poc";
 
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const MAX_PARTICIPATION_WINDOW: u64 = 100;
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
     deps: DepsMut<NeutronQuery>,
@@ -94,6 +99,7 @@ pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> StdResult<Bi
         QueryMsg::GetProposal { proposal_id } => query_proposal(deps, proposal_id),
         QueryMsg::GetProposals {} => query_proposals(deps),
         QueryMsg::Metrics {} => query_metrics(deps),
+        QueryMsg::Participation { window } => query_participation(deps, window),
     }
 }
 
@@ -118,13 +124,111 @@ fn query_metrics(deps: Deps<NeutronQuery>) -> StdResult<Binary> {
     })
 }
 
+fn query_participation(deps: Deps<NeutronQuery>, window: u64) -> StdResult<Binary> {
+    if window == 0 || window > MAX_PARTICIPATION_WINDOW {
+        return Err(StdError::generic_err(format!(
+            "participation window must be between 1 and {MAX_PARTICIPATION_WINDOW}"
+        )));
+    }
+
+    let proposals = FINISHED_PROPOSALS
+        .range(deps.storage, None, None, Order::Descending)
+        .filter(|item| item.as_ref().map_or(true, |(_, is_spam)| !is_spam))
+        .take(window as usize)
+        .map(|item| item.map(|(proposal_id, _)| proposal_id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut validators: BTreeMap<String, ValidatorParticipation> = BTreeMap::new();
+    for proposal_id in proposals.iter() {
+        for item in VOTES
+            .prefix(*proposal_id)
+            .range(deps.storage, None, None, Order::Ascending)
+        {
+            let (voter, options) = item?;
+            let participation =
+                validators
+                    .entry(voter.clone())
+                    .or_insert_with(|| ValidatorParticipation {
+                        voter,
+                        voted: 0,
+                        weighted: 0,
+                        yes: Decimal::zero(),
+                        abstain: Decimal::zero(),
+                        no: Decimal::zero(),
+                        no_with_veto: Decimal::zero(),
+                        participation: Decimal::zero(),
+                    });
+            if options.is_empty() {
+                continue;
+            }
+
+            participation.voted += 1;
+            if options.len() > 1 {
+                participation.weighted += 1;
+            }
+            for option in options {
+                let weight = parse_vote_weight(&option.weight)?;
+                let total = if option.option == VoteOption::Yes as i32 {
+                    &mut participation.yes
+                } else if option.option == VoteOption::Abstain as i32 {
+                    &mut participation.abstain
+                } else if option.option == VoteOption::No as i32 {
+                    &mut participation.no
+                } else if option.option == VoteOption::NoWithVeto as i32 {
+                    &mut participation.no_with_veto
+                } else {
+                    continue;
+                };
+                *total += weight;
+            }
+        }
+    }
+
+    let validators = validators
+        .into_values()
+        .map(|mut participation| {
+            participation.participation =
+                Decimal::from_ratio(participation.voted, proposals.len() as u64);
+            participation
+        })
+        .collect();
+
+    to_json_binary(&ParticipationResponse {
+        proposals,
+        validators,
+    })
+}
+
+// weights come as sdk.Dec, either as a decimal string or as its atomics without the point
+fn parse_vote_weight(weight: &str) -> StdResult<Decimal> {
+    if weight.contains('.') {
+        Decimal::from_str(weight)
+    } else {
+        Decimal::from_atomics(Uint128::from_str(weight)?, 18)
+            .map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+fn load_votes(deps: Deps<NeutronQuery>, proposal_id: u64) -> StdResult<Option<Vec<ProposalVote>>> {
+    let votes = VOTES
+        .prefix(proposal_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(voter, options)| ProposalVote {
+                proposal_id,
+                voter,
+                options,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(if votes.is_empty() { None } else { Some(votes) })
+}
+
 fn query_proposal(deps: Deps<NeutronQuery>, proposal_id: u64) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     let proposal: Proposal = PROPOSALS.load(deps.storage, proposal_id)?;
-    let votes = PROPOSALS_VOTES
-        .may_load(deps.storage, proposal_id)
-        .ok()
-        .unwrap_or_default();
+    let votes = load_votes(deps, proposal_id)?;
     to_json_binary(&ProposalInfo {
         proposal: proposal.clone(),
         votes,
@@ -137,17 +241,13 @@ fn query_proposals(deps: Deps<NeutronQuery>) -> StdResult<Binary> {
     let proposals: StdResult<Vec<_>> = PROPOSALS
         .range_raw(deps.storage, None, None, Order::Ascending)
         .map(|item| {
-            item.map(|(_key, value)| {
-                let votes = PROPOSALS_VOTES
-                    .may_load(deps.storage, value.proposal_id)
-                    .ok()
-                    .unwrap_or_default();
-
-                ProposalInfo {
-                    proposal: value.clone(),
-                    votes,
-                    is_spam: is_spam_proposal(&value, config.veto_spam_threshold),
-                }
+            let (_key, value) = item?;
+            let votes = load_votes(deps, value.proposal_id)?;
+
+            Ok(ProposalInfo {
+                is_spam: is_spam_proposal(&value, config.veto_spam_threshold),
+                proposal: value,
+                votes,
             })
         })
         .collect();
@@ -253,20 +353,31 @@ pub fn execute_update_votes(
         ContractError::Unauthorized {}
     );
 
-    let mut votes_map: HashMap<u64, Vec<ProposalVote>> = HashMap::new();
-
-    for vote in votes.clone() {
-        votes_map.entry(vote.proposal_id).or_default().push(vote);
-    }
-
-    for (proposal_id, votes) in votes_map.iter() {
-        PROPOSALS_VOTES.save(deps.storage, *proposal_id, votes)?;
+    let total_count = votes.len();
+    let mut kept_count = 0u64;
+
+    for vote in votes {
+        let key = (vote.proposal_id, vote.voter.as_str());
+        // votes can't be withdrawn, so an empty vote after a real one means the host chain
+        // has tallied the proposal and deleted its votes, the last known vote is kept
+        if vote.options.is_empty()
+            && VOTES
+                .may_load(deps.storage, key)?
+                .map_or(false, |options| !options.is_empty())
+        {
+            kept_count += 1;
+            continue;
+        }
+        VOTES.save(deps.storage, key, &vote.options)?;
     }
 
     Ok(response(
         "config_update",
         CONTRACT_NAME,
-        [attr("total_count", votes.len().to_string())],
+        [
+            attr("total_count", total_count.to_string()),
+            attr("kept_count", kept_count.to_string()),
+        ],
     ))
 }
 
@@ -313,57 +424,60 @@ fn sudo_proposals_query(
     let data: GovernmentProposal =
         KVReconstruct::reconstruct(&interchain_query_result.result.kv_results)?;
 
+    let config = CONFIG.load(deps.storage)?;
     let mut msgs: Vec<CosmosMsg<NeutronMsg>> = Vec::new();
-    match data.proposals.first() {
-        Some(first_proposal) => {
-            if is_proposal_finished(first_proposal) {
-                let query_id = QUERY_ID.may_load(deps.storage)?;
-                let config = CONFIG.load(deps.storage)?;
-                if let Some(query_id) = query_id {
-                    let new_proposals: Vec<u64> = (first_proposal.proposal_id
-                        ..first_proposal.proposal_id + config.proposals_prefetch)
-                        .collect();
-
-                    let reg_msg = CosmosMsg::Custom(update_gov_proposals_query_msg(
-                        query_id,
-                        new_proposals.to_owned(),
-                        None,
-                    )?);
+    let mut attrs: Vec<Attribute> = Vec::new();
 
-                    msgs.push(reg_msg);
-
-                    let votes = PROPOSALS_VOTES
-                        .may_load(deps.storage, first_proposal.proposal_id)
-                        .ok()
-                        .unwrap_or_default();
-
-                    let update_msg = CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: config.validators_set_address,
-                        msg: to_json_binary(&ValidatorSetExecuteMsg::UpdateValidatorsVoting {
-                            proposal: ProposalInfo {
-                                proposal: first_proposal.clone(),
-                                votes,
-                                is_spam: is_spam_proposal(
-                                    first_proposal,
-                                    config.veto_spam_threshold,
-                                ),
-                            },
-                        })?,
-                        funds: vec![],
-                    });
+    // several proposals may finish between two results, each one is reported exactly once
+    for proposal in data.proposals.iter() {
+        if !is_proposal_finished(proposal)
+            || FINISHED_PROPOSALS.has(deps.storage, proposal.proposal_id)
+        {
+            continue;
+        }
 
-                    msgs.push(update_msg);
+        let is_spam = is_spam_proposal(proposal, config.veto_spam_threshold);
+        FINISHED_PROPOSALS.save(deps.storage, proposal.proposal_id, &is_spam)?;
+
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.validators_set_address.to_string(),
+            msg: to_json_binary(&ValidatorSetExecuteMsg::UpdateValidatorsVoting {
+                proposal: ProposalInfo {
+                    proposal: proposal.clone(),
+                    votes: load_votes(deps.as_ref(), proposal.proposal_id)?,
+                    is_spam,
+                },
+            })?,
+            funds: vec![],
+        }));
+        attrs.push(attr("finished_proposal", proposal.proposal_id.to_string()));
+    }
 
-                    if let Some(proposal_votes_address) = config.proposal_votes_address {
-                        msgs.push(update_voting_proposals_msg(
-                            proposal_votes_address,
-                            new_proposals,
-                        )?);
-                    }
+    match (data.proposals.first(), get_window_start(&data.proposals)) {
+        (Some(first_proposal), Some(window_start))
+            if window_start != first_proposal.proposal_id =>
+        {
+            if let Some(query_id) = QUERY_ID.may_load(deps.storage)? {
+                let new_proposals: Vec<u64> =
+                    (window_start..window_start + config.proposals_prefetch).collect();
+
+                msgs.push(CosmosMsg::Custom(update_gov_proposals_query_msg(
+                    query_id,
+                    new_proposals.to_owned(),
+                    None,
+                )?));
+
+                if let Some(proposal_votes_address) = config.proposal_votes_address {
+                    msgs.push(update_voting_proposals_msg(
+                        proposal_votes_address,
+                        new_proposals,
+                    )?);
                 }
+                attrs.push(attr("window_start", window_start.to_string()));
             }
         }
-        None => deps.api.debug("WASMDEBUG: first_proposal is None"),
+        (None, _) => deps.api.debug("WASMDEBUG: first_proposal is None"),
+        _ => {}
     }
 
     for proposal in data.proposals {
@@ -372,7 +486,29 @@ fn sudo_proposals_query(
         }
     }
 
-    Ok(Response::new().add_messages(msgs))
+    Ok(response("sudo_proposals_query", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+// The window starts at the first proposal which is still in progress. Proposals removed
+// for an insufficient deposit come back unspecified and are skipped once a later one exists.
+pub(crate) fn get_window_start(proposals: &[Proposal]) -> Option<u64> {
+    let last_existing = proposals
+        .iter()
+        .rposition(|proposal| proposal.status != ProposalStatus::Unspecified as i32);
+
+    for (i, proposal) in proposals.iter().enumerate() {
+        if is_proposal_finished(proposal) {
+            continue;
+        }
+        if proposal.status == ProposalStatus::Unspecified as i32
+            && last_existing.map_or(false, |last| i < last)
+        {
+            continue;
+        }
+        return Some(proposal.proposal_id);
+    }
+
+    proposals.last().map(|proposal| proposal.proposal_id + 1)
 }
 
 fn is_proposal_finished(proposal: &Proposal) -> bool {
@@ -436,12 +572,43 @@ pub fn migrate(
     _msg: MigrateMsg,
 ) -> ContractResult<Response<NeutronMsg>> {
     let version: semver::Version = CONTRACT_VERSION.parse()?;
-    let storage_version: semver::Version =
-        cw2::get_contract_version(deps.storage)?.version.parse()?;
+    let contract_version = cw2::get_contract_version(deps.storage)?;
+    let storage_version: semver::Version = contract_version.version.parse()?;
 
-    if storage_version < version {
+    if storage_version < version || contract_version.contract == LEGACY_CONTRACT_NAME {
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
+    // votes snapshots per proposal are split into per-validator records
+    let legacy_votes = PROPOSALS_VOTES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (proposal_id, votes) in legacy_votes {
+        for vote in votes {
+            VOTES.save(
+                deps.storage,
+                (proposal_id, vote.voter.as_str()),
+                &vote.options,
+            )?;
+        }
+        PROPOSALS_VOTES.remove(deps.storage, proposal_id);
+    }
+
+    // finished proposals were already reported to the validators set
+    let config = CONFIG.load(deps.storage)?;
+    let finished_proposals = PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, proposal)| is_proposal_finished(proposal))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (proposal_id, proposal) in finished_proposals {
+        if !FINISHED_PROPOSALS.has(deps.storage, proposal_id) {
+            let is_spam = is_spam_proposal(&proposal, config.veto_spam_threshold);
+            FINISHED_PROPOSALS.save(deps.storage, proposal_id, &is_spam)?;
+        }
+    }
+
     Ok(Response::new())
 }
@@ -90,7 +90,7 @@ fn instantiate() {
     assert_eq!(
         response.events,
         vec![
-            Event::new("crates.io:drop-staking__drop-provider-proposals-poc-instantiate")
+            Event::new("crates.io:drop-staking__drop-provider-proposals-instantiate")
                 .add_attributes([
                     attr("connection_id", "connection-0"),
                     attr("port_id", "transfer"),
@@ -464,3 +464,262 @@ fn update_votes_with_data() {
 }
 
 // TODO: Add more tests
+
+fn save_config(deps: cosmwasm_std::DepsMut<NeutronQuery>) {
+    drop_staking_base::state::provider_proposals::CONFIG
+        .save(
+            deps.storage,
+            &drop_staking_base::state::provider_proposals::Config {
+                connection_id: "connection-0".to_string(),
+                port_id: "transfer".to_string(),
+                update_period: 100,
+                core_address: "core".to_string(),
+                proposal_votes_address: Some("proposal_votes".to_string()),
+                validators_set_address: "validators_set".to_string(),
+                init_proposal: 1,
+                proposals_prefetch: 5,
+                veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+            },
+        )
+        .unwrap();
+}
+
+fn vote(
+    proposal_id: u64,
+    voter: &str,
+    options: &[(i32, &str)],
+) -> neutron_sdk::interchain_queries::v045::types::ProposalVote {
+    neutron_sdk::interchain_queries::v045::types::ProposalVote {
+        proposal_id,
+        voter: voter.to_string(),
+        options: options
+            .iter()
+            .map(|(option, weight)| {
+                neutron_sdk::interchain_queries::v045::types::WeightedVoteOption {
+                    option: *option,
+                    weight: weight.to_string(),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn proposal(
+    proposal_id: u64,
+    status: i32,
+) -> neutron_sdk::interchain_queries::v045::types::Proposal {
+    neutron_sdk::interchain_queries::v045::types::Proposal {
+        proposal_id,
+        proposal_type: None,
+        total_deposit: vec![],
+        status,
+        submit_time: None,
+        deposit_end_time: None,
+        voting_start_time: None,
+        voting_end_time: None,
+        final_tally_result: None,
+    }
+}
+
+#[test]
+fn update_votes_keeps_last_vote() {
+    let mut deps = mock_dependencies::<MockQuerier>();
+    save_config(deps.as_mut());
+
+    for votes in [
+        vec![vote(1, "voter1", &[(1, "1.0")]), vote(1, "voter2", &[])],
+        vec![vote(1, "voter1", &[]), vote(1, "voter2", &[])],
+    ] {
+        crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("proposal_votes", &[]),
+            drop_staking_base::msg::provider_proposals::ExecuteMsg::UpdateProposalVotes { votes },
+        )
+        .unwrap();
+    }
+
+    // the vote deleted by the tally is kept, the missing vote stays missing
+    let votes: Vec<(
+        String,
+        Vec<neutron_sdk::interchain_queries::v045::types::WeightedVoteOption>,
+    )> = drop_staking_base::state::provider_proposals::VOTES
+        .prefix(1)
+        .range(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<cosmwasm_std::StdResult<_>>()
+        .unwrap();
+    assert_eq!(
+        votes,
+        vec![
+            (
+                "voter1".to_string(),
+                vote(1, "voter1", &[(1, "1.0")]).options
+            ),
+            ("voter2".to_string(), vec![]),
+        ]
+    );
+}
+
+#[test]
+fn query_participation() {
+    let mut deps = mock_dependencies::<MockQuerier>();
+    save_config(deps.as_mut());
+
+    for (proposal_id, is_spam) in [(1u64, false), (2, true), (3, false)] {
+        drop_staking_base::state::provider_proposals::FINISHED_PROPOSALS
+            .save(deps.as_mut().storage, proposal_id, &is_spam)
+            .unwrap();
+    }
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("proposal_votes", &[]),
+        drop_staking_base::msg::provider_proposals::ExecuteMsg::UpdateProposalVotes {
+            votes: vec![
+                vote(1, "voter1", &[(1, "1.000000000000000000")]),
+                vote(1, "voter2", &[]),
+                vote(2, "voter2", &[(4, "1.000000000000000000")]),
+                vote(
+                    3,
+                    "voter1",
+                    &[(1, "500000000000000000"), (3, "500000000000000000")],
+                ),
+                vote(3, "voter2", &[(3, "1.000000000000000000")]),
+            ],
+        },
+    )
+    .unwrap();
+
+    let participation: drop_staking_base::state::provider_proposals::ParticipationResponse =
+        cosmwasm_std::from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                drop_staking_base::msg::provider_proposals::QueryMsg::Participation { window: 5 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(
+        participation,
+        drop_staking_base::state::provider_proposals::ParticipationResponse {
+            proposals: vec![3, 1],
+            validators: vec![
+                drop_staking_base::state::provider_proposals::ValidatorParticipation {
+                    voter: "voter1".to_string(),
+                    voted: 2,
+                    weighted: 1,
+                    yes: Decimal::percent(150),
+                    abstain: Decimal::zero(),
+                    no: Decimal::percent(50),
+                    no_with_veto: Decimal::zero(),
+                    participation: Decimal::one(),
+                },
+                drop_staking_base::state::provider_proposals::ValidatorParticipation {
+                    voter: "voter2".to_string(),
+                    voted: 1,
+                    weighted: 0,
+                    yes: Decimal::zero(),
+                    abstain: Decimal::zero(),
+                    no: Decimal::one(),
+                    no_with_veto: Decimal::zero(),
+                    participation: Decimal::percent(50),
+                },
+            ],
+        }
+    );
+
+    let participation: drop_staking_base::state::provider_proposals::ParticipationResponse =
+        cosmwasm_std::from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                drop_staking_base::msg::provider_proposals::QueryMsg::Participation { window: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(participation.proposals, vec![3]);
+    assert_eq!(participation.validators[1].participation, Decimal::one());
+
+    crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_staking_base::msg::provider_proposals::QueryMsg::Participation { window: 101 },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn window_start() {
+    // 1 passed, 2 rejected, 3 in voting
+    assert_eq!(
+        crate::contract::get_window_start(&[proposal(1, 3), proposal(2, 4), proposal(3, 2)]),
+        Some(3)
+    );
+    // 2 was removed for an insufficient deposit, 4 doesn't exist yet
+    assert_eq!(
+        crate::contract::get_window_start(&[
+            proposal(1, 3),
+            proposal(2, 0),
+            proposal(3, 3),
+            proposal(4, 0),
+        ]),
+        Some(4)
+    );
+    // every proposal is finished
+    assert_eq!(
+        crate::contract::get_window_start(&[proposal(1, 3), proposal(2, 5)]),
+        Some(3)
+    );
+    assert_eq!(crate::contract::get_window_start(&[]), None);
+}
+
+#[test]
+fn migrate_from_poc() {
+    let mut deps = mock_dependencies::<MockQuerier>();
+    cw2::set_contract_version(
+        deps.as_mut().storage,
+        "crates.io:drop-staking__drop-provider-proposals-poc",
+        "1.0.0",
+    )
+    .unwrap();
+    save_config(deps.as_mut());
+    drop_staking_base::state::provider_proposals::PROPOSALS_VOTES
+        .save(
+            deps.as_mut().storage,
+            1,
+            &vec![vote(1, "voter1", &[(1, "1.0")])],
+        )
+        .unwrap();
+
+    crate::contract::migrate(
+        deps.as_mut(),
+        mock_env(),
+        drop_staking_base::msg::provider_proposals::MigrateMsg {},
+    )
+    .unwrap();
+
+    assert_eq!(
+        cw2::get_contract_version(deps.as_ref().storage).unwrap(),
+        cw2::ContractVersion {
+            contract: "crates.io:drop-staking__drop-provider-proposals".to_string(),
+            version: "1.0.0".to_string(),
+        }
+    );
+    assert_eq!(
+        drop_staking_base::state::provider_proposals::VOTES
+            .load(deps.as_ref().storage, (1, "voter1"))
+            .unwrap(),
+        vote(1, "voter1", &[(1, "1.0")]).options
+    );
+    assert!(
+        !drop_staking_base::state::provider_proposals::PROPOSALS_VOTES
+            .has(deps.as_ref().storage, 1)
+    );
+}
@@ -10,7 +10,7 @@ use cosmwasm_std::{
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
 use drop_helpers::{
     answer::response, get_contracts, ibc_client_state::query_client_state, ibc_fee::query_ibc_fee,
-    icq_initia::new_delegations_and_balance_query_msg, interchain::prepare_any_msg,
+    ica::IcaState, icq_initia::new_delegations_and_balance_query_msg, interchain::prepare_any_msg,
     validation::validate_addresses,
 };
 use drop_proto::proto::initia::mstaking::v1::InitiaMsgDelegate;
@@ -209,6 +209,7 @@ pub fn execute(
             reply_to,
         } => execute_redelegate(deps, info, validator_from, validator_to, amount, reply_to),
         ExecuteMsg::TokenizeShare { .. } => unimplemented!(),
+        ExecuteMsg::TransferShare { .. } => unimplemented!(),
         ExecuteMsg::RedeemShares { .. } => {
             unimplemented!()
         }
@@ -228,6 +229,8 @@ pub fn execute(
         ExecuteMsg::RegisterNonNativeRewardsBalancesQuery { .. } => unimplemented!(),
         ExecuteMsg::Transfer { items, reply_to } => execute_transfer(deps, info, items, reply_to),
         ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::CancelQueued { id } => puppeteer_base.cancel_queued_tx(deps, info, id),
+        ExecuteMsg::VoteWeighted { .. } => unimplemented!(),
         ExecuteMsg::UpdateOwnership(action) => {
             let attrs = vec![attr("action", "update_ownership")];
             cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
@@ -312,7 +315,6 @@ fn execute_delegate(
     let config = puppeteer_base.config.load(deps.storage)?;
     let addrs = get_contracts!(deps, config.factory_contract, native_bond_provider_contract);
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
 
     let non_staked_balance = deps.querier.query_wasm_smart::<Uint128>(
         &addrs.native_bond_provider_contract,
@@ -392,7 +394,7 @@ fn execute_delegate(
         )?);
     }
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config,
         any_delegation_msgs,
@@ -403,7 +405,7 @@ fn execute_delegate(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(response("stake", CONTRACT_NAME, attrs).add_submessage(submsg))
+    Ok(response("stake", CONTRACT_NAME, attrs).add_submessages(submsgs))
 }
 
 fn register_delegations_and_balance_query(
@@ -469,7 +471,6 @@ fn execute_setup_protocol(
     let puppeteer_base = Puppeteer::default();
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
     let mut any_msgs = vec![];
 
@@ -482,7 +483,7 @@ fn execute_setup_protocol(
         set_withdraw_address_msg,
         "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
     )?);
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -494,7 +495,7 @@ fn execute_setup_protocol(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 fn execute_transfer(
     mut deps: DepsMut<NeutronQuery>,
@@ -506,7 +507,6 @@ fn execute_transfer(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
     let mut any_msgs = vec![];
     for (val, amount) in items.clone() {
@@ -527,7 +527,7 @@ fn execute_transfer(
             "/cosmos.bank.v1beta1.MsgSend",
         )?);
     }
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -539,7 +539,7 @@ fn execute_transfer(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_claim_rewards_and_optionaly_transfer(
@@ -553,7 +553,6 @@ fn execute_claim_rewards_and_optionaly_transfer(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
     let mut any_msgs = vec![];
     if let Some(transfer) = transfer.clone() {
@@ -590,7 +589,7 @@ fn execute_claim_rewards_and_optionaly_transfer(
 
     any_msgs.push(prepare_any_msg(grant_msg, "/cosmos.authz.v1beta1.MsgExec")?);
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -604,7 +603,7 @@ fn execute_claim_rewards_and_optionaly_transfer(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_undelegate(
@@ -618,7 +617,6 @@ fn execute_undelegate(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
     let delegator = puppeteer_base.ica.get_address(deps.storage)?;
     let mut undelegation_msgs = vec![];
     for (validator, amount) in items.iter() {
@@ -644,7 +642,7 @@ fn execute_undelegate(
     let any_msgs: Vec<neutron_sdk::bindings::types::ProtobufAny> =
         vec![prepare_any_msg(grant_msg, "/cosmos.authz.v1beta1.MsgExec")?];
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -658,7 +656,7 @@ fn execute_undelegate(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_redelegate(
@@ -673,7 +671,6 @@ fn execute_redelegate(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
     let delegator = puppeteer_base.ica.get_address(deps.storage)?;
     let redelegate_msg = MsgBeginRedelegate {
         delegator_address: delegator,
@@ -685,7 +682,7 @@ fn execute_redelegate(
         }],
     };
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         vec![prepare_any_msg(
@@ -703,24 +700,46 @@ fn execute_redelegate(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
+/// Puts the transaction at the end of the queue and submits the head of the
+/// queue right away if there is no transaction waiting for an ack.
 fn compose_submsg(
-    mut deps: DepsMut<NeutronQuery>,
+    deps: DepsMut<NeutronQuery>,
     config: Config,
     any_msgs: Vec<ProtobufAny>,
     transaction: Transaction,
     reply_to: String,
     reply_id: u64,
-) -> NeutronResult<SubMsg<NeutronMsg>> {
+) -> NeutronResult<Vec<SubMsg<NeutronMsg>>> {
+    let puppeteer_base = Puppeteer::default();
+    puppeteer_base.enqueue_tx(deps.storage, any_msgs, transaction, reply_to, reply_id)?;
+    Ok(submit_next_queued_tx(deps, &config)?.into_iter().collect())
+}
+
+fn submit_next_queued_tx(
+    mut deps: DepsMut<NeutronQuery>,
+    config: &Config,
+) -> NeutronResult<Option<SubMsg<NeutronMsg>>> {
     let puppeteer_base = Puppeteer::default();
+    // queued transactions wait for the ICA to be registered again after a timeout
+    if !matches!(
+        puppeteer_base.ica.load(deps.storage)?,
+        IcaState::Registered { .. }
+    ) {
+        return Ok(None);
+    }
+    let queued_tx = match puppeteer_base.pop_queued_tx(deps.storage)? {
+        Some(queued_tx) => queued_tx,
+        None => return Ok(None),
+    };
+    // the fee is queried at submission time, as the queued transaction may wait for a while
     let ibc_fee = query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?;
-    let connection_id = config.connection_id;
     let cosmos_msg = NeutronMsg::submit_tx(
-        connection_id,
+        config.connection_id.to_string(),
         ICA_ID.to_string(),
-        any_msgs,
+        queued_tx.msgs,
         "".to_string(),
         config.timeout,
         ibc_fee,
@@ -728,16 +747,16 @@ fn compose_submsg(
     let submsg = puppeteer_base.msg_with_sudo_callback(
         deps.branch(),
         cosmos_msg,
-        transaction,
-        reply_to,
-        reply_id,
+        queued_tx.transaction,
+        queued_tx.reply_to,
+        queued_tx.reply_id,
     )?;
-    Ok(submsg)
+    Ok(Some(submsg))
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn sudo(
-    deps: DepsMut<NeutronQuery>,
+    mut deps: DepsMut<NeutronQuery>,
     env: Env,
     msg: SudoMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
@@ -776,14 +795,19 @@ pub fn sudo(
             channel_id,
             counterparty_channel_id,
             counterparty_version,
-        } => puppeteer_base.sudo_open_ack(
-            deps,
-            env,
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        ),
+        } => {
+            let res = puppeteer_base.sudo_open_ack(
+                deps.branch(),
+                env,
+                port_id,
+                channel_id,
+                counterparty_channel_id,
+                counterparty_version,
+            )?;
+            // transactions queued while the ICA was closed after a timeout
+            let config = puppeteer_base.config.load(deps.storage)?;
+            Ok(res.add_submessages(submit_next_queued_tx(deps, &config)?))
+        }
     }
 }
 
@@ -863,7 +887,11 @@ fn sudo_response(
             funds: vec![],
         }));
     }
-    Ok(response("sudo-response", "puppeteer", attrs).add_messages(msgs))
+    let config = puppeteer_base.config.load(deps.storage)?;
+    let next_submsg = submit_next_queued_tx(deps, &config)?;
+    Ok(response("sudo-response", "puppeteer", attrs)
+        .add_messages(msgs)
+        .add_submessages(next_submsg))
 }
 
 fn sudo_error(
@@ -909,7 +937,11 @@ fn sudo_error(
             reply_to: None,
         },
     )?;
-    Ok(response("sudo-error", "puppeteer", attrs).add_message(msg))
+    let config = puppeteer_base.config.load(deps.storage)?;
+    let next_submsg = submit_next_queued_tx(deps, &config)?;
+    Ok(response("sudo-error", "puppeteer", attrs)
+        .add_message(msg)
+        .add_submessages(next_submsg))
 }
 
 fn sudo_timeout(
@@ -981,6 +1013,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
         }
         ReplyMsg::KvNonNativeRewardsBalances => unimplemented!(),
         ReplyMsg::KvUnbondingDelegations { .. } => unimplemented!(),
+        ReplyMsg::SubmitQueued => unimplemented!(),
     }
 }
 
@@ -13,7 +13,7 @@ use drop_helpers::{
     },
     testing::mock_dependencies,
 };
-use drop_puppeteer_base::state::{BalancesAndDelegationsState, PuppeteerBase, ReplyMsg};
+use drop_puppeteer_base::state::{BalancesAndDelegationsState, PuppeteerBase, QueuedTx, ReplyMsg};
 use drop_staking_base::{
     msg::puppeteer::InstantiateMsg,
     state::{
@@ -761,6 +761,165 @@ fn test_sudo_response_timeout() {
     );
 }
 
+fn queue_undelegate(deps: DepsMut<NeutronQuery>, puppeteer_base: &PuppeteerBaseType) {
+    puppeteer_base
+        .tx_state
+        .save(
+            deps.storage,
+            &drop_puppeteer_base::state::TxState {
+                seq_id: Some(1u64),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("reply_to_contract".to_string()),
+                transaction: Some(
+                    drop_puppeteer_base::peripheral_hook::Transaction::SetupProtocol {
+                        interchain_account_id: "ica_address".to_string(),
+                        rewards_withdraw_address: "rewards_withdraw_address".to_string(),
+                    },
+                ),
+            },
+        )
+        .unwrap();
+    let res = crate::contract::execute(
+        deps,
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::Undelegate {
+            batch_id: 0u128,
+            items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+            reply_to: "some_reply_to".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+}
+
+fn query_tx_queue(deps: cosmwasm_std::Deps<NeutronQuery>) -> Vec<QueuedTx> {
+    from_json(
+        crate::contract::query(
+            deps,
+            mock_env(),
+            drop_puppeteer_base::msg::QueryMsg::TxQueue {},
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_execute_undelegate_queued_while_waiting_for_ack() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    queue_undelegate(deps.as_mut(), &puppeteer_base);
+    let queue = query_tx_queue(deps.as_ref());
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].reply_to, "some_reply_to".to_string());
+
+    // an error ack frees the ICA, so the queued undelegation is submitted
+    let res = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Error {
+            request: neutron_sdk::sudo::msg::RequestPacket {
+                sequence: Some(1u64),
+                source_port: Some("source_port".to_string()),
+                source_channel: Some("source_channel".to_string()),
+                destination_port: Some("destination_port".to_string()),
+                destination_channel: Some("destination_channel".to_string()),
+                data: None,
+                timeout_height: None,
+                timeout_timestamp: None,
+            },
+            details: "error".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1],
+        SubMsg::reply_on_success(
+            CosmosMsg::Custom(NeutronMsg::submit_tx(
+                "connection_id".to_string(),
+                "DROP".to_string(),
+                queue[0].msgs.clone(),
+                "".to_string(),
+                100u64,
+                get_standard_fees()
+            )),
+            ReplyMsg::SudoPayload.to_reply_id()
+        )
+    );
+    assert_eq!(query_tx_queue(deps.as_ref()), vec![]);
+    let tx_state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        tx_state.status,
+        drop_puppeteer_base::state::TxStateStatus::InProgress
+    );
+    assert_eq!(tx_state.reply_to, Some("some_reply_to".to_string()));
+}
+
+#[test]
+fn test_cancel_queued() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    queue_undelegate(deps.as_mut(), &puppeteer_base);
+
+    let err = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::CancelQueued { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        drop_puppeteer_base::error::ContractError::Unauthorized {}
+    );
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::CancelQueued { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(
+            cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "some_reply_to".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::PeripheralHook(
+                    Box::new(
+                        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
+                            drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
+                                transaction:
+                                    drop_puppeteer_base::peripheral_hook::Transaction::Undelegate {
+                                        batch_id: 0u128,
+                                        interchain_account_id: "DROP".to_string(),
+                                        denom: get_base_config().remote_denom,
+                                        items: vec![(
+                                            "valoper1".to_string(),
+                                            Uint128::from(1000u128)
+                                        )]
+                                    },
+                                details: "Cancelled".to_string()
+                            }
+                        )
+                    )
+                ))
+                .unwrap(),
+                funds: vec![]
+            }
+        ))]
+    );
+    assert_eq!(query_tx_queue(deps.as_ref()), vec![]);
+}
+
 mod register_delegations_and_balance_query {
     use cosmwasm_std::{testing::MockApi, MemoryStorage, OwnedDeps, StdResult};
     use drop_helpers::testing::WasmMockQuerier;
@@ -1,10 +1,17 @@
 use cosmos_sdk_proto::cosmos::{
-    bank::v1beta1::MsgSend, base::v1beta1::Coin, distribution::v1beta1::MsgSetWithdrawAddress,
+    bank::v1beta1::MsgSend,
+    base::{abci::v1beta1::TxMsgData, v1beta1::Coin},
+    distribution::v1beta1::MsgSetWithdrawAddress,
+    gov::v1beta1::{
+        MsgVoteWeighted, VoteOption as ProtoVoteOption,
+        WeightedVoteOption as ProtoWeightedVoteOption,
+    },
     staking::v1beta1::MsgDelegate,
 };
+use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
 use cosmwasm_std::{
     attr, ensure, to_json_binary, Addr, Attribute, Coin as StdCoin, CosmosMsg, Deps, Order, Reply,
-    StdError, SubMsg, Timestamp, Uint128, WasmMsg,
+    ReplyOn, StdError, SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
 use drop_helpers::{
@@ -12,6 +19,7 @@ use drop_helpers::{
     get_contracts,
     ibc_client_state::query_client_state,
     ibc_fee::query_ibc_fee,
+    ica::IcaState,
     icq::{
         new_delegations_and_balance_query_msg, new_multiple_balances_query_msg,
         update_multiple_balances_query_msg,
@@ -23,7 +31,10 @@ use drop_proto::proto::{
     cosmos::base::v1beta1::Coin as ProtoCoin,
     liquidstaking::{
         distribution::v1beta1::MsgWithdrawDelegatorReward,
-        staking::v1beta1::{MsgBeginRedelegate, MsgRedeemTokensforShares, MsgTokenizeShares},
+        staking::v1beta1::{
+            MsgBeginRedelegate, MsgRedeemTokensforShares, MsgTokenizeShares,
+            MsgTokenizeSharesResponse,
+        },
     },
 };
 use drop_puppeteer_base::{
@@ -31,7 +42,7 @@ use drop_puppeteer_base::{
     msg::{QueryMsg, TransferReadyBatchesMsg},
     peripheral_hook::{
         ReceiverExecuteMsg, ResponseHookErrorMsg, ResponseHookMsg, ResponseHookSuccessMsg,
-        Transaction,
+        Transaction, VoteOption, WeightedVoteOption,
     },
     r#trait::PuppeteerReconstruct,
     state::{
@@ -57,6 +68,7 @@ use neutron_sdk::{
     sudo::msg::{RequestPacket, SudoMsg},
     NeutronResult,
 };
+use prost::Message;
 use std::vec;
 
 pub type Puppeteer<'a> = PuppeteerBase<'a, Config, KVQueryType, BalancesAndDelegations>;
@@ -231,11 +243,21 @@ pub fn execute(
         ExecuteMsg::TokenizeShare {
             validator,
             amount,
+            request_id,
             reply_to,
-        } => execute_tokenize_share(deps, info, validator, amount, reply_to),
+        } => execute_tokenize_share(deps, info, validator, amount, request_id, reply_to),
         ExecuteMsg::RedeemShares { items, reply_to } => {
             execute_redeem_shares(deps, info, items, reply_to)
         }
+        ExecuteMsg::TransferShare {
+            share,
+            channel_id,
+            receiver,
+            request_id,
+            reply_to,
+        } => execute_transfer_share(
+            deps, env, info, share, channel_id, receiver, request_id, reply_to,
+        ),
         ExecuteMsg::ClaimRewardsAndOptionalyTransfer {
             validators,
             transfer,
@@ -254,6 +276,12 @@ pub fn execute(
         }
         ExecuteMsg::Transfer { items, reply_to } => execute_transfer(deps, info, items, reply_to),
         ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::CancelQueued { id } => puppeteer_base.cancel_queued_tx(deps, info, id),
+        ExecuteMsg::VoteWeighted {
+            proposal_id,
+            options,
+            reply_to,
+        } => execute_vote_weighted(deps, info, proposal_id, options, reply_to),
         ExecuteMsg::UpdateOwnership(action) => {
             let attrs = vec![attr("action", "update_ownership")];
             cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
@@ -337,12 +365,20 @@ fn execute_delegate(
 ) -> ContractResult<Response<NeutronMsg>> {
     let puppeteer_base = Puppeteer::default();
     let config = puppeteer_base.config.load(deps.storage)?;
-    let addrs = get_contracts!(deps, config.factory_contract, native_bond_provider_contract);
+    let addrs = get_contracts!(deps, config.factory_contract, core_contract);
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
 
+    // every bond provider stakes what it holds itself
+    let bond_providers: Vec<Addr> = deps.querier.query_wasm_smart(
+        &addrs.core_contract,
+        &drop_staking_base::msg::core::QueryMsg::BondProviders {},
+    )?;
+    ensure!(
+        bond_providers.contains(&info.sender),
+        ContractError::Unauthorized {}
+    );
     let non_staked_balance = deps.querier.query_wasm_smart::<Uint128>(
-        &addrs.native_bond_provider_contract,
+        &info.sender,
         &drop_staking_base::msg::native_bond_provider::QueryMsg::NonStakedBalance {},
     )?;
 
@@ -368,7 +404,7 @@ fn execute_delegate(
         attr("ica_id", ICA_ID),
         attr("amount_to_stake", amount_to_stake.to_string()),
     ];
-    let ica_address = puppeteer_base.ica.get_address(deps.storage)?;
+    let ica_address = puppeteer_base.get_ica_address(deps.storage)?;
 
     let mut any_delegation_msgs = vec![];
     for (validator, amount) in items.clone() {
@@ -386,7 +422,7 @@ fn execute_delegate(
         )?);
     }
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config,
         any_delegation_msgs,
@@ -397,7 +433,7 @@ fn execute_delegate(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(response("stake", CONTRACT_NAME, attrs).add_submessage(submsg))
+    Ok(response("stake", CONTRACT_NAME, attrs).add_submessages(submsgs))
 }
 
 fn register_non_native_rewards_balances_query(
@@ -561,8 +597,7 @@ fn execute_setup_protocol(
     let puppeteer_base = Puppeteer::default();
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
-    let ica = puppeteer_base.ica.get_address(deps.storage)?;
+    let ica = puppeteer_base.get_ica_address(deps.storage)?;
     let mut any_msgs = vec![];
 
     let set_withdraw_address_msg = MsgSetWithdrawAddress {
@@ -574,7 +609,7 @@ fn execute_setup_protocol(
         set_withdraw_address_msg,
         "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
     )?);
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -586,7 +621,7 @@ fn execute_setup_protocol(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_transfer(
@@ -599,8 +634,7 @@ fn execute_transfer(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
-    let ica = puppeteer_base.ica.get_address(deps.storage)?;
+    let ica = puppeteer_base.get_ica_address(deps.storage)?;
     let mut any_msgs = vec![];
     for (val, amount) in items.clone() {
         deps.api.addr_validate(&val)?;
@@ -621,7 +655,7 @@ fn execute_transfer(
             "/cosmos.bank.v1beta1.MsgSend",
         )?);
     }
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -633,7 +667,7 @@ fn execute_transfer(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_claim_rewards_and_optionaly_transfer(
@@ -647,8 +681,7 @@ fn execute_claim_rewards_and_optionaly_transfer(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
-    let ica = puppeteer_base.ica.get_address(deps.storage)?;
+    let ica = puppeteer_base.get_ica_address(deps.storage)?;
     let mut any_msgs = vec![];
     if let Some(transfer) = transfer.clone() {
         let transfer_msg = MsgSend {
@@ -677,7 +710,7 @@ fn execute_claim_rewards_and_optionaly_transfer(
         )?);
     }
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         any_msgs,
@@ -691,7 +724,7 @@ fn execute_claim_rewards_and_optionaly_transfer(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_undelegate(
@@ -705,8 +738,7 @@ fn execute_undelegate(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
-    let delegator = puppeteer_base.ica.get_address(deps.storage)?;
+    let delegator = puppeteer_base.get_ica_address(deps.storage)?;
     let mut undelegation_msgs = vec![];
     for (validator, amount) in items.iter() {
         let undelegation_msg = cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate {
@@ -724,7 +756,7 @@ fn execute_undelegate(
         )?);
     }
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         undelegation_msgs,
@@ -738,7 +770,7 @@ fn execute_undelegate(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_redelegate(
@@ -753,8 +785,7 @@ fn execute_redelegate(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
-    let delegator = puppeteer_base.ica.get_address(deps.storage)?;
+    let delegator = puppeteer_base.get_ica_address(deps.storage)?;
     let redelegate_msg = MsgBeginRedelegate {
         delegator_address: delegator,
         validator_src_address: validator_from.to_string(),
@@ -765,7 +796,7 @@ fn execute_redelegate(
         }),
     };
 
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         vec![prepare_any_msg(
@@ -783,7 +814,7 @@ fn execute_redelegate(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
 }
 
 fn execute_tokenize_share(
@@ -791,14 +822,14 @@ fn execute_tokenize_share(
     info: MessageInfo,
     validator: String,
     amount: Uint128,
+    request_id: Option<u64>,
     reply_to: String,
 ) -> ContractResult<Response<NeutronMsg>> {
     let puppeteer_base = Puppeteer::default();
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
-    let delegator = puppeteer_base.ica.get_address(deps.storage)?;
+    let delegator = puppeteer_base.get_ica_address(deps.storage)?;
     let tokenize_msg = MsgTokenizeShares {
         delegator_address: delegator.clone(),
         validator_address: validator.to_string(),
@@ -808,7 +839,7 @@ fn execute_tokenize_share(
             amount: amount.to_string(),
         }),
     };
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config.clone(),
         vec![prepare_any_msg(
@@ -820,12 +851,71 @@ fn execute_tokenize_share(
             validator,
             denom: config.remote_denom,
             amount: amount.into(),
+            share: None,
+            request_id,
         },
         reply_to,
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
 
-    Ok(Response::default().add_submessages(vec![submsg]))
+    Ok(Response::default().add_submessages(submsgs))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_transfer_share(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    share: StdCoin,
+    channel_id: String,
+    receiver: String,
+    request_id: Option<u64>,
+    reply_to: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let puppeteer_base = Puppeteer::default();
+    deps.api.addr_validate(&reply_to)?;
+    let config: Config = puppeteer_base.config.load(deps.storage)?;
+    validate_sender(&config, &info.sender)?;
+    let ica = puppeteer_base.get_ica_address(deps.storage)?;
+    let attrs = vec![
+        attr("action", "transfer_share"),
+        attr("share", share.to_string()),
+        attr("channel_id", &channel_id),
+        attr("receiver", &receiver),
+    ];
+    let transfer_msg = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: channel_id.to_string(),
+        token: Some(Coin {
+            denom: share.denom.to_string(),
+            amount: share.amount.to_string(),
+        }),
+        sender: ica,
+        receiver: receiver.to_string(),
+        timeout_height: None,
+        timeout_timestamp: env.block.time.plus_seconds(config.timeout).nanos(),
+    };
+    let submsgs = compose_submsg(
+        deps.branch(),
+        config,
+        vec![prepare_any_msg(
+            transfer_msg,
+            "/ibc.applications.transfer.v1.MsgTransfer",
+        )?],
+        Transaction::TransferShare {
+            interchain_account_id: ICA_ID.to_string(),
+            share,
+            channel_id,
+            receiver,
+            request_id,
+        },
+        reply_to,
+        ReplyMsg::SudoPayload.to_reply_id(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(submsgs)
+        .add_attributes(attrs))
 }
 
 fn execute_redeem_shares(
@@ -840,10 +930,9 @@ fn execute_redeem_shares(
     ];
     let puppeteer_base = Puppeteer::default();
     deps.api.addr_validate(&reply_to)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    let delegator = puppeteer_base.ica.get_address(deps.storage)?;
+    let delegator = puppeteer_base.get_ica_address(deps.storage)?;
     let any_msgs = items
         .iter()
         .map(|one| MsgRedeemTokensforShares {
@@ -855,7 +944,7 @@ fn execute_redeem_shares(
         })
         .map(|msg| prepare_any_msg(msg, "/cosmos.staking.v1beta1.MsgRedeemTokensForShares"))
         .collect::<NeutronResult<Vec<ProtobufAny>>>()?;
-    let submsg = compose_submsg(
+    let submsgs = compose_submsg(
         deps.branch(),
         config,
         any_msgs,
@@ -864,25 +953,101 @@ fn execute_redeem_shares(
         ReplyMsg::SudoPayload.to_reply_id(),
     )?;
     Ok(Response::default()
-        .add_submessages(vec![submsg])
+        .add_submessages(submsgs)
         .add_attributes(attrs))
 }
 
-fn compose_submsg(
+fn execute_vote_weighted(
     mut deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+    reply_to: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let puppeteer_base = Puppeteer::default();
+    deps.api.addr_validate(&reply_to)?;
+    let config: Config = puppeteer_base.config.load(deps.storage)?;
+    validate_sender(&config, &info.sender)?;
+    let voter = puppeteer_base.get_ica_address(deps.storage)?;
+    let attrs = vec![
+        attr("action", "vote_weighted"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("options", format!("{:?}", options)),
+    ];
+    let vote_msg = MsgVoteWeighted {
+        proposal_id,
+        voter,
+        options: options
+            .iter()
+            .map(|one| ProtoWeightedVoteOption {
+                option: match one.option {
+                    VoteOption::Yes => ProtoVoteOption::Yes,
+                    VoteOption::Abstain => ProtoVoteOption::Abstain,
+                    VoteOption::No => ProtoVoteOption::No,
+                    VoteOption::NoWithVeto => ProtoVoteOption::NoWithVeto,
+                } as i32,
+                // sdk decimals are encoded as their atomics
+                weight: one.weight.atomics().to_string(),
+            })
+            .collect(),
+    };
+    let submsgs = compose_submsg(
+        deps.branch(),
+        config,
+        vec![prepare_any_msg(
+            vote_msg,
+            "/cosmos.gov.v1beta1.MsgVoteWeighted",
+        )?],
+        Transaction::VoteWeighted {
+            interchain_account_id: ICA_ID.to_string(),
+            proposal_id,
+            options,
+        },
+        reply_to,
+        ReplyMsg::SudoPayload.to_reply_id(),
+    )?;
+
+    Ok(response("vote_weighted", CONTRACT_NAME, attrs).add_submessages(submsgs))
+}
+
+/// Puts the transaction at the end of the queue and submits the head of the
+/// queue right away if there is no transaction waiting for an ack.
+fn compose_submsg(
+    deps: DepsMut<NeutronQuery>,
     config: Config,
     any_msgs: Vec<ProtobufAny>,
     transaction: Transaction,
     reply_to: String,
     reply_id: u64,
-) -> NeutronResult<SubMsg<NeutronMsg>> {
+) -> NeutronResult<Vec<SubMsg<NeutronMsg>>> {
     let puppeteer_base = Puppeteer::default();
+    puppeteer_base.enqueue_tx(deps.storage, any_msgs, transaction, reply_to, reply_id)?;
+    Ok(submit_next_queued_tx(deps, &config)?.into_iter().collect())
+}
+
+fn submit_next_queued_tx(
+    mut deps: DepsMut<NeutronQuery>,
+    config: &Config,
+) -> NeutronResult<Option<SubMsg<NeutronMsg>>> {
+    let puppeteer_base = Puppeteer::default();
+    if !matches!(
+        puppeteer_base.ica.load(deps.storage)?,
+        IcaState::Registered { .. }
+    ) {
+        // a timed out ICA is registered again with RecoverICA,
+        // queued transactions are submitted once its channel is open
+        return Ok(None);
+    }
+    let queued_tx = match puppeteer_base.pop_queued_tx(deps.storage)? {
+        Some(queued_tx) => queued_tx,
+        None => return Ok(None),
+    };
     let ibc_fee = query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?;
-    let connection_id = config.connection_id;
+    puppeteer_base.in_flight_tx.save(deps.storage, &queued_tx)?;
     let cosmos_msg = NeutronMsg::submit_tx(
-        connection_id,
+        config.connection_id.to_string(),
         ICA_ID.to_string(),
-        any_msgs,
+        queued_tx.msgs,
         "".to_string(),
         config.timeout,
         ibc_fee,
@@ -890,16 +1055,55 @@ fn compose_submsg(
     let submsg = puppeteer_base.msg_with_sudo_callback(
         deps.branch(),
         cosmos_msg,
-        transaction,
-        reply_to,
-        reply_id,
+        queued_tx.transaction,
+        queued_tx.reply_to,
+        queued_tx.reply_id,
+    )?;
+    Ok(Some(submsg))
+}
+
+/// Submits the oldest queued transaction once an ack frees the ICA. The fee is
+/// paid from the contract balance, so a failed submission must not revert the
+/// ack handling: the reply keeps the transaction queued for the next call.
+fn submit_queued_tx_after_ack(
+    mut deps: DepsMut<NeutronQuery>,
+    config: &Config,
+) -> NeutronResult<Option<SubMsg<NeutronMsg>>> {
+    let puppeteer_base = Puppeteer::default();
+    if !matches!(
+        puppeteer_base.ica.load(deps.storage)?,
+        IcaState::Registered { .. }
+    ) {
+        return Ok(None);
+    }
+    let queued_tx = match puppeteer_base.peek_queued_tx(deps.storage)? {
+        Some(queued_tx) => queued_tx,
+        None => return Ok(None),
+    };
+    let ibc_fee = query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?;
+    puppeteer_base.in_flight_tx.save(deps.storage, &queued_tx)?;
+    let cosmos_msg = NeutronMsg::submit_tx(
+        config.connection_id.to_string(),
+        ICA_ID.to_string(),
+        queued_tx.msgs,
+        "".to_string(),
+        config.timeout,
+        ibc_fee,
+    );
+    let mut submsg = puppeteer_base.msg_with_sudo_callback(
+        deps.branch(),
+        cosmos_msg,
+        queued_tx.transaction,
+        queued_tx.reply_to,
+        ReplyMsg::SubmitQueued.to_reply_id(),
     )?;
-    Ok(submsg)
+    submsg.reply_on = ReplyOn::Always;
+    Ok(Some(submsg))
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn sudo(
-    deps: DepsMut<NeutronQuery>,
+    mut deps: DepsMut<NeutronQuery>,
     env: Env,
     msg: SudoMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
@@ -946,14 +1150,19 @@ pub fn sudo(
             channel_id,
             counterparty_channel_id,
             counterparty_version,
-        } => puppeteer_base.sudo_open_ack(
-            deps,
-            env,
-            port_id,
-            channel_id,
-            counterparty_channel_id,
-            counterparty_version,
-        ),
+        } => {
+            let res = puppeteer_base.sudo_open_ack(
+                deps.branch(),
+                env,
+                port_id,
+                channel_id,
+                counterparty_channel_id,
+                counterparty_version,
+            )?;
+            // transactions queued while the ICA was closed after a timeout
+            let config = puppeteer_base.config.load(deps.storage)?;
+            Ok(res.add_submessages(submit_queued_tx_after_ack(deps, &config)?))
+        }
     }
 }
 
@@ -961,7 +1170,7 @@ fn sudo_response(
     deps: DepsMut<NeutronQuery>,
     env: Env,
     request: RequestPacket,
-    _data: Binary,
+    data: Binary,
 ) -> NeutronResult<Response<NeutronMsg>> {
     deps.api.debug("WASMDEBUG: sudo response");
 
@@ -984,6 +1193,8 @@ fn sudo_response(
     let transaction = tx_state
         .transaction
         .ok_or_else(|| StdError::generic_err("transaction not found"))?;
+    let transaction = with_tokenized_share(transaction, &data);
+    puppeteer_base.in_flight_tx.remove(deps.storage);
     deps.api.debug(&format!(
         "WASMDEBUG: transaction: {transaction:?}",
         transaction = transaction
@@ -1031,7 +1242,58 @@ fn sudo_response(
             funds: vec![],
         }));
     }
-    Ok(response("sudo-response", "puppeteer", attrs).add_messages(msgs))
+    let config = puppeteer_base.config.load(deps.storage)?;
+    let next_submsg = submit_queued_tx_after_ack(deps, &config)?;
+    Ok(response("sudo-response", "puppeteer", attrs)
+        .add_messages(msgs)
+        .add_submessages(next_submsg))
+}
+
+/// Fills in the LSM share minted by `MsgTokenizeShares` from the ack data,
+/// so the caller knows which denom to move further.
+fn with_tokenized_share(transaction: Transaction, data: &Binary) -> Transaction {
+    match transaction {
+        Transaction::TokenizeShare {
+            interchain_account_id,
+            validator,
+            denom,
+            amount,
+            request_id,
+            ..
+        } => Transaction::TokenizeShare {
+            interchain_account_id,
+            validator,
+            denom,
+            amount,
+            share: decode_tokenized_share(data),
+            request_id,
+        },
+        transaction => transaction,
+    }
+}
+
+fn decode_tokenized_share(data: &Binary) -> Option<StdCoin> {
+    let msg_data = TxMsgData::decode(data.as_slice()).ok()?;
+    // sdk 0.46+ puts responses into msg_responses, older ones into data
+    #[allow(deprecated)]
+    let (_, value) = msg_data
+        .msg_responses
+        .into_iter()
+        .map(|any| (any.type_url, any.value))
+        .chain(
+            msg_data
+                .data
+                .into_iter()
+                .map(|item| (item.msg_type, item.data)),
+        )
+        .find(|(type_url, _)| type_url.contains("MsgTokenizeShares"))?;
+    let share = MsgTokenizeSharesResponse::decode(value.as_slice())
+        .ok()?
+        .amount?;
+    Some(StdCoin::new(
+        share.amount.parse::<u128>().ok()?,
+        share.denom,
+    ))
 }
 
 fn sudo_error(
@@ -1056,6 +1318,7 @@ fn sudo_error(
     let transaction = tx_state
         .transaction
         .ok_or_else(|| StdError::generic_err("transaction not found"))?;
+    puppeteer_base.in_flight_tx.remove(deps.storage);
 
     let mut fund_to_return = vec![];
     if let Transaction::IBCTransfer { amount, denom, .. } = transaction.clone() {
@@ -1083,19 +1346,23 @@ fn sudo_error(
             reply_to: None,
         },
     )?;
-    Ok(response("sudo-error", "puppeteer", attrs).add_message(msg))
+    let config = puppeteer_base.config.load(deps.storage)?;
+    let next_submsg = submit_queued_tx_after_ack(deps, &config)?;
+    Ok(response("sudo-error", "puppeteer", attrs)
+        .add_message(msg)
+        .add_submessages(next_submsg))
 }
 
 fn sudo_timeout(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     request: RequestPacket,
 ) -> NeutronResult<Response<NeutronMsg>> {
     deps.api.debug(&format!(
         "WASMDEBUG: sudo_timeout: request: {request:?}",
         request = request
     ));
-    let attrs = vec![
+    let mut attrs = vec![
         attr("action", "sudo_timeout"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
     ];
@@ -1110,7 +1377,7 @@ fn sudo_timeout(
         fund_to_return.push(StdCoin::new(amount, denom));
     }
     puppeteer_base.validate_tx_waiting_state(deps.as_ref())?;
-    puppeteer_base.ica.set_timeout(deps.storage)?;
+    puppeteer_base.set_ica_timeout(deps.storage, env.block.height)?;
     puppeteer_base.tx_state.save(
         deps.storage,
         &TxState {
@@ -1124,6 +1391,16 @@ fn sudo_timeout(
         "WASMDEBUG: sudo_timeout: request: {request:?}",
         request = request
     ));
+    // the interrupted transaction goes back to the head of the queue and is
+    // submitted again once the ICA is recovered, so reply_to keeps waiting for it
+    if let Some(queued_tx) = puppeteer_base.in_flight_tx.may_load(deps.storage)? {
+        puppeteer_base.in_flight_tx.remove(deps.storage);
+        puppeteer_base
+            .tx_queue
+            .save(deps.storage, queued_tx.id, &queued_tx)?;
+        attrs.push(attr("requeued_id", queued_tx.id.to_string()));
+        return Ok(response("sudo-timeout", "puppeteer", attrs));
+    }
     let msg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: tx_state
             .reply_to
@@ -1146,6 +1423,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
     match ReplyMsg::from_reply_id(msg.id) {
         ReplyMsg::SudoPayload => puppeteer_base.submit_tx_reply(deps, msg),
         ReplyMsg::IbcTransfer => puppeteer_base.submit_ibc_transfer_reply(deps, msg),
+        ReplyMsg::SubmitQueued => puppeteer_base.submit_queued_tx_reply(deps, msg),
         ReplyMsg::KvDelegationsAndBalance { i } => puppeteer_base
             .register_delegations_and_balance_query_reply(
                 deps,
@@ -3,17 +3,17 @@ use cosmwasm_schema::schemars;
 use cosmwasm_std::{
     coin, coins, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal256, DepsMut, Event, Response, StdError,
-    SubMsg, Timestamp, Uint128, Uint64,
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal256, Deps, DepsMut, Event, Response,
+    StdError, SubMsg, Timestamp, Uint128, Uint64,
 };
 use drop_helpers::{
     ibc_client_state::{
         ChannelClientStateResponse, ClientState, Fraction, Height, IdentifiedClientState,
     },
-    testing::mock_dependencies,
+    testing::{mock_dependencies, mock_state_query},
 };
 
-use drop_puppeteer_base::state::{BalancesAndDelegationsState, PuppeteerBase, ReplyMsg};
+use drop_puppeteer_base::state::{BalancesAndDelegationsState, PuppeteerBase, QueuedTx, ReplyMsg};
 use drop_staking_base::state::puppeteer::NON_NATIVE_REWARD_BALANCES;
 use drop_staking_base::{
     msg::puppeteer::InstantiateMsg,
@@ -577,15 +577,9 @@ fn test_execute_setup_protocol_not_idle() {
             rewards_withdraw_address: "rewards_withdraw_address".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -654,15 +648,9 @@ fn test_execute_undelegate_sender_not_idle() {
             reply_to: "some_reply_to".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -734,6 +722,107 @@ fn test_execute_undelegate() {
     );
 }
 
+#[test]
+fn test_execute_delegate_from_deposit_bond_provider() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    mock_state_query(&mut deps);
+    deps.querier.add_wasm_query_response("core_contract", |_| {
+        to_json_binary(&vec![
+            Addr::unchecked("native_bond_provider_contract"),
+            Addr::unchecked("allowed_sender"),
+        ])
+        .unwrap()
+    });
+    deps.querier.add_wasm_query_response("allowed_sender", |_| {
+        to_json_binary(&Uint128::from(1000u128)).unwrap()
+    });
+    deps.querier
+        .add_wasm_query_response("native_bond_provider_contract", |_| {
+            to_json_binary(&Uint128::zero()).unwrap()
+        });
+    base_init(&mut deps.as_mut(), "0.47.10".to_string());
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::Delegate {
+            items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+            reply_to: "some_reply_to".to_string(),
+        },
+    )
+    .unwrap();
+
+    let delegate_msg = drop_helpers::interchain::prepare_any_msg(
+        cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate {
+            delegator_address: "ica_address".to_string(),
+            validator_address: "valoper1".to_string(),
+            amount: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                denom: "remote_denom".to_string(),
+                amount: "1000".to_string(),
+            }),
+        },
+        "/cosmos.staking.v1beta1.MsgDelegate",
+    )
+    .unwrap();
+
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(
+                Event::new("crates.io:drop-neutron-contracts__drop-puppeteer-stake")
+                    .add_attributes(vec![
+                        ("action", "stake"),
+                        ("connection_id", "connection_id"),
+                        ("ica_id", "DROP"),
+                        ("amount_to_stake", "1000"),
+                    ])
+            )
+            .add_submessage(SubMsg::reply_on_success(
+                CosmosMsg::Custom(NeutronMsg::submit_tx(
+                    "connection_id".to_string(),
+                    "DROP".to_string(),
+                    vec![delegate_msg],
+                    "".to_string(),
+                    100u64,
+                    get_standard_fees()
+                )),
+                ReplyMsg::SudoPayload.to_reply_id()
+            ))
+    );
+}
+
+#[test]
+fn test_execute_delegate_sender_is_not_bond_provider() {
+    let mut deps = mock_dependencies(&[]);
+    mock_state_query(&mut deps);
+    deps.querier.add_wasm_query_response("core_contract", |_| {
+        to_json_binary(&vec![Addr::unchecked("native_bond_provider_contract")]).unwrap()
+    });
+    base_init(&mut deps.as_mut(), "0.47.10".to_string());
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::Delegate {
+            items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+            reply_to: "some_reply_to".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        drop_puppeteer_base::error::ContractError::Unauthorized {}
+    );
+}
+
 #[test]
 fn test_execute_redelegate_sender_is_not_allowed() {
     let mut deps = mock_dependencies(&[]);
@@ -802,15 +891,9 @@ fn test_execute_redelegate_sender_not_idle() {
             reply_to: "some_reply_to".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -900,6 +983,7 @@ fn test_execute_tokenize_share_sender_is_not_allowed() {
         drop_staking_base::msg::puppeteer::ExecuteMsg::TokenizeShare {
             validator: "validator".to_string(),
             amount: Uint128::from(123u64),
+            request_id: None,
             reply_to: "some_reply_to".to_string(),
         },
     )
@@ -946,18 +1030,13 @@ fn test_execute_tokenize_share_sender_not_idle() {
         drop_staking_base::msg::puppeteer::ExecuteMsg::TokenizeShare {
             validator: "validator".to_string(),
             amount: Uint128::from(123u64),
+            request_id: None,
             reply_to: "some_reply_to".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -978,6 +1057,7 @@ fn test_execute_tokenize_share() {
         drop_staking_base::msg::puppeteer::ExecuteMsg::TokenizeShare {
             validator: "validator".to_string(),
             amount: Uint128::from(123u64),
+            request_id: None,
             reply_to: "some_reply_to".to_string(),
         },
     )
@@ -1102,15 +1182,9 @@ fn test_execute_redeeem_shares_sender_not_idle() {
             reply_to: "some_reply_to".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -1184,6 +1258,84 @@ fn test_execute_redeem_share() {
     );
 }
 
+#[test]
+fn test_execute_vote_weighted() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+    let options = vec![
+        drop_puppeteer_base::peripheral_hook::WeightedVoteOption {
+            option: drop_puppeteer_base::peripheral_hook::VoteOption::Yes,
+            weight: cosmwasm_std::Decimal::percent(70),
+        },
+        drop_puppeteer_base::peripheral_hook::WeightedVoteOption {
+            option: drop_puppeteer_base::peripheral_hook::VoteOption::NoWithVeto,
+            weight: cosmwasm_std::Decimal::percent(30),
+        },
+    ];
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::VoteWeighted {
+            proposal_id: 7,
+            options: options.clone(),
+            reply_to: "some_reply_to".to_string(),
+        },
+    )
+    .unwrap();
+    let any_msg = neutron_sdk::bindings::types::ProtobufAny {
+        type_url: "/cosmos.gov.v1beta1.MsgVoteWeighted".to_string(),
+        value: Binary::from(
+            cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVoteWeighted {
+                proposal_id: 7,
+                voter: "ica_address".to_string(),
+                options: vec![
+                    cosmos_sdk_proto::cosmos::gov::v1beta1::WeightedVoteOption {
+                        option: 1,
+                        weight: "700000000000000000".to_string(),
+                    },
+                    cosmos_sdk_proto::cosmos::gov::v1beta1::WeightedVoteOption {
+                        option: 4,
+                        weight: "300000000000000000".to_string(),
+                    },
+                ],
+            }
+            .encode_to_vec(),
+        ),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Custom(NeutronMsg::submit_tx(
+                "connection_id".to_string(),
+                "DROP".to_string(),
+                vec![any_msg],
+                "".to_string(),
+                100u64,
+                get_standard_fees()
+            )),
+            ReplyMsg::SudoPayload.to_reply_id()
+        )]
+    );
+    let tx_state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        tx_state.transaction,
+        Some(
+            drop_puppeteer_base::peripheral_hook::Transaction::VoteWeighted {
+                interchain_account_id: "DROP".to_string(),
+                proposal_id: 7,
+                options,
+            }
+        )
+    );
+}
+
 #[test]
 fn test_execute_claim_rewards_and_optionaly_transfer_sender_is_not_allowed() {
     let mut deps = mock_dependencies(&[]);
@@ -1260,15 +1412,9 @@ fn test_execute_claim_rewards_and_optionaly_transfer_not_idle() {
             reply_to: "some_reply_to".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -2011,15 +2157,9 @@ fn test_execute_transfer_not_idle() {
             reply_to: "neutron1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqhufaa6".to_string(),
         },
     )
-    .unwrap_err();
-    assert_eq!(
-        res,
-        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
-            cosmwasm_std::StdError::generic_err(
-                "Transaction txState is not equal to expected: Idle".to_string()
-            )
-        ))
-    );
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_tx_queued(deps.as_ref());
 }
 
 #[test]
@@ -2441,8 +2581,46 @@ fn test_sudo_response_ok() {
 }
 
 #[test]
-fn test_sudo_response_error() {
+fn test_sudo_response_tokenize_share() {
     let mut deps = mock_dependencies(&[]);
+
+    deps.querier.add_stargate_query_response(
+        "/ibc.core.channel.v1.Query/ChannelClientState",
+        |_data| {
+            to_json_binary(&ChannelClientStateResponse {
+                identified_client_state: Some(IdentifiedClientState {
+                    client_id: "07-tendermint-0".to_string(),
+                    client_state: ClientState {
+                        chain_id: "test-1".to_string(),
+                        type_url: "type_url".to_string(),
+                        trust_level: Fraction {
+                            numerator: Uint64::from(1u64),
+                            denominator: Uint64::from(3u64),
+                        },
+                        trusting_period: Some("1000".to_string()),
+                        unbonding_period: Some("1500".to_string()),
+                        max_clock_drift: Some("1000".to_string()),
+                        frozen_height: None,
+                        latest_height: Some(Height {
+                            revision_number: Uint64::from(0u64),
+                            revision_height: Uint64::from(54321u64),
+                        }),
+                        proof_specs: vec![],
+                        upgrade_path: vec![],
+                        allow_update_after_expiry: true,
+                        allow_update_after_misbehaviour: true,
+                    },
+                }),
+                proof: None,
+                proof_height: Height {
+                    revision_number: Uint64::from(0u64),
+                    revision_height: Uint64::from(33333u64),
+                },
+            })
+            .unwrap()
+        },
+    );
+
     let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
     let request = neutron_sdk::sudo::msg::RequestPacket {
         sequence: Some(1u64),
@@ -2454,18 +2632,27 @@ fn test_sudo_response_error() {
         timeout_height: None,
         timeout_timestamp: None,
     };
-    let transaction = drop_puppeteer_base::peripheral_hook::Transaction::IBCTransfer {
+    let transaction = drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare {
+        interchain_account_id: "DROP".to_string(),
+        validator: "validator".to_string(),
         denom: "remote_denom".to_string(),
-        amount: 1000u128,
-        real_amount: 1000u128,
-        recipient: "recipient".to_string(),
-        reason: drop_puppeteer_base::peripheral_hook::IBCTransferReason::Delegate,
+        amount: 123u128,
+        share: None,
+        request_id: Some(7),
     };
-    let msg = SudoMsg::Error {
-        request: request.clone(),
-        details: "some shit happened".to_string(),
+    let data = cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData {
+        data: vec![],
+        msg_responses: vec![prost_types::Any {
+            type_url: "/cosmos.staking.v1beta1.MsgTokenizeSharesResponse".to_string(),
+            value: drop_proto::proto::liquidstaking::staking::v1beta1::MsgTokenizeSharesResponse {
+                amount: Some(drop_proto::proto::cosmos::base::v1beta1::Coin {
+                    denom: "validator/1".to_string(),
+                    amount: "123".to_string(),
+                }),
+            }
+            .encode_to_vec(),
+        }],
     };
-    let env = mock_env();
     puppeteer_base
         .tx_state
         .save(
@@ -2474,48 +2661,195 @@ fn test_sudo_response_error() {
                 seq_id: None,
                 status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
                 reply_to: Some("reply_to_contract".to_string()),
-                transaction: Some(transaction.clone()),
+                transaction: Some(transaction),
             },
         )
         .unwrap();
-    let res = crate::contract::sudo(deps.as_mut(), env, msg).unwrap();
+    let res = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Response {
+            request,
+            data: Binary::from(data.encode_to_vec()),
+        },
+    )
+    .unwrap();
     assert_eq!(
-        res,
-        Response::new()
-            .add_message(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
-                contract_addr: "reply_to_contract".to_string(),
-                msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::PeripheralHook(
-                    Box::new(
-                        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
-                            drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
-                                transaction,
-                                details: "some shit happened".to_string()
-                            }
-                        )
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: "reply_to_contract".to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::PeripheralHook(
+                Box::new(
+                    drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Success(
+                        drop_puppeteer_base::peripheral_hook::ResponseHookSuccessMsg {
+                            local_height: 12345,
+                            remote_height: 54321,
+                            transaction:
+                                drop_puppeteer_base::peripheral_hook::Transaction::TokenizeShare {
+                                    interchain_account_id: "DROP".to_string(),
+                                    validator: "validator".to_string(),
+                                    denom: "remote_denom".to_string(),
+                                    amount: 123u128,
+                                    share: Some(coin(123, "validator/1")),
+                                    request_id: Some(7),
+                                },
+                        }
                     )
-                ))
-                .unwrap(),
-                funds: vec![Coin::new(1000u128, "remote_denom".to_string())]
-            }))
-            .add_event(Event::new("puppeteer-sudo-error").add_attributes(vec![
-                ("action", "sudo_error"),
-                ("request_id", "1"),
-                ("details", "some shit happened")
-            ]))
-    );
-    let ica = puppeteer_base.ica.load(deps.as_ref().storage).unwrap();
-    assert_eq!(
-        ica,
-        drop_helpers::ica::IcaState::Registered {
-            ica_address: "ica_address".to_string(),
-            port_id: "port".to_string(),
-            channel_id: "channel".to_string(),
-        }
+                )
+            ))
+            .unwrap(),
+            funds: vec![]
+        }))]
     );
-    let state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
-    assert_eq!(
-        state,
-        drop_puppeteer_base::state::TxState {
+}
+
+#[test]
+fn test_execute_transfer_share() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+    let env = mock_env();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::TransferShare {
+            share: coin(123, "validator/1"),
+            channel_id: "channel-0".to_string(),
+            receiver: "receiver".to_string(),
+            request_id: Some(7),
+            reply_to: "some_reply_to".to_string(),
+        },
+    )
+    .unwrap();
+    let any_msg = neutron_sdk::bindings::types::ProtobufAny {
+        type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+        value: Binary::from(
+            cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer {
+                source_port: "transfer".to_string(),
+                source_channel: "channel-0".to_string(),
+                token: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                    denom: "validator/1".to_string(),
+                    amount: "123".to_string(),
+                }),
+                sender: "ica_address".to_string(),
+                receiver: "receiver".to_string(),
+                timeout_height: None,
+                timeout_timestamp: env.block.time.plus_seconds(100).nanos(),
+            }
+            .encode_to_vec(),
+        ),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Custom(NeutronMsg::submit_tx(
+                "connection_id".to_string(),
+                "DROP".to_string(),
+                vec![any_msg],
+                "".to_string(),
+                100u64,
+                get_standard_fees()
+            )),
+            ReplyMsg::SudoPayload.to_reply_id()
+        )]
+    );
+    let tx_state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        tx_state.transaction,
+        Some(
+            drop_puppeteer_base::peripheral_hook::Transaction::TransferShare {
+                interchain_account_id: "DROP".to_string(),
+                share: coin(123, "validator/1"),
+                channel_id: "channel-0".to_string(),
+                receiver: "receiver".to_string(),
+                request_id: Some(7),
+            }
+        )
+    );
+}
+
+#[test]
+fn test_sudo_response_error() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+    let request = neutron_sdk::sudo::msg::RequestPacket {
+        sequence: Some(1u64),
+        source_port: Some("source_port".to_string()),
+        source_channel: Some("source_channel".to_string()),
+        destination_port: Some("destination_port".to_string()),
+        destination_channel: Some("destination_channel".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+    let transaction = drop_puppeteer_base::peripheral_hook::Transaction::IBCTransfer {
+        denom: "remote_denom".to_string(),
+        amount: 1000u128,
+        real_amount: 1000u128,
+        recipient: "recipient".to_string(),
+        reason: drop_puppeteer_base::peripheral_hook::IBCTransferReason::Delegate,
+    };
+    let msg = SudoMsg::Error {
+        request: request.clone(),
+        details: "some shit happened".to_string(),
+    };
+    let env = mock_env();
+    puppeteer_base
+        .tx_state
+        .save(
+            deps.as_mut().storage,
+            &drop_puppeteer_base::state::TxState {
+                seq_id: None,
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("reply_to_contract".to_string()),
+                transaction: Some(transaction.clone()),
+            },
+        )
+        .unwrap();
+    let res = crate::contract::sudo(deps.as_mut(), env, msg).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_message(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "reply_to_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::PeripheralHook(
+                    Box::new(
+                        drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
+                            drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
+                                transaction,
+                                details: "some shit happened".to_string()
+                            }
+                        )
+                    )
+                ))
+                .unwrap(),
+                funds: vec![Coin::new(1000u128, "remote_denom".to_string())]
+            }))
+            .add_event(Event::new("puppeteer-sudo-error").add_attributes(vec![
+                ("action", "sudo_error"),
+                ("request_id", "1"),
+                ("details", "some shit happened")
+            ]))
+    );
+    let ica = puppeteer_base.ica.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        ica,
+        drop_helpers::ica::IcaState::Registered {
+            ica_address: "ica_address".to_string(),
+            port_id: "port".to_string(),
+            channel_id: "channel".to_string(),
+        }
+    );
+    let state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        state,
+        drop_puppeteer_base::state::TxState {
             seq_id: None,
             status: drop_puppeteer_base::state::TxStateStatus::Idle,
             reply_to: None,
@@ -2611,6 +2945,19 @@ fn test_sudo_response_timeout() {
     );
     let ica = puppeteer_base.ica.load(deps.as_ref().storage).unwrap();
     assert_eq!(ica, drop_helpers::ica::IcaState::Timeout);
+    let ica_health = puppeteer_base
+        .ica_health
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(
+        ica_health,
+        drop_puppeteer_base::state::IcaHealth {
+            ica_address: Some("ica_address".to_string()),
+            timeout_count: 1,
+            last_timeout_height: Some(12345),
+            last_recovery_height: None,
+        }
+    );
     let state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
     assert_eq!(
         state,
@@ -3675,3 +4022,592 @@ fn test_unbonding_delegations() {
     .unwrap();
     assert_eq!(query_res, unbonding_delegations);
 }
+
+fn assert_tx_queued(deps: Deps<NeutronQuery>) {
+    let queue: Vec<QueuedTx> = from_json(
+        crate::contract::query(
+            deps,
+            mock_env(),
+            drop_puppeteer_base::msg::QueryMsg::TxQueue {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].id, 0);
+}
+
+fn queue_undelegate(deps: DepsMut<NeutronQuery>, puppeteer_base: &PuppeteerBaseType) {
+    puppeteer_base
+        .tx_state
+        .save(
+            deps.storage,
+            &drop_puppeteer_base::state::TxState {
+                seq_id: Some(1u64),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("reply_to_contract".to_string()),
+                transaction: Some(
+                    drop_puppeteer_base::peripheral_hook::Transaction::SetupProtocol {
+                        interchain_account_id: "ica_address".to_string(),
+                        rewards_withdraw_address: "rewards_withdraw_address".to_string(),
+                    },
+                ),
+            },
+        )
+        .unwrap();
+    crate::contract::execute(
+        deps,
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::Undelegate {
+            batch_id: 0u128,
+            items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+            reply_to: "some_reply_to".to_string(),
+        },
+    )
+    .unwrap();
+}
+
+mod tx_queue {
+    use super::*;
+
+    fn undelegate_transaction() -> drop_puppeteer_base::peripheral_hook::Transaction {
+        drop_puppeteer_base::peripheral_hook::Transaction::Undelegate {
+            interchain_account_id: "DROP".to_string(),
+            denom: "remote_denom".to_string(),
+            batch_id: 0u128,
+            items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+        }
+    }
+
+    #[test]
+    fn submitted_after_ack() {
+        let mut deps = mock_dependencies(&[]);
+        deps.querier.add_custom_query_response(|_| {
+            to_json_binary(&MinIbcFeeResponse {
+                min_fee: get_standard_fees(),
+            })
+            .unwrap()
+        });
+        deps.querier.add_stargate_query_response(
+            "/ibc.core.channel.v1.Query/ChannelClientState",
+            |_data| {
+                to_json_binary(&ChannelClientStateResponse {
+                    identified_client_state: Some(IdentifiedClientState {
+                        client_id: "07-tendermint-0".to_string(),
+                        client_state: ClientState {
+                            chain_id: "test-1".to_string(),
+                            type_url: "type_url".to_string(),
+                            trust_level: Fraction {
+                                numerator: Uint64::from(1u64),
+                                denominator: Uint64::from(3u64),
+                            },
+                            trusting_period: Some("1000".to_string()),
+                            unbonding_period: Some("1500".to_string()),
+                            max_clock_drift: Some("1000".to_string()),
+                            frozen_height: None,
+                            latest_height: Some(Height {
+                                revision_number: Uint64::from(0u64),
+                                revision_height: Uint64::from(54321u64),
+                            }),
+                            proof_specs: vec![],
+                            upgrade_path: vec![],
+                            allow_update_after_expiry: true,
+                            allow_update_after_misbehaviour: true,
+                        },
+                    }),
+                    proof: None,
+                    proof_height: Height {
+                        revision_number: Uint64::from(0u64),
+                        revision_height: Uint64::from(33333u64),
+                    },
+                })
+                .unwrap()
+            },
+        );
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        queue_undelegate(deps.as_mut(), &puppeteer_base);
+        assert_tx_queued(deps.as_ref());
+
+        let res = crate::contract::sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::Response {
+                request: neutron_sdk::sudo::msg::RequestPacket {
+                    sequence: Some(1u64),
+                    source_port: Some("source_port".to_string()),
+                    source_channel: Some("source_channel".to_string()),
+                    destination_port: Some("destination_port".to_string()),
+                    destination_channel: Some("destination_channel".to_string()),
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+                data: Binary::from(vec![]),
+            },
+        )
+        .unwrap();
+        let undelegate_msg = neutron_sdk::bindings::types::ProtobufAny {
+            type_url: "/cosmos.staking.v1beta1.MsgUndelegate".to_string(),
+            value: Binary::from(
+                cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate {
+                    delegator_address: "ica_address".to_string(),
+                    validator_address: "valoper1".to_string(),
+                    amount: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                        denom: "remote_denom".to_string(),
+                        amount: "1000".to_string(),
+                    }),
+                }
+                .encode_to_vec(),
+            ),
+        };
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[1],
+            SubMsg::reply_always(
+                CosmosMsg::Custom(NeutronMsg::submit_tx(
+                    "connection_id".to_string(),
+                    "DROP".to_string(),
+                    vec![undelegate_msg],
+                    "".to_string(),
+                    100u64,
+                    get_standard_fees()
+                )),
+                ReplyMsg::SubmitQueued.to_reply_id()
+            )
+        );
+        assert_eq!(
+            puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap(),
+            drop_puppeteer_base::state::TxState {
+                seq_id: None,
+                status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+                reply_to: Some("some_reply_to".to_string()),
+                transaction: Some(undelegate_transaction()),
+            }
+        );
+        // the transaction leaves the queue only once the submission succeeds
+        assert_tx_queued(deps.as_ref());
+    }
+
+    fn submitting_queued(deps: DepsMut<NeutronQuery>, puppeteer_base: &PuppeteerBaseType) {
+        puppeteer_base
+            .enqueue_tx(
+                deps.storage,
+                vec![],
+                undelegate_transaction(),
+                "some_reply_to".to_string(),
+                ReplyMsg::SudoPayload.to_reply_id(),
+            )
+            .unwrap();
+        puppeteer_base
+            .tx_state
+            .save(
+                deps.storage,
+                &drop_puppeteer_base::state::TxState {
+                    seq_id: None,
+                    status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+                    reply_to: Some("some_reply_to".to_string()),
+                    transaction: Some(undelegate_transaction()),
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn submit_queued_reply() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        submitting_queued(deps.as_mut(), &puppeteer_base);
+
+        crate::contract::reply(
+            deps.as_mut().into_empty(),
+            mock_env(),
+            cosmwasm_std::Reply {
+                id: ReplyMsg::SubmitQueued.to_reply_id(),
+                result: cosmwasm_std::SubMsgResult::Ok(cosmwasm_std::SubMsgResponse {
+                    events: vec![],
+                    data: Some(Binary::from(
+                        "{\"sequence_id\":0,\"channel\":\"channel-0\"}".as_bytes(),
+                    )),
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap(),
+            drop_puppeteer_base::state::TxState {
+                seq_id: Some(0),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("some_reply_to".to_string()),
+                transaction: Some(undelegate_transaction()),
+            }
+        );
+        assert!(puppeteer_base.tx_queue.is_empty(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn submit_queued_reply_error_keeps_tx_queued() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        submitting_queued(deps.as_mut(), &puppeteer_base);
+
+        let res = crate::contract::reply(
+            deps.as_mut().into_empty(),
+            mock_env(),
+            cosmwasm_std::Reply {
+                id: ReplyMsg::SubmitQueued.to_reply_id(),
+                result: cosmwasm_std::SubMsgResult::Err("insufficient fee".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new().add_event(
+                Event::new("puppeteer-base-submit-queued-failed")
+                    .add_attributes(vec![("details", "insufficient fee")])
+            )
+        );
+        assert_eq!(
+            puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap(),
+            drop_puppeteer_base::state::TxState::default()
+        );
+        assert_tx_queued(deps.as_ref());
+    }
+
+    #[test]
+    fn requeued_on_timeout() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        puppeteer_base
+            .in_flight_tx
+            .save(
+                deps.as_mut().storage,
+                &QueuedTx {
+                    id: 0,
+                    msgs: vec![],
+                    transaction: undelegate_transaction(),
+                    reply_to: "some_reply_to".to_string(),
+                    reply_id: ReplyMsg::SudoPayload.to_reply_id(),
+                },
+            )
+            .unwrap();
+        puppeteer_base
+            .tx_state
+            .save(
+                deps.as_mut().storage,
+                &drop_puppeteer_base::state::TxState {
+                    seq_id: Some(1u64),
+                    status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                    reply_to: Some("some_reply_to".to_string()),
+                    transaction: Some(undelegate_transaction()),
+                },
+            )
+            .unwrap();
+
+        let res = crate::contract::sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::Timeout {
+                request: neutron_sdk::sudo::msg::RequestPacket {
+                    sequence: Some(1u64),
+                    source_port: Some("source_port".to_string()),
+                    source_channel: Some("source_channel".to_string()),
+                    destination_port: Some("destination_port".to_string()),
+                    destination_channel: Some("destination_channel".to_string()),
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+            },
+        )
+        .unwrap();
+        // no error hook, the transaction is submitted again after the ICA recovery
+        assert_eq!(
+            res,
+            Response::new().add_event(Event::new("puppeteer-sudo-timeout").add_attributes(vec![
+                ("action", "sudo_timeout"),
+                ("request_id", "1"),
+                ("requeued_id", "0"),
+            ]))
+        );
+        assert_tx_queued(deps.as_ref());
+        assert!(puppeteer_base
+            .in_flight_tx
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            puppeteer_base.ica.load(deps.as_ref().storage).unwrap(),
+            drop_helpers::ica::IcaState::Timeout
+        );
+        assert_eq!(
+            puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap(),
+            drop_puppeteer_base::state::TxState::default()
+        );
+    }
+
+    #[test]
+    fn cancel_unauthorized() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        queue_undelegate(deps.as_mut(), &puppeteer_base);
+        let err = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::CancelQueued { id: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            drop_puppeteer_base::error::ContractError::Unauthorized {}
+        );
+    }
+
+    #[test]
+    fn cancel_not_found() {
+        let mut deps = mock_dependencies(&[]);
+        base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        let err = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::CancelQueued { id: 0 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            drop_puppeteer_base::error::ContractError::QueuedTxNotFound { id: 0 }
+        );
+    }
+
+    #[test]
+    fn cancel_by_reply_to() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        queue_undelegate(deps.as_mut(), &puppeteer_base);
+        let res = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some_reply_to", &[]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::CancelQueued { id: 0 },
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+        assert!(puppeteer_base.tx_queue.is_empty(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn cancel_by_owner_notifies_reply_to() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        queue_undelegate(deps.as_mut(), &puppeteer_base);
+        let res = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::CancelQueued { id: 0 },
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Response::new()
+                .add_message(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                    contract_addr: "some_reply_to".to_string(),
+                    msg: to_json_binary(
+                        &drop_puppeteer_base::peripheral_hook::ReceiverExecuteMsg::PeripheralHook(
+                            drop_puppeteer_base::peripheral_hook::ResponseHookMsg::Error(
+                                drop_puppeteer_base::peripheral_hook::ResponseHookErrorMsg {
+                                    transaction: undelegate_transaction(),
+                                    details: "Cancelled".to_string(),
+                                }
+                            )
+                        )
+                    )
+                    .unwrap(),
+                    funds: vec![]
+                }))
+                .add_event(
+                    Event::new("puppeteer-base-cancel-queued").add_attributes(vec![
+                        ("action", "cancel_queued"),
+                        ("id", "0"),
+                        ("reply_to", "some_reply_to"),
+                    ])
+                )
+        );
+        assert!(puppeteer_base.tx_queue.is_empty(deps.as_ref().storage));
+    }
+}
+
+mod ica_recovery {
+    use super::*;
+
+    fn open_ack_msg(address: &str) -> SudoMsg {
+        SudoMsg::OpenAck {
+            port_id: "port_id_1".to_string(),
+            channel_id: "channel_1".to_string(),
+            counterparty_channel_id: "counterparty_channel_id_1".to_string(),
+            counterparty_version: format!("{{\"version\": \"1\",\"controller_connection_id\": \"connection_id\",\"host_connection_id\": \"host_connection_id\",\"address\": \"{}\",\"encoding\": \"amino\",\"tx_type\": \"cosmos-sdk/MsgSend\"}}", address),
+        }
+    }
+
+    fn setup_timed_out(
+        deps: &mut cosmwasm_std::OwnedDeps<
+            cosmwasm_std::MemoryStorage,
+            cosmwasm_std::testing::MockApi,
+            drop_helpers::testing::WasmMockQuerier,
+            NeutronQuery,
+        >,
+    ) -> PuppeteerBaseType {
+        let puppeteer_base = base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        puppeteer_base
+            .set_ica_timeout(deps.as_mut().storage, 100)
+            .unwrap();
+        puppeteer_base
+    }
+
+    #[test]
+    fn recover_and_submit_after_open_ack() {
+        let mut deps = mock_dependencies(&[]);
+        deps.querier.add_custom_query_response(|_| {
+            to_json_binary(&MinIbcFeeResponse {
+                min_fee: get_standard_fees(),
+            })
+            .unwrap()
+        });
+        let puppeteer_base = setup_timed_out(&mut deps);
+
+        // permitted calls only queue transactions while the ICA is timed out
+        let res = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("allowed_sender", &[coin(1_000_000, "untrn")]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::Undelegate {
+                batch_id: 0u128,
+                items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+                reply_to: "some_reply_to".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages, vec![]);
+        assert_tx_queued(deps.as_ref());
+
+        let res = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[coin(1_500_000, "untrn")]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::RecoverICA {
+                register_fee: Uint128::new(1_000_000),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(NeutronMsg::register_interchain_account(
+                    "connection_id".to_string(),
+                    "DROP".to_string(),
+                    Some(vec![coin(1_000_000, "untrn")]),
+                )),
+                SubMsg::new(cosmwasm_std::BankMsg::Send {
+                    to_address: "anyone".to_string(),
+                    amount: vec![coin(500_000, "untrn")],
+                }),
+            ]
+        );
+        assert_eq!(
+            puppeteer_base.ica.load(deps.as_ref().storage).unwrap(),
+            drop_helpers::ica::IcaState::InProgress
+        );
+
+        let res =
+            crate::contract::sudo(deps.as_mut(), mock_env(), open_ack_msg("ica_address")).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            puppeteer_base
+                .tx_state
+                .load(deps.as_ref().storage)
+                .unwrap()
+                .status,
+            drop_puppeteer_base::state::TxStateStatus::InProgress
+        );
+        assert_tx_queued(deps.as_ref());
+
+        let ica_health: drop_puppeteer_base::state::IcaHealth = from_json(
+            crate::contract::query(
+                deps.as_ref(),
+                mock_env(),
+                drop_puppeteer_base::msg::QueryMsg::IcaHealth {},
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            ica_health,
+            drop_puppeteer_base::state::IcaHealth {
+                ica_address: Some("ica_address".to_string()),
+                timeout_count: 1,
+                last_timeout_height: Some(100),
+                last_recovery_height: Some(12345),
+            }
+        );
+    }
+
+    #[test]
+    fn recover_register_fee_not_paid() {
+        let mut deps = mock_dependencies(&[]);
+        let puppeteer_base = setup_timed_out(&mut deps);
+        let err = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[coin(500_000, "untrn")]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::RecoverICA {
+                register_fee: Uint128::new(1_000_000),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            drop_puppeteer_base::error::ContractError::InvalidFunds {
+                reason: "register fee 1000000untrn is not paid".to_string()
+            }
+        );
+        assert_eq!(
+            puppeteer_base.ica.load(deps.as_ref().storage).unwrap(),
+            drop_helpers::ica::IcaState::Timeout
+        );
+    }
+
+    #[test]
+    fn recover_not_timed_out() {
+        let mut deps = mock_dependencies(&[]);
+        base_init(&mut deps.as_mut(), "0.47.10".to_string());
+        let err = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[coin(1_000_000, "untrn")]),
+            drop_staking_base::msg::puppeteer::ExecuteMsg::RecoverICA {
+                register_fee: Uint128::new(1_000_000),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            drop_puppeteer_base::error::ContractError::IcaNotTimedOut {}
+        );
+    }
+
+    #[test]
+    fn address_mismatch() {
+        let mut deps = mock_dependencies(&[]);
+        setup_timed_out(&mut deps);
+        let err = crate::contract::sudo(deps.as_mut(), mock_env(), open_ack_msg("other_address"))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            NeutronError::Std(StdError::generic_err(
+                "recovered ICA address other_address does not match the stored one ica_address"
+            ))
+        );
+    }
+}
@@ -1,10 +1,11 @@
-use cosmwasm_std::{attr, to_json_binary, Attribute, Decimal, Deps};
+use cosmwasm_std::{attr, from_json, to_json_binary, Attribute, Decimal, Deps};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response};
 use cw_ownable::{get_ownership, update_ownership};
 use drop_helpers::answer::response;
 use drop_staking_base::error::redemption_rate_adapter::{ContractError, ContractResult};
 use drop_staking_base::msg::redemption_rate_adapter::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RedemptionRateResponse, UpdateConfig,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RedemptionRateParams, RedemptionRateResponse,
+    UpdateConfig,
 };
 
 use drop_staking_base::state::redemption_rate_adapter::{Config, CONFIG};
@@ -43,7 +44,9 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> ContractResul
     match msg {
         QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
         QueryMsg::Config {} => query_config(deps, env),
-        QueryMsg::RedemptionRate { denom, .. } => query_redemption_rate(deps, env, denom),
+        QueryMsg::RedemptionRate { denom, params } => {
+            query_redemption_rate(deps, env, denom, params)
+        }
     }
 }
 
@@ -56,11 +59,27 @@ fn query_redemption_rate(
     deps: Deps<NeutronQuery>,
     env: Env,
     denom: String,
+    params: Option<Binary>,
 ) -> ContractResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     if denom != config.denom {
         return Err(ContractError::InvalidDenom {});
     }
+    let twap_window_seconds = match params {
+        Some(params) => from_json::<RedemptionRateParams>(params)?.twap_window_seconds,
+        None => None,
+    };
+    if let Some(window_seconds) = twap_window_seconds {
+        let twap: drop_staking_base::msg::core::ExchangeRateTwapResponse =
+            deps.querier.query_wasm_smart(
+                config.core_contract,
+                &drop_staking_base::msg::core::QueryMsg::ExchangeRateTwap { window_seconds },
+            )?;
+        return Ok(to_json_binary(&RedemptionRateResponse {
+            redemption_rate: twap.twap,
+            update_time: twap.window_end,
+        })?);
+    }
     let exchange_rate: Decimal = deps.querier.query_wasm_smart(
         config.core_contract.clone(),
         &drop_staking_base::msg::core::QueryMsg::ExchangeRate {},
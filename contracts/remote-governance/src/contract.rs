@@ -0,0 +1,467 @@
+use crate::error::{ContractError, ContractResult};
+use cosmwasm_std::{
+    attr, ensure, ensure_eq, to_json_binary, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, Uint128, WasmMsg,
+};
+use drop_helpers::answer::response;
+use drop_puppeteer_base::peripheral_hook::{
+    ResponseHookMsg, Transaction, VoteOption, WeightedVoteOption,
+};
+use drop_staking_base::{
+    msg::{
+        provider_proposals::QueryMsg as ProviderProposalsQueryMsg,
+        puppeteer::ExecuteMsg as PuppeteerExecuteMsg,
+        remote_governance::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    },
+    state::{
+        provider_proposals::ProposalInfo,
+        remote_governance::{
+            Ballot, Config, ConfigOptional, ProposalVoting, SubmissionStatus, Tally, VoterSet,
+            BALLOTS, CONFIG, PROPOSALS,
+        },
+    },
+};
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+use std::collections::HashSet;
+
+pub const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const PROPOSAL_STATUS_VOTING_PERIOD: i32 = 2;
+// every failed submission may time out and close the puppeteer ICA channel
+pub const MAX_FAILED_SUBMISSIONS: u32 = 3;
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn instantiate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
+
+    let config = Config {
+        puppeteer_address: deps.api.addr_validate(&msg.puppeteer_address)?,
+        provider_proposals_address: deps.api.addr_validate(&msg.provider_proposals_address)?,
+        dasset_denom: msg.dasset_denom,
+        voter_set: msg.voter_set,
+        submission_window: msg.submission_window,
+    };
+    validate_voter_set(deps.as_ref(), &config.voter_set)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response(
+        "instantiate",
+        CONTRACT_NAME,
+        [
+            attr("puppeteer_address", config.puppeteer_address),
+            attr(
+                "provider_proposals_address",
+                config.provider_proposals_address,
+            ),
+            attr("dasset_denom", config.dasset_denom),
+            attr("voter_set", format!("{:?}", config.voter_set)),
+            attr("submission_window", config.submission_window.to_string()),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
+        QueryMsg::Config {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?)?),
+        QueryMsg::Proposal { proposal_id } => {
+            Ok(to_json_binary(&PROPOSALS.load(deps.storage, proposal_id)?)?)
+        }
+        QueryMsg::Ballot { proposal_id, voter } => Ok(to_json_binary(
+            &BALLOTS.may_load(deps.storage, (proposal_id, voter.as_str()))?,
+        )?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(response::<(&str, &str), _>(
+                "execute-update-ownership",
+                CONTRACT_NAME,
+                [],
+            ))
+        }
+        ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::Vote {
+            proposal_id,
+            options,
+        } => execute_vote(deps, env, info, proposal_id, options),
+        ExecuteMsg::Unlock { proposal_id } => execute_unlock(deps, env, info, proposal_id),
+        ExecuteMsg::SubmitVote { proposal_id } => execute_submit_vote(deps, env, info, proposal_id),
+        ExecuteMsg::PeripheralHook(msg) => execute_peripheral_hook(deps, info, msg),
+    }
+}
+
+fn execute_update_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    new_config: ConfigOptional,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut attrs = vec![];
+    if let Some(puppeteer_address) = new_config.puppeteer_address {
+        config.puppeteer_address = deps.api.addr_validate(puppeteer_address.as_str())?;
+        attrs.push(attr("puppeteer_address", &config.puppeteer_address));
+    }
+    if let Some(provider_proposals_address) = new_config.provider_proposals_address {
+        config.provider_proposals_address = deps
+            .api
+            .addr_validate(provider_proposals_address.as_str())?;
+        attrs.push(attr(
+            "provider_proposals_address",
+            &config.provider_proposals_address,
+        ));
+    }
+    if let Some(dasset_denom) = new_config.dasset_denom {
+        attrs.push(attr("dasset_denom", &dasset_denom));
+        config.dasset_denom = dasset_denom;
+    }
+    if let Some(voter_set) = new_config.voter_set {
+        validate_voter_set(deps.as_ref(), &voter_set)?;
+        attrs.push(attr("voter_set", format!("{:?}", voter_set)));
+        config.voter_set = voter_set;
+    }
+    if let Some(submission_window) = new_config.submission_window {
+        attrs.push(attr("submission_window", submission_window.to_string()));
+        config.submission_window = submission_window;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response("execute-update-config", CONTRACT_NAME, attrs))
+}
+
+fn execute_vote(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+) -> ContractResult<Response<NeutronMsg>> {
+    validate_vote_options(&options)?;
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    let mut proposal = match PROPOSALS.may_load(deps.storage, proposal_id)? {
+        Some(proposal) => proposal,
+        None => {
+            let proposal_info: ProposalInfo = deps.querier.query_wasm_smart(
+                &config.provider_proposals_address,
+                &ProviderProposalsQueryMsg::GetProposal { proposal_id },
+            )?;
+            let voting_end_time = match proposal_info.proposal.voting_end_time {
+                Some(voting_end_time)
+                    if proposal_info.proposal.status == PROPOSAL_STATUS_VOTING_PERIOD =>
+                {
+                    voting_end_time
+                }
+                _ => return Err(ContractError::ProposalNotInVotingPeriod { proposal_id }),
+            };
+            ProposalVoting {
+                voting_end_time,
+                tally: Tally::default(),
+                submission_status: None,
+                submitted_options: vec![],
+                failed_submissions: 0,
+            }
+        }
+    };
+    ensure!(
+        now < proposal.voting_end_time,
+        ContractError::VotingEnded { proposal_id }
+    );
+
+    let previous = BALLOTS.may_load(deps.storage, (proposal_id, info.sender.as_str()))?;
+    let previously_locked = previous.as_ref().and_then(|ballot| ballot.locked.clone());
+    let (power, locked) = match &config.voter_set {
+        VoterSet::Holders {} => {
+            let locked_amount = match &previously_locked {
+                Some(locked) => {
+                    ensure_eq!(
+                        locked.denom,
+                        config.dasset_denom,
+                        ContractError::LockedDenomChanged {
+                            denom: locked.denom.clone()
+                        }
+                    );
+                    locked.amount
+                }
+                None => Uint128::zero(),
+            };
+            let paid = if info.funds.is_empty() {
+                Uint128::zero()
+            } else {
+                cw_utils::must_pay(&info, &config.dasset_denom)?
+            };
+            let power = locked_amount + paid;
+            (
+                power,
+                (!power.is_zero()).then(|| Coin::new(power.u128(), &config.dasset_denom)),
+            )
+        }
+        VoterSet::Committee { members } => {
+            cw_utils::nonpayable(&info)?;
+            let power = members
+                .iter()
+                .find(|member| member.address == info.sender.as_str())
+                .map(|member| member.power)
+                .unwrap_or_default();
+            // dAsset locked before the switch to the committee stays unlockable
+            (power, previously_locked)
+        }
+    };
+    ensure!(!power.is_zero(), ContractError::NoVotingPower {});
+
+    if let Some(previous) = previous {
+        for one in previous.options.iter() {
+            let counted = proposal.tally.get_mut(one.option);
+            *counted = counted.checked_sub(previous.power * one.weight)?;
+        }
+    }
+    for one in options.iter() {
+        let counted = proposal.tally.get_mut(one.option);
+        *counted = counted.checked_add(power * one.weight)?;
+    }
+    BALLOTS.save(
+        deps.storage,
+        (proposal_id, info.sender.as_str()),
+        &Ballot {
+            power,
+            options: options.clone(),
+            locked,
+        },
+    )?;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(response(
+        "execute-vote",
+        CONTRACT_NAME,
+        [
+            attr("proposal_id", proposal_id.to_string()),
+            attr("voter", info.sender),
+            attr("power", power),
+            attr("options", format!("{:?}", options)),
+        ],
+    ))
+}
+
+fn execute_unlock(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    ensure!(
+        env.block.time.seconds() >= proposal.voting_end_time,
+        ContractError::VotingNotEnded { proposal_id }
+    );
+    // the ballot keeps the locked coin so that voter set or denom updates can't affect it
+    let locked = BALLOTS
+        .may_load(deps.storage, (proposal_id, info.sender.as_str()))?
+        .and_then(|ballot| ballot.locked)
+        .ok_or(ContractError::NothingToUnlock {})?;
+    BALLOTS.remove(deps.storage, (proposal_id, info.sender.as_str()));
+
+    Ok(response(
+        "execute-unlock",
+        CONTRACT_NAME,
+        [
+            attr("proposal_id", proposal_id.to_string()),
+            attr("voter", &info.sender),
+            attr("amount", locked.to_string()),
+        ],
+    )
+    .add_message(BankMsg::Send {
+        to_address: info.sender.into_string(),
+        amount: vec![locked],
+    }))
+}
+
+fn execute_submit_vote(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let now = env.block.time.seconds();
+    let opens_at = proposal
+        .voting_end_time
+        .saturating_sub(config.submission_window);
+    ensure!(
+        now >= opens_at,
+        ContractError::SubmissionWindowNotOpen { opens_at }
+    );
+    ensure!(
+        now < proposal.voting_end_time,
+        ContractError::VotingEnded { proposal_id }
+    );
+    ensure!(
+        proposal.submission_status != Some(SubmissionStatus::InProgress),
+        ContractError::SubmissionInProgress {}
+    );
+    ensure!(
+        proposal.failed_submissions < MAX_FAILED_SUBMISSIONS,
+        ContractError::TooManyFailedSubmissions {
+            max: MAX_FAILED_SUBMISSIONS
+        }
+    );
+
+    let options = tally_to_options(&proposal.tally).ok_or(ContractError::NoVotes {})?;
+    proposal.submission_status = Some(SubmissionStatus::InProgress);
+    proposal.submitted_options = options.clone();
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(response(
+        "execute-submit-vote",
+        CONTRACT_NAME,
+        [
+            attr("proposal_id", proposal_id.to_string()),
+            attr("options", format!("{:?}", options)),
+        ],
+    )
+    .add_message(WasmMsg::Execute {
+        contract_addr: config.puppeteer_address.into_string(),
+        msg: to_json_binary(&PuppeteerExecuteMsg::VoteWeighted {
+            proposal_id,
+            options,
+            reply_to: env.contract.address.into_string(),
+        })?,
+        funds: info.funds,
+    }))
+}
+
+fn execute_peripheral_hook(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    msg: ResponseHookMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_eq!(
+        info.sender,
+        config.puppeteer_address,
+        ContractError::Unauthorized {}
+    );
+    let (transaction, status) = match msg {
+        ResponseHookMsg::Success(success) => (success.transaction, SubmissionStatus::Submitted),
+        ResponseHookMsg::Error(error) => (error.transaction, SubmissionStatus::Failed),
+    };
+    let proposal_id = match transaction {
+        Transaction::VoteWeighted { proposal_id, .. } => proposal_id,
+        _ => return Err(ContractError::InvalidTransaction {}),
+    };
+    PROPOSALS.update(deps.storage, proposal_id, |proposal| {
+        let mut proposal = proposal.ok_or(ContractError::InvalidTransaction {})?;
+        if status == SubmissionStatus::Failed {
+            proposal.failed_submissions += 1;
+        }
+        proposal.submission_status = Some(status.clone());
+        ContractResult::Ok(proposal)
+    })?;
+
+    Ok(response(
+        "execute-peripheral-hook",
+        CONTRACT_NAME,
+        [
+            attr("proposal_id", proposal_id.to_string()),
+            attr("submission_status", format!("{:?}", status)),
+        ],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn migrate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let version: semver::Version = CONTRACT_VERSION.parse()?;
+    let storage_version: semver::Version =
+        cw2::get_contract_version(deps.storage)?.version.parse()?;
+
+    if storage_version < version {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new())
+}
+
+fn validate_voter_set(deps: Deps<NeutronQuery>, voter_set: &VoterSet) -> ContractResult<()> {
+    if let VoterSet::Committee { members } = voter_set {
+        let mut seen = HashSet::new();
+        for member in members {
+            deps.api.addr_validate(&member.address)?;
+            ensure!(
+                seen.insert(member.address.as_str()),
+                ContractError::DuplicatedCommitteeMember {
+                    address: member.address.clone(),
+                }
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_vote_options(options: &[WeightedVoteOption]) -> ContractResult<()> {
+    let invalid = |reason: &str| ContractError::InvalidVoteOptions {
+        reason: reason.to_string(),
+    };
+    ensure!(!options.is_empty(), invalid("no options given"));
+    let mut seen = HashSet::new();
+    let mut total = Decimal::zero();
+    for one in options {
+        ensure!(seen.insert(one.option), invalid("duplicated option"));
+        ensure!(!one.weight.is_zero(), invalid("zero weight"));
+        total = total.checked_add(one.weight)?;
+    }
+    ensure!(total == Decimal::one(), invalid("weights must sum up to 1"));
+    Ok(())
+}
+
+/// Turns the aggregated tally into weighted options. The last non-zero option
+/// takes the rounding remainder so the weights always sum up to exactly 1.
+fn tally_to_options(tally: &Tally) -> Option<Vec<WeightedVoteOption>> {
+    let voted: Vec<(VoteOption, Uint128)> = tally
+        .options()
+        .into_iter()
+        .filter(|(_, power)| !power.is_zero())
+        .collect();
+    let total: Uint128 = voted.iter().map(|(_, power)| *power).sum();
+    if total.is_zero() {
+        return None;
+    }
+    let mut options = vec![];
+    let mut remainder = Decimal::one();
+    for (i, (option, power)) in voted.iter().enumerate() {
+        let weight = if i + 1 == voted.len() {
+            remainder
+        } else {
+            Decimal::from_ratio(*power, total)
+        };
+        remainder -= weight;
+        options.push(WeightedVoteOption {
+            option: *option,
+            weight,
+        });
+    }
+    Some(options)
+}
@@ -0,0 +1,64 @@
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] cosmwasm_std::StdError),
+
+    #[error("{0}")]
+    OwnershipError(#[from] cw_ownable::OwnershipError),
+
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("invalid vote options: {reason}")]
+    InvalidVoteOptions { reason: String },
+
+    #[error("committee member {address} is listed more than once")]
+    DuplicatedCommitteeMember { address: String },
+
+    #[error("proposal {proposal_id} is not in the voting period")]
+    ProposalNotInVotingPeriod { proposal_id: u64 },
+
+    #[error("voting on proposal {proposal_id} is over")]
+    VotingEnded { proposal_id: u64 },
+
+    #[error("voting on proposal {proposal_id} is not over yet")]
+    VotingNotEnded { proposal_id: u64 },
+
+    #[error("sender has no voting power")]
+    NoVotingPower {},
+
+    #[error("nothing to unlock")]
+    NothingToUnlock {},
+
+    #[error("{denom} is locked for this proposal, unlock it once the voting is over")]
+    LockedDenomChanged { denom: String },
+
+    #[error("submission window opens at {opens_at}")]
+    SubmissionWindowNotOpen { opens_at: u64 },
+
+    #[error("vote submission is already in progress")]
+    SubmissionInProgress {},
+
+    #[error("vote submission failed {max} times, no more attempts are allowed")]
+    TooManyFailedSubmissions { max: u32 },
+
+    #[error("no votes to submit")]
+    NoVotes {},
+
+    #[error("invalid transaction")]
+    InvalidTransaction {},
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
@@ -0,0 +1,5 @@
+#[cfg(test)]
+mod tests;
+
+pub mod contract;
+pub mod error;
@@ -0,0 +1,999 @@
+use crate::{contract, error::ContractError};
+use cosmwasm_std::{
+    coin, coins, from_json,
+    testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR},
+    to_json_binary, Addr, BankMsg, Decimal, Env, Event, Response, SubMsg, Timestamp, Uint128,
+    WasmMsg,
+};
+use drop_helpers::testing::mock_dependencies;
+use drop_puppeteer_base::peripheral_hook::{
+    ResponseHookErrorMsg, ResponseHookMsg, ResponseHookSuccessMsg, Transaction, VoteOption,
+    WeightedVoteOption,
+};
+use drop_staking_base::{
+    msg::{
+        puppeteer::ExecuteMsg as PuppeteerExecuteMsg,
+        remote_governance::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    },
+    state::{
+        provider_proposals::ProposalInfo,
+        remote_governance::{
+            Ballot, CommitteeMember, Config, ProposalVoting, SubmissionStatus, Tally, VoterSet,
+            CONFIG, PROPOSALS,
+        },
+    },
+};
+use neutron_sdk::interchain_queries::v045::types::Proposal;
+
+const VOTING_END_TIME: u64 = 1_000_000;
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(seconds);
+    env
+}
+
+fn get_default_config(voter_set: VoterSet) -> Config {
+    Config {
+        puppeteer_address: Addr::unchecked("puppeteer"),
+        provider_proposals_address: Addr::unchecked("provider_proposals"),
+        dasset_denom: "dasset".to_string(),
+        voter_set,
+        submission_window: 3600,
+    }
+}
+
+fn get_proposal_info(status: i32, voting_end_time: Option<u64>) -> ProposalInfo {
+    ProposalInfo {
+        proposal: Proposal {
+            proposal_id: 1,
+            proposal_type: None,
+            total_deposit: vec![],
+            status,
+            submit_time: None,
+            deposit_end_time: None,
+            voting_start_time: None,
+            voting_end_time,
+            final_tally_result: None,
+        },
+        votes: None,
+        is_spam: false,
+    }
+}
+
+fn get_voting(tally: Tally, submission_status: Option<SubmissionStatus>) -> ProposalVoting {
+    ProposalVoting {
+        voting_end_time: VOTING_END_TIME,
+        tally,
+        submission_status,
+        submitted_options: vec![],
+        failed_submissions: 0,
+    }
+}
+
+fn single(option: VoteOption) -> Vec<WeightedVoteOption> {
+    vec![WeightedVoteOption {
+        option,
+        weight: Decimal::one(),
+    }]
+}
+
+#[test]
+fn instantiate() {
+    let mut deps = mock_dependencies(&[]);
+    let response = contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            puppeteer_address: "puppeteer".to_string(),
+            provider_proposals_address: "provider_proposals".to_string(),
+            dasset_denom: "dasset".to_string(),
+            voter_set: VoterSet::Holders {},
+            submission_window: 3600,
+        },
+    )
+    .unwrap();
+
+    cw_ownable::assert_owner(deps.as_ref().storage, &Addr::unchecked("owner")).unwrap();
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap(),
+        get_default_config(VoterSet::Holders {})
+    );
+    assert_eq!(
+        response,
+        Response::new().add_event(
+            Event::new("crates.io:drop-staking__drop-remote-governance-instantiate")
+                .add_attributes([
+                    ("puppeteer_address", "puppeteer"),
+                    ("provider_proposals_address", "provider_proposals"),
+                    ("dasset_denom", "dasset"),
+                    ("voter_set", "Holders"),
+                    ("submission_window", "3600"),
+                ])
+        )
+    );
+}
+
+#[test]
+fn instantiate_duplicated_committee_member() {
+    let mut deps = mock_dependencies(&[]);
+    let member = CommitteeMember {
+        address: "alice".to_string(),
+        power: Uint128::new(1),
+    };
+    let error = contract::instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            puppeteer_address: "puppeteer".to_string(),
+            provider_proposals_address: "provider_proposals".to_string(),
+            dasset_denom: "dasset".to_string(),
+            voter_set: VoterSet::Committee {
+                members: vec![member.clone(), member],
+            },
+            submission_window: 3600,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::DuplicatedCommitteeMember {
+            address: "alice".to_string()
+        }
+    );
+}
+
+#[test]
+fn vote_invalid_options() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: vec![
+                WeightedVoteOption {
+                    option: VoteOption::Yes,
+                    weight: Decimal::percent(50),
+                },
+                WeightedVoteOption {
+                    option: VoteOption::No,
+                    weight: Decimal::percent(40),
+                },
+            ],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::InvalidVoteOptions {
+            reason: "weights must sum up to 1".to_string()
+        }
+    );
+}
+
+#[test]
+fn vote_proposal_not_in_voting_period() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    deps.querier
+        .add_wasm_query_response("provider_proposals", |_| {
+            to_json_binary(&get_proposal_info(3, Some(VOTING_END_TIME))).unwrap()
+        });
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::ProposalNotInVotingPeriod { proposal_id: 1 }
+    );
+}
+
+#[test]
+fn vote_holders() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    deps.querier
+        .add_wasm_query_response("provider_proposals", |_| {
+            to_json_binary(&get_proposal_info(2, Some(VOTING_END_TIME))).unwrap()
+        });
+    contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap();
+    // the second vote adds the new funds to the locked ones and replaces the options
+    let response = contract::execute(
+        deps.as_mut(),
+        env_at(1),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: vec![
+                WeightedVoteOption {
+                    option: VoteOption::Yes,
+                    weight: Decimal::percent(75),
+                },
+                WeightedVoteOption {
+                    option: VoteOption::NoWithVeto,
+                    weight: Decimal::percent(25),
+                },
+            ],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.events[0].attributes[2].value,
+        Uint128::new(200).to_string()
+    );
+    contract::execute(
+        deps.as_mut(),
+        env_at(2),
+        mock_info("bob", &coins(50, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::No),
+        },
+    )
+    .unwrap();
+
+    let voting: ProposalVoting = from_json(
+        contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        voting,
+        get_voting(
+            Tally {
+                yes: Uint128::new(150),
+                abstain: Uint128::zero(),
+                no: Uint128::new(50),
+                no_with_veto: Uint128::new(50),
+            },
+            None
+        )
+    );
+    let ballot: Option<Ballot> = from_json(
+        contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Ballot {
+                proposal_id: 1,
+                voter: "bob".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ballot,
+        Some(Ballot {
+            power: Uint128::new(50),
+            options: single(VoteOption::No),
+            locked: Some(coin(50, "dasset")),
+        })
+    );
+}
+
+#[test]
+fn vote_holders_without_funds() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::NoVotingPower {});
+}
+
+#[test]
+fn vote_after_voting_end() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::VotingEnded { proposal_id: 1 });
+}
+
+#[test]
+fn vote_committee() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Committee {
+                members: vec![CommitteeMember {
+                    address: "alice".to_string(),
+                    power: Uint128::new(3),
+                }],
+            }),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("bob", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::NoVotingPower {});
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(1, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::PaymentError(cw_utils::PaymentError::NonPayable {})
+    );
+
+    contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Abstain),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        PROPOSALS.load(deps.as_ref().storage, 1).unwrap().tally,
+        Tally {
+            abstain: Uint128::new(3),
+            ..Tally::default()
+        }
+    );
+}
+
+#[test]
+fn unlock() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap();
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 1),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unlock { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::VotingNotEnded { proposal_id: 1 });
+
+    let response = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unlock { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(100, "dasset"),
+        })]
+    );
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unlock { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::NothingToUnlock {});
+}
+
+#[test]
+fn unlock_after_voter_set_switch() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap();
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Committee {
+                members: vec![CommitteeMember {
+                    address: "alice".to_string(),
+                    power: Uint128::new(3),
+                }],
+            }),
+        )
+        .unwrap();
+    // voting as a committee member keeps the dAsset locked before the switch
+    contract::execute(
+        deps.as_mut(),
+        env_at(1),
+        mock_info("alice", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::No),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        PROPOSALS.load(deps.as_ref().storage, 1).unwrap().tally,
+        Tally {
+            no: Uint128::new(3),
+            ..Tally::default()
+        }
+    );
+
+    let response = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unlock { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(100, "dasset"),
+        })]
+    );
+}
+
+#[test]
+fn unlock_committee_ballot_after_voter_set_switch() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Committee {
+                members: vec![CommitteeMember {
+                    address: "alice".to_string(),
+                    power: Uint128::new(3),
+                }],
+            }),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap();
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+
+    // the committee power was never locked, so there is no dAsset to take
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unlock { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::NothingToUnlock {});
+}
+
+#[test]
+fn unlock_after_dasset_denom_switch() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        env_at(0),
+        mock_info("alice", &coins(100, "dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::Yes),
+        },
+    )
+    .unwrap();
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                dasset_denom: "new_dasset".to_string(),
+                ..get_default_config(VoterSet::Holders {})
+            },
+        )
+        .unwrap();
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(1),
+        mock_info("alice", &coins(100, "new_dasset")),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            options: single(VoteOption::No),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::LockedDenomChanged {
+            denom: "dasset".to_string()
+        }
+    );
+
+    let response = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unlock { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(100, "dasset"),
+        })]
+    );
+}
+
+#[test]
+fn submit_vote_window_not_open() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(
+                Tally {
+                    yes: Uint128::new(1),
+                    ..Tally::default()
+                },
+                None,
+            ),
+        )
+        .unwrap();
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 3601),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::SubmissionWindowNotOpen {
+            opens_at: VOTING_END_TIME - 3600
+        }
+    );
+}
+
+#[test]
+fn submit_vote_no_votes() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), None),
+        )
+        .unwrap();
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::NoVotes {});
+}
+
+#[test]
+fn submit_vote() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(
+                Tally {
+                    yes: Uint128::new(1),
+                    abstain: Uint128::zero(),
+                    no: Uint128::new(1),
+                    no_with_veto: Uint128::new(1),
+                },
+                Some(SubmissionStatus::Failed),
+            ),
+        )
+        .unwrap();
+    let response = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 3600),
+        mock_info("anyone", &coins(10, "untrn")),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap();
+    let options = vec![
+        WeightedVoteOption {
+            option: VoteOption::Yes,
+            weight: Decimal::from_ratio(1u128, 3u128),
+        },
+        WeightedVoteOption {
+            option: VoteOption::No,
+            weight: Decimal::from_ratio(1u128, 3u128),
+        },
+        WeightedVoteOption {
+            option: VoteOption::NoWithVeto,
+            weight: Decimal::one() - Decimal::from_ratio(2u128, 3u128),
+        },
+    ];
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "puppeteer".to_string(),
+            msg: to_json_binary(&PuppeteerExecuteMsg::VoteWeighted {
+                proposal_id: 1,
+                options: options.clone(),
+                reply_to: MOCK_CONTRACT_ADDR.to_string(),
+            })
+            .unwrap(),
+            funds: coins(10, "untrn"),
+        })]
+    );
+    let voting = PROPOSALS.load(deps.as_ref().storage, 1).unwrap();
+    assert_eq!(voting.submission_status, Some(SubmissionStatus::InProgress));
+    assert_eq!(voting.submitted_options, options);
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::SubmissionInProgress {});
+}
+
+#[test]
+fn submit_vote_after_submitted() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(
+                Tally {
+                    yes: Uint128::new(1),
+                    ..Tally::default()
+                },
+                Some(SubmissionStatus::Submitted),
+            ),
+        )
+        .unwrap();
+    let response = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 3600),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(response.messages.len(), 1);
+    assert_eq!(
+        PROPOSALS
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .submission_status,
+        Some(SubmissionStatus::InProgress)
+    );
+}
+
+#[test]
+fn submit_vote_too_many_failed_submissions() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    let mut voting = get_voting(
+        Tally {
+            yes: Uint128::new(1),
+            ..Tally::default()
+        },
+        Some(SubmissionStatus::Failed),
+    );
+    voting.failed_submissions = contract::MAX_FAILED_SUBMISSIONS - 1;
+    PROPOSALS.save(deps.as_mut().storage, 1, &voting).unwrap();
+    contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 3600),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap();
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("puppeteer", &[]),
+        ExecuteMsg::PeripheralHook(ResponseHookMsg::Error(ResponseHookErrorMsg {
+            transaction: Transaction::VoteWeighted {
+                interchain_account_id: "drop".to_string(),
+                proposal_id: 1,
+                options: single(VoteOption::Yes),
+            },
+            details: "timeout".to_string(),
+        })),
+    )
+    .unwrap();
+
+    let error = contract::execute(
+        deps.as_mut(),
+        env_at(VOTING_END_TIME - 3000),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitVote { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::TooManyFailedSubmissions {
+            max: contract::MAX_FAILED_SUBMISSIONS
+        }
+    );
+}
+
+#[test]
+fn peripheral_hook() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(VoterSet::Holders {}),
+        )
+        .unwrap();
+    PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &get_voting(Tally::default(), Some(SubmissionStatus::InProgress)),
+        )
+        .unwrap();
+    let transaction = Transaction::VoteWeighted {
+        interchain_account_id: "drop".to_string(),
+        proposal_id: 1,
+        options: single(VoteOption::Yes),
+    };
+
+    let error = contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        ExecuteMsg::PeripheralHook(ResponseHookMsg::Error(ResponseHookErrorMsg {
+            transaction: transaction.clone(),
+            details: "".to_string(),
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::Unauthorized {});
+
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("puppeteer", &[]),
+        ExecuteMsg::PeripheralHook(ResponseHookMsg::Error(ResponseHookErrorMsg {
+            transaction: transaction.clone(),
+            details: "timeout".to_string(),
+        })),
+    )
+    .unwrap();
+    assert_eq!(
+        PROPOSALS
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .submission_status,
+        Some(SubmissionStatus::Failed)
+    );
+    assert_eq!(
+        PROPOSALS
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .failed_submissions,
+        1
+    );
+
+    contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("puppeteer", &[]),
+        ExecuteMsg::PeripheralHook(ResponseHookMsg::Success(ResponseHookSuccessMsg {
+            transaction,
+            local_height: 1,
+            remote_height: 1,
+        })),
+    )
+    .unwrap();
+    assert_eq!(
+        PROPOSALS
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .submission_status,
+        Some(SubmissionStatus::Submitted)
+    );
+}
@@ -45,8 +45,9 @@ pub fn instantiate(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
         QueryMsg::Config {} => query_config(deps, env),
-        QueryMsg::CalcDeposit { deposit } => query_calc_deposit(deps, deposit),
-        QueryMsg::CalcWithdraw { withdraw } => query_calc_withdraw(deps, withdraw),
+        QueryMsg::CalcDeposit { deposit } => query_calc_deposit(deps, env, deposit),
+        QueryMsg::CalcWithdraw { withdraw } => query_calc_withdraw(deps, env, withdraw),
+        QueryMsg::CalcRebalance { max_moves } => query_calc_rebalance(deps, env, max_moves),
         QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
     }
 }
@@ -61,10 +62,10 @@ fn query_config(deps: Deps, _env: Env) -> ContractResult<Binary> {
     })?)
 }
 
-pub fn query_calc_deposit(deps: Deps, deposit: Uint128) -> ContractResult<Binary> {
+pub fn query_calc_deposit(deps: Deps, env: Env, deposit: Uint128) -> ContractResult<Binary> {
     let factory_contract = FACTORY_CONTRACT.load(deps.storage)?.to_string();
     let addrs = drop_helpers::get_contracts!(deps, factory_contract, distribution_contract);
-    let delegations = prepare_delegation_data(deps)?;
+    let delegations = prepare_delegation_data(deps, &env)?;
 
     let deposit_changes: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
         addrs.distribution_contract.to_string(),
@@ -84,11 +85,11 @@ pub fn query_calc_deposit(deps: Deps, deposit: Uint128) -> ContractResult<Binary
     Ok(to_json_binary(&deposit_changes)?)
 }
 
-pub fn query_calc_withdraw(deps: Deps, withdraw: Uint128) -> ContractResult<Binary> {
+pub fn query_calc_withdraw(deps: Deps, env: Env, withdraw: Uint128) -> ContractResult<Binary> {
     let factory_contract = FACTORY_CONTRACT.load(deps.storage)?.to_string();
     let addrs = drop_helpers::get_contracts!(deps, factory_contract, distribution_contract);
 
-    let delegations = prepare_delegation_data(deps)?;
+    let delegations = prepare_delegation_data(deps, &env)?;
 
     let deposit_changes: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
         addrs.distribution_contract.to_string(),
@@ -108,8 +109,27 @@ pub fn query_calc_withdraw(deps: Deps, withdraw: Uint128) -> ContractResult<Bina
     Ok(to_json_binary(&deposit_changes)?)
 }
 
+pub fn query_calc_rebalance(deps: Deps, env: Env, max_moves: u32) -> ContractResult<Binary> {
+    let factory_contract = FACTORY_CONTRACT.load(deps.storage)?.to_string();
+    let addrs = drop_helpers::get_contracts!(deps, factory_contract, distribution_contract);
+
+    let delegations = prepare_delegation_data(deps, &env)?;
+
+    let redelegations: Vec<drop_staking_base::msg::distribution::Redelegation> =
+        deps.querier.query_wasm_smart(
+            addrs.distribution_contract.to_string(),
+            &drop_staking_base::msg::distribution::QueryMsg::CalcRebalance {
+                delegations,
+                max_moves,
+            },
+        )?;
+
+    Ok(to_json_binary(&redelegations)?)
+}
+
 fn prepare_delegation_data(
     deps: Deps,
+    env: &Env,
 ) -> NeutronResult<drop_staking_base::msg::distribution::Delegations> {
     let factory_contract = FACTORY_CONTRACT.load(deps.storage)?.to_string();
     let addrs = drop_helpers::get_contracts!(
@@ -133,6 +153,16 @@ fn prepare_delegation_data(
             &drop_staking_base::msg::validatorset::QueryMsg::Validators {},
         )?;
 
+    let weight_ramps: Vec<drop_staking_base::msg::validatorset::ValidatorWeightRamp> =
+        deps.querier.query_wasm_smart(
+            addrs.validators_set_contract.to_string(),
+            &drop_staking_base::msg::validatorset::QueryMsg::WeightRamps {},
+        )?;
+    let weight_ramps: HashMap<_, _> = weight_ramps
+        .into_iter()
+        .map(|weight_ramp| (weight_ramp.valoper_address, weight_ramp.ramp))
+        .collect();
+
     let mut delegations: Vec<drop_staking_base::msg::distribution::Delegation> = Vec::new();
     let mut total_delegations: Uint128 = Uint128::zero();
     let mut total_weight: u64 = 0;
@@ -151,15 +181,21 @@ fn prepare_delegation_data(
             .copied()
             .unwrap_or_default();
 
+        // weights move gradually while a ramp is in progress
+        let weight = weight_ramps
+            .get(&validator.valoper_address)
+            .and_then(|ramp| ramp.weight_at(env.block.time.seconds()))
+            .unwrap_or(validator.weight);
+
         let delegation = drop_staking_base::msg::distribution::Delegation {
             valoper_address: validator.valoper_address.clone(),
             stake: validator_denom_delegation,
-            weight: validator.weight,
+            weight,
             on_top: validator.on_top,
         };
 
         total_delegations += validator_denom_delegation;
-        total_weight += validator.weight;
+        total_weight += weight;
         total_on_top += validator.on_top;
         delegations.push(delegation);
     }
@@ -5,8 +5,8 @@ use crate::contract::{execute, instantiate, query};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Attribute, Binary, Decimal, Decimal256, Deps, Empty, Env,
-    Event, Response, StdResult, Timestamp, Uint128,
+    from_json, to_json_binary, to_json_vec, Addr, Attribute, Binary, Decimal, Decimal256, Deps,
+    Empty, Env, Event, Response, StdResult, Timestamp, Uint128,
 };
 use cw_multi_test::{custom_app, App, Contract, ContractWrapper, Executor};
 use drop_helpers::testing::mock_dependencies;
@@ -17,12 +17,14 @@ use drop_staking_base::error::factory::ContractError as FactoryContractError;
 use drop_staking_base::error::validatorset::ContractError as ValidatorSetContractError;
 use drop_staking_base::msg::strategy::QueryMsg;
 use drop_staking_base::msg::validatorset::QueryMsg as ValidatorSetQueryMsg;
+use drop_staking_base::msg::validatorset::ValidatorWeightRamp;
 use drop_staking_base::msg::{
     distribution::QueryMsg as DistributionQueryMsg, factory::QueryMsg as FactoryQueryMsg,
     strategy::InstantiateMsg,
 };
 use drop_staking_base::state::puppeteer::{Delegations, DropDelegation};
 use drop_staking_base::state::strategy::{DENOM, FACTORY_CONTRACT};
+use drop_staking_base::state::validatorset::WeightRamp;
 
 const CORE_CONTRACT_ADDR: &str = "core_contract";
 const FACTORY_CONTRACT_ADDR: &str = "factory_contract";
@@ -83,6 +85,8 @@ fn puppeteer_query(
         PuppeteerQueryMsg::Config {} => todo!(),
         PuppeteerQueryMsg::Ica {} => todo!(),
         PuppeteerQueryMsg::TxState {} => todo!(),
+        PuppeteerQueryMsg::TxQueue {} => todo!(),
+        PuppeteerQueryMsg::IcaHealth {} => todo!(),
         PuppeteerQueryMsg::Transactions {} => todo!(),
         PuppeteerQueryMsg::KVQueryIds {} => todo!(),
         PuppeteerQueryMsg::Extension { msg } => match msg {
@@ -180,7 +184,9 @@ fn instantiate_factory_contract(app: &mut App) -> Addr {
     instantiate_contract(app, factory_contract, "drop factory contract".to_string())
 }
 
-fn validator_set_query(_deps: Deps, _env: Env, msg: ValidatorSetQueryMsg) -> StdResult<Binary> {
+const WEIGHT_RAMPS_KEY: &[u8] = b"weight_ramps";
+
+fn validator_set_query(deps: Deps, _env: Env, msg: ValidatorSetQueryMsg) -> StdResult<Binary> {
     match msg {
         ValidatorSetQueryMsg::Ownership {} => todo!(),
         ValidatorSetQueryMsg::Config {} => todo!(),
@@ -207,19 +213,30 @@ fn validator_set_query(_deps: Deps, _env: Env, msg: ValidatorSetQueryMsg) -> Std
             }
             Ok(to_json_binary(&validators)?)
         }
+        ValidatorSetQueryMsg::ScoringPolicy {} => todo!(),
+        ValidatorSetQueryMsg::ValidatorScores {} => todo!(),
+        ValidatorSetQueryMsg::WeightRamps {} => match deps.storage.get(WEIGHT_RAMPS_KEY) {
+            Some(weight_ramps) => Ok(Binary::from(weight_ramps)),
+            None => to_json_binary(&Vec::<ValidatorWeightRamp>::new()),
+        },
+        ValidatorSetQueryMsg::DrainingValidators {} => todo!(),
     }
 }
 
 fn validator_set_contract() -> Box<dyn Contract<Empty>> {
     let contract: ContractWrapper<
-        EmptyMsg,
+        Vec<ValidatorWeightRamp>,
         EmptyMsg,
         ValidatorSetQueryMsg,
         ValidatorSetContractError,
         ValidatorSetContractError,
         cosmwasm_std::StdError,
     > = ContractWrapper::new(
-        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |deps, _, _, weight_ramps: Vec<ValidatorWeightRamp>| {
+            deps.storage
+                .set(WEIGHT_RAMPS_KEY, &to_json_vec(&weight_ramps)?);
+            Ok(Response::new())
+        },
         |_, _, _, _: EmptyMsg| Ok(Response::new()),
         validator_set_query,
     );
@@ -363,6 +380,64 @@ fn test_ideal_deposit_calculation() {
     );
 }
 
+#[test]
+fn test_deposit_calculation_with_weight_ramp() {
+    let mut app = mock_app();
+    let factory_contract = instantiate_factory_contract(&mut app);
+    let validator_set_contract = instantiate_validator_set_contract(&mut app);
+    let _puppeteer_contract = instantiate_puppeteer_contract(&mut app);
+    let _distribution_contract = instantiate_distribution_contract(&mut app);
+
+    let strategy_id = app.store_code(strategy_contract());
+
+    let strategy_contract = instantiate_strategy_contract(
+        &mut app,
+        strategy_id,
+        InstantiateMsg {
+            owner: CORE_CONTRACT_ADDR.to_string(),
+            factory_contract: factory_contract.to_string(),
+            denom: "uatom".to_string(),
+        },
+    );
+
+    // valoper0 is halfway through offboarding
+    let now = app.block_info().time.seconds();
+    app.execute_contract(
+        Addr::unchecked(CORE_CONTRACT_ADDR),
+        validator_set_contract,
+        &vec![ValidatorWeightRamp {
+            valoper_address: "valoper0".to_string(),
+            ramp: WeightRamp {
+                from_weight: 100,
+                to_weight: 0,
+                start: now - 500,
+                duration: 1000,
+            },
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let mut ideal_deposit: Vec<(String, Uint128)> = app
+        .wrap()
+        .query_wasm_smart(
+            strategy_contract,
+            &QueryMsg::CalcDeposit {
+                deposit: 100u128.into(),
+            },
+        )
+        .unwrap();
+    ideal_deposit.sort();
+
+    assert_eq!(
+        ideal_deposit,
+        vec![
+            ("valoper1".to_string(), Uint128::from(60u128)),
+            ("valoper2".to_string(), Uint128::from(40u128))
+        ]
+    );
+}
+
 #[test]
 fn test_ideal_withdraw_calculation() {
     let mut app = mock_app();
@@ -404,6 +479,34 @@ fn test_ideal_withdraw_calculation() {
     );
 }
 
+#[test]
+fn test_rebalance_calculation_balanced() {
+    let mut app = mock_app();
+    let factory_contract = instantiate_factory_contract(&mut app);
+    let _validator_set_contract = instantiate_validator_set_contract(&mut app);
+    let _puppeteer_contract = instantiate_puppeteer_contract(&mut app);
+    let _distribution_contract = instantiate_distribution_contract(&mut app);
+
+    let strategy_id = app.store_code(strategy_contract());
+
+    let strategy_contract = instantiate_strategy_contract(
+        &mut app,
+        strategy_id,
+        InstantiateMsg {
+            owner: CORE_CONTRACT_ADDR.to_string(),
+            factory_contract: factory_contract.to_string(),
+            denom: "uatom".to_string(),
+        },
+    );
+
+    let redelegations: Vec<drop_staking_base::msg::distribution::Redelegation> = app
+        .wrap()
+        .query_wasm_smart(strategy_contract, &QueryMsg::CalcRebalance { max_moves: 5 })
+        .unwrap();
+
+    assert_eq!(redelegations, vec![]);
+}
+
 #[test]
 fn test_update_config_unauthorized() {
     let mut deps = mock_dependencies(&[]);
@@ -1,21 +1,25 @@
-use cosmwasm_std::{attr, ensure_eq, to_json_binary, Addr, Attribute, Deps, Order, Uint128};
+use cosmwasm_std::{
+    attr, ensure, ensure_eq, to_json_binary, Addr, Attribute, Decimal, Deps, Order, Uint128,
+};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
 use cw_ownable::{get_ownership, update_ownership};
 use drop_helpers::answer::response;
 use drop_staking_base::error::validatorset::{ContractError, ContractResult};
 use drop_staking_base::msg::validatorset::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, OnTopEditOperation, QueryMsg, ValidatorData,
-    ValidatorInfoUpdate, ValidatorResponse,
+    DrainingValidator, ExecuteMsg, InstantiateMsg, MigrateMsg, OnTopEditOperation, QueryMsg,
+    ValidatorData, ValidatorInfoUpdate, ValidatorResponse, ValidatorScore, ValidatorWeightRamp,
+    WeightRampData,
 };
 use drop_staking_base::state::provider_proposals::ProposalInfo;
 use drop_staking_base::state::validatorset::{
-    Config, ConfigOptional, ValidatorInfo, CONFIG, CONFIG_DEPRECATED, VALIDATORS_LIST_CACHE,
-    VALIDATORS_LIST_CACHE_DEPRECATED, VALIDATORS_SET, VALIDATORS_SET_DEPRECATED,
+    Config, ConfigOptional, ScoringPolicy, ValidatorInfo, WeightRamp, CONFIG, CONFIG_DEPRECATED,
+    DRAINING_VALIDATORS, SCORING_POLICY, VALIDATORS_LIST_CACHE, VALIDATORS_LIST_CACHE_DEPRECATED,
+    VALIDATORS_SET, VALIDATORS_SET_DEPRECATED, VOTING_PROPOSALS, WEIGHT_RAMPS,
 };
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_sdk::bindings::query::NeutronQuery;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -35,6 +39,7 @@ pub fn instantiate(
         stats_contract: stats_contract.clone(),
         provider_proposals_contract: None,
         val_ref_contract: None,
+        puppeteer_contract: None,
     };
     CONFIG.save(deps.storage, config)?;
 
@@ -52,9 +57,44 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> ContractResul
         QueryMsg::Config {} => query_config(deps, env),
         QueryMsg::Validator { valoper } => query_validator(deps, valoper),
         QueryMsg::Validators {} => query_validators(deps),
+        QueryMsg::ScoringPolicy {} => Ok(to_json_binary(&SCORING_POLICY.may_load(deps.storage)?)?),
+        QueryMsg::ValidatorScores {} => query_validator_scores(deps),
+        QueryMsg::WeightRamps {} => query_weight_ramps(deps, env),
+        QueryMsg::DrainingValidators {} => query_draining_validators(deps),
     }
 }
 
+fn query_weight_ramps(deps: Deps<NeutronQuery>, env: Env) -> ContractResult<Binary> {
+    let now = env.block.time.seconds();
+    let ramps = WEIGHT_RAMPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |(_, ramp)| ramp.weight_at(now).is_some())
+        })
+        .map(|item| {
+            item.map(|(valoper_address, ramp)| ValidatorWeightRamp {
+                valoper_address,
+                ramp,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(to_json_binary(&ramps)?)
+}
+
+fn query_draining_validators(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+    let validators = DRAINING_VALIDATORS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(valoper_address, since)| DrainingValidator {
+                valoper_address,
+                since,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(to_json_binary(&validators)?)
+}
+
 fn query_config(deps: Deps<NeutronQuery>, _env: Env) -> ContractResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
     Ok(to_json_binary(&config)?)
@@ -71,6 +111,18 @@ fn query_validators(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
     Ok(to_json_binary(&validators)?)
 }
 
+fn query_validator_scores(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+    let scores = match SCORING_POLICY.may_load(deps.storage)? {
+        Some(policy) => VALIDATORS_LIST_CACHE
+            .load(deps.storage)?
+            .iter()
+            .map(|validator| calc_validator_score(&policy, validator))
+            .collect(),
+        None => vec![],
+    };
+    Ok(to_json_binary(&scores)?)
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn execute(
     deps: DepsMut<NeutronQuery>,
@@ -85,7 +137,7 @@ pub fn execute(
         }
         ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
         ExecuteMsg::UpdateValidators { validators } => {
-            execute_update_validators(deps, info, validators)
+            execute_update_validators(deps, env, info, validators)
         }
         ExecuteMsg::UpdateValidatorsInfo { validators } => {
             execute_update_validators_info(deps, info, validators)
@@ -94,6 +146,13 @@ pub fn execute(
             execute_update_validators_voting(deps, info, proposal)
         }
         ExecuteMsg::EditOnTop { operations } => execute_edit_on_top(deps, info, operations),
+        ExecuteMsg::UpdateScoringPolicy { policy } => {
+            execute_update_scoring_policy(deps, info, policy)
+        }
+        ExecuteMsg::ScheduleWeightRamps { ramps } => {
+            execute_schedule_weight_ramps(deps, env, info, ramps)
+        }
+        ExecuteMsg::ReleaseDrainingValidators {} => execute_release_draining_validators(deps),
     }
 }
 
@@ -126,6 +185,11 @@ fn execute_update_config(
         attrs.push(attr("val_ref_contract", val_ref_contract));
     }
 
+    if let Some(puppeteer_contract) = new_config.puppeteer_contract {
+        state.puppeteer_contract = Some(deps.api.addr_validate(&puppeteer_contract)?);
+        attrs.push(attr("puppeteer_contract", puppeteer_contract));
+    }
+
     CONFIG.save(deps.storage, &state)?;
 
     Ok(response("update_config", CONTRACT_NAME, Vec::<Attribute>::new()).add_attributes(attrs))
@@ -133,6 +197,7 @@ fn execute_update_config(
 
 fn execute_update_validators(
     deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     validators: Vec<ValidatorData>,
 ) -> ContractResult<Response<NeutronMsg>> {
@@ -147,27 +212,25 @@ fn execute_update_validators(
 
     VALIDATORS_SET.clear(deps.storage);
 
+    let new_validators: HashSet<String> = validators
+        .iter()
+        .map(|validator| validator.valoper_address.clone())
+        .collect();
+
     for validator in validators {
         let on_top_value = old_validator_set
             .get(&validator.valoper_address)
             .map(|validator_info| validator_info.on_top)
             .unwrap_or_default();
 
-        let validator_info = ValidatorInfo {
-            valoper_address: validator.valoper_address,
-            weight: validator.weight,
-            on_top: validator.on_top.unwrap_or(on_top_value),
-            last_processed_remote_height: None,
-            last_processed_local_height: None,
-            last_validated_height: None,
-            last_commission_in_range: None,
-            uptime: Default::default(),
-            tombstone: false,
-            jailed_number: None,
-            init_proposal: None,
-            total_passed_proposals: 0,
-            total_voted_proposals: 0,
-        };
+        let validator_info = new_validator_info(
+            validator.valoper_address,
+            validator.weight,
+            validator.on_top.unwrap_or(on_top_value),
+        );
+        // the whole set is replaced right away, pending ramps are dropped
+        WEIGHT_RAMPS.remove(deps.storage, &validator_info.valoper_address);
+        DRAINING_VALIDATORS.remove(deps.storage, &validator_info.valoper_address);
         VALIDATORS_SET.save(
             deps.storage,
             &validator_info.valoper_address,
@@ -175,15 +238,175 @@ fn execute_update_validators(
         )?;
     }
 
+    // removed validators still hold delegations, so they stay with zero weight
+    // until the stake is moved away
+    let mut draining_count = 0u64;
+    for (valoper_address, mut validator_info) in old_validator_set {
+        if new_validators.contains(&valoper_address) {
+            continue;
+        }
+
+        validator_info.weight = 0;
+        validator_info.on_top = Uint128::zero();
+        if !DRAINING_VALIDATORS.has(deps.storage, &valoper_address) {
+            DRAINING_VALIDATORS.save(deps.storage, &valoper_address, &env.block.time.seconds())?;
+        }
+        // a ramp down to zero keeps offboarding the validator gradually
+        if WEIGHT_RAMPS
+            .may_load(deps.storage, &valoper_address)?
+            .map_or(false, |ramp| ramp.to_weight != 0)
+        {
+            WEIGHT_RAMPS.remove(deps.storage, &valoper_address);
+        }
+        VALIDATORS_SET.save(deps.storage, &valoper_address, &validator_info)?;
+        draining_count += 1;
+    }
+
     update_validators_list(deps)?;
 
     Ok(response(
         "update_validators",
         CONTRACT_NAME,
-        [attr("total_count", total_count.to_string())],
+        [
+            attr("total_count", total_count.to_string()),
+            attr("draining_count", draining_count.to_string()),
+        ],
     ))
 }
 
+fn execute_schedule_weight_ramps(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    ramps: Vec<WeightRampData>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    // ramped weights would be overwritten by scores right away
+    ensure!(
+        SCORING_POLICY.may_load(deps.storage)?.is_none(),
+        ContractError::ScoringPolicyIsActive {}
+    );
+
+    let now = env.block.time.seconds();
+    let mut attrs: Vec<Attribute> = Vec::new();
+
+    for ramp in ramps {
+        let (mut validator_info, current_weight) =
+            match VALIDATORS_SET.may_load(deps.storage, &ramp.valoper_address)? {
+                Some(validator_info) => {
+                    let current_weight = WEIGHT_RAMPS
+                        .may_load(deps.storage, &ramp.valoper_address)?
+                        .and_then(|ramp| ramp.weight_at(now))
+                        .unwrap_or(validator_info.weight);
+                    (validator_info, current_weight)
+                }
+                None => (
+                    new_validator_info(ramp.valoper_address.clone(), 0, Uint128::zero()),
+                    0,
+                ),
+            };
+
+        validator_info.weight = ramp.target_weight;
+        if ramp.target_weight > 0 {
+            DRAINING_VALIDATORS.remove(deps.storage, &ramp.valoper_address);
+        }
+        if ramp.duration == 0 {
+            WEIGHT_RAMPS.remove(deps.storage, &ramp.valoper_address);
+        } else {
+            WEIGHT_RAMPS.save(
+                deps.storage,
+                &ramp.valoper_address,
+                &WeightRamp {
+                    from_weight: current_weight,
+                    to_weight: ramp.target_weight,
+                    start: now,
+                    duration: ramp.duration,
+                },
+            )?;
+        }
+        VALIDATORS_SET.save(deps.storage, &ramp.valoper_address, &validator_info)?;
+
+        attrs.push(attr(
+            ramp.valoper_address,
+            format!("{}-{}", current_weight, ramp.target_weight),
+        ));
+    }
+
+    update_validators_list(deps)?;
+
+    Ok(response(
+        "execute_schedule_weight_ramps",
+        CONTRACT_NAME,
+        attrs,
+    ))
+}
+
+fn execute_release_draining_validators(
+    deps: DepsMut<NeutronQuery>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let puppeteer_contract = config
+        .puppeteer_contract
+        .ok_or(ContractError::PuppeteerNotConfigured {})?;
+
+    let delegations: drop_staking_base::msg::puppeteer::DelegationsResponse =
+        deps.querier.query_wasm_smart(
+            puppeteer_contract,
+            &drop_puppeteer_base::msg::QueryMsg::Extension {
+                msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Delegations {},
+            },
+        )?;
+    let delegated: HashSet<String> = delegations
+        .delegations
+        .delegations
+        .into_iter()
+        .filter(|delegation| !delegation.amount.amount.is_zero())
+        .map(|delegation| delegation.validator)
+        .collect();
+
+    let draining = DRAINING_VALIDATORS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut released: Vec<String> = Vec::new();
+    for (valoper_address, since) in draining {
+        // a snapshot taken before the drain started doesn't show the stake moved away
+        if delegations.timestamp.seconds() <= since || delegated.contains(&valoper_address) {
+            continue;
+        }
+        VALIDATORS_SET.remove(deps.storage, &valoper_address);
+        WEIGHT_RAMPS.remove(deps.storage, &valoper_address);
+        DRAINING_VALIDATORS.remove(deps.storage, &valoper_address);
+        released.push(valoper_address);
+    }
+
+    update_validators_list(deps)?;
+
+    Ok(response(
+        "execute_release_draining_validators",
+        CONTRACT_NAME,
+        [attr("released", released.join(","))],
+    ))
+}
+
+fn new_validator_info(valoper_address: String, weight: u64, on_top: Uint128) -> ValidatorInfo {
+    ValidatorInfo {
+        valoper_address,
+        weight,
+        on_top,
+        last_processed_remote_height: None,
+        last_processed_local_height: None,
+        last_validated_height: None,
+        last_commission_in_range: None,
+        uptime: Default::default(),
+        tombstone: false,
+        jailed_number: None,
+        init_proposal: None,
+        total_passed_proposals: 0,
+        total_voted_proposals: 0,
+    }
+}
+
 fn execute_update_validators_info(
     deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
@@ -263,6 +486,16 @@ fn execute_update_validators_voting(
         ContractError::Unauthorized {}
     );
 
+    let proposal_id = proposal.proposal.proposal_id;
+    if VOTING_PROPOSALS.has(deps.storage, proposal_id) {
+        return Ok(response(
+            "execute_update_validators_voting",
+            CONTRACT_NAME,
+            [attr("already_processed_proposal", proposal_id.to_string())],
+        ));
+    }
+    VOTING_PROPOSALS.save(deps.storage, proposal_id, &proposal.is_spam)?;
+
     if proposal.is_spam {
         return Ok(response(
             "update_validators_info",
@@ -362,13 +595,135 @@ fn execute_edit_on_top(
     Ok(response("execute-edit-on-top", CONTRACT_NAME, attrs))
 }
 
+fn execute_update_scoring_policy(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    policy: Option<ScoringPolicy>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let attrs = match policy {
+        Some(policy) => {
+            ensure!(
+                !(policy.uptime_coefficient
+                    + policy.commission_coefficient
+                    + policy.voting_coefficient)
+                    .is_zero(),
+                ContractError::InvalidScoringPolicy {
+                    reason: "coefficients sum must be greater than zero".to_string()
+                }
+            );
+            ensure!(
+                policy.min_uptime <= Decimal::one()
+                    && policy.min_voting_participation <= Decimal::one(),
+                ContractError::InvalidScoringPolicy {
+                    reason: "thresholds must not be greater than one".to_string()
+                }
+            );
+            SCORING_POLICY.save(deps.storage, &policy)?;
+            // scores replace manual weights, including the ones still being ramped
+            WEIGHT_RAMPS.clear(deps.storage);
+            vec![
+                attr("enabled", "true"),
+                attr("base_weight", policy.base_weight.to_string()),
+            ]
+        }
+        None => {
+            SCORING_POLICY.remove(deps.storage);
+            vec![attr("enabled", "false")]
+        }
+    };
+
+    update_validators_list(deps)?;
+
+    Ok(response("update_scoring_policy", CONTRACT_NAME, attrs))
+}
+
+pub fn calc_validator_score(policy: &ScoringPolicy, validator: &ValidatorInfo) -> ValidatorScore {
+    let commission_in_range = match (
+        validator.last_commission_in_range,
+        validator.last_processed_local_height,
+    ) {
+        (Some(in_range_height), Some(processed_height)) => {
+            in_range_height + policy.commission_max_age >= processed_height
+        }
+        _ => false,
+    };
+    // validators which haven't seen any proposal yet are not penalized
+    let voting_participation = if validator.total_passed_proposals == 0 {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(
+            validator.total_voted_proposals,
+            validator.total_passed_proposals,
+        )
+    };
+    let jailed_number = validator.jailed_number.unwrap_or_default();
+    let jail_penalty =
+        (policy.jail_penalty * Decimal::from_ratio(jailed_number, 1u64)).min(Decimal::one());
+
+    let excluded_reason = if validator.tombstone {
+        Some("tombstoned")
+    } else if validator.uptime < policy.min_uptime {
+        Some("uptime is below minimum")
+    } else if jailed_number > policy.max_jailed_number {
+        Some("jailed too many times")
+    } else if voting_participation < policy.min_voting_participation {
+        Some("voting participation is below minimum")
+    } else {
+        None
+    };
+
+    let score = if excluded_reason.is_some() {
+        Decimal::zero()
+    } else {
+        let commission_score = if commission_in_range {
+            Decimal::one()
+        } else {
+            Decimal::zero()
+        };
+        let total_coefficient =
+            policy.uptime_coefficient + policy.commission_coefficient + policy.voting_coefficient;
+        let weighted = policy.uptime_coefficient * validator.uptime
+            + policy.commission_coefficient * commission_score
+            + policy.voting_coefficient * voting_participation;
+        Decimal::from_ratio(weighted.atomics(), total_coefficient.atomics())
+            * (Decimal::one() - jail_penalty)
+    };
+
+    ValidatorScore {
+        valoper_address: validator.valoper_address.clone(),
+        uptime: validator.uptime,
+        commission_in_range,
+        voting_participation,
+        jail_penalty,
+        excluded_reason: excluded_reason.map(|reason| reason.to_string()),
+        score,
+        weight: (Uint128::from(policy.base_weight) * score).u128() as u64,
+    }
+}
+
 fn update_validators_list(deps: DepsMut<NeutronQuery>) -> StdResult<()> {
     let validators: StdResult<Vec<_>> = VALIDATORS_SET
         .range_raw(deps.storage, None, None, Order::Ascending)
         .map(|item| item.map(|(_key, value)| value))
         .collect();
+    let mut validators = validators.unwrap_or_default();
+
+    // weights are derived from validators stats once a scoring policy is set
+    if let Some(policy) = SCORING_POLICY.may_load(deps.storage)? {
+        for validator in validators.iter_mut() {
+            validator.weight = if DRAINING_VALIDATORS.has(deps.storage, &validator.valoper_address)
+            {
+                0
+            } else {
+                calc_validator_score(&policy, validator).weight
+            };
+            VALIDATORS_SET.save(deps.storage, &validator.valoper_address, validator)?;
+        }
+    }
 
-    VALIDATORS_LIST_CACHE.save(deps.storage, &validators.unwrap_or_default())?;
+    VALIDATORS_LIST_CACHE.save(deps.storage, &validators)?;
 
     Ok(())
 }
@@ -390,6 +745,7 @@ pub fn migrate(
         CONFIG_DEPRECATED.remove(deps.storage);
         let new_config = Config {
             val_ref_contract: None,
+            puppeteer_contract: None,
             stats_contract: old_config.stats_contract,
             provider_proposals_contract: old_config.provider_proposals_contract,
         };
@@ -29,6 +29,7 @@ fn instantiate() {
             stats_contract: Addr::unchecked("stats_contract"),
             provider_proposals_contract: None,
             val_ref_contract: None,
+            puppeteer_contract: None,
         }
     );
 
@@ -53,6 +54,7 @@ fn query_config() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -69,6 +71,7 @@ fn query_config() {
             stats_contract: Addr::unchecked("stats_contract"),
             provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
             val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+            puppeteer_contract: None,
         })
         .unwrap()
     );
@@ -85,6 +88,7 @@ fn update_config_wrong_owner() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -98,6 +102,7 @@ fn update_config_wrong_owner() {
                 stats_contract: Some("stats_contract1".to_string()),
                 provider_proposals_contract: Some("provider_proposals_contract1".to_string()),
                 val_ref_contract: Some("val_ref_contract1".to_string()),
+                puppeteer_contract: None,
             },
         },
     )
@@ -129,6 +134,7 @@ fn update_config_ok() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -142,6 +148,7 @@ fn update_config_ok() {
                 stats_contract: Some("stats_contract1".to_string()),
                 provider_proposals_contract: Some("provider_proposals_contract1".to_string()),
                 val_ref_contract: Some("val_ref_contract1".to_string()),
+                puppeteer_contract: None,
             },
         },
     )
@@ -160,6 +167,7 @@ fn update_config_ok() {
             stats_contract: Addr::unchecked("stats_contract1"),
             provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract1")),
             val_ref_contract: Some(Addr::unchecked("val_ref_contract1")),
+            puppeteer_contract: None,
         })
         .unwrap()
     );
@@ -443,6 +451,7 @@ fn update_validators_info_wrong_sender() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -504,6 +513,7 @@ fn update_validators_info_ok() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -585,6 +595,7 @@ fn test_execute_update_validators_voting_unauthorized() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -627,6 +638,7 @@ fn test_execute_update_validators_voting_spam_proposal() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -674,6 +686,7 @@ fn test_execute_update_validators_voting() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -744,6 +757,64 @@ fn test_execute_update_validators_voting() {
     );
 }
 
+#[test]
+fn test_execute_update_validators_voting_already_processed() {
+    let mut deps = mock_dependencies(&[]);
+    drop_staking_base::state::validatorset::CONFIG
+        .save(
+            deps.as_mut().storage,
+            &drop_staking_base::state::validatorset::Config {
+                stats_contract: Addr::unchecked("stats_contract"),
+                provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
+                val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
+            },
+        )
+        .unwrap();
+    drop_staking_base::state::validatorset::VOTING_PROPOSALS
+        .save(deps.as_mut().storage, 1u64, &false)
+        .unwrap();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("provider_proposals_contract", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateValidatorsVoting {
+            proposal: ProposalInfo {
+                proposal: neutron_sdk::interchain_queries::v047::types::Proposal {
+                    proposal_id: 1u64,
+                    proposal_type: None,
+                    total_deposit: vec![],
+                    status: 3i32,
+                    submit_time: None,
+                    deposit_end_time: None,
+                    voting_start_time: None,
+                    voting_end_time: None,
+                    final_tally_result: None,
+                },
+                votes: Some(vec![
+                    neutron_sdk::interchain_queries::v047::types::ProposalVote {
+                        proposal_id: 1u64,
+                        voter: "voter".to_string(),
+                        options: vec![],
+                    },
+                ]),
+                is_spam: false,
+            },
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        cosmwasm_std::Response::new().add_event(
+            cosmwasm_std::Event::new(
+                "crates.io:drop-staking__drop-validators-set-execute_update_validators_voting"
+                    .to_string()
+            )
+            .add_attribute("already_processed_proposal".to_string(), "1".to_string())
+        )
+    );
+}
+
 #[test]
 fn query_ownership() {
     let mut deps = mock_dependencies(&[]);
@@ -821,6 +892,7 @@ fn execute_edit_on_top_unauthorized_no_authorizations() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: None,
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -855,6 +927,7 @@ fn execute_edit_on_top_unauthorized_stranger() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -889,6 +962,7 @@ fn execute_edit_on_top_authorized_owner() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -925,6 +999,7 @@ fn execute_edit_on_top_authorized_val_ref_contract() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -956,6 +1031,7 @@ fn execute_edit_on_top_add() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -1038,6 +1114,7 @@ fn execute_edit_on_top_subtract() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -1120,6 +1197,7 @@ fn execute_edit_on_top_mixed() {
                 stats_contract: Addr::unchecked("stats_contract"),
                 provider_proposals_contract: Some(Addr::unchecked("provider_proposals_contract")),
                 val_ref_contract: Some(Addr::unchecked("val_ref_contract")),
+                puppeteer_contract: None,
             },
         )
         .unwrap();
@@ -1236,3 +1314,574 @@ fn execute_edit_on_top_mixed() {
         )
     );
 }
+
+fn default_scoring_policy() -> drop_staking_base::state::validatorset::ScoringPolicy {
+    drop_staking_base::state::validatorset::ScoringPolicy {
+        base_weight: 1000,
+        min_uptime: Decimal::percent(90),
+        uptime_coefficient: Decimal::one(),
+        commission_coefficient: Decimal::one(),
+        commission_max_age: 100,
+        voting_coefficient: Decimal::from_ratio(2u128, 1u128),
+        min_voting_participation: Decimal::percent(50),
+        jail_penalty: Decimal::percent(10),
+        max_jailed_number: 3,
+    }
+}
+
+fn scored_validator(
+    valoper_address: &str,
+    uptime: Decimal,
+    last_commission_in_range: Option<u64>,
+    jailed_number: Option<u64>,
+    total_voted_proposals: u64,
+) -> drop_staking_base::state::validatorset::ValidatorInfo {
+    drop_staking_base::state::validatorset::ValidatorInfo {
+        valoper_address: valoper_address.to_string(),
+        weight: 1,
+        on_top: Uint128::zero(),
+        last_processed_remote_height: Some(1000),
+        last_processed_local_height: Some(1000),
+        last_validated_height: Some(1000),
+        last_commission_in_range,
+        uptime,
+        tombstone: false,
+        jailed_number,
+        init_proposal: Some(1),
+        total_passed_proposals: 4,
+        total_voted_proposals,
+    }
+}
+
+#[test]
+fn update_scoring_policy_wrong_owner() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core1", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateScoringPolicy {
+            policy: Some(default_scoring_policy()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        drop_staking_base::error::validatorset::ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        )
+    );
+}
+
+#[test]
+fn update_scoring_policy_zero_coefficients() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateScoringPolicy {
+            policy: Some(drop_staking_base::state::validatorset::ScoringPolicy {
+                uptime_coefficient: Decimal::zero(),
+                commission_coefficient: Decimal::zero(),
+                voting_coefficient: Decimal::zero(),
+                ..default_scoring_policy()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        drop_staking_base::error::validatorset::ContractError::InvalidScoringPolicy {
+            reason: "coefficients sum must be greater than zero".to_string()
+        }
+    );
+}
+
+#[test]
+fn update_scoring_policy_recomputes_weights() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+
+    let validators = [
+        // perfect validator
+        scored_validator("valoper1", Decimal::one(), Some(1000), None, 4),
+        // stale commission, one jail, half of the proposals voted
+        scored_validator("valoper2", Decimal::one(), Some(800), Some(1), 2),
+        // low uptime
+        scored_validator("valoper3", Decimal::percent(80), Some(1000), None, 4),
+    ];
+    for validator in validators.iter() {
+        drop_staking_base::state::validatorset::VALIDATORS_SET
+            .save(deps.as_mut().storage, &validator.valoper_address, validator)
+            .unwrap();
+    }
+
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateScoringPolicy {
+            policy: Some(default_scoring_policy()),
+        },
+    )
+    .unwrap();
+
+    let weights: Vec<(String, u64)> = drop_staking_base::state::validatorset::VALIDATORS_LIST_CACHE
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .into_iter()
+        .map(|validator| (validator.valoper_address, validator.weight))
+        .collect();
+    assert_eq!(
+        weights,
+        vec![
+            ("valoper1".to_string(), 1000),
+            ("valoper2".to_string(), 450),
+            ("valoper3".to_string(), 0),
+        ]
+    );
+
+    let scores: Vec<drop_staking_base::msg::validatorset::ValidatorScore> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            drop_staking_base::msg::validatorset::QueryMsg::ValidatorScores {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        scores[1],
+        drop_staking_base::msg::validatorset::ValidatorScore {
+            valoper_address: "valoper2".to_string(),
+            uptime: Decimal::one(),
+            commission_in_range: false,
+            voting_participation: Decimal::percent(50),
+            jail_penalty: Decimal::percent(10),
+            excluded_reason: None,
+            score: Decimal::percent(45),
+            weight: 450,
+        }
+    );
+    assert_eq!(
+        scores[2].excluded_reason,
+        Some("uptime is below minimum".to_string())
+    );
+}
+
+fn init_validators(deps: cosmwasm_std::DepsMut<neutron_sdk::bindings::query::NeutronQuery>) {
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some("core")).unwrap();
+    drop_staking_base::state::validatorset::CONFIG
+        .save(
+            deps.storage,
+            &drop_staking_base::state::validatorset::Config {
+                stats_contract: Addr::unchecked("stats_contract"),
+                provider_proposals_contract: None,
+                val_ref_contract: None,
+                puppeteer_contract: Some(Addr::unchecked("puppeteer_contract")),
+            },
+        )
+        .unwrap();
+    crate::contract::execute(
+        deps,
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateValidators {
+            validators: vec![
+                drop_staking_base::msg::validatorset::ValidatorData {
+                    valoper_address: "valoper1".to_string(),
+                    weight: 100,
+                    on_top: None,
+                },
+                drop_staking_base::msg::validatorset::ValidatorData {
+                    valoper_address: "valoper2".to_string(),
+                    weight: 100,
+                    on_top: Some(Uint128::new(10)),
+                },
+            ],
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn weight_ramp_weight_at() {
+    let ramp_up = drop_staking_base::state::validatorset::WeightRamp {
+        from_weight: 10,
+        to_weight: 110,
+        start: 1000,
+        duration: 100,
+    };
+    assert_eq!(ramp_up.weight_at(900), Some(10));
+    assert_eq!(ramp_up.weight_at(1000), Some(10));
+    assert_eq!(ramp_up.weight_at(1025), Some(35));
+    assert_eq!(ramp_up.weight_at(1100), None);
+
+    let ramp_down = drop_staking_base::state::validatorset::WeightRamp {
+        from_weight: 100,
+        to_weight: 0,
+        start: 1000,
+        duration: 300,
+    };
+    assert_eq!(ramp_down.weight_at(1100), Some(67));
+    assert_eq!(ramp_down.weight_at(1300), None);
+}
+
+#[test]
+fn schedule_weight_ramps_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    init_validators(deps.as_mut());
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ScheduleWeightRamps { ramps: vec![] },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        drop_staking_base::error::validatorset::ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        )
+    );
+}
+
+#[test]
+fn schedule_weight_ramps() {
+    let mut deps = mock_dependencies(&[]);
+    init_validators(deps.as_mut());
+
+    let mut env = mock_env();
+    let start = env.block.time.seconds();
+    crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ScheduleWeightRamps {
+            ramps: vec![
+                drop_staking_base::msg::validatorset::WeightRampData {
+                    valoper_address: "valoper1".to_string(),
+                    target_weight: 0,
+                    duration: 1000,
+                },
+                drop_staking_base::msg::validatorset::WeightRampData {
+                    valoper_address: "valoper3".to_string(),
+                    target_weight: 200,
+                    duration: 1000,
+                },
+            ],
+        },
+    )
+    .unwrap();
+
+    // a new ramp starts from the weight reached by the current one
+    env.block.time = env.block.time.plus_seconds(500);
+    crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ScheduleWeightRamps {
+            ramps: vec![drop_staking_base::msg::validatorset::WeightRampData {
+                valoper_address: "valoper1".to_string(),
+                target_weight: 150,
+                duration: 100,
+            }],
+        },
+    )
+    .unwrap();
+
+    let ramps: Vec<drop_staking_base::msg::validatorset::ValidatorWeightRamp> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            drop_staking_base::msg::validatorset::QueryMsg::WeightRamps {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ramps,
+        vec![
+            drop_staking_base::msg::validatorset::ValidatorWeightRamp {
+                valoper_address: "valoper1".to_string(),
+                ramp: drop_staking_base::state::validatorset::WeightRamp {
+                    from_weight: 50,
+                    to_weight: 150,
+                    start: start + 500,
+                    duration: 100,
+                },
+            },
+            drop_staking_base::msg::validatorset::ValidatorWeightRamp {
+                valoper_address: "valoper3".to_string(),
+                ramp: drop_staking_base::state::validatorset::WeightRamp {
+                    from_weight: 0,
+                    to_weight: 200,
+                    start,
+                    duration: 1000,
+                },
+            },
+        ]
+    );
+
+    // the onboarded validator gets the target weight, finished ramps are not reported
+    let validator = drop_staking_base::state::validatorset::VALIDATORS_SET
+        .load(deps.as_ref().storage, "valoper3")
+        .unwrap();
+    assert_eq!(validator.weight, 200);
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let ramps: Vec<drop_staking_base::msg::validatorset::ValidatorWeightRamp> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env,
+            drop_staking_base::msg::validatorset::QueryMsg::WeightRamps {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(ramps.len(), 1);
+    assert_eq!(ramps[0].valoper_address, "valoper3");
+}
+
+#[test]
+fn update_scoring_policy_overrides_manual_weights() {
+    let mut deps = mock_dependencies(&[]);
+    init_validators(deps.as_mut());
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ScheduleWeightRamps {
+            ramps: vec![drop_staking_base::msg::validatorset::WeightRampData {
+                valoper_address: "valoper1".to_string(),
+                target_weight: 0,
+                duration: 1000,
+            }],
+        },
+    )
+    .unwrap();
+
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateScoringPolicy {
+            policy: Some(default_scoring_policy()),
+        },
+    )
+    .unwrap();
+    let ramps: Vec<drop_staking_base::msg::validatorset::ValidatorWeightRamp> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            drop_staking_base::msg::validatorset::QueryMsg::WeightRamps {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(ramps, vec![]);
+
+    // weights can't be ramped while the policy is set
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ScheduleWeightRamps {
+            ramps: vec![drop_staking_base::msg::validatorset::WeightRampData {
+                valoper_address: "valoper1".to_string(),
+                target_weight: 100,
+                duration: 0,
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        drop_staking_base::error::validatorset::ContractError::ScoringPolicyIsActive {}
+    );
+
+    // weights passed along with the set are replaced by scores
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateValidators {
+            validators: vec![drop_staking_base::msg::validatorset::ValidatorData {
+                valoper_address: "valoper1".to_string(),
+                weight: 500,
+                on_top: None,
+            }],
+        },
+    )
+    .unwrap();
+    let validator = drop_staking_base::state::validatorset::VALIDATORS_SET
+        .load(deps.as_ref().storage, "valoper1")
+        .unwrap();
+    let score = crate::contract::calc_validator_score(&default_scoring_policy(), &validator);
+    assert_eq!(validator.weight, score.weight);
+    assert_ne!(validator.weight, 500);
+}
+
+#[test]
+fn update_validators_drains_removed_validators() {
+    let mut deps = mock_dependencies(&[]);
+    init_validators(deps.as_mut());
+
+    let env = mock_env();
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateValidators {
+            validators: vec![drop_staking_base::msg::validatorset::ValidatorData {
+                valoper_address: "valoper1".to_string(),
+                weight: 100,
+                on_top: None,
+            }],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-validators-set-update_validators")
+                .add_attributes([attr("total_count", "1"), attr("draining_count", "1")])
+        ]
+    );
+
+    let validator = drop_staking_base::state::validatorset::VALIDATORS_SET
+        .load(deps.as_ref().storage, "valoper2")
+        .unwrap();
+    assert_eq!(validator.weight, 0);
+    assert_eq!(validator.on_top, Uint128::zero());
+
+    let draining: Vec<drop_staking_base::msg::validatorset::DrainingValidator> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            drop_staking_base::msg::validatorset::QueryMsg::DrainingValidators {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        draining,
+        vec![drop_staking_base::msg::validatorset::DrainingValidator {
+            valoper_address: "valoper2".to_string(),
+            since: env.block.time.seconds(),
+        }]
+    );
+}
+
+#[test]
+fn release_draining_validators() {
+    let mut deps = mock_dependencies(&[]);
+    init_validators(deps.as_mut());
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateValidators { validators: vec![] },
+    )
+    .unwrap();
+
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&drop_staking_base::msg::puppeteer::DelegationsResponse {
+                delegations: drop_staking_base::state::puppeteer::Delegations {
+                    delegations: vec![drop_staking_base::state::puppeteer::DropDelegation {
+                        delegator: Addr::unchecked("ica"),
+                        validator: "valoper1".to_string(),
+                        amount: cosmwasm_std::coin(100, "uatom"),
+                        share_ratio: cosmwasm_std::Decimal256::one(),
+                    }],
+                },
+                remote_height: 10,
+                local_height: 10,
+                timestamp: mock_env().block.time.plus_seconds(1),
+            })
+            .unwrap()
+        });
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ReleaseDrainingValidators {},
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new(
+            "crates.io:drop-staking__drop-validators-set-execute_release_draining_validators"
+        )
+        .add_attributes([attr("released", "valoper2")])]
+    );
+
+    let validators: Vec<drop_staking_base::state::validatorset::ValidatorInfo> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            drop_staking_base::msg::validatorset::QueryMsg::Validators {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(validators.len(), 1);
+    assert_eq!(validators[0].valoper_address, "valoper1");
+    assert!(drop_staking_base::state::validatorset::DRAINING_VALIDATORS
+        .has(deps.as_ref().storage, "valoper1"));
+}
+
+#[test]
+fn release_draining_validators_stale_delegations() {
+    let mut deps = mock_dependencies(&[]);
+    init_validators(deps.as_mut());
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("core", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::UpdateValidators { validators: vec![] },
+    )
+    .unwrap();
+
+    // the snapshot predates the removal, so it can't prove the stake was moved away
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&drop_staking_base::msg::puppeteer::DelegationsResponse {
+                delegations: drop_staking_base::state::puppeteer::Delegations {
+                    delegations: vec![],
+                },
+                remote_height: 10,
+                local_height: 10,
+                timestamp: mock_env().block.time,
+            })
+            .unwrap()
+        });
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        drop_staking_base::msg::validatorset::ExecuteMsg::ReleaseDrainingValidators {},
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new(
+            "crates.io:drop-staking__drop-validators-set-execute_release_draining_validators"
+        )
+        .add_attributes([attr("released", "")])]
+    );
+    assert!(drop_staking_base::state::validatorset::DRAINING_VALIDATORS
+        .has(deps.as_ref().storage, "valoper1"));
+    assert!(drop_staking_base::state::validatorset::DRAINING_VALIDATORS
+        .has(deps.as_ref().storage, "valoper2"));
+}
@@ -1,12 +1,20 @@
 use bech32::{encode, Bech32, Hrp};
-use cosmwasm_std::{to_json_binary, Decimal, Deps, Order, Reply, StdError, SubMsg};
+use cosmwasm_std::{
+    to_json_binary, Decimal, Deps, Order, Reply, StdError, Storage, SubMsg, SubMsgResult, WasmMsg,
+};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
 use drop_helpers::query_id::get_query_id;
-use drop_staking_base::msg::validatorsstats::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use drop_staking_base::msg::validatorset::{
+    ExecuteMsg as ValidatorSetExecuteMsg, ValidatorInfoUpdate,
+};
+use drop_staking_base::msg::validatorsstats::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StatsFreshnessResponse, ValidatorFreshness,
+};
 use drop_staking_base::state::validatorsstats::{
-    Config, KVQueryIds, MissedBlocks, ValidatorMissedBlocksForPeriod, ValidatorState, CONFIG,
-    MISSED_BLOCKS, SIGNING_INFO_QUERY_ID, SIGNING_INFO_REPLY_ID, STATE_MAP, VALCONS_TO_VALOPER,
-    VALIDATOR_PROFILE_QUERY_ID, VALIDATOR_PROFILE_REPLY_ID,
+    Config, KVQueryIds, MissedBlocks, PushConfig, StatsParams, ValidatorState, CONFIG, LAST_PUSH,
+    LAST_REMOTE_HEIGHT, MISSED_BLOCKS, MISSED_BLOCKS_CURSOR, PUSH_CONFIG,
+    PUSH_VALIDATORS_INFO_REPLY_ID, SIGNING_INFO_QUERY_ID, SIGNING_INFO_REPLY_ID, STATE_MAP,
+    STATS_PARAMS, VALCONS_TO_VALOPER, VALIDATOR_PROFILE_QUERY_ID, VALIDATOR_PROFILE_REPLY_ID,
 };
 use neutron_sdk::bindings::query::QueryRegisteredQueryResultResponse;
 use neutron_sdk::interchain_queries::queries::get_raw_interchain_query_result;
@@ -26,6 +34,11 @@ use sha2::{Digest, Sha256};
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const DEFAULT_UPTIME_WINDOW_SECONDS: u64 = 60 * 60 * 24 * 30;
+const DEFAULT_MISSED_BLOCKS_RETENTION: u64 = 100;
+// storage key of the missed blocks of all validators kept in a single item before
+const LEGACY_MISSED_BLOCKS_KEY: &[u8] = b"missed_blocks";
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -48,7 +61,13 @@ pub fn instantiate(
 
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
 
+    let stats_params = msg
+        .stats_params
+        .unwrap_or_else(|| default_stats_params(config.avg_block_time));
+    validate_stats_params(&stats_params)?;
+
     CONFIG.save(deps.storage, config)?;
+    STATS_PARAMS.save(deps.storage, &stats_params)?;
 
     Ok(Response::default())
 }
@@ -59,9 +78,48 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Bin
         QueryMsg::State {} => query_state(deps, env),
         QueryMsg::Config {} => query_config(deps, env),
         QueryMsg::KVQueryIds {} => query_kv_query_ids(deps, env),
+        QueryMsg::StatsParams {} => to_json_binary(&STATS_PARAMS.load(deps.storage)?),
+        QueryMsg::MissedBlocks { valoper_address } => query_missed_blocks(deps, valoper_address),
+        QueryMsg::PushConfig {} => to_json_binary(&PUSH_CONFIG.may_load(deps.storage)?),
+        QueryMsg::StatsFreshness { max_lag } => query_stats_freshness(deps, max_lag),
     }
 }
 
+fn query_stats_freshness(deps: Deps<NeutronQuery>, max_lag: u64) -> StdResult<Binary> {
+    let remote_height = LAST_REMOTE_HEIGHT.may_load(deps.storage)?;
+    let validators = STATE_MAP
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (_, state) = item?;
+            let lag = remote_height
+                .zip(state.last_processed_remote_height)
+                .map(|(remote_height, processed)| remote_height.saturating_sub(processed));
+            Ok(ValidatorFreshness {
+                valoper_address: state.valoper_address,
+                last_processed_remote_height: state.last_processed_remote_height,
+                lag,
+                stale: lag.map_or(true, |lag| lag > max_lag),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&StatsFreshnessResponse {
+        remote_height,
+        last_push: LAST_PUSH.may_load(deps.storage)?,
+        validators,
+    })
+}
+
+fn query_missed_blocks(deps: Deps<NeutronQuery>, valoper_address: String) -> StdResult<Binary> {
+    let missed_blocks = MISSED_BLOCKS
+        .prefix(&valoper_address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_json_binary(&missed_blocks)
+}
+
 fn query_kv_query_ids(deps: Deps<NeutronQuery>, _env: Env) -> StdResult<Binary> {
     to_json_binary(&KVQueryIds {
         signing_info_id: SIGNING_INFO_QUERY_ID
@@ -90,15 +148,132 @@ fn query_state(deps: Deps<NeutronQuery>, _env: Env) -> StdResult<Binary> {
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn execute(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: ExecuteMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
     // TODO: Add update config support
     // TODO: Add block time change support
     match msg {
         ExecuteMsg::RegisterStatsQueries { validators } => register_stats_queries(deps, validators),
+        ExecuteMsg::UpdatePushConfig { config } => update_push_config(deps, info, config),
+        ExecuteMsg::PushValidatorsInfo {} => {
+            let push_msg = get_push_validators_info_msg(deps, &env)?;
+            Ok(Response::new().add_submessages(push_msg))
+        }
+        ExecuteMsg::UpdateStatsParams { params } => update_stats_params(deps, info, params),
+    }
+}
+
+fn update_stats_params(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    params: StatsParams,
+) -> NeutronResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    validate_stats_params(&params)?;
+    STATS_PARAMS.save(deps.storage, &params)?;
+
+    Ok(Response::new())
+}
+
+fn default_stats_params(avg_block_time: u64) -> StatsParams {
+    StatsParams {
+        min_commission: Decimal::percent(1),
+        max_commission: Decimal::percent(10),
+        uptime_window: DEFAULT_UPTIME_WINDOW_SECONDS / avg_block_time.max(1),
+        missed_blocks_retention: DEFAULT_MISSED_BLOCKS_RETENTION,
+    }
+}
+
+fn validate_stats_params(params: &StatsParams) -> StdResult<()> {
+    if params.min_commission > params.max_commission || params.max_commission > Decimal::one() {
+        return Err(StdError::generic_err("invalid commission range"));
     }
+    if params.uptime_window == 0 || params.missed_blocks_retention == 0 {
+        return Err(StdError::generic_err(
+            "uptime window and missed blocks retention must be positive",
+        ));
+    }
+
+    Ok(())
+}
+
+fn update_push_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    config: Option<PushConfig>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    match config {
+        Some(config) => {
+            deps.api
+                .addr_validate(config.validators_set_contract.as_str())?;
+            PUSH_CONFIG.save(deps.storage, &config)?;
+        }
+        None => PUSH_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new())
+}
+
+// Pushes stats of all validators to the validators set, at most once per `min_interval`.
+// Every push carries the full state, so skipped ones are caught up by the next push.
+fn get_push_validators_info_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+) -> StdResult<Option<SubMsg<NeutronMsg>>> {
+    let push_config = match PUSH_CONFIG.may_load(deps.storage)? {
+        Some(push_config) => push_config,
+        None => return Ok(None),
+    };
+
+    let now = env.block.time.seconds();
+    if let Some(last_push) = LAST_PUSH.may_load(deps.storage)? {
+        if now < last_push + push_config.min_interval {
+            return Ok(None);
+        }
+    }
+
+    let validators = STATE_MAP
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(_, state)| ValidatorInfoUpdate {
+                valoper_address: state.valoper_address,
+                last_processed_remote_height: state.last_processed_remote_height,
+                last_processed_local_height: state.last_processed_local_height,
+                last_validated_height: state.last_validated_height,
+                last_commission_in_range: state.last_commission_in_range,
+                uptime: state.uptime,
+                tombstone: state.tombstone,
+                jailed_number: state.jailed_number,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    if validators.is_empty() {
+        return Ok(None);
+    }
+
+    LAST_PUSH.save(deps.storage, &now)?;
+
+    // a failed push must not revert processing of the ICQ result
+    Ok(Some(SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: push_config.validators_set_contract.to_string(),
+            msg: to_json_binary(&ValidatorSetExecuteMsg::UpdateValidatorsInfo { validators })?,
+            funds: vec![],
+        },
+        PUSH_VALIDATORS_INFO_REPLY_ID,
+    )))
+}
+
+fn save_last_remote_height(storage: &mut dyn Storage, remote_height: u64) -> StdResult<()> {
+    let last_remote_height = LAST_REMOTE_HEIGHT.may_load(storage)?.unwrap_or(0);
+    LAST_REMOTE_HEIGHT.save(storage, &last_remote_height.max(remote_height))
 }
 
 fn register_stats_queries(
@@ -187,6 +362,10 @@ fn sudo_validator_info(
         return register_signing_infos_query(deps, data.validators);
     }
 
+    save_last_remote_height(deps.storage, interchain_query_result.result.height)?;
+
+    let params = STATS_PARAMS.load(deps.storage)?;
+
     for validator in data.validators.iter() {
         let mut validator_state = get_validator_state(&deps, validator.operator_address.clone())?;
 
@@ -200,7 +379,7 @@ fn sudo_validator_info(
         };
 
         validator_state.last_commission_in_range = if let Some(rate) = validator.rate {
-            if commission_in_range(rate, Decimal::percent(1), Decimal::percent(10)) {
+            if commission_in_range(rate, params.min_commission, params.max_commission) {
                 Some(env.block.height)
             } else {
                 validator_state.last_commission_in_range
@@ -226,10 +405,11 @@ fn sudo_validator_info(
         )?;
     }
 
-    Ok(Response::new())
+    let push_msg = get_push_validators_info_msg(deps, &env)?;
+
+    Ok(Response::new().add_submessages(push_msg))
 }
 
-// TODO: move min/max commission to config
 fn commission_in_range(rate: Decimal, min: Decimal, max: Decimal) -> bool {
     rate >= min && rate <= max
 }
@@ -243,97 +423,110 @@ fn sudo_signing_info(
     deps.api
         .debug(&format!("WASMDEBUG: signing_info_sudo data: {data:?}",));
 
+    save_last_remote_height(deps.storage, interchain_query_result.result.height)?;
+
+    let params = STATS_PARAMS.load(deps.storage)?;
+
     for info in data.signing_infos.iter() {
         let valoper_address = VALCONS_TO_VALOPER.may_load(deps.storage, info.address.clone())?;
 
-        if valoper_address.is_none() {
-            deps.api.debug(&format!(
-                "WASMDEBUG: signing_info_sudo: validator operator address was not found: {:?}",
-                info.address.clone()
-            ));
-            continue;
-        }
+        let address = match valoper_address {
+            Some(address) => address,
+            None => {
+                deps.api.debug(&format!(
+                    "WASMDEBUG: signing_info_sudo: validator operator address was not found: {:?}",
+                    info.address.clone()
+                ));
+                continue;
+            }
+        };
 
-        // all_missed_blocks contains all missed blocks for all validators for the specific period
-        let mut all_missed_blocks = MISSED_BLOCKS.may_load(deps.storage)?.unwrap_or_default();
-        if !all_missed_blocks.is_empty()
-            && all_missed_blocks[0].timestamp <= env.block.time.seconds() - 60 * 60 * 24 * 30
-        // TODO: move timeout to config
-        {
-            all_missed_blocks.remove(0);
-        }
+        let mut validator_state = get_validator_state(&deps, address.clone())?;
 
-        let mut missed_blocks = MissedBlocks {
-            remote_height: interchain_query_result.result.height,
-            timestamp: env.block.time.seconds(),
-            validators: Vec::new(),
+        validator_state.valcons_address = info.address.clone();
+        validator_state.tombstone = if info.tombstoned {
+            true
+        } else {
+            validator_state.tombstone
         };
 
-        if let Some(address) = valoper_address {
-            let mut validator_state = get_validator_state(&deps, address.clone())?;
-
-            validator_state.valcons_address = info.address.clone();
-            validator_state.tombstone = if info.tombstoned {
-                true
-            } else {
-                validator_state.tombstone
-            };
+        let missed_blocks_percent = update_missed_blocks(
+            deps.storage,
+            &params,
+            &address,
+            MissedBlocks {
+                remote_height: interchain_query_result.result.height,
+                timestamp: env.block.time.seconds(),
+                missed_blocks: info.missed_blocks_counter as u64,
+            },
+        )?;
 
-            let missed_blocks_percent = calucalate_missed_blocks_percent(
-                &all_missed_blocks,
-                &mut missed_blocks,
-                address.clone(),
-                info.missed_blocks_counter as u64,
-            );
+        validator_state.uptime = Decimal::one() - missed_blocks_percent;
 
-            validator_state.uptime = Decimal::one() - missed_blocks_percent;
+        STATE_MAP.save(deps.storage, address, &validator_state)?;
+    }
 
-            STATE_MAP.save(deps.storage, address.clone(), &validator_state)?;
-        }
+    let push_msg = get_push_validators_info_msg(deps, &env)?;
 
-        all_missed_blocks.push(missed_blocks);
+    Ok(Response::new().add_submessages(push_msg))
+}
 
-        MISSED_BLOCKS.save(deps.storage, &all_missed_blocks)?;
+// Appends the record to the validator ring buffer, evicting the oldest records beyond
+// the retention, and returns the missed blocks percent over the uptime window.
+// Only the validator's own records are touched, so gas doesn't grow with the validator set.
+pub(crate) fn update_missed_blocks(
+    storage: &mut dyn Storage,
+    params: &StatsParams,
+    address: &str,
+    record: MissedBlocks,
+) -> StdResult<Decimal> {
+    let mut cursor = MISSED_BLOCKS_CURSOR
+        .may_load(storage, address)?
+        .unwrap_or_default();
+
+    let last_record = match cursor.end.checked_sub(1) {
+        Some(last) => MISSED_BLOCKS.may_load(storage, (address, last))?,
+        None => None,
+    };
+    // the same ICQ result may be delivered more than once
+    if last_record.map_or(true, |last| last.remote_height < record.remote_height) {
+        MISSED_BLOCKS.save(storage, (address, cursor.end), &record)?;
+        cursor.end += 1;
     }
-
-    Ok(Response::new())
+    while cursor.end - cursor.start > params.missed_blocks_retention {
+        MISSED_BLOCKS.remove(storage, (address, cursor.start));
+        cursor.start += 1;
+    }
+    MISSED_BLOCKS_CURSOR.save(storage, address, &cursor)?;
+
+    let window_start = record.remote_height.saturating_sub(params.uptime_window);
+    let history = MISSED_BLOCKS
+        .prefix(address)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, record)| record))
+        .filter(|item| {
+            item.as_ref()
+                .map_or(true, |record| record.remote_height >= window_start)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(calculate_missed_blocks_percent(&history))
 }
 
-// TODO: Implement tests
-fn calucalate_missed_blocks_percent(
-    all_missed_blocks: &[MissedBlocks],
-    missed_blocks: &mut MissedBlocks,
-    address: String,
-    missed_blocks_counter: u64,
-) -> Decimal {
-    let validator_missed_blocks = ValidatorMissedBlocksForPeriod {
-        address: address.clone(),
-        missed_blocks: missed_blocks_counter,
+fn calculate_missed_blocks_percent(history: &[MissedBlocks]) -> Decimal {
+    let (first, last) = match (history.first(), history.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Decimal::zero(),
     };
 
-    missed_blocks.validators.push(validator_missed_blocks);
-
-    let total_blocks_diff = missed_blocks.remote_height
-        - (if !all_missed_blocks.is_empty() {
-            all_missed_blocks[0].remote_height
-        } else {
-            missed_blocks.remote_height
-        });
-
-    let sum_missed_blocks: u64 = all_missed_blocks
-        .iter()
-        .flat_map(|x| &x.validators)
-        .filter(|x| x.address == address)
-        .map(|inner| inner.missed_blocks)
-        .sum();
-
-    let sum_missed_blocks = sum_missed_blocks + missed_blocks_counter;
-
+    let total_blocks_diff = last.remote_height - first.remote_height;
     if total_blocks_diff == 0 {
-        Decimal::zero()
-    } else {
-        Decimal::from_ratio(sum_missed_blocks, total_blocks_diff)
+        return Decimal::zero();
     }
+
+    let sum_missed_blocks: u64 = history.iter().map(|record| record.missed_blocks).sum();
+
+    Decimal::from_ratio(sum_missed_blocks, total_blocks_diff).min(Decimal::one())
 }
 
 fn register_signing_infos_query(
@@ -419,6 +612,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     match msg.id {
         VALIDATOR_PROFILE_REPLY_ID => validator_info_reply(deps, env, msg),
         SIGNING_INFO_REPLY_ID => signing_info_reply(deps, env, msg),
+        PUSH_VALIDATORS_INFO_REPLY_ID => push_validators_info_reply(deps, env, msg),
         _ => Err(StdError::generic_err(format!(
             "unsupported reply message id {}",
             msg.id
@@ -448,6 +642,18 @@ fn signing_info_reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Respons
     Ok(Response::new())
 }
 
+fn push_validators_info_reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+    };
+    deps.api.debug(&format!(
+        "WASMDEBUG: push_validators_info_reply error: {error:?}",
+    ));
+
+    Ok(Response::new().add_attribute("push_validators_info_error", error))
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn migrate(
     deps: DepsMut<NeutronQuery>,
@@ -466,6 +672,12 @@ pub fn migrate(
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
+    if STATS_PARAMS.may_load(deps.storage)?.is_none() {
+        let config = CONFIG.load(deps.storage)?;
+        STATS_PARAMS.save(deps.storage, &default_stats_params(config.avg_block_time))?;
+    }
+    deps.storage.remove(LEGACY_MISSED_BLOCKS_KEY);
+
     Ok(Response::new())
 }
 
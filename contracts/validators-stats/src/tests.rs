@@ -0,0 +1,396 @@
+use cosmwasm_std::{
+    from_json,
+    testing::{mock_env, mock_info},
+    to_json_binary, Addr, Decimal, DepsMut, SubMsg, WasmMsg,
+};
+use drop_helpers::testing::mock_dependencies;
+use drop_staking_base::{
+    msg::{
+        validatorset::{ExecuteMsg as ValidatorSetExecuteMsg, ValidatorInfoUpdate},
+        validatorsstats::{ExecuteMsg, QueryMsg, StatsFreshnessResponse, ValidatorFreshness},
+    },
+    state::validatorsstats::{
+        MissedBlocks, MissedBlocksCursor, PushConfig, StatsParams, ValidatorState, LAST_PUSH,
+        LAST_REMOTE_HEIGHT, MISSED_BLOCKS_CURSOR, PUSH_CONFIG, PUSH_VALIDATORS_INFO_REPLY_ID,
+        STATE_MAP, STATS_PARAMS,
+    },
+};
+use neutron_sdk::bindings::query::NeutronQuery;
+
+fn validator_state(valoper_address: &str, remote_height: Option<u64>) -> ValidatorState {
+    ValidatorState {
+        valoper_address: valoper_address.to_string(),
+        valcons_address: format!("{valoper_address}_valcons"),
+        last_processed_remote_height: remote_height,
+        last_processed_local_height: remote_height.map(|height| height + 1),
+        last_validated_height: None,
+        last_commission_in_range: Some(5),
+        uptime: Decimal::percent(99),
+        tombstone: false,
+        prev_jailed_state: false,
+        jailed_number: Some(0),
+    }
+}
+
+fn base_init(deps: DepsMut<NeutronQuery>) {
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some("owner")).unwrap();
+    PUSH_CONFIG
+        .save(
+            deps.storage,
+            &PushConfig {
+                validators_set_contract: Addr::unchecked("validators_set"),
+                min_interval: 100,
+            },
+        )
+        .unwrap();
+    STATE_MAP
+        .save(
+            deps.storage,
+            "valoper1".to_string(),
+            &validator_state("valoper1", Some(90)),
+        )
+        .unwrap();
+}
+
+#[test]
+fn update_push_config_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::UpdatePushConfig { config: None },
+    );
+    assert!(res.is_err());
+    assert!(PUSH_CONFIG.may_load(&deps.storage).unwrap().is_some());
+}
+
+#[test]
+fn update_push_config() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdatePushConfig { config: None },
+    )
+    .unwrap();
+    let config: Option<PushConfig> = from_json(
+        crate::contract::query(deps.as_ref(), mock_env(), QueryMsg::PushConfig {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(config, None);
+}
+
+#[test]
+fn push_validators_info() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let env = mock_env();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::PushValidatorsInfo {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: "validators_set".to_string(),
+                msg: to_json_binary(&ValidatorSetExecuteMsg::UpdateValidatorsInfo {
+                    validators: vec![ValidatorInfoUpdate {
+                        valoper_address: "valoper1".to_string(),
+                        last_processed_remote_height: Some(90),
+                        last_processed_local_height: Some(91),
+                        last_validated_height: None,
+                        last_commission_in_range: Some(5),
+                        uptime: Decimal::percent(99),
+                        tombstone: false,
+                        jailed_number: Some(0),
+                    }],
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+            PUSH_VALIDATORS_INFO_REPLY_ID,
+        )]
+    );
+    assert_eq!(
+        LAST_PUSH.load(&deps.storage).unwrap(),
+        env.block.time.seconds()
+    );
+}
+
+#[test]
+fn push_validators_info_rate_limited() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let mut env = mock_env();
+    LAST_PUSH
+        .save(deps.as_mut().storage, &(env.block.time.seconds() - 50))
+        .unwrap();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::PushValidatorsInfo {},
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    env.block.time = env.block.time.plus_seconds(50);
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("somebody", &[]),
+        ExecuteMsg::PushValidatorsInfo {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn query_stats_freshness() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    STATE_MAP
+        .save(
+            deps.as_mut().storage,
+            "valoper2".to_string(),
+            &validator_state("valoper2", Some(40)),
+        )
+        .unwrap();
+    STATE_MAP
+        .save(
+            deps.as_mut().storage,
+            "valoper3".to_string(),
+            &validator_state("valoper3", None),
+        )
+        .unwrap();
+    LAST_REMOTE_HEIGHT
+        .save(deps.as_mut().storage, &100)
+        .unwrap();
+
+    let freshness: StatsFreshnessResponse = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::StatsFreshness { max_lag: 20 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        freshness,
+        StatsFreshnessResponse {
+            remote_height: Some(100),
+            last_push: None,
+            validators: vec![
+                ValidatorFreshness {
+                    valoper_address: "valoper1".to_string(),
+                    last_processed_remote_height: Some(90),
+                    lag: Some(10),
+                    stale: false,
+                },
+                ValidatorFreshness {
+                    valoper_address: "valoper2".to_string(),
+                    last_processed_remote_height: Some(40),
+                    lag: Some(60),
+                    stale: true,
+                },
+                ValidatorFreshness {
+                    valoper_address: "valoper3".to_string(),
+                    last_processed_remote_height: None,
+                    lag: None,
+                    stale: true,
+                },
+            ],
+        }
+    );
+}
+
+fn stats_params() -> StatsParams {
+    StatsParams {
+        min_commission: Decimal::percent(1),
+        max_commission: Decimal::percent(10),
+        uptime_window: 1000,
+        missed_blocks_retention: 3,
+    }
+}
+
+fn missed_blocks(remote_height: u64, missed_blocks: u64) -> MissedBlocks {
+    MissedBlocks {
+        remote_height,
+        timestamp: remote_height * 6,
+        missed_blocks,
+    }
+}
+
+#[test]
+fn update_stats_params_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::UpdateStatsParams {
+            params: stats_params(),
+        },
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn update_stats_params_invalid() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    for params in [
+        StatsParams {
+            min_commission: Decimal::percent(20),
+            ..stats_params()
+        },
+        StatsParams {
+            max_commission: Decimal::percent(101),
+            ..stats_params()
+        },
+        StatsParams {
+            uptime_window: 0,
+            ..stats_params()
+        },
+        StatsParams {
+            missed_blocks_retention: 0,
+            ..stats_params()
+        },
+    ] {
+        let res = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateStatsParams { params },
+        );
+        assert!(res.is_err());
+    }
+}
+
+#[test]
+fn update_stats_params() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateStatsParams {
+            params: stats_params(),
+        },
+    )
+    .unwrap();
+    let params: StatsParams = from_json(
+        crate::contract::query(deps.as_ref(), mock_env(), QueryMsg::StatsParams {}).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(params, stats_params());
+}
+
+#[test]
+fn missed_blocks_ring_buffer() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let params = stats_params();
+    STATS_PARAMS.save(deps.as_mut().storage, &params).unwrap();
+
+    let mut percents = vec![];
+    for (height, missed) in [(100, 0), (200, 10), (200, 10), (300, 20), (400, 30)] {
+        percents.push(
+            crate::contract::update_missed_blocks(
+                deps.as_mut().storage,
+                &params,
+                "valoper1",
+                missed_blocks(height, missed),
+            )
+            .unwrap(),
+        );
+    }
+    assert_eq!(
+        percents,
+        vec![
+            Decimal::zero(),
+            Decimal::from_ratio(10u64, 100u64),
+            Decimal::from_ratio(10u64, 100u64),
+            Decimal::from_ratio(30u64, 200u64),
+            Decimal::from_ratio(60u64, 200u64),
+        ]
+    );
+
+    // the oldest record is evicted once the retention is exceeded
+    let history: Vec<MissedBlocks> = from_json(
+        crate::contract::query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MissedBlocks {
+                valoper_address: "valoper1".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        history,
+        vec![
+            missed_blocks(200, 10),
+            missed_blocks(300, 20),
+            missed_blocks(400, 30),
+        ]
+    );
+    assert_eq!(
+        MISSED_BLOCKS_CURSOR
+            .load(deps.as_ref().storage, "valoper1")
+            .unwrap(),
+        MissedBlocksCursor { start: 1, end: 4 }
+    );
+}
+
+#[test]
+fn missed_blocks_uptime_window() {
+    let mut deps = mock_dependencies(&[]);
+    base_init(deps.as_mut());
+    let params = StatsParams {
+        uptime_window: 150,
+        missed_blocks_retention: 10,
+        ..stats_params()
+    };
+
+    for (height, missed) in [(100, 50), (200, 10)] {
+        crate::contract::update_missed_blocks(
+            deps.as_mut().storage,
+            &params,
+            "valoper1",
+            missed_blocks(height, missed),
+        )
+        .unwrap();
+    }
+    // the record at height 100 is out of the window
+    let percent = crate::contract::update_missed_blocks(
+        deps.as_mut().storage,
+        &params,
+        "valoper1",
+        missed_blocks(300, 5),
+    )
+    .unwrap();
+    assert_eq!(percent, Decimal::from_ratio(15u64, 100u64));
+
+    // other validators history is not affected
+    let percent = crate::contract::update_missed_blocks(
+        deps.as_mut().storage,
+        &params,
+        "valoper2",
+        missed_blocks(300, 5),
+    )
+    .unwrap();
+    assert_eq!(percent, Decimal::zero());
+}
@@ -1,9 +1,11 @@
 use cosmwasm_std::{
-    attr, ensure_eq, from_json, to_json_binary, Attribute, BankMsg, Binary, Coin, CosmosMsg,
-    Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    attr, ensure, ensure_eq, from_json, to_json_binary, Attribute, BankMsg, Binary, Coin,
+    CosmosMsg, Decimal, Deps, DepsMut, Env, Fraction, MessageInfo, Order, Response, StdError,
+    StdResult, Uint128, WasmMsg,
 };
-use cw721::NftInfoResponse;
+use cw721::{NftInfoResponse, OwnerOfResponse};
 use cw_ownable::{get_ownership, update_ownership};
+use cw_storage_plus::Bound;
 use drop_helpers::{
     answer::response,
     get_contracts,
@@ -11,20 +13,30 @@ use drop_helpers::{
 };
 use drop_staking_base::{
     msg::{
-        withdrawal_manager::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveNftMsg},
+        withdrawal_manager::{
+            ExecuteMsg, InstantiateMsg, ListingsResponse, MigrateMsg, QueryMsg, ReceiveNftMsg,
+            VoucherQuoteResponse,
+        },
         withdrawal_voucher::Extension,
     },
     state::{
         core::{UnbondBatch, UnbondBatchStatus},
-        withdrawal_manager::{Config, Cw721ReceiveMsg, CONFIG},
+        withdrawal_manager::{
+            Config, Cw721ReceiveMsg, MarketConfig, VoucherListing, CONFIG, MARKET_CONFIG,
+            VOUCHER_LISTINGS,
+        },
     },
 };
 use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+use std::collections::{BTreeMap, HashSet};
 
 use crate::error::{ContractError, ContractResult};
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const LISTINGS_PAGINATION_DEFAULT_LIMIT: u32 = 100;
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -51,14 +63,115 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::PauseInfo {} => query_pause_info(deps),
+        QueryMsg::MarketConfig {} => {
+            to_json_binary(&MARKET_CONFIG.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::VoucherQuote { token_id } => {
+            to_json_binary(&query_voucher_quote(deps, env, token_id)?)
+        }
+        QueryMsg::Listing { token_id } => {
+            to_json_binary(&VOUCHER_LISTINGS.load(deps.storage, &token_id)?)
+        }
+        QueryMsg::Listings { start_after, limit } => query_listings(deps, start_after, limit),
     }
 }
 
+fn query_voucher_quote(
+    deps: Deps<NeutronQuery>,
+    env: Env,
+    token_id: String,
+) -> StdResult<VoucherQuoteResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let market_config = MARKET_CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    let addrs = get_contracts!(
+        deps,
+        config.factory_contract,
+        withdrawal_voucher_contract,
+        core_contract
+    );
+    let voucher: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+        addrs.withdrawal_voucher_contract,
+        &drop_staking_base::msg::withdrawal_voucher::QueryMsg::NftInfo { token_id },
+    )?;
+    let voucher_extension = voucher
+        .extension
+        .ok_or_else(|| StdError::generic_err("extension is not set"))?;
+    let batch_id = voucher_extension
+        .batch_id
+        .parse::<u128>()
+        .map_err(|_| StdError::generic_err("invalid batch_id"))?;
+    let unbond_batch: UnbondBatch = deps.querier.query_wasm_smart(
+        &addrs.core_contract,
+        &drop_staking_base::msg::core::QueryMsg::UnbondBatch {
+            batch_id: batch_id.into(),
+        },
+    )?;
+    // funds of such batches sit on the emergency address until the batch is resolved
+    if matches!(
+        unbond_batch.status,
+        UnbondBatchStatus::WithdrawingEmergency | UnbondBatchStatus::WithdrawnEmergency
+    ) {
+        return Err(StdError::generic_err(format!(
+            "batch {} is withdrawn in emergency, payout is unknown until it is resolved",
+            batch_id
+        )));
+    }
+
+    let user_share = Decimal::from_ratio(
+        voucher_extension.amount,
+        unbond_batch.total_dasset_amount_to_withdraw,
+    );
+    let (expected_payout, remaining_time) = match unbond_batch.status {
+        UnbondBatchStatus::Withdrawn => (
+            user_share * unbond_batch.unbonded_amount.unwrap_or_default(),
+            0,
+        ),
+        _ => (
+            user_share
+                * unbond_batch.expected_native_asset_amount
+                * unbond_batch.slashing_effect.unwrap_or(Decimal::one()),
+            unbond_batch
+                .expected_release_time
+                .saturating_sub(env.block.time.seconds()),
+        ),
+    };
+    // simple interest discount for the time left until the batch is released
+    let discount = Decimal::one()
+        + market_config.discount_rate * Decimal::from_ratio(remaining_time, SECONDS_PER_YEAR);
+    let fair_price = expected_payout.multiply_ratio(discount.denominator(), discount.numerator());
+
+    Ok(VoucherQuoteResponse {
+        batch_id: batch_id.into(),
+        amount: voucher_extension.amount,
+        expected_payout,
+        remaining_time,
+        fair_price,
+    })
+}
+
+fn query_listings(
+    deps: Deps<NeutronQuery>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(LISTINGS_PAGINATION_DEFAULT_LIMIT);
+    let listings = VOUCHER_LISTINGS
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&ListingsResponse { listings })
+}
+
 fn query_pause_info(deps: Deps<NeutronQuery>) -> StdResult<Binary> {
     if is_paused(deps.storage)? {
         to_json_binary(&PauseInfoResponse::Paused {})
@@ -93,8 +206,22 @@ pub fn execute(
                 ReceiveNftMsg::Withdraw { receiver } => {
                     execute_receive_nft_withdraw(deps, info, sender, token_id, receiver)
                 }
+                ReceiveNftMsg::List { price } => {
+                    execute_receive_nft_list(deps, info, sender, token_id, price)
+                }
             }
         }
+        ExecuteMsg::UpdateMarketConfig { discount_rate } => {
+            execute_update_market_config(deps, info, discount_rate)
+        }
+        ExecuteMsg::BuyVoucher { token_id, receiver } => {
+            execute_buy_voucher(deps, info, token_id, receiver)
+        }
+        ExecuteMsg::DelistVoucher { token_id } => execute_delist_voucher(deps, info, token_id),
+        ExecuteMsg::WithdrawMany {
+            token_ids,
+            receiver,
+        } => execute_withdraw_many(deps, env, info, token_ids, receiver),
         ExecuteMsg::Pause {} => exec_pause(deps, info),
         ExecuteMsg::Unpause {} => exec_unpause(deps, info),
     }
@@ -175,8 +302,300 @@ fn execute_receive_nft_withdraw(
         info.sender,
         ContractError::Unauthorized {}
     );
-    let voucher: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+    let (batch_id, payout_amount) = get_voucher_payout(
+        deps.as_ref(),
+        &addrs.withdrawal_voucher_contract,
+        &addrs.core_contract,
+        token_id,
+    )?;
+    let to_address = receiver.unwrap_or(sender);
+    attrs.push(attr("batch_id", batch_id.to_string()));
+    attrs.push(attr("payout_amount", payout_amount.to_string()));
+    attrs.push(attr("to_address", &to_address));
+
+    let mut messages = vec![CosmosMsg::Bank(BankMsg::Send {
+        to_address,
+        amount: vec![Coin {
+            denom: config.base_denom,
+            amount: payout_amount,
+        }],
+    })];
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.core_contract.to_string(),
+        msg: to_json_binary(
+            &drop_staking_base::msg::core::ExecuteMsg::UpdateWithdrawnAmount {
+                batch_id,
+                withdrawn_amount: payout_amount,
+            },
+        )?,
+        funds: info.funds,
+    }));
+
+    Ok(response("execute-receive_nft", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn execute_update_market_config(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    discount_rate: Decimal,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    MARKET_CONFIG.save(deps.storage, &MarketConfig { discount_rate })?;
+    Ok(response(
+        "update_market_config",
+        CONTRACT_NAME,
+        [attr("discount_rate", discount_rate.to_string())],
+    ))
+}
+
+fn execute_receive_nft_list(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    sender: String,
+    token_id: String,
+    price: Uint128,
+) -> ContractResult<Response<NeutronMsg>> {
+    pause_guard(deps.storage)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let addrs = get_contracts!(deps, config.factory_contract, withdrawal_voucher_contract);
+    ensure_eq!(
         addrs.withdrawal_voucher_contract,
+        info.sender,
+        ContractError::Unauthorized {}
+    );
+    ensure!(!price.is_zero(), ContractError::InvalidListingPrice {});
+
+    VOUCHER_LISTINGS.save(
+        deps.storage,
+        &token_id,
+        &VoucherListing {
+            seller: deps.api.addr_validate(&sender)?,
+            price,
+        },
+    )?;
+
+    let attrs = vec![
+        attr("action", "list_voucher"),
+        attr("token_id", token_id),
+        attr("seller", sender),
+        attr("price", price.to_string()),
+    ];
+    Ok(response("execute-receive_nft", CONTRACT_NAME, attrs))
+}
+
+fn execute_buy_voucher(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    token_id: String,
+    receiver: Option<String>,
+) -> ContractResult<Response<NeutronMsg>> {
+    pause_guard(deps.storage)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let listing = VOUCHER_LISTINGS
+        .may_load(deps.storage, &token_id)?
+        .ok_or_else(|| ContractError::VoucherIsNotListed {
+            token_id: token_id.to_string(),
+        })?;
+    let paid = cw_utils::must_pay(&info, &config.base_denom)?;
+    ensure_eq!(
+        paid,
+        listing.price,
+        ContractError::InvalidPayment {
+            expected: listing.price,
+            got: paid,
+        }
+    );
+    let recipient = match receiver {
+        Some(receiver) => deps.api.addr_validate(&receiver)?,
+        None => info.sender,
+    };
+    VOUCHER_LISTINGS.remove(deps.storage, &token_id);
+
+    let addrs = get_contracts!(deps, config.factory_contract, withdrawal_voucher_contract);
+    let messages = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: addrs.withdrawal_voucher_contract,
+            msg: to_json_binary(
+                &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::TransferNft {
+                    recipient: recipient.to_string(),
+                    token_id: token_id.to_string(),
+                },
+            )?,
+            funds: vec![],
+        }),
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: listing.seller.to_string(),
+            amount: vec![Coin {
+                denom: config.base_denom,
+                amount: listing.price,
+            }],
+        }),
+    ];
+
+    let attrs = vec![
+        attr("action", "buy_voucher"),
+        attr("token_id", token_id),
+        attr("seller", listing.seller),
+        attr("recipient", recipient),
+        attr("price", listing.price.to_string()),
+    ];
+    Ok(response("execute-buy_voucher", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn execute_delist_voucher(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    token_id: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let listing = VOUCHER_LISTINGS
+        .may_load(deps.storage, &token_id)?
+        .ok_or_else(|| ContractError::VoucherIsNotListed {
+            token_id: token_id.to_string(),
+        })?;
+    ensure_eq!(listing.seller, info.sender, ContractError::Unauthorized {});
+    VOUCHER_LISTINGS.remove(deps.storage, &token_id);
+
+    let addrs = get_contracts!(deps, config.factory_contract, withdrawal_voucher_contract);
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: addrs.withdrawal_voucher_contract,
+        msg: to_json_binary(
+            &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::TransferNft {
+                recipient: listing.seller.to_string(),
+                token_id: token_id.to_string(),
+            },
+        )?,
+        funds: vec![],
+    });
+
+    let attrs = vec![
+        attr("action", "delist_voucher"),
+        attr("token_id", token_id),
+        attr("seller", listing.seller),
+    ];
+    Ok(response("execute-delist_voucher", CONTRACT_NAME, attrs).add_message(msg))
+}
+
+fn execute_withdraw_many(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<String>,
+    receiver: Option<String>,
+) -> ContractResult<Response<NeutronMsg>> {
+    pause_guard(deps.storage)?;
+
+    if token_ids.is_empty() {
+        return Err(ContractError::EmptyTokenIds {});
+    }
+    let mut seen = HashSet::new();
+    if let Some(token_id) = token_ids.iter().find(|token_id| !seen.insert(*token_id)) {
+        return Err(ContractError::DuplicateTokenId {
+            token_id: token_id.to_string(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let addrs = get_contracts!(
+        deps,
+        config.factory_contract,
+        withdrawal_voucher_contract,
+        core_contract
+    );
+
+    let mut messages = vec![];
+    let mut batch_payouts: BTreeMap<u128, Uint128> = BTreeMap::new();
+    let mut total_payout = Uint128::zero();
+    let mut withdrawn_token_ids = vec![];
+    let mut skipped_token_ids = vec![];
+    for token_id in token_ids {
+        let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+            &addrs.withdrawal_voucher_contract,
+            &drop_staking_base::msg::withdrawal_voucher::QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )?;
+        ensure_eq!(owner.owner, info.sender, ContractError::Unauthorized {});
+
+        // vouchers of batches that are not withdrawn yet stay with the owner
+        let (batch_id, payout_amount) = match get_voucher_payout(
+            deps.as_ref(),
+            &addrs.withdrawal_voucher_contract,
+            &addrs.core_contract,
+            token_id.to_string(),
+        ) {
+            Err(ContractError::BatchIsNotWithdrawn {}) => {
+                skipped_token_ids.push(token_id);
+                continue;
+            }
+            res => res?,
+        };
+        *batch_payouts.entry(batch_id).or_default() += payout_amount;
+        total_payout += payout_amount;
+
+        // voucher contract checks that we were approved by the owner
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: addrs.withdrawal_voucher_contract.to_string(),
+            msg: to_json_binary(
+                &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::TransferNft {
+                    recipient: env.contract.address.to_string(),
+                    token_id: token_id.to_string(),
+                },
+            )?,
+            funds: vec![],
+        }));
+        withdrawn_token_ids.push(token_id);
+    }
+    ensure!(
+        !withdrawn_token_ids.is_empty(),
+        ContractError::BatchIsNotWithdrawn {}
+    );
+
+    for (batch_id, withdrawn_amount) in batch_payouts {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: addrs.core_contract.to_string(),
+            msg: to_json_binary(
+                &drop_staking_base::msg::core::ExecuteMsg::UpdateWithdrawnAmount {
+                    batch_id,
+                    withdrawn_amount,
+                },
+            )?,
+            funds: vec![],
+        }));
+    }
+
+    let to_address = receiver.unwrap_or(info.sender.to_string());
+    messages.push(CosmosMsg::Bank(BankMsg::Send {
+        to_address: to_address.clone(),
+        amount: vec![Coin {
+            denom: config.base_denom,
+            amount: total_payout,
+        }],
+    }));
+
+    let attrs = vec![
+        attr("action", "withdraw_many"),
+        attr("token_ids", withdrawn_token_ids.join(",")),
+        attr("skipped_token_ids", skipped_token_ids.join(",")),
+        attr("payout_amount", total_payout.to_string()),
+        attr("to_address", to_address),
+    ];
+    Ok(response("execute-withdraw_many", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn get_voucher_payout(
+    deps: Deps<NeutronQuery>,
+    withdrawal_voucher_contract: &str,
+    core_contract: &str,
+    token_id: String,
+) -> ContractResult<(u128, Uint128)> {
+    let voucher: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+        withdrawal_voucher_contract,
         &drop_staking_base::msg::withdrawal_voucher::QueryMsg::NftInfo { token_id },
     )?;
     let voucher_extension = voucher.extension.ok_or_else(|| ContractError::InvalidNFT {
@@ -192,7 +611,7 @@ fn execute_receive_nft_withdraw(
             })?;
 
     let unbond_batch: UnbondBatch = deps.querier.query_wasm_smart(
-        &addrs.core_contract,
+        core_contract,
         &drop_staking_base::msg::core::QueryMsg::UnbondBatch {
             batch_id: batch_id.into(),
         },
@@ -208,32 +627,10 @@ fn execute_receive_nft_withdraw(
         unbond_batch.total_dasset_amount_to_withdraw,
     );
 
-    let payout_amount = user_share * unbond_batch.unbonded_amount.unwrap_or(Uint128::zero());
-    let to_address = receiver.unwrap_or(sender);
-    attrs.push(attr("batch_id", batch_id.to_string()));
-    attrs.push(attr("payout_amount", payout_amount.to_string()));
-    attrs.push(attr("to_address", &to_address));
-
-    let mut messages = vec![CosmosMsg::Bank(BankMsg::Send {
-        to_address,
-        amount: vec![Coin {
-            denom: config.base_denom,
-            amount: payout_amount,
-        }],
-    })];
-
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: addrs.core_contract.to_string(),
-        msg: to_json_binary(
-            &drop_staking_base::msg::core::ExecuteMsg::UpdateWithdrawnAmount {
-                batch_id,
-                withdrawn_amount: payout_amount,
-            },
-        )?,
-        funds: info.funds,
-    }));
-
-    Ok(response("execute-receive_nft", CONTRACT_NAME, attrs).add_messages(messages))
+    Ok((
+        batch_id,
+        user_share * unbond_batch.unbonded_amount.unwrap_or(Uint128::zero()),
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use cw_ownable::OwnershipError;
 use drop_helpers::pause::PauseError;
 use neutron_sdk::NeutronError;
@@ -24,12 +24,30 @@ pub enum ContractError {
     #[error("Batch is not withdrawn yet")]
     BatchIsNotWithdrawn {},
 
+    #[error("No token ids provided")]
+    EmptyTokenIds {},
+
+    #[error("Duplicate token id: {token_id}")]
+    DuplicateTokenId { token_id: String },
+
     #[error("Missing unbonded amount in batch")]
     BatchAmountIsEmpty {},
 
     #[error("Slashing effect is not set")]
     BatchSlashingEffectIsEmpty {},
 
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("Voucher {token_id} is not listed")]
+    VoucherIsNotListed { token_id: String },
+
+    #[error("Listing price must be greater than zero")]
+    InvalidListingPrice {},
+
+    #[error("Invalid payment: expected {expected}, got {got}")]
+    InvalidPayment { expected: Uint128, got: Uint128 },
+
     #[error("{0}")]
     OwnershipError(#[from] OwnershipError),
 
@@ -1,2 +1,5 @@
 pub mod contract;
 mod error;
+
+#[cfg(test)]
+mod tests;
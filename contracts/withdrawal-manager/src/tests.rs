@@ -0,0 +1,745 @@
+use crate::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+};
+use cosmwasm_std::{
+    attr, coin, from_json,
+    testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR},
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Event, Uint128, WasmMsg,
+};
+use cw721::{NftInfoResponse, OwnerOfResponse};
+use drop_helpers::testing::{mock_dependencies, mock_state_query, WasmMockQuerier};
+use drop_staking_base::{
+    msg::{
+        withdrawal_manager::{
+            ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveNftMsg, VoucherQuoteResponse,
+        },
+        withdrawal_voucher::QueryMsg as VoucherQueryMsg,
+    },
+    state::{
+        core::{UnbondBatch, UnbondBatchStatus, UnbondBatchStatusTimestamps},
+        withdrawal_manager::{Cw721ReceiveMsg, VoucherListing, VOUCHER_LISTINGS},
+        withdrawal_voucher::Metadata,
+    },
+};
+
+fn setup(
+    deps: &mut cosmwasm_std::OwnedDeps<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        WasmMockQuerier,
+        neutron_sdk::bindings::query::NeutronQuery,
+    >,
+) {
+    mock_state_query(deps);
+    instantiate(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            factory_contract: "factory_contract".to_string(),
+            base_denom: "untrn".to_string(),
+            owner: "owner".to_string(),
+        },
+    )
+    .unwrap();
+}
+
+fn unbond_batch(
+    status: UnbondBatchStatus,
+    total_dasset_amount_to_withdraw: u128,
+    unbonded_amount: Option<u128>,
+) -> UnbondBatch {
+    UnbondBatch {
+        total_dasset_amount_to_withdraw: Uint128::from(total_dasset_amount_to_withdraw),
+        expected_native_asset_amount: Uint128::from(total_dasset_amount_to_withdraw),
+        expected_release_time: 0,
+        total_unbond_items: 1,
+        status,
+        slashing_effect: None,
+        unbonded_amount: unbonded_amount.map(Uint128::from),
+        withdrawn_amount: None,
+        status_timestamps: UnbondBatchStatusTimestamps {
+            new: 0,
+            unbond_requested: None,
+            unbond_failed: None,
+            unbonding: None,
+            withdrawing: None,
+            withdrawn: None,
+            withdrawing_emergency: None,
+            withdrawn_emergency: None,
+        },
+    }
+}
+
+/// Mocks voucher and core queries for the given vouchers, a voucher is
+/// `(token_id, owner, batch_id, amount)`
+fn mock_vouchers(
+    querier: &mut WasmMockQuerier,
+    vouchers: &[(&str, &str, u128, u128)],
+    batches: &[(u128, UnbondBatch)],
+) {
+    let vouchers: Vec<(String, String, u128, u128)> = vouchers
+        .iter()
+        .map(|(token_id, owner, batch_id, amount)| {
+            (token_id.to_string(), owner.to_string(), *batch_id, *amount)
+        })
+        .collect();
+    for _ in 0..vouchers.len() * 2 {
+        let vouchers = vouchers.clone();
+        querier.add_wasm_query_response("withdrawal_voucher_contract", move |msg| {
+            let find = |token_id: &str| {
+                vouchers
+                    .iter()
+                    .find(|(id, _, _, _)| id == token_id)
+                    .unwrap()
+                    .clone()
+            };
+            match from_json(msg).unwrap() {
+                VoucherQueryMsg::OwnerOf { token_id, .. } => to_json_binary(&OwnerOfResponse {
+                    owner: find(&token_id).1,
+                    approvals: vec![],
+                })
+                .unwrap(),
+                VoucherQueryMsg::NftInfo { token_id } => {
+                    let (_, _, batch_id, amount) = find(&token_id);
+                    to_json_binary(&NftInfoResponse {
+                        token_uri: None,
+                        extension: Some(Metadata {
+                            name: token_id,
+                            batch_id: batch_id.to_string(),
+                            amount: Uint128::from(amount),
+                            ..Default::default()
+                        }),
+                    })
+                    .unwrap()
+                }
+                _ => unimplemented!(),
+            }
+        });
+    }
+    for _ in 0..vouchers.len() {
+        let batches = batches.to_vec();
+        querier.add_wasm_query_response("core_contract", move |msg| {
+            match from_json(msg).unwrap() {
+                drop_staking_base::msg::core::QueryMsg::UnbondBatch { batch_id } => {
+                    let (_, batch) = batches
+                        .iter()
+                        .find(|(id, _)| Uint128::from(*id) == batch_id)
+                        .unwrap();
+                    to_json_binary(batch).unwrap()
+                }
+                _ => unimplemented!(),
+            }
+        });
+    }
+}
+
+fn transfer_to_manager_msg(token_id: &str) -> CosmosMsg<neutron_sdk::bindings::msg::NeutronMsg> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "withdrawal_voucher_contract".to_string(),
+        msg: to_json_binary(
+            &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::TransferNft {
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                token_id: token_id.to_string(),
+            },
+        )
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn update_withdrawn_amount_msg(
+    batch_id: u128,
+    withdrawn_amount: u128,
+) -> CosmosMsg<neutron_sdk::bindings::msg::NeutronMsg> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "core_contract".to_string(),
+        msg: to_json_binary(
+            &drop_staking_base::msg::core::ExecuteMsg::UpdateWithdrawnAmount {
+                batch_id,
+                withdrawn_amount: Uint128::from(withdrawn_amount),
+            },
+        )
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+#[test]
+fn withdraw_many_mixed_batches() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    mock_vouchers(
+        &mut deps.querier,
+        &[
+            ("0_1", "user", 0, 100),
+            ("1_1", "user", 1, 50),
+            ("0_2", "user", 0, 300),
+        ],
+        &[
+            (
+                0,
+                unbond_batch(UnbondBatchStatus::Withdrawn, 400, Some(800)),
+            ),
+            (
+                1,
+                unbond_batch(UnbondBatchStatus::Withdrawn, 100, Some(150)),
+            ),
+        ],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec!["0_1".to_string(), "1_1".to_string(), "0_2".to_string()],
+            receiver: Some("receiver".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            transfer_to_manager_msg("0_1"),
+            transfer_to_manager_msg("1_1"),
+            transfer_to_manager_msg("0_2"),
+            update_withdrawn_amount_msg(0, 800),
+            update_withdrawn_amount_msg(1, 75),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "receiver".to_string(),
+                amount: vec![Coin::new(875, "untrn")],
+            }),
+        ]
+    );
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-withdrawal-manager-execute-withdraw_many")
+                .add_attributes(vec![
+                    attr("action", "withdraw_many"),
+                    attr("token_ids", "0_1,1_1,0_2"),
+                    attr("skipped_token_ids", ""),
+                    attr("payout_amount", "875"),
+                    attr("to_address", "receiver"),
+                ])
+        ]
+    );
+}
+
+#[test]
+fn withdraw_many_partial_success() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    mock_vouchers(
+        &mut deps.querier,
+        &[("0_1", "user", 0, 100), ("1_1", "user", 1, 50)],
+        &[
+            (
+                0,
+                unbond_batch(UnbondBatchStatus::Withdrawn, 100, Some(110)),
+            ),
+            (1, unbond_batch(UnbondBatchStatus::Unbonding, 50, None)),
+        ],
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec!["0_1".to_string(), "1_1".to_string()],
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            transfer_to_manager_msg("0_1"),
+            update_withdrawn_amount_msg(0, 110),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user".to_string(),
+                amount: vec![Coin::new(110, "untrn")],
+            }),
+        ]
+    );
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-withdrawal-manager-execute-withdraw_many")
+                .add_attributes(vec![
+                    attr("action", "withdraw_many"),
+                    attr("token_ids", "0_1"),
+                    attr("skipped_token_ids", "1_1"),
+                    attr("payout_amount", "110"),
+                    attr("to_address", "user"),
+                ])
+        ]
+    );
+}
+
+#[test]
+fn withdraw_many_nothing_withdrawn() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    mock_vouchers(
+        &mut deps.querier,
+        &[("1_1", "user", 1, 50)],
+        &[(1, unbond_batch(UnbondBatchStatus::Unbonding, 50, None))],
+    );
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec!["1_1".to_string()],
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BatchIsNotWithdrawn {});
+}
+
+#[test]
+fn withdraw_many_not_owner() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    mock_vouchers(
+        &mut deps.querier,
+        &[("0_1", "user", 0, 100), ("0_2", "somebody", 0, 100)],
+        &[(
+            0,
+            unbond_batch(UnbondBatchStatus::Withdrawn, 200, Some(200)),
+        )],
+    );
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec!["0_1".to_string(), "0_2".to_string()],
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn withdraw_many_duplicate_token_ids() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec!["0_1".to_string(), "0_2".to_string(), "0_1".to_string()],
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicateTokenId {
+            token_id: "0_1".to_string()
+        }
+    );
+}
+
+#[test]
+fn withdraw_many_empty_token_ids() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec![],
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EmptyTokenIds {});
+}
+
+#[test]
+fn withdraw_many_paused() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawMany {
+            token_ids: vec!["0_1".to_string()],
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PauseError(drop_helpers::pause::PauseError::Paused {})
+    );
+}
+
+fn transfer_msg(
+    recipient: &str,
+    token_id: &str,
+) -> CosmosMsg<neutron_sdk::bindings::msg::NeutronMsg> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "withdrawal_voucher_contract".to_string(),
+        msg: to_json_binary(
+            &drop_staking_base::msg::withdrawal_voucher::ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.to_string(),
+            },
+        )
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn list_voucher_msg(token_id: &str, price: u128) -> ExecuteMsg {
+    ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+        sender: "seller".to_string(),
+        token_id: token_id.to_string(),
+        msg: to_json_binary(&ReceiveNftMsg::List {
+            price: Uint128::from(price),
+        })
+        .unwrap(),
+    })
+}
+
+fn setup_listing(
+    deps: &mut cosmwasm_std::OwnedDeps<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        WasmMockQuerier,
+        neutron_sdk::bindings::query::NeutronQuery,
+    >,
+) {
+    setup(deps);
+    VOUCHER_LISTINGS
+        .save(
+            deps.as_mut().storage,
+            "0_1",
+            &VoucherListing {
+                seller: Addr::unchecked("seller"),
+                price: Uint128::from(100u128),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn list_voucher() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("withdrawal_voucher_contract", &[]),
+        list_voucher_msg("0_1", 100),
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-withdrawal-manager-execute-receive_nft")
+                .add_attributes(vec![
+                    attr("action", "list_voucher"),
+                    attr("token_id", "0_1"),
+                    attr("seller", "seller"),
+                    attr("price", "100"),
+                ])
+        ]
+    );
+    let listing: VoucherListing = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Listing {
+                token_id: "0_1".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        listing,
+        VoucherListing {
+            seller: Addr::unchecked("seller"),
+            price: Uint128::from(100u128),
+        }
+    );
+}
+
+#[test]
+fn list_voucher_not_from_voucher_contract() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        list_voucher_msg("0_1", 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn list_voucher_zero_price() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("withdrawal_voucher_contract", &[]),
+        list_voucher_msg("0_1", 0),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidListingPrice {});
+}
+
+#[test]
+fn buy_voucher() {
+    let mut deps = mock_dependencies(&[]);
+    setup_listing(&mut deps);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("buyer", &[coin(100, "untrn")]),
+        ExecuteMsg::BuyVoucher {
+            token_id: "0_1".to_string(),
+            receiver: Some("receiver".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            transfer_msg("receiver", "0_1"),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "seller".to_string(),
+                amount: vec![Coin::new(100, "untrn")],
+            }),
+        ]
+    );
+    assert!(!VOUCHER_LISTINGS.has(deps.as_ref().storage, "0_1"));
+}
+
+#[test]
+fn buy_voucher_invalid_payment() {
+    let mut deps = mock_dependencies(&[]);
+    setup_listing(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("buyer", &[coin(99, "untrn")]),
+        ExecuteMsg::BuyVoucher {
+            token_id: "0_1".to_string(),
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPayment {
+            expected: Uint128::from(100u128),
+            got: Uint128::from(99u128),
+        }
+    );
+}
+
+#[test]
+fn buy_voucher_invalid_receiver() {
+    let mut deps = mock_dependencies(&[]);
+    setup_listing(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("buyer", &[coin(100, "untrn")]),
+        ExecuteMsg::BuyVoucher {
+            token_id: "0_1".to_string(),
+            receiver: Some("Receiver".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+    assert!(VOUCHER_LISTINGS.has(deps.as_ref().storage, "0_1"));
+}
+
+#[test]
+fn buy_voucher_not_listed() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("buyer", &[coin(100, "untrn")]),
+        ExecuteMsg::BuyVoucher {
+            token_id: "0_1".to_string(),
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::VoucherIsNotListed {
+            token_id: "0_1".to_string()
+        }
+    );
+}
+
+#[test]
+fn delist_voucher() {
+    let mut deps = mock_dependencies(&[]);
+    setup_listing(&mut deps);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("seller", &[]),
+        ExecuteMsg::DelistVoucher {
+            token_id: "0_1".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![transfer_msg("seller", "0_1")]
+    );
+    assert!(!VOUCHER_LISTINGS.has(deps.as_ref().storage, "0_1"));
+}
+
+#[test]
+fn delist_voucher_not_seller() {
+    let mut deps = mock_dependencies(&[]);
+    setup_listing(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        ExecuteMsg::DelistVoucher {
+            token_id: "0_1".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+fn query_voucher_quote(
+    deps: &cosmwasm_std::OwnedDeps<
+        cosmwasm_std::testing::MockStorage,
+        cosmwasm_std::testing::MockApi,
+        WasmMockQuerier,
+        neutron_sdk::bindings::query::NeutronQuery,
+    >,
+) -> cosmwasm_std::StdResult<VoucherQuoteResponse> {
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::VoucherQuote {
+            token_id: "0_1".to_string(),
+        },
+    )
+    .map(|res| from_json(res).unwrap())
+}
+
+#[test]
+fn voucher_quote_unbonding() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateMarketConfig {
+            discount_rate: Decimal::percent(10),
+        },
+    )
+    .unwrap();
+    let mut batch = unbond_batch(UnbondBatchStatus::Unbonding, 400, None);
+    batch.expected_native_asset_amount = Uint128::from(800u128);
+    batch.slashing_effect = Some(Decimal::percent(90));
+    // half a year left until the release
+    batch.expected_release_time = mock_env().block.time.seconds() + 365 * 24 * 60 * 60 / 2;
+    mock_vouchers(&mut deps.querier, &[("0_1", "user", 0, 100)], &[(0, batch)]);
+    assert_eq!(
+        query_voucher_quote(&deps).unwrap(),
+        VoucherQuoteResponse {
+            batch_id: Uint128::zero(),
+            amount: Uint128::from(100u128),
+            expected_payout: Uint128::from(180u128),
+            remaining_time: 365 * 24 * 60 * 60 / 2,
+            // 180 / (1 + 10% * 0.5)
+            fair_price: Uint128::from(171u128),
+        }
+    );
+}
+
+#[test]
+fn voucher_quote_withdrawn() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    mock_vouchers(
+        &mut deps.querier,
+        &[("0_1", "user", 0, 100)],
+        &[(
+            0,
+            unbond_batch(UnbondBatchStatus::Withdrawn, 400, Some(820)),
+        )],
+    );
+    assert_eq!(
+        query_voucher_quote(&deps).unwrap(),
+        VoucherQuoteResponse {
+            batch_id: Uint128::zero(),
+            amount: Uint128::from(100u128),
+            expected_payout: Uint128::from(205u128),
+            remaining_time: 0,
+            fair_price: Uint128::from(205u128),
+        }
+    );
+}
+
+#[test]
+fn voucher_quote_withdrawn_emergency() {
+    let mut deps = mock_dependencies(&[]);
+    setup(&mut deps);
+    mock_vouchers(
+        &mut deps.querier,
+        &[("0_1", "user", 0, 100)],
+        &[(
+            0,
+            unbond_batch(UnbondBatchStatus::WithdrawnEmergency, 400, None),
+        )],
+    );
+    let err = query_voucher_quote(&deps).unwrap_err();
+    assert_eq!(
+        err,
+        cosmwasm_std::StdError::generic_err(
+            "batch 0 is withdrawn in emergency, payout is unknown until it is resolved"
+        )
+    );
+}
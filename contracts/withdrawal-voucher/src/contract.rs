@@ -1,17 +1,75 @@
-use cosmwasm_std::Empty;
-pub use cw721_base::{ContractError, MinterResponse};
-use drop_staking_base::msg::withdrawal_voucher::Extension;
+use crate::error::{ContractError, ContractResult};
+use cosmwasm_std::{DepsMut, Empty, Env, MessageInfo, Response, Uint128};
+use cw721_base::state::TokenInfo;
+pub use cw721_base::MinterResponse;
+use drop_staking_base::msg::withdrawal_voucher::{Extension, ExtensionMsg};
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub type Cw721VoucherContract<'a> = cw721_base::Cw721Contract<'a, Extension, Empty, Empty, Empty>;
+pub type Cw721VoucherContract<'a> =
+    cw721_base::Cw721Contract<'a, Extension, Empty, ExtensionMsg, Empty>;
+
+pub fn execute_split(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    amount: Uint128,
+) -> ContractResult<Response> {
+    let contract = Cw721VoucherContract::default();
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    contract.check_can_send(deps.as_ref(), &env, &info, &token)?;
+
+    let metadata = token
+        .extension
+        .clone()
+        .ok_or(ContractError::MetadataIsNotSet {})?;
+    if amount.is_zero() || amount >= metadata.amount {
+        return Err(ContractError::InvalidSplitAmount {});
+    }
+
+    contract.tokens.remove(deps.storage, &token_id)?;
+    let mut new_token_ids = vec![];
+    for (suffix, part) in [("a", amount), ("b", metadata.amount - amount)] {
+        let new_token_id = format!("{}_{}", token_id, suffix);
+        let mut extension = metadata.clone();
+        extension.amount = part;
+        if let Some(attributes) = extension.attributes.as_mut() {
+            for attribute in attributes
+                .iter_mut()
+                .filter(|attribute| attribute.trait_type == "received_amount")
+            {
+                attribute.value = part.to_string();
+            }
+        }
+        contract
+            .tokens
+            .update(deps.storage, &new_token_id, |old| match old {
+                Some(_) => Err(ContractError::Cw721(cw721_base::ContractError::Claimed {})),
+                None => Ok(TokenInfo {
+                    owner: token.owner.clone(),
+                    approvals: vec![],
+                    token_uri: token.token_uri.clone(),
+                    extension: Some(extension),
+                }),
+            })?;
+        new_token_ids.push(new_token_id);
+    }
+    // one voucher is replaced with two
+    contract.increment_tokens(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "split")
+        .add_attribute("token_id", token_id)
+        .add_attribute("new_token_ids", new_token_ids.join(",")))
+}
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
     use super::*;
 
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+    use cosmwasm_std::{Binary, Deps, StdError, StdResult};
     use drop_staking_base::msg::withdrawal_voucher::{
         ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
     };
@@ -34,8 +92,13 @@ pub mod entry {
         env: Env,
         info: MessageInfo,
         msg: ExecuteMsg,
-    ) -> Result<Response, ContractError> {
-        Cw721VoucherContract::default().execute(deps, env, info, msg)
+    ) -> ContractResult<Response> {
+        match msg {
+            ExecuteMsg::Extension {
+                msg: ExtensionMsg::Split { token_id, amount },
+            } => execute_split(deps, env, info, token_id, amount),
+            msg => Ok(Cw721VoucherContract::default().execute(deps, env, info, msg)?),
+        }
     }
 
     #[cosmwasm_std::entry_point]
@@ -44,7 +107,7 @@ pub mod entry {
     }
 
     #[cosmwasm_std::entry_point]
-    pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
         let version: semver::Version = CONTRACT_VERSION
             .parse()
             .map_err(|e: semver::Error| StdError::generic_err(e.to_string()))?;
@@ -0,0 +1,19 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Cw721(#[from] cw721_base::ContractError),
+
+    #[error("Voucher metadata is not set")]
+    MetadataIsNotSet {},
+
+    #[error("Split amount must be greater than zero and less than voucher amount")]
+    InvalidSplitAmount {},
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
@@ -1 +1,5 @@
 pub mod contract;
+pub mod error;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use crate::{
+    contract::{execute_split, Cw721VoucherContract},
+    error::ContractError,
+};
+use cosmwasm_std::{
+    attr,
+    testing::{mock_dependencies, mock_env, mock_info},
+    DepsMut, Uint128,
+};
+use drop_staking_base::{
+    msg::withdrawal_voucher::{ExecuteMsg, InstantiateMsg},
+    state::withdrawal_voucher::{Metadata, Trait},
+};
+
+fn mint_voucher(mut deps: DepsMut, extension: Option<Metadata>) {
+    let contract = Cw721VoucherContract::default();
+    contract
+        .instantiate(
+            deps.branch(),
+            mock_env(),
+            mock_info("core_contract", &[]),
+            InstantiateMsg {
+                name: "Drop Voucher".to_string(),
+                symbol: "DROPV".to_string(),
+                minter: "core_contract".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps,
+            mock_env(),
+            mock_info("core_contract", &[]),
+            ExecuteMsg::Mint {
+                token_id: "0_owner_1".to_string(),
+                owner: "owner".to_string(),
+                token_uri: None,
+                extension,
+            },
+        )
+        .unwrap();
+}
+
+fn metadata(amount: u128) -> Metadata {
+    Metadata {
+        name: "dATOM voucher".to_string(),
+        description: Some("Withdrawal voucher".into()),
+        attributes: Some(vec![
+            Trait {
+                display_type: None,
+                trait_type: "unbond_batch_id".to_string(),
+                value: "0".to_string(),
+            },
+            Trait {
+                display_type: None,
+                trait_type: "received_amount".to_string(),
+                value: amount.to_string(),
+            },
+        ]),
+        batch_id: "0".to_string(),
+        amount: Uint128::from(amount),
+    }
+}
+
+#[test]
+fn split() {
+    let mut deps = mock_dependencies();
+    mint_voucher(deps.as_mut(), Some(metadata(100)));
+    let res = execute_split(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        "0_owner_1".to_string(),
+        Uint128::from(30u128),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            attr("action", "split"),
+            attr("token_id", "0_owner_1"),
+            attr("new_token_ids", "0_owner_1_a,0_owner_1_b"),
+        ]
+    );
+
+    let contract = Cw721VoucherContract::default();
+    assert!(!contract.tokens.has(deps.as_ref().storage, "0_owner_1"));
+    for (token_id, amount) in [("0_owner_1_a", 30), ("0_owner_1_b", 70)] {
+        let token = contract
+            .tokens
+            .load(deps.as_ref().storage, token_id)
+            .unwrap();
+        assert_eq!(token.owner, "owner");
+        assert_eq!(token.extension, Some(metadata(amount)));
+    }
+    assert_eq!(contract.token_count(deps.as_ref().storage).unwrap(), 2);
+}
+
+#[test]
+fn split_invalid_amount() {
+    let mut deps = mock_dependencies();
+    mint_voucher(deps.as_mut(), Some(metadata(100)));
+    for amount in [0u128, 100, 101] {
+        let err = execute_split(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            "0_owner_1".to_string(),
+            Uint128::from(amount),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidSplitAmount {});
+    }
+}
+
+#[test]
+fn split_without_metadata() {
+    let mut deps = mock_dependencies();
+    mint_voucher(deps.as_mut(), None);
+    let err = execute_split(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        "0_owner_1".to_string(),
+        Uint128::from(30u128),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MetadataIsNotSet {});
+}
+
+#[test]
+fn split_not_owner() {
+    let mut deps = mock_dependencies();
+    mint_voucher(deps.as_mut(), Some(metadata(100)));
+    let err = execute_split(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        "0_owner_1".to_string(),
+        Uint128::from(30u128),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Cw721(_)));
+}
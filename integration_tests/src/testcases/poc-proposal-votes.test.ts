@@ -110,7 +110,7 @@ describe('POC Proposal Votes', () => {
         fs.readFileSync(
           join(
             __dirname,
-            '../../../artifacts/drop_provider_proposals_poc.wasm',
+            '../../../artifacts/drop_provider_proposals.wasm',
           ),
         ),
       ),
@@ -153,7 +153,7 @@ describe('POC Proposal Votes', () => {
       account.address,
       Uint8Array.from(
         fs.readFileSync(
-          join(__dirname, '../../../artifacts/drop_proposal_votes_poc.wasm'),
+          join(__dirname, '../../../artifacts/drop_proposal_votes.wasm'),
         ),
       ),
       1.5,
@@ -104,7 +104,7 @@ describe('POC Provider Proposals', () => {
         fs.readFileSync(
           join(
             __dirname,
-            '../../../artifacts/drop_provider_proposals_poc.wasm',
+            '../../../artifacts/drop_provider_proposals.wasm',
           ),
         ),
       ),
@@ -1,5 +1,6 @@
 use cosmwasm_std::{
     ConversionOverflowError, Decimal256RangeExceeded, DivideByZeroError, OverflowError, StdError,
+    Uint128,
 };
 use cw_ownable::OwnershipError;
 use drop_helpers::pause::PauseError;
@@ -86,6 +87,9 @@ pub enum ContractError {
     #[error("Non Native rewards denom not found {denom}")]
     NonNativeRewardsDenomNotFound { denom: String },
 
+    #[error("Invalid non native rewards denom {denom}")]
+    InvalidNonNativeRewardsDenom { denom: String },
+
     #[error(
         "Puppeteer balance is outdated: ICA height {ica_height}, control height {control_height}"
     )]
@@ -124,15 +128,24 @@ pub enum ContractError {
     #[error("Unbonded amount must be less or equal to expected amount")]
     UnbondedAmountTooHigh {},
 
+    #[error("Batch ids list is empty")]
+    EmptyBatchIds {},
+
+    #[error("Duplicate batch id: {batch_id}")]
+    DuplicateBatchId { batch_id: u128 },
+
     #[error("Validator info not found: {validator}")]
     ValidatorInfoNotFound { validator: String },
 
-    #[error("Fee must be in range [0.0, 1.0]")]
+    #[error("Fee must be in range [0.0, 1.0)")]
     InvalidFee {},
 
     #[error("Bond provider already exists")]
     BondProviderAlreadyExists {},
 
+    #[error("Bond provider not found")]
+    BondProviderNotFound {},
+
     #[error("Empty list of providers")]
     BondProvidersListAreEmpty {},
 
@@ -150,6 +163,54 @@ pub enum ContractError {
 
     #[error("Bond provider balance isn't empty")]
     BondProviderBalanceNotEmpty {},
+
+    #[error("Exchange rate history is empty")]
+    ExchangeRateHistoryIsEmpty {},
+
+    #[error("TWAP window must be greater than zero")]
+    InvalidTwapWindow {},
+
+    #[error("Instant unbond is disabled")]
+    InstantUnbondDisabled {},
+
+    #[error("Instant unbond payout {payout} is less than min receive {min_receive}")]
+    InstantUnbondMinReceive {
+        payout: Uint128,
+        min_receive: Uint128,
+    },
+
+    #[error("Liquidity buffer is insufficient: available {available}, requested {requested}")]
+    LiquidityBufferInsufficient {
+        available: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("Instant unbond epoch limit exceeded: available {available}, requested {requested}")]
+    InstantUnbondEpochLimitExceeded {
+        available: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("Unbond in kind is disabled")]
+    UnbondInKindDisabled {},
+
+    #[error("Unbond in kind request {id} not found")]
+    UnbondInKindRequestNotFound { id: u64 },
+
+    #[error("Unbond in kind request {id} has no failed transfer to retry")]
+    UnbondInKindNotRetryable { id: u64 },
+
+    #[error("Share of unbond in kind request {id} is unknown and must be set by the owner")]
+    UnbondInKindShareRequired { id: u64 },
+
+    #[error("Unbond in kind request {id} is not transferred")]
+    UnbondInKindNotTransferred { id: u64 },
+
+    #[error("Validator {validator} is not in the validator set")]
+    UnbondInKindUnknownValidator { validator: String },
+
+    #[error("Validator {validator} has not enough stake delegated")]
+    UnbondInKindInsufficientDelegation { validator: String },
 }
 
 impl From<semver::Error> for ContractError {
@@ -0,0 +1,90 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_ownable::OwnershipError;
+use neutron_sdk::NeutronError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    NeutronError(#[from] NeutronError),
+
+    #[error("{0}")]
+    OwnershipError(#[from] OwnershipError),
+
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("Message is not supported")]
+    MessageIsNotSupported {},
+
+    #[error("Invalid denom")]
+    InvalidDenom {},
+
+    #[error("Puppeteer error: {message}")]
+    PuppeteerError { message: String },
+
+    #[error("Invalid State: {reason}")]
+    InvalidState { reason: String },
+
+    #[error("Puppeteer ICA is not registered")]
+    IcaNotRegistered {},
+
+    #[error("Not enough non staked balance to process idle. Min stake amount: {min_stake_amount}, non staked balance: {non_staked_balance}")]
+    NotEnoughToProcessIdle {
+        min_stake_amount: Uint128,
+        non_staked_balance: Uint128,
+    },
+
+    #[error("Deposit account {id} not found")]
+    DepositAccountNotFound { id: u64 },
+
+    #[error("Too many deposit accounts")]
+    TooManyDepositAccounts {},
+
+    #[error("Deposit address {id} is not registered")]
+    DepositAddressNotRegistered { id: u64 },
+
+    #[error("Deposit address {id} is not closed")]
+    DepositAddressNotClosed { id: u64 },
+
+    #[error("Deposit account {id} is busy")]
+    DepositAccountBusy { id: u64 },
+
+    #[error("Balance of deposit address {id} is not updated since the last deposit")]
+    DepositBalanceNotUpdated { id: u64 },
+
+    #[error("Deposit is too small. Min deposit: {min_deposit}, deposit: {deposit}")]
+    DepositTooSmall {
+        min_deposit: Uint128,
+        deposit: Uint128,
+    },
+
+    #[error("Nothing to retry for deposit account {id}")]
+    NothingToRetry { id: u64 },
+
+    #[error("IBC fee is not paid. Required: {required}, paid: {paid}")]
+    IbcFeeNotPaid { required: Uint128, paid: Uint128 },
+
+    #[error("No tokens minted")]
+    NoTokensMinted,
+
+    #[error("Invalid port id: {port_id}")]
+    InvalidPortId { port_id: String },
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
@@ -0,0 +1,47 @@
+use cosmwasm_std::{CoinFromStrError, StdError, Uint128};
+use cw_ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OwnershipError(#[from] OwnershipError),
+
+    #[error("{0}")]
+    CoinFromStrError(#[from] CoinFromStrError),
+
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("Either receiver or forward has to be set")]
+    InvalidDestination,
+
+    #[error("IBC timeout can't be zero")]
+    InvalidIbcTimeout,
+
+    #[error("IBC fee is not paid. Required: {required}, paid: {paid}")]
+    IbcFeeNotPaid { required: Uint128, paid: Uint128 },
+
+    #[error("No tokens minted")]
+    NoTokensMinted,
+
+    #[error("Unknown transfer {sequence} on channel {channel}")]
+    UnknownTransfer { channel: String, sequence: u64 },
+
+    #[error("Id {id} doesn't fit into a reply id")]
+    IdOverflow { id: u64 },
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
@@ -0,0 +1,51 @@
+use cosmwasm_std::StdError;
+use cw_ownable::OwnershipError;
+use neutron_sdk::NeutronError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    NeutronError(#[from] NeutronError),
+
+    #[error("{0}")]
+    OwnershipError(#[from] OwnershipError),
+
+    #[error("{0}")]
+    PaymentError(#[from] cw_utils::PaymentError),
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("Invalid denom")]
+    InvalidDenom {},
+
+    #[error("Message is not supported")]
+    MessageIsNotSupported {},
+
+    #[error("Max spread is zero")]
+    ZeroMaxSpread,
+
+    #[error("Max spread is too big. Max: 0.01")]
+    MaxSpreadTooBig,
+
+    #[error("Invalid swap route for {denom}: {reason}")]
+    InvalidSwapRoute { denom: String, reason: String },
+
+    #[error("Asset price query failed: {details}")]
+    AssetPriceQueryFailed { details: String },
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
@@ -40,6 +40,12 @@ pub enum ContractError {
     #[error("No tokens minted amount found")]
     NoTokensMintedAmountFound,
 
+    #[error("No withdrawal voucher minted")]
+    NoVoucherMinted,
+
+    #[error("No base asset received for the withdrawal voucher")]
+    NothingWithdrawn,
+
     #[error("Wrong bond state. Expected {expected:?}, got {got:?}")]
     WrongBondState { expected: String, got: String },
 
@@ -48,6 +54,15 @@ pub enum ContractError {
 
     #[error("IBC timeout out of range")]
     IbcTimeoutOutOfRange,
+
+    #[error("Invalid retry policy: {reason}")]
+    InvalidRetryPolicy { reason: String },
+
+    #[error("Bond {id} can't be retried before {retry_at}")]
+    RetryTooEarly { id: u64, retry_at: u64 },
+
+    #[error("Id {id} doesn't fit into a reply id")]
+    IdOverflow { id: u64 },
 }
 
 impl From<semver::Error> for ContractError {
@@ -1,8 +1,11 @@
 pub mod astroport_exchange_handler;
 pub mod core;
+pub mod deposit_bond_provider;
 pub mod distribution;
 pub mod factory;
+pub mod ibc_hook_bonder;
 pub mod lsm_share_bond_provider;
+pub mod lst_swap_bond_provider;
 pub mod mirror;
 pub mod native_bond_provider;
 pub mod price_provider;
@@ -16,6 +16,15 @@ pub enum ContractError {
     #[error("unauthorized")]
     Unauthorized,
 
+    #[error("Invalid scoring policy: {reason}")]
+    InvalidScoringPolicy { reason: String },
+
+    #[error("Weights are derived from the scoring policy while it is set")]
+    ScoringPolicyIsActive {},
+
+    #[error("Puppeteer contract is not configured")]
+    PuppeteerNotConfigured,
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 }
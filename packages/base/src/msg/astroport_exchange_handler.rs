@@ -19,6 +19,9 @@ pub enum ExecuteMsg {
     UpdateSwapOperations {
         operations: Option<Vec<SwapOperation>>,
     },
+    UpdateSwapReceiver {
+        receiver: Option<String>,
+    },
     Exchange {},
 }
 
@@ -40,6 +43,7 @@ pub struct ConfigResponse {
     pub from_denom: String,
     pub min_rewards: Uint128,
     pub swap_operations: Option<Vec<SwapOperation>>,
+    pub swap_receiver: Option<String>,
 }
 
 #[cw_serde]
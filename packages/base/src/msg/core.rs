@@ -1,11 +1,19 @@
 use crate::{
     error::core::ContractResult,
-    state::core::{Config, ConfigOptional, Pause},
+    msg::distribution::Redelegation,
+    state::{
+        bond_providers::BondProviderRoute,
+        core::{
+            Config, ConfigOptional, ExchangeRateSnapshot, FeeLedger, FeeLedgerEntry,
+            InstantUnbondConfig, NonNativeRewardsItem, Pause, RebalanceConfig, RewardsFeeConfig,
+            SlashingEvent, UnbondInKindConfig, UnbondInKindRequest,
+        },
+    },
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 
 #[allow(unused_imports)]
-use cosmwasm_std::{Addr, Deps, Uint128, Uint64};
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, Uint128, Uint64};
 use cw_ownable::cw_ownable_execute;
 use drop_puppeteer_base::peripheral_hook::ResponseHookMsg as PuppeteerResponseHookMsg;
 
@@ -53,6 +61,48 @@ pub struct FailedBatchResponse {
     pub response: Option<u128>,
 }
 
+#[cw_serde]
+pub struct LiquidityBufferResponse {
+    pub available: Uint128,
+    pub pending_refill_dasset: Uint128,
+    pub epoch_start: u64,
+    pub epoch_paid_out: Uint128,
+    pub epoch_limit: Uint128,
+}
+
+#[cw_serde]
+pub struct ExchangeRateHistoryResponse {
+    pub snapshots: Vec<(u64, ExchangeRateSnapshot)>,
+}
+
+#[cw_serde]
+pub struct ExchangeRateTwapResponse {
+    pub twap: Decimal,
+    pub window_start: u64,
+    pub window_end: u64,
+}
+
+#[cw_serde]
+pub struct FeeLedgerEntriesResponse {
+    pub entries: Vec<(u64, FeeLedgerEntry)>,
+}
+
+#[cw_serde]
+pub struct SlashingEventsResponse {
+    pub events: Vec<(u64, SlashingEvent)>,
+}
+
+#[cw_serde]
+pub struct UnbondInKindRequestsResponse {
+    pub requests: Vec<(u64, UnbondInKindRequest)>,
+}
+
+#[cw_serde]
+pub struct SimulateBondResponse {
+    pub bond_provider: Addr,
+    pub issue_amount: Uint128,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -62,6 +112,13 @@ pub enum QueryMsg {
     Owner {},
     #[returns(cosmwasm_std::Decimal)]
     ExchangeRate {},
+    #[returns(ExchangeRateHistoryResponse)]
+    ExchangeRateHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(ExchangeRateTwapResponse)]
+    ExchangeRateTwap { window_seconds: u64 },
     #[returns(Uint128)]
     CurrentUnbondBatch {},
     #[returns(crate::state::core::UnbondBatch)]
@@ -79,6 +136,10 @@ pub enum QueryMsg {
     TotalBonded {},
     #[returns(Vec<Addr>)]
     BondProviders {},
+    #[returns(Vec<(Addr, BondProviderRoute)>)]
+    BondProviderRoutes {},
+    #[returns(SimulateBondResponse)]
+    SimulateBond { coin: Coin },
     #[returns(Uint128)]
     TotalAsyncTokens {},
     #[returns(FailedBatchResponse)]
@@ -87,6 +148,39 @@ pub enum QueryMsg {
     Pause {},
     #[returns(Vec<String>)]
     BondHooks {},
+    #[returns(Option<InstantUnbondConfig>)]
+    InstantUnbondConfig {},
+    #[returns(Option<RewardsFeeConfig>)]
+    RewardsFeeConfig {},
+    #[returns(FeeLedger)]
+    FeeLedger {},
+    #[returns(FeeLedgerEntriesResponse)]
+    FeeLedgerEntries {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(LiquidityBufferResponse)]
+    LiquidityBuffer {},
+    #[returns(Option<RebalanceConfig>)]
+    RebalanceConfig {},
+    #[returns(Vec<Redelegation>)]
+    RebalanceQueue {},
+    #[returns(Vec<(String, u64)>)]
+    RedelegationCooldowns {},
+    #[returns(Vec<NonNativeRewardsItem>)]
+    NonNativeRewardsConfig {},
+    #[returns(SlashingEventsResponse)]
+    SlashingEvents {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<UnbondInKindConfig>)]
+    UnbondInKindConfig {},
+    #[returns(UnbondInKindRequestsResponse)]
+    UnbondInKindRequests {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_ownable_execute]
@@ -96,7 +190,33 @@ pub enum ExecuteMsg {
         receiver: Option<String>,
         r#ref: Option<String>,
     },
+    /// Mints dAsset for base asset a registered bond provider has already moved
+    /// to the staking ICA on its own, e.g. from a deposit address on the host chain.
+    BondFromProvider {
+        amount: Uint128,
+        receiver: String,
+    },
     Unbond {},
+    InstantUnbond {
+        min_receive: Uint128,
+    },
+    /// Exits with the delegation itself: the matching stake is tokenized
+    /// into an LSM share on the host chain and sent to the sender over IBC.
+    UnbondInKind {
+        validator: String,
+    },
+    /// Sends the LSM share of a request again once its IBC transfer failed,
+    /// or, for the receiver, once a sent transfer wasn't confirmed in time.
+    /// The owner sets the share if it couldn't be read from the host chain ack.
+    RetryUnbondInKind {
+        id: u64,
+        share: Option<cosmwasm_std::Coin>,
+    },
+    /// Closes a request once the receiver got the LSM share.
+    ConfirmUnbondInKind {
+        id: u64,
+    },
+    FundLiquidityBuffer {},
     Tick {},
     //permissioned
     AddBondProvider {
@@ -105,6 +225,10 @@ pub enum ExecuteMsg {
     RemoveBondProvider {
         bond_provider_address: String,
     },
+    SetBondProviderRoute {
+        bond_provider_address: String,
+        route: BondProviderRoute,
+    },
     UpdateConfig {
         new_config: Box<ConfigOptional>,
     },
@@ -117,10 +241,35 @@ pub enum ExecuteMsg {
         batch_id: u128,
         unbonded_amount: Uint128,
     },
+    ResolveEmergencyBatches {
+        batch_ids: Option<Vec<u128>>, // all batches withdrawn in emergency if not set
+    },
     SetPause(Pause),
     SetBondHooks {
         hooks: Vec<String>,
     },
+    UpdateInstantUnbondConfig {
+        config: Option<InstantUnbondConfig>,
+    },
+    UpdateUnbondInKindConfig {
+        config: Option<UnbondInKindConfig>,
+    },
+    UpdateRewardsFeeConfig {
+        fee: Decimal,
+        fee_collector: String,
+    },
+    WithdrawLiquidityBuffer {
+        amount: Uint128,
+        receiver: Option<String>,
+    },
+    UpdateRebalanceConfig {
+        config: Option<RebalanceConfig>,
+    },
+    /// Reward denoms other than the staked one which are moved from the ICA,
+    /// swapped by the rewards manager and restaked. An empty list disables it.
+    UpdateNonNativeRewardsConfig {
+        items: Vec<NonNativeRewardsItem>,
+    },
 }
 
 #[cw_serde]
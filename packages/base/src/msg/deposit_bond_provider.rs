@@ -0,0 +1,94 @@
+use crate::state::deposit_bond_provider::{ConfigOptional, DepositAccount};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+#[allow(unused_imports)]
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use drop_macros::{bond_provider, bond_provider_query};
+use drop_puppeteer_base::peripheral_hook::ResponseHookMsg as PuppeteerResponseHookMsg;
+
+#[allow(unused_imports)]
+use super::core::LastPuppeteerResponse;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub factory_contract: String,
+    pub base_denom: String,
+    pub connection_id: String,
+    pub sdk_version: String,
+    pub update_period: u64,
+    pub timeout: u64,
+    pub min_deposit: Uint128,
+    pub min_stake_amount: Uint128,
+    pub dasset_transfer_channel_id: String,
+}
+
+#[cw_serde]
+pub enum Receiver {
+    /// dAssets are minted to an address on Neutron
+    Local(String),
+    /// dAssets are minted to the provider and sent to an address on the host chain
+    Remote(String),
+}
+
+#[bond_provider]
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        new_config: ConfigOptional,
+    },
+    /// Registers a new deposit address on the host chain, attached funds pay the ICA registration fee
+    RegisterDepositAddress {
+        receiver: Receiver,
+    },
+    /// Registers the ICA of a deposit address again once its channel got closed
+    ReopenDepositAddress {
+        id: u64,
+    },
+    /// Moves the balance of a deposit address to the staking ICA and mints dAssets for it,
+    /// attached untrn pay the IBC fees, twice for a remote receiver to cover the dAsset transfer
+    ProcessDeposit {
+        id: u64,
+    },
+    /// Attached untrn pay the IBC fee of the dAsset transfer for a remote receiver
+    RetryMint {
+        id: u64,
+    },
+    /// Attached untrn pay the IBC fee of the dAsset transfer
+    RetryTransfer {
+        id: u64,
+    },
+    PeripheralHook(Box<PuppeteerResponseHookMsg>),
+}
+
+#[cw_serde]
+pub struct DepositAccountResponse {
+    pub id: u64,
+    pub account: DepositAccount,
+}
+
+#[bond_provider_query]
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::state::deposit_bond_provider::Config)]
+    Config {},
+    #[returns(DepositAccountResponse)]
+    DepositAccount { id: u64 },
+    #[returns(Vec<DepositAccountResponse>)]
+    DepositAccounts {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Uint128)]
+    NonStakedBalance {},
+    #[returns(crate::state::deposit_bond_provider::TxState)]
+    TxState {},
+    #[returns(LastPuppeteerResponse)]
+    LastPuppeteerResponse {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -17,6 +17,13 @@ pub struct Delegation {
     pub weight: u64,
 }
 
+#[cw_serde]
+pub struct Redelegation {
+    pub validator_from: String,
+    pub validator_to: String,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -30,6 +37,11 @@ pub enum QueryMsg {
         withdraw: Uint128,
         delegations: Delegations,
     },
+    #[returns(Vec<Redelegation>)]
+    CalcRebalance {
+        delegations: Delegations,
+        max_moves: u32,
+    },
 }
 
 #[cw_serde]
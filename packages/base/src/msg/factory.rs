@@ -1,7 +1,7 @@
 use crate::msg::token::DenomMetadata;
 use crate::state::factory::{CodeIds, RemoteOpts};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Decimal, Uint128};
+use cosmwasm_std::{Binary, CosmosMsg, Decimal, Uint128};
 use cw_ownable::cw_ownable_execute;
 use drop_macros::pausable;
 use neutron_sdk::bindings::msg::NeutronMsg;
@@ -74,7 +74,15 @@ pub enum ValidatorSetMsg {
 pub enum ExecuteMsg {
     UpdateConfig(Box<UpdateConfigMsg>),
     Proxy(ProxyMsg),
-    AdminExecute { msgs: Vec<CosmosMsg<NeutronMsg>> },
+    AdminExecute {
+        msgs: Vec<CosmosMsg<NeutronMsg>>,
+    },
+    /// Migrates every child contract to the given code ids, keyed
+    /// migrate messages override the default empty one
+    MigrateAll {
+        code_ids: CodeIds,
+        migrate_msgs: std::collections::HashMap<String, Binary>,
+    },
 }
 #[cw_serde]
 pub struct MigrateMsg {}
@@ -87,4 +95,6 @@ pub enum QueryMsg {
     State {},
     #[returns(std::collections::HashMap<String, String>)]
     PauseInfo {},
+    #[returns(Vec<crate::state::factory::Deployment>)]
+    Deployments {},
 }
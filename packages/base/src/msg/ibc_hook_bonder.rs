@@ -0,0 +1,48 @@
+use crate::state::ibc_hook_bonder::{ConfigOptional, Forward};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: Option<String>,
+    pub core_contract: String,
+    pub ibc_timeout: u64,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Target of ibc-hooks memos, e.g.
+    /// `{"wasm":{"contract":"<this contract>","msg":{"bond":{"fallback":"neutron1..","forward":{..}}}}}`.
+    /// Minted dAsset goes to `receiver` on Neutron or is forwarded to the next hop,
+    /// anything which can't be bonded or delivered is sent to `fallback`.
+    /// Forwarding needs the untrn IBC fee of the transfer attached next to the base asset,
+    /// untrn paid above the fee is sent to `fallback`.
+    Bond {
+        receiver: Option<String>,
+        forward: Option<Forward>,
+        fallback: String,
+        r#ref: Option<String>,
+    },
+    UpdateConfig {
+        new_config: ConfigOptional,
+    },
+}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::state::ibc_hook_bonder::Config)]
+    Config {},
+    #[returns(crate::state::ibc_hook_bonder::HookBond)]
+    HookBond { id: u64 },
+    #[returns(Vec<(u64, crate::state::ibc_hook_bonder::HookBond)>)]
+    HookBonds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
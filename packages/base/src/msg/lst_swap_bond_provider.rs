@@ -0,0 +1,47 @@
+use crate::state::lst_swap_bond_provider::ConfigOptional;
+use astroport::router::SwapOperation;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+#[allow(unused_imports)]
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use drop_macros::{bond_provider, bond_provider_query};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub factory_contract: String,
+    pub base_denom: String,
+    pub router_contract: String,
+    pub price_provider_contract: String,
+    pub max_spread: Decimal,
+}
+
+#[bond_provider]
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        new_config: ConfigOptional,
+    },
+    SetSwapRoute {
+        denom: String,
+        operations: Vec<SwapOperation>,
+    },
+    RemoveSwapRoute {
+        denom: String,
+    },
+}
+
+#[bond_provider_query]
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::state::lst_swap_bond_provider::Config)]
+    Config {},
+    #[returns(Vec<(String, Vec<SwapOperation>)>)]
+    SwapRoutes {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
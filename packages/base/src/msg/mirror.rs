@@ -9,6 +9,7 @@ pub struct InstantiateMsg {
     pub source_channel: String,
     pub ibc_timeout: u64,
     pub prefix: String,
+    pub retry_policy: Option<crate::state::mirror::RetryPolicy>,
 }
 
 #[cw_ownable_execute]
@@ -29,6 +30,25 @@ pub enum ExecuteMsg {
         id: u64,
         return_type: crate::state::mirror::ReturnType,
     },
+    /// Unbonds attached dAsset through the core, the base asset is returned
+    /// to the receiver once the unbonding is released
+    Unbond {
+        receiver: String,
+        backup: Option<String>,
+    },
+    /// Exchanges withdrawal vouchers of released unbondings for the base asset and returns it
+    Withdraw {
+        items: Vec<u64>,
+    },
+    ChangeUnbondReturnType {
+        id: u64,
+        return_type: crate::state::mirror::ReturnType,
+    },
+    /// Sends failed bonds and unbonds again once their backoff has passed,
+    /// at most `limit` of them (30 by default, 100 at most)
+    RetryFailed {
+        limit: Option<u32>,
+    },
     // by admin
     UpdateBond {
         id: u64,
@@ -53,6 +73,44 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+
+    #[returns(Vec<(u64, crate::state::mirror::BondItem)>)]
+    BondsByReceiver {
+        receiver: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<(u64, crate::state::mirror::BondItem)>)]
+    FailedBonds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(crate::state::mirror::UnbondItem)]
+    OneUnbond { id: u64 },
+
+    #[returns(Vec<(u64, crate::state::mirror::UnbondItem)>)]
+    AllUnbonds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<(u64, crate::state::mirror::UnbondItem)>)]
+    UnbondsByReceiver {
+        receiver: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<(u64, crate::state::mirror::UnbondItem)>)]
+    FailedUnbonds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(crate::state::mirror::RetryPolicy)]
+    RetryPolicy {},
 }
 
 #[cw_serde]
@@ -32,6 +32,9 @@ pub enum QueryMsg {
     GetProposals {},
     #[returns(crate::state::provider_proposals::Metrics)]
     Metrics {},
+    /// Participation of validators in the last `window` finished non-spam proposals
+    #[returns(crate::state::provider_proposals::ParticipationResponse)]
+    Participation { window: u64 },
 }
 
 #[cw_serde]
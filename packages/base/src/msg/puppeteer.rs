@@ -8,6 +8,7 @@ use crate::state::puppeteer::{ConfigOptional, Delegations};
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as CosmosCoin;
 use drop_puppeteer_base::{
     msg::{ExecuteMsg as BaseExecuteMsg, TransferReadyBatchesMsg},
+    peripheral_hook::WeightedVoteOption,
     r#trait::PuppeteerReconstruct,
     state::RedeemShareItem,
 };
@@ -38,6 +39,11 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     RegisterICA {},
     RegisterQuery {},
+    /// Registers the ICA again after a channel timeout. Anyone may call it,
+    /// untrn attached above the `register_fee` is refunded.
+    RecoverICA {
+        register_fee: Uint128,
+    },
     RegisterBalanceAndDelegatorDelegationsQuery {
         validators: Vec<String>,
     },
@@ -68,12 +74,22 @@ pub enum ExecuteMsg {
     TokenizeShare {
         validator: String,
         amount: Uint128,
+        /// echoed back in the hook, so the caller can match the response
+        request_id: Option<u64>,
         reply_to: String,
     },
     RedeemShares {
         items: Vec<RedeemShareItem>,
         reply_to: String,
     },
+    /// IBC transfers an LSM share held by the ICA over the host chain channel
+    TransferShare {
+        share: cosmwasm_std::Coin,
+        channel_id: String,
+        receiver: String,
+        request_id: Option<u64>,
+        reply_to: String,
+    },
     Transfer {
         items: Vec<(String, cosmwasm_std::Coin)>,
         reply_to: String,
@@ -86,6 +102,14 @@ pub enum ExecuteMsg {
     UpdateConfig {
         new_config: ConfigOptional,
     },
+    CancelQueued {
+        id: u64,
+    },
+    VoteWeighted {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+        reply_to: String,
+    },
 }
 
 impl ExecuteMsg {
@@ -93,6 +117,9 @@ impl ExecuteMsg {
         match self {
             ExecuteMsg::RegisterICA {} => BaseExecuteMsg::RegisterICA {},
             ExecuteMsg::RegisterQuery {} => BaseExecuteMsg::RegisterQuery {},
+            ExecuteMsg::RecoverICA { register_fee } => BaseExecuteMsg::RecoverICA {
+                register_fee: *register_fee,
+            },
             _ => unimplemented!(),
         }
     }
@@ -23,6 +23,12 @@ pub enum QueryMsg {
     },
 }
 
+// optional `params` of the `RedemptionRate` query
+#[cw_serde]
+pub struct RedemptionRateParams {
+    pub twap_window_seconds: Option<u64>,
+}
+
 #[cw_serde]
 pub struct RedemptionRateResponse {
     pub redemption_rate: Decimal,
@@ -0,0 +1,53 @@
+use crate::state::remote_governance::{Ballot, ConfigOptional, ProposalVoting, VoterSet};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use drop_puppeteer_base::peripheral_hook::{ResponseHookMsg, WeightedVoteOption};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub puppeteer_address: String,
+    pub provider_proposals_address: String,
+    pub dasset_denom: String,
+    pub voter_set: VoterSet,
+    pub submission_window: u64,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        new_config: ConfigOptional,
+    },
+    /// Casts or replaces the sender's weighted vote. In the holders mode the
+    /// attached dAsset is added to the locked voting power.
+    Vote {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+    /// Returns the locked dAsset once the voting period is over, even if the
+    /// voter set or the dAsset denom was updated in the meantime.
+    Unlock {
+        proposal_id: u64,
+    },
+    /// Sends the aggregated vote to the host chain through the puppeteer.
+    SubmitVote {
+        proposal_id: u64,
+    },
+    PeripheralHook(ResponseHookMsg),
+}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::state::remote_governance::Config)]
+    Config {},
+    #[returns(ProposalVoting)]
+    Proposal { proposal_id: u64 },
+    #[returns(Option<Ballot>)]
+    Ballot { proposal_id: u64, voter: String },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -19,6 +19,8 @@ pub enum QueryMsg {
     CalcDeposit { deposit: Uint128 },
     #[returns(Vec<(String, Uint128)>)]
     CalcWithdraw { withdraw: Uint128 },
+    #[returns(Vec<crate::msg::distribution::Redelegation>)]
+    CalcRebalance { max_moves: u32 },
 }
 
 #[optfield(pub ConfigOptional, attrs)]
@@ -1,6 +1,6 @@
 use crate::state::{
     provider_proposals::ProposalInfo,
-    validatorset::{ConfigOptional, ValidatorInfo},
+    validatorset::{ConfigOptional, ScoringPolicy, ValidatorInfo, WeightRamp},
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Decimal, Uint128};
@@ -19,6 +19,13 @@ pub struct ValidatorData {
     pub on_top: Option<Uint128>,
 }
 
+#[cw_serde]
+pub struct WeightRampData {
+    pub valoper_address: String,
+    pub target_weight: u64,
+    pub duration: u64, // seconds, zero sets the weight right away
+}
+
 #[cw_serde]
 pub struct ValidatorInfoUpdate {
     pub valoper_address: String,
@@ -37,6 +44,8 @@ pub enum ExecuteMsg {
     UpdateConfig {
         new_config: ConfigOptional,
     },
+    /// Replaces the validators set. While a scoring policy is set,
+    /// the passed weights are replaced by validators scores
     UpdateValidators {
         validators: Vec<ValidatorData>,
     },
@@ -49,6 +58,19 @@ pub enum ExecuteMsg {
     EditOnTop {
         operations: Vec<OnTopEditOperation>,
     },
+    /// Validators weights are derived from their scores while a policy is set,
+    /// pending weight ramps are dropped once it is
+    UpdateScoringPolicy {
+        policy: Option<ScoringPolicy>,
+    },
+    /// Moves validators weights to the targets gradually, unknown validators are onboarded
+    /// starting from zero weight. Not available while a scoring policy is set
+    ScheduleWeightRamps {
+        ramps: Vec<WeightRampData>,
+    },
+    /// Drops draining validators which have no delegations left in a delegations
+    /// snapshot taken after they started draining
+    ReleaseDrainingValidators {},
 }
 
 #[cw_serde]
@@ -68,6 +90,30 @@ pub struct ValidatorResponse {
     pub validator: Option<ValidatorInfo>,
 }
 
+#[cw_serde]
+pub struct ValidatorWeightRamp {
+    pub valoper_address: String,
+    pub ramp: WeightRamp,
+}
+
+#[cw_serde]
+pub struct DrainingValidator {
+    pub valoper_address: String,
+    pub since: u64,
+}
+
+#[cw_serde]
+pub struct ValidatorScore {
+    pub valoper_address: String,
+    pub uptime: Decimal,
+    pub commission_in_range: bool,
+    pub voting_participation: Decimal,
+    pub jail_penalty: Decimal,
+    pub excluded_reason: Option<String>,
+    pub score: Decimal,
+    pub weight: u64,
+}
+
 #[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -78,6 +124,15 @@ pub enum QueryMsg {
     Validator { valoper: String },
     #[returns(Vec<crate::state::validatorset::ValidatorInfo>)]
     Validators {},
+    #[returns(Option<ScoringPolicy>)]
+    ScoringPolicy {},
+    #[returns(Vec<ValidatorScore>)]
+    ValidatorScores {},
+    /// Ramps which are still in progress
+    #[returns(Vec<ValidatorWeightRamp>)]
+    WeightRamps {},
+    #[returns(Vec<DrainingValidator>)]
+    DrainingValidators {},
 }
 
 #[cw_serde]
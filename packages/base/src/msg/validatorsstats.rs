@@ -8,11 +8,40 @@ pub struct InstantiateMsg {
     pub info_update_period: u64,
     pub avg_block_time: u64,
     pub owner: String,
+    /// Defaults to 1%-10% commission and a 30 days uptime window
+    pub stats_params: Option<crate::state::validatorsstats::StatsParams>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    RegisterStatsQueries { validators: Vec<String> },
+    RegisterStatsQueries {
+        validators: Vec<String>,
+    },
+    /// Enables pushing validators info to the validators set, the stats contract
+    /// has to be its `stats_contract`. `None` disables pushes.
+    UpdatePushConfig {
+        config: Option<crate::state::validatorsstats::PushConfig>,
+    },
+    /// Pushes validators info right away if `min_interval` has passed since the last push
+    PushValidatorsInfo {},
+    UpdateStatsParams {
+        params: crate::state::validatorsstats::StatsParams,
+    },
+}
+
+#[cw_serde]
+pub struct ValidatorFreshness {
+    pub valoper_address: String,
+    pub last_processed_remote_height: Option<u64>,
+    pub lag: Option<u64>, // remote blocks behind the latest processed ICQ result
+    pub stale: bool,
+}
+
+#[cw_serde]
+pub struct StatsFreshnessResponse {
+    pub remote_height: Option<u64>,
+    pub last_push: Option<u64>,
+    pub validators: Vec<ValidatorFreshness>,
 }
 
 #[cw_serde]
@@ -24,6 +53,15 @@ pub enum QueryMsg {
     KVQueryIds {},
     #[returns(Vec<crate::state::validatorsstats::ValidatorState>)]
     State {},
+    #[returns(crate::state::validatorsstats::StatsParams)]
+    StatsParams {},
+    #[returns(Vec<crate::state::validatorsstats::MissedBlocks>)]
+    MissedBlocks { valoper_address: String },
+    #[returns(Option<crate::state::validatorsstats::PushConfig>)]
+    PushConfig {},
+    /// Flags validators lagging more than `max_lag` remote blocks behind
+    #[returns(StatsFreshnessResponse)]
+    StatsFreshness { max_lag: u64 },
 }
 
 #[cw_serde]
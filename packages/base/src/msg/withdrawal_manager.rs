@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
 use cw721::Cw721ReceiveMsg;
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 #[allow(unused_imports)]
@@ -19,6 +20,31 @@ pub struct InstantiateMsg {
 pub enum QueryMsg {
     #[returns(crate::state::withdrawal_manager::Config)]
     Config {},
+    #[returns(crate::state::withdrawal_manager::MarketConfig)]
+    MarketConfig {},
+    #[returns(VoucherQuoteResponse)]
+    VoucherQuote { token_id: String },
+    #[returns(crate::state::withdrawal_manager::VoucherListing)]
+    Listing { token_id: String },
+    #[returns(ListingsResponse)]
+    Listings {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct VoucherQuoteResponse {
+    pub batch_id: Uint128,
+    pub amount: Uint128,
+    pub expected_payout: Uint128,
+    pub remaining_time: u64,
+    pub fair_price: Uint128,
+}
+
+#[cw_serde]
+pub struct ListingsResponse {
+    pub listings: Vec<(String, crate::state::withdrawal_manager::VoucherListing)>,
 }
 
 #[cw_ownable_execute]
@@ -30,11 +56,28 @@ pub enum ExecuteMsg {
         base_denom: Option<String>,
     },
     ReceiveNft(Cw721ReceiveMsg),
+    // pulls approved vouchers from the voucher contract and pays them out at once,
+    // vouchers of batches that are not withdrawn yet are skipped
+    WithdrawMany {
+        token_ids: Vec<String>,
+        receiver: Option<String>,
+    },
+    UpdateMarketConfig {
+        discount_rate: Decimal,
+    },
+    BuyVoucher {
+        token_id: String,
+        receiver: Option<String>,
+    },
+    DelistVoucher {
+        token_id: String,
+    },
 }
 
 #[cw_serde]
 pub enum ReceiveNftMsg {
     Withdraw { receiver: Option<String> },
+    List { price: Uint128 },
 }
 
 #[cw_serde]
@@ -1,12 +1,21 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Empty;
+use cosmwasm_std::{CustomMsg, Empty, Uint128};
 pub use cw721_base::{ContractError, InstantiateMsg as CW721InstantiateMsg, MinterResponse};
 
 use crate::state::withdrawal_voucher::Metadata;
 
+#[cw_serde]
+pub enum ExtensionMsg {
+    // replaces the voucher with two vouchers of the same batch holding `amount`
+    // and the rest of the original amount
+    Split { token_id: String, amount: Uint128 },
+}
+
+impl CustomMsg for ExtensionMsg {}
+
 pub type Extension = Option<Metadata>;
 pub type InstantiateMsg = CW721InstantiateMsg;
-pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension, Empty>;
+pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension, ExtensionMsg>;
 pub type QueryMsg = cw721_base::QueryMsg<Empty>;
 
 #[cw_serde]
@@ -17,3 +17,5 @@ pub struct Config {
 pub const CONFIG: Item<Config> = Item::new("config");
 
 pub const SWAP_OPERATIONS: Item<Vec<SwapOperation>> = Item::new("swap_operations");
+// receives swapped rewards instead of the core, e.g. the native bond provider to restake them
+pub const SWAP_RECEIVER: Item<String> = Item::new("swap_receiver");
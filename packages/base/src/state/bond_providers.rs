@@ -1,17 +1,35 @@
 use crate::error::core::{ContractError, ContractResult};
-use cosmwasm_std::{Addr, Empty, StdResult, Storage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Empty, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 
+#[cw_serde]
+#[derive(Default)]
+pub struct BondProviderRoute {
+    // providers with lower priority value are tried first
+    pub priority: u32,
+    // provider is skipped once its async tokens amount would exceed the cap
+    pub async_tokens_cap: Option<Uint128>,
+    // providers preferring the bonded denom are tried before all others
+    pub preferred_denoms: Vec<String>,
+}
+
 pub struct BondProviders<'a> {
     pub providers: Map<'a, Addr, Empty>,
     pub next_provider_ptr: Item<'a, u64>,
+    pub routes: Map<'a, Addr, BondProviderRoute>,
 }
 
 impl<'a> BondProviders<'a> {
-    pub const fn new(storage_key: &'a str, pointer_storage_key: &'a str) -> Self {
+    pub const fn new(
+        storage_key: &'a str,
+        pointer_storage_key: &'a str,
+        routes_storage_key: &'a str,
+    ) -> Self {
         Self {
             providers: Map::new(storage_key),
             next_provider_ptr: Item::new(pointer_storage_key),
+            routes: Map::new(routes_storage_key),
         }
     }
 
@@ -41,10 +59,54 @@ impl<'a> BondProviders<'a> {
     }
 
     pub fn remove(&self, storage: &mut dyn Storage, provider: Addr) -> ContractResult<()> {
+        self.routes.remove(storage, provider.clone());
         self.providers.remove(storage, provider);
         Ok(())
     }
 
+    pub fn set_route(
+        &self,
+        storage: &mut dyn Storage,
+        provider: Addr,
+        route: &BondProviderRoute,
+    ) -> ContractResult<()> {
+        if !self.providers.has(storage, provider.clone()) {
+            return Err(ContractError::BondProviderNotFound {});
+        }
+
+        self.routes.save(storage, provider, route)?;
+        Ok(())
+    }
+
+    pub fn get_route(&self, storage: &dyn Storage, provider: Addr) -> StdResult<BondProviderRoute> {
+        Ok(self.routes.may_load(storage, provider)?.unwrap_or_default())
+    }
+
+    // returns providers in the order they should be tried for the given denom
+    pub fn get_routed_providers(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+    ) -> StdResult<Vec<(Addr, BondProviderRoute)>> {
+        let mut providers = self
+            .get_all_providers(storage)?
+            .into_iter()
+            .map(|provider| {
+                let route = self.get_route(storage, provider.clone())?;
+                Ok((provider, route))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        providers.sort_by_key(|(_, route)| {
+            (
+                !route.preferred_denoms.iter().any(|d| d == denom),
+                route.priority,
+            )
+        });
+
+        Ok(providers)
+    }
+
     pub fn next(&self, storage: &mut dyn Storage) -> ContractResult<Addr> {
         let mut next_provider_ptr = self.next_provider_ptr.load(storage)?;
         let providers = self.get_all_providers(storage)?;
@@ -67,16 +129,19 @@ impl<'a> BondProviders<'a> {
 
 #[cfg(test)]
 mod test {
-    use cosmwasm_std::{testing::MockStorage, Addr};
+    use cosmwasm_std::{testing::MockStorage, Addr, Uint128};
 
     use crate::error::core::ContractError;
 
-    use super::BondProviders;
+    use super::{BondProviderRoute, BondProviders};
 
     #[test]
     fn get_empty_providers_list() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
         let providers = bond_providers
             .get_all_providers(&MockStorage::default())
             .unwrap();
@@ -85,8 +150,11 @@ mod test {
 
     #[test]
     fn add_one_provider_to_list() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
 
@@ -99,8 +167,11 @@ mod test {
 
     #[test]
     fn add_two_providers_to_list() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
 
@@ -116,8 +187,11 @@ mod test {
 
     #[test]
     fn remove_one_provider_from_list() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
 
@@ -137,8 +211,11 @@ mod test {
 
     #[test]
     fn remove_two_providers_from_list() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
 
@@ -160,8 +237,11 @@ mod test {
 
     #[test]
     fn error_on_same_provider() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
 
@@ -177,8 +257,11 @@ mod test {
 
     #[test]
     fn error_empty_providers_list_iterate() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
         bond_providers.init(storage).unwrap();
@@ -190,8 +273,11 @@ mod test {
 
     #[test]
     fn add_one_provider_to_list_and_iterate() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
         bond_providers.init(storage).unwrap();
@@ -211,8 +297,11 @@ mod test {
 
     #[test]
     fn add_two_providers_to_list_and_iterate() {
-        let bond_providers: BondProviders =
-            BondProviders::new("bond_providers", "bond_providers_ptr");
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
 
         let storage = &mut MockStorage::default();
         bond_providers.init(storage).unwrap();
@@ -236,4 +325,182 @@ mod test {
         let provider = bond_providers.next(storage).unwrap();
         assert_eq!(provider, Addr::unchecked("2_provider_address"));
     }
+
+    #[test]
+    fn routed_providers_default_order() {
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
+
+        let storage = &mut MockStorage::default();
+
+        bond_providers
+            .add(storage, Addr::unchecked("1_provider_address"))
+            .unwrap();
+        bond_providers
+            .add(storage, Addr::unchecked("2_provider_address"))
+            .unwrap();
+
+        let providers = bond_providers
+            .get_routed_providers(storage, "base_denom")
+            .unwrap();
+        assert_eq!(
+            providers,
+            vec![
+                (
+                    Addr::unchecked("1_provider_address"),
+                    BondProviderRoute::default()
+                ),
+                (
+                    Addr::unchecked("2_provider_address"),
+                    BondProviderRoute::default()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn routed_providers_priority_and_denom_preference() {
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
+
+        let storage = &mut MockStorage::default();
+
+        for provider in [
+            "1_provider_address",
+            "2_provider_address",
+            "3_provider_address",
+        ] {
+            bond_providers
+                .add(storage, Addr::unchecked(provider))
+                .unwrap();
+        }
+        let preferring_route = BondProviderRoute {
+            priority: 10,
+            async_tokens_cap: Some(Uint128::new(100)),
+            preferred_denoms: vec!["lsm_denom".to_string()],
+        };
+        bond_providers
+            .set_route(
+                storage,
+                Addr::unchecked("1_provider_address"),
+                &preferring_route,
+            )
+            .unwrap();
+        let prioritized_route = BondProviderRoute {
+            priority: 0,
+            async_tokens_cap: None,
+            preferred_denoms: vec![],
+        };
+        let low_priority_route = BondProviderRoute {
+            priority: 5,
+            async_tokens_cap: None,
+            preferred_denoms: vec![],
+        };
+        bond_providers
+            .set_route(
+                storage,
+                Addr::unchecked("2_provider_address"),
+                &low_priority_route,
+            )
+            .unwrap();
+        bond_providers
+            .set_route(
+                storage,
+                Addr::unchecked("3_provider_address"),
+                &prioritized_route,
+            )
+            .unwrap();
+
+        let providers = bond_providers
+            .get_routed_providers(storage, "lsm_denom")
+            .unwrap()
+            .into_iter()
+            .map(|(provider, _)| provider)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            providers,
+            vec![
+                Addr::unchecked("1_provider_address"),
+                Addr::unchecked("3_provider_address"),
+                Addr::unchecked("2_provider_address"),
+            ]
+        );
+
+        let providers = bond_providers
+            .get_routed_providers(storage, "base_denom")
+            .unwrap()
+            .into_iter()
+            .map(|(provider, _)| provider)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            providers,
+            vec![
+                Addr::unchecked("3_provider_address"),
+                Addr::unchecked("2_provider_address"),
+                Addr::unchecked("1_provider_address"),
+            ]
+        );
+    }
+
+    #[test]
+    fn error_on_route_for_unknown_provider() {
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
+
+        let storage = &mut MockStorage::default();
+
+        let err = bond_providers
+            .set_route(
+                storage,
+                Addr::unchecked("native_provider_address"),
+                &BondProviderRoute::default(),
+            )
+            .unwrap_err();
+
+        assert_eq!(err, ContractError::BondProviderNotFound {});
+    }
+
+    #[test]
+    fn remove_provider_removes_route() {
+        let bond_providers: BondProviders = BondProviders::new(
+            "bond_providers",
+            "bond_providers_ptr",
+            "bond_providers_routes",
+        );
+
+        let storage = &mut MockStorage::default();
+
+        bond_providers
+            .add(storage, Addr::unchecked("native_provider_address"))
+            .unwrap();
+        bond_providers
+            .set_route(
+                storage,
+                Addr::unchecked("native_provider_address"),
+                &BondProviderRoute {
+                    priority: 1,
+                    async_tokens_cap: None,
+                    preferred_denoms: vec![],
+                },
+            )
+            .unwrap();
+        bond_providers
+            .remove(storage, Addr::unchecked("native_provider_address"))
+            .unwrap();
+
+        assert!(bond_providers
+            .routes
+            .may_load(storage, Addr::unchecked("native_provider_address"))
+            .unwrap()
+            .is_none());
+    }
 }
@@ -1,10 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{Addr, Coin, Decimal, Decimal256, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use drop_helpers::fsm::{Fsm, Transition};
 use drop_puppeteer_base::peripheral_hook::ResponseHookMsg as PuppeteerResponseHookMsg;
 
 use super::bond_providers::BondProviders;
+use crate::msg::distribution::Redelegation;
 
 #[cw_serde]
 pub struct ConfigOptional {
@@ -147,6 +148,126 @@ pub struct Pause {
     pub tick: bool,
 }
 pub const MAX_BOND_PROVIDERS: u64 = 10;
+pub const MAX_EXCHANGE_RATE_HISTORY_SIZE: u64 = 1000;
+
+#[cw_serde]
+pub struct ExchangeRateSnapshot {
+    pub height: u64,
+    pub time: u64,
+    pub exchange_rate: Decimal,
+    pub total_bonded: Uint128,
+    pub dasset_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct RewardsFeeConfig {
+    pub fee: Decimal,
+    pub fee_collector: Addr,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeLedger {
+    pub cumulative_rewards: Uint128,
+    pub cumulative_fee: Uint128,
+    pub cumulative_fee_dasset_minted: Uint128,
+    // highest exchange rate the fee was charged up to, only growth above it is charged
+    #[serde(alias = "last_exchange_rate")]
+    pub high_water_mark: Option<Decimal>,
+    // dAsset, including unprocessed unbonds, as of the last idle tick
+    #[serde(default)]
+    pub last_dasset_amount: Uint128,
+    pub entries_count: u64,
+}
+
+#[cw_serde]
+pub struct FeeLedgerEntry {
+    pub height: u64,
+    pub time: u64,
+    pub exchange_rate: Decimal,
+    pub rewards: Uint128,
+    pub fee: Uint128,
+    pub fee_dasset_minted: Uint128,
+}
+
+#[cw_serde]
+pub struct InstantUnbondConfig {
+    pub fee: Decimal,        // share of the payout kept by the liquidity buffer
+    pub epoch_duration: u64, //seconds
+    pub epoch_limit: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct InstantUnbondEpoch {
+    pub start: u64,
+    pub paid_out: Uint128,
+}
+
+// voucher minted to the core itself on instant unbond, redeemed once the batch is withdrawn
+#[cw_serde]
+pub struct LiquidityBufferVoucher {
+    pub batch_id: u128,
+    pub dasset_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct UnbondInKindConfig {
+    pub remote_transfer_channel_id: String, // host chain end of the transfer channel
+    pub transfer_timeout: u64, // seconds after which an unconfirmed share transfer can be sent again
+}
+
+#[cw_serde]
+pub enum UnbondInKindStatus {
+    Tokenizing,
+    Transferring,
+    /// the host chain accepted the IBC transfer, the receiver is yet to confirm it
+    Transferred,
+    TransferFailed,
+}
+
+#[cw_serde]
+pub struct UnbondInKindRequest {
+    pub receiver: Addr,
+    pub validator: String,
+    pub dasset_amount: Uint128, // kept by the core until the delegation is tokenized
+    pub amount: Uint128,
+    pub share: Option<Coin>,
+    pub status: UnbondInKindStatus,
+    pub transferred_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct RebalanceConfig {
+    pub max_moves: u32,      // redelegations planned per rebalance round
+    pub min_interval: u64,   //seconds between rebalance rounds
+    pub min_amount: Uint128, // smaller redelegations are skipped
+}
+
+#[cw_serde]
+pub struct NonNativeRewardsItem {
+    pub denom: String,       // reward denom on the host chain
+    pub local_denom: String, // the same denom on Neutron once pumped over IBC
+    pub min_amount: Uint128, // smaller ICA balances are left to accumulate
+}
+
+#[cw_serde]
+pub struct ValidatorSlashing {
+    pub validator: String,
+    pub slashing_fraction: Decimal256,
+    pub loss: Uint128,
+}
+
+#[cw_serde]
+pub struct SlashingEvent {
+    pub height: u64,
+    pub time: u64,
+    pub remote_height: u64,
+    pub validators: Vec<ValidatorSlashing>,
+    pub total_loss: Uint128,
+    pub slashing_fraction: Decimal, // share of the whole delegated stake lost
+    pub batch_adjustments: Vec<(u128, Uint128)>, // expected amount cut from unbonding batches
+}
 
 pub const FSM: Fsm<ContractState> = Fsm::new("machine_state", TRANSITIONS);
 pub const LAST_IDLE_CALL: Item<u64> = Item::new("last_tick");
@@ -156,9 +277,41 @@ pub const LAST_PUPPETEER_RESPONSE: Item<PuppeteerResponseHookMsg> =
 pub const FAILED_BATCH_ID: Item<u128> = Item::new("failed_batch_id");
 pub const LAST_LSM_REDEEM: Item<u64> = Item::new("last_lsm_redeem");
 pub const EXCHANGE_RATE: Item<(Decimal, u64)> = Item::new("exchange_rate");
+pub const EXCHANGE_RATE_HISTORY: Map<u64, ExchangeRateSnapshot> = Map::new("exchange_rate_history");
+pub const EXCHANGE_RATE_HISTORY_NEXT_ID: Item<u64> = Item::new("exchange_rate_history_next_id");
 pub const LD_DENOM: Item<String> = Item::new("ld_denom");
 pub const PAUSE: Item<Pause> = Item::new("pause");
 pub const BOND_HOOKS: Item<Vec<Addr>> = Item::new("bond_hooks");
+pub const REWARDS_FEE_CONFIG: Item<RewardsFeeConfig> = Item::new("rewards_fee_config");
+pub const FEE_LEDGER: Item<FeeLedger> = Item::new("fee_ledger");
+pub const FEE_LEDGER_ENTRIES: Map<u64, FeeLedgerEntry> = Map::new("fee_ledger_entries");
+pub const INSTANT_UNBOND_CONFIG: Item<InstantUnbondConfig> = Item::new("instant_unbond_config");
+pub const INSTANT_UNBOND_EPOCH: Item<InstantUnbondEpoch> = Item::new("instant_unbond_epoch");
+// base asset the core holds for instant unbonds, kept apart from the rest of its balance
+pub const LIQUIDITY_BUFFER: Item<Uint128> = Item::new("liquidity_buffer");
+pub const LIQUIDITY_BUFFER_VOUCHERS: Map<&str, LiquidityBufferVoucher> =
+    Map::new("liquidity_buffer_vouchers");
+pub const UNBOND_IN_KIND_CONFIG: Item<UnbondInKindConfig> = Item::new("unbond_in_kind_config");
+pub const UNBOND_IN_KIND_REQUESTS: Map<u64, UnbondInKindRequest> =
+    Map::new("unbond_in_kind_requests");
+pub const UNBOND_IN_KIND_NEXT_ID: Item<u64> = Item::new("unbond_in_kind_next_id");
+pub const REBALANCE_CONFIG: Item<RebalanceConfig> = Item::new("rebalance_config");
+pub const REBALANCE_QUEUE: Item<Vec<Redelegation>> = Item::new("rebalance_queue");
+pub const LAST_REBALANCE: Item<u64> = Item::new("last_rebalance");
+// validator -> time until which it can't be a redelegation source
+pub const REDELEGATION_COOLDOWNS: Map<&str, u64> = Map::new("redelegation_cooldowns");
+pub const NON_NATIVE_REWARDS_CONFIG: Item<Vec<NonNativeRewardsItem>> =
+    Item::new("non_native_rewards_config");
+// validator tokens per share seen on the previous tick, a drop means the validator was slashed
+pub const LAST_SHARE_RATIOS: Map<&str, Decimal256> = Map::new("last_share_ratios");
+pub const SLASHING_EVENTS: Map<u64, SlashingEvent> = Map::new("slashing_events");
+// validators each unbonding batch was undelegated from, reduced by the slashing seen since
+pub const UNBOND_BATCH_UNDELEGATIONS: Map<u128, Vec<(String, Uint128)>> =
+    Map::new("unbond_batch_undelegations");
+pub const SLASHING_EVENTS_NEXT_ID: Item<u64> = Item::new("slashing_events_next_id");
 
-pub const BOND_PROVIDERS: BondProviders =
-    BondProviders::new("bond_providers", "bond_providers_ptr");
+pub const BOND_PROVIDERS: BondProviders = BondProviders::new(
+    "bond_providers",
+    "bond_providers_ptr",
+    "bond_providers_routes",
+);
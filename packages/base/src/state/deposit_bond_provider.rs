@@ -0,0 +1,144 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_storage_plus::{Item, Map};
+use drop_helpers::ica::IcaState;
+use drop_puppeteer_base::peripheral_hook::ResponseHookMsg as PuppeteerResponseHookMsg;
+use optfield::optfield;
+
+pub use crate::state::native_bond_provider::{TxState, TxStateStatus};
+
+#[optfield(pub ConfigOptional, attrs)]
+#[cw_serde]
+pub struct Config {
+    pub factory_contract: Addr,
+    pub base_denom: String,
+    pub connection_id: String,
+    pub sdk_version: String,
+    pub update_period: u64, // update period of deposit balance queries in blocks
+    pub timeout: u64,       // timeout for interchain transactions in seconds
+    pub min_deposit: Uint128,
+    pub min_stake_amount: Uint128,
+    // channel on Neutron dAssets are sent to host chain receivers through
+    pub dasset_transfer_channel_id: String,
+}
+
+#[cw_serde]
+pub enum DepositReceiver {
+    Local { address: Addr },
+    Remote { address: String },
+}
+
+#[cw_serde]
+pub struct DepositAccount {
+    pub owner: Addr,
+    pub receiver: DepositReceiver,
+    pub ica: IcaState,
+    pub balance_query_id: Option<u64>,
+    // deposit being moved to the puppeteer ICA right now
+    pub in_flight: Option<Uint128>,
+    // deposit which reached the puppeteer ICA but has no dAssets minted for it yet
+    pub unminted: Uint128,
+    // dAssets which failed to reach a remote receiver
+    pub undelivered: Option<Coin>,
+    // deposit balances submitted at or below this remote height are already processed
+    pub processed_remote_height: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const DEPOSIT_ACCOUNTS: Map<u64, DepositAccount> = Map::new("deposit_accounts");
+pub const NEXT_DEPOSIT_ACCOUNT_ID: Item<u64> = Item::new("next_deposit_account_id");
+pub const TX_STATE: Item<TxState> = Item::new("tx_state");
+pub const NON_STAKED_BALANCE: Item<Uint128> = Item::new("non_staked_balance");
+pub const LAST_PUPPETEER_RESPONSE: Item<PuppeteerResponseHookMsg> =
+    Item::new("last_puppeteer_response");
+
+pub const DEPOSIT_ICA_ID_PREFIX: &str = "deposit";
+
+pub fn deposit_ica_id(account_id: u64) -> String {
+    format!("{DEPOSIT_ICA_ID_PREFIX}{account_id}")
+}
+
+pub use reply_msg::ReplyMsg;
+pub mod reply_msg {
+    // deposit account id is carried in the lower bits of the reply id
+    const OFFSET: u64 = u32::BITS as u64;
+    const ACCOUNT_ID_MASK: u64 = (1 << OFFSET) - 1;
+    pub const BOND: u64 = 1 << OFFSET;
+    pub const PUPPETEER_HOOK_FORWARD: u64 = 2 << OFFSET;
+    pub const REGISTER_BALANCE_QUERY: u64 = 3 << OFFSET;
+    pub const MINT: u64 = 4 << OFFSET;
+
+    #[cosmwasm_schema::cw_serde]
+    pub enum ReplyMsg {
+        Bond,
+        PuppeteerHookForward,
+        RegisterBalanceQuery { account_id: u64 },
+        Mint { account_id: u64 },
+    }
+
+    impl ReplyMsg {
+        pub fn to_reply_id(&self) -> u64 {
+            match self {
+                ReplyMsg::Bond => BOND,
+                ReplyMsg::PuppeteerHookForward => PUPPETEER_HOOK_FORWARD,
+                ReplyMsg::RegisterBalanceQuery { account_id } => {
+                    REGISTER_BALANCE_QUERY | (account_id & ACCOUNT_ID_MASK)
+                }
+                ReplyMsg::Mint { account_id } => MINT | (account_id & ACCOUNT_ID_MASK),
+            }
+        }
+
+        pub fn from_reply_id(reply_id: u64) -> Self {
+            let account_id = reply_id & ACCOUNT_ID_MASK;
+            match reply_id & !ACCOUNT_ID_MASK {
+                BOND => Self::Bond,
+                PUPPETEER_HOOK_FORWARD => Self::PuppeteerHookForward,
+                REGISTER_BALANCE_QUERY => Self::RegisterBalanceQuery { account_id },
+                MINT => Self::Mint { account_id },
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn enum_variant_from_reply_id() {
+            assert_eq!(ReplyMsg::from_reply_id(BOND), ReplyMsg::Bond);
+            assert_eq!(
+                ReplyMsg::from_reply_id(PUPPETEER_HOOK_FORWARD),
+                ReplyMsg::PuppeteerHookForward
+            );
+            assert_eq!(
+                ReplyMsg::from_reply_id(REGISTER_BALANCE_QUERY + 7),
+                ReplyMsg::RegisterBalanceQuery { account_id: 7 }
+            );
+            assert_eq!(
+                ReplyMsg::from_reply_id(MINT + 42),
+                ReplyMsg::Mint { account_id: 42 }
+            );
+        }
+
+        #[test]
+        fn enum_variant_to_reply_id() {
+            assert_eq!(ReplyMsg::Bond.to_reply_id(), BOND);
+            assert_eq!(
+                ReplyMsg::PuppeteerHookForward.to_reply_id(),
+                PUPPETEER_HOOK_FORWARD
+            );
+            assert_eq!(
+                ReplyMsg::RegisterBalanceQuery { account_id: 7 }.to_reply_id(),
+                REGISTER_BALANCE_QUERY + 7
+            );
+            assert_eq!(ReplyMsg::Mint { account_id: 42 }.to_reply_id(), MINT + 42);
+        }
+
+        #[test]
+        #[should_panic]
+        fn invalid_reply_id() {
+            ReplyMsg::from_reply_id(MINT << 1);
+        }
+    }
+}
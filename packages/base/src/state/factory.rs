@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, HexBinary, Timestamp};
 use cw_storage_plus::Map;
 
 #[cw_serde]
@@ -43,4 +43,22 @@ pub struct PauseInfoResponse {
     pub rewards_manager: drop_helpers::pause::PauseInfoResponse,
 }
 
+#[cw_serde]
+pub struct DeployedContract {
+    pub name: String,
+    pub address: Addr,
+    pub code_id: u64,
+    pub checksum: HexBinary,
+}
+
+/// Snapshot of the code deployed for every child contract
+#[cw_serde]
+pub struct Deployment {
+    pub version: u64,
+    pub height: u64,
+    pub timestamp: Timestamp,
+    pub contracts: Vec<DeployedContract>,
+}
+
 pub const STATE: Map<&str, Addr> = Map::new("state");
+pub const DEPLOYMENTS: Map<u64, Deployment> = Map::new("deployments");
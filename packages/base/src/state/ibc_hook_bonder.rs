@@ -0,0 +1,98 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Item, Map};
+use optfield::optfield;
+
+#[optfield(pub ConfigOptional, attrs)]
+#[cw_serde]
+pub struct Config {
+    pub core_contract: Addr,
+    pub ibc_timeout: u64, // default timeout of forwarding transfers in seconds
+}
+
+/// Next hop of the minted dAsset, mirrors the `forward` object of Packet Forward Middleware memos
+#[cw_serde]
+pub struct Forward {
+    pub receiver: String,
+    pub port: String,
+    pub channel: String,
+    pub timeout: Option<u64>, // seconds
+    // JSON memo passed as is to the next hop, e.g. a further PFM `forward`
+    pub next: Option<String>,
+}
+
+#[cw_serde]
+pub struct HookBond {
+    pub fallback: Addr,
+    pub deposit: Coin,
+    // untrn paid for the forwarding transfer
+    pub fee: Vec<Coin>,
+    pub receiver: Option<Addr>,
+    pub forward: Option<Forward>,
+    pub minted: Option<Coin>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const HOOK_BONDS: Map<u64, HookBond> = Map::new("hook_bonds");
+pub const HOOK_BOND_COUNTER: Item<u64> = Item::new("hook_bond_counter");
+// (channel, sequence) of forwarding transfers waiting for an ack
+pub const FORWARDS_IN_FLIGHT: Map<(&str, u64), u64> = Map::new("forwards_in_flight");
+
+pub use reply_msg::ReplyMsg;
+pub mod reply_msg {
+    // hook bond id is carried in the lower bits of the reply id
+    const OFFSET: u64 = u32::BITS as u64;
+    const ID_MASK: u64 = (1 << OFFSET) - 1;
+    pub const BOND: u64 = 1 << OFFSET;
+    pub const FORWARD: u64 = 2 << OFFSET;
+
+    #[cosmwasm_schema::cw_serde]
+    pub enum ReplyMsg {
+        Bond { id: u64 },
+        Forward { id: u64 },
+    }
+
+    impl ReplyMsg {
+        pub fn to_reply_id(&self) -> u64 {
+            match self {
+                ReplyMsg::Bond { id } => BOND | (id & ID_MASK),
+                ReplyMsg::Forward { id } => FORWARD | (id & ID_MASK),
+            }
+        }
+
+        pub fn from_reply_id(reply_id: u64) -> Self {
+            let id = reply_id & ID_MASK;
+            match reply_id & !ID_MASK {
+                BOND => Self::Bond { id },
+                FORWARD => Self::Forward { id },
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn enum_variant_from_reply_id() {
+            assert_eq!(ReplyMsg::from_reply_id(BOND + 3), ReplyMsg::Bond { id: 3 });
+            assert_eq!(
+                ReplyMsg::from_reply_id(FORWARD + 3),
+                ReplyMsg::Forward { id: 3 }
+            );
+        }
+
+        #[test]
+        fn enum_variant_to_reply_id() {
+            assert_eq!(ReplyMsg::Bond { id: 3 }.to_reply_id(), BOND + 3);
+            assert_eq!(ReplyMsg::Forward { id: 3 }.to_reply_id(), FORWARD + 3);
+        }
+
+        #[test]
+        #[should_panic]
+        fn invalid_reply_id() {
+            ReplyMsg::from_reply_id(3);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use astroport::router::SwapOperation;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map};
+use optfield::optfield;
+
+#[optfield(pub ConfigOptional, attrs)]
+#[cw_serde]
+pub struct Config {
+    pub factory_contract: Addr,
+    pub base_denom: String,
+    pub router_contract: Addr,
+    pub price_provider_contract: Addr,
+    pub max_spread: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+// accepted LST denom -> astroport route swapping it into the base denom
+pub const SWAP_ROUTES: Map<&str, Vec<SwapOperation>> = Map::new("swap_routes");
@@ -1,6 +1,6 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Coin, Uint128};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
 
 #[cw_serde]
 #[derive(Default)]
@@ -46,6 +46,67 @@ pub struct BondItem {
     pub received: Option<Coin>,
     pub return_type: ReturnType,
     pub state: BondState,
+    // failed IBC returns of the bond so far
+    #[serde(default)]
+    pub retries: u32,
+    // the next return attempt is not allowed before this time
+    #[serde(default)]
+    pub retry_at: Option<u64>,
+}
+
+impl BondItem {
+    // a local return without a backup has nowhere to go, so it is not retried
+    pub fn is_failed(&self) -> bool {
+        self.state == BondState::Bonded
+            && self.retries > 0
+            && (self.return_type == ReturnType::Remote || self.backup.is_some())
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum UnbondState {
+    #[default]
+    Initiated,
+    Unbonding,
+    Withdrawn,
+    Sent,
+}
+
+impl std::fmt::Display for UnbondState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnbondState::Initiated => write!(f, "Initiated"),
+            UnbondState::Unbonding => write!(f, "Unbonding"),
+            UnbondState::Withdrawn => write!(f, "Withdrawn"),
+            UnbondState::Sent => write!(f, "Sent"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct UnbondItem {
+    pub receiver: String,
+    pub backup: Option<Addr>,
+    // dAsset amount sent to unbond
+    pub amount: Uint128,
+    // withdrawal voucher held by the mirror until the unbonding is released
+    pub token_id: Option<String>,
+    // base asset paid out for the voucher
+    pub received: Option<Coin>,
+    pub return_type: ReturnType,
+    pub state: UnbondState,
+    pub retries: u32,
+    pub retry_at: Option<u64>,
+}
+
+impl UnbondItem {
+    // a local return without a backup has nowhere to go, so it is not retried
+    pub fn is_failed(&self) -> bool {
+        self.state == UnbondState::Withdrawn
+            && self.retries > 0
+            && (self.return_type == ReturnType::Remote || self.backup.is_some())
+    }
 }
 
 #[cw_serde]
@@ -63,6 +124,33 @@ pub struct ConfigOptional {
     pub source_channel: Option<String>,
     pub ibc_timeout: Option<u64>,
     pub prefix: Option<String>,
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+#[cw_serde]
+pub struct RetryPolicy {
+    pub backoff: u64, // delay before the first retry in seconds, doubled on every failure
+    pub max_backoff: u64, // upper bound of the delay in seconds
+    pub max_retries: u32, // failures after which the bond falls back to the backup address
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            backoff: 60,
+            max_backoff: 86400,
+            max_retries: 5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn retry_delay(&self, retries: u32) -> u64 {
+        let exponent = retries.saturating_sub(1).min(32);
+        self.backoff
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_backoff)
+    }
 }
 
 #[cw_serde]
@@ -72,9 +160,123 @@ pub struct TimeoutRange {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const BONDS: Map<u64, BondItem> = Map::new("bonds");
+pub const RETRY_POLICY: Item<RetryPolicy> = Item::new("retry_policy");
+
+pub struct BondIndexes<'a> {
+    pub receiver: MultiIndex<'a, String, BondItem, u64>,
+    pub failed: MultiIndex<'a, u8, BondItem, u64>,
+}
+
+impl<'a> IndexList<BondItem> for BondIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<BondItem>> + '_> {
+        let v: Vec<&dyn Index<BondItem>> = vec![&self.receiver, &self.failed];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn bonds_map<'a>() -> IndexedMap<'a, u64, BondItem, BondIndexes<'a>> {
+    IndexedMap::new(
+        "bonds",
+        BondIndexes {
+            receiver: MultiIndex::new(|_pk, b| b.receiver.to_string(), "bonds", "bonds__receiver"),
+            failed: MultiIndex::new(|_pk, b| b.is_failed() as u8, "bonds", "bonds__failed"),
+        },
+    )
+}
 pub const COUNTER: Item<u64> = Item::new("counter");
+
+pub struct UnbondIndexes<'a> {
+    pub receiver: MultiIndex<'a, String, UnbondItem, u64>,
+    pub failed: MultiIndex<'a, u8, UnbondItem, u64>,
+}
+
+impl<'a> IndexList<UnbondItem> for UnbondIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<UnbondItem>> + '_> {
+        let v: Vec<&dyn Index<UnbondItem>> = vec![&self.receiver, &self.failed];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn unbonds_map<'a>() -> IndexedMap<'a, u64, UnbondItem, UnbondIndexes<'a>> {
+    IndexedMap::new(
+        "unbonds",
+        UnbondIndexes {
+            receiver: MultiIndex::new(
+                |_pk, u| u.receiver.to_string(),
+                "unbonds",
+                "unbonds__receiver",
+            ),
+            failed: MultiIndex::new(|_pk, u| u.is_failed() as u8, "unbonds", "unbonds__failed"),
+        },
+    )
+}
+pub const UNBOND_COUNTER: Item<u64> = Item::new("unbond_counter");
+
+// memo of IBC transfers returning unbonded base asset, bond returns carry the plain bond id
+pub const UNBOND_MEMO_PREFIX: &str = "unbond-";
 pub const TIMEOUT_RANGE: TimeoutRange = TimeoutRange {
     from: 0,
     to: 2592000, // 30d
 };
+
+pub use reply_msg::ReplyMsg;
+pub mod reply_msg {
+    // item id is carried in the lower bits of the reply id, bonds keep the plain id
+    const OFFSET: u64 = u32::BITS as u64;
+    const ID_MASK: u64 = (1 << OFFSET) - 1;
+    pub const BOND: u64 = 0;
+    pub const UNBOND: u64 = 1 << OFFSET;
+    pub const WITHDRAW: u64 = 2 << OFFSET;
+
+    #[cosmwasm_schema::cw_serde]
+    pub enum ReplyMsg {
+        Bond { id: u64 },
+        Unbond { id: u64 },
+        Withdraw { id: u64 },
+    }
+
+    impl ReplyMsg {
+        pub fn to_reply_id(&self) -> u64 {
+            match self {
+                ReplyMsg::Bond { id } => BOND | (id & ID_MASK),
+                ReplyMsg::Unbond { id } => UNBOND | (id & ID_MASK),
+                ReplyMsg::Withdraw { id } => WITHDRAW | (id & ID_MASK),
+            }
+        }
+
+        pub fn from_reply_id(reply_id: u64) -> Self {
+            let id = reply_id & ID_MASK;
+            match reply_id & !ID_MASK {
+                BOND => Self::Bond { id },
+                UNBOND => Self::Unbond { id },
+                WITHDRAW => Self::Withdraw { id },
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn enum_variant_from_reply_id() {
+            assert_eq!(ReplyMsg::from_reply_id(7), ReplyMsg::Bond { id: 7 });
+            assert_eq!(
+                ReplyMsg::from_reply_id(UNBOND + 7),
+                ReplyMsg::Unbond { id: 7 }
+            );
+            assert_eq!(
+                ReplyMsg::from_reply_id(WITHDRAW + 7),
+                ReplyMsg::Withdraw { id: 7 }
+            );
+        }
+
+        #[test]
+        fn enum_variant_to_reply_id() {
+            assert_eq!(ReplyMsg::Bond { id: 7 }.to_reply_id(), 7);
+            assert_eq!(ReplyMsg::Unbond { id: 7 }.to_reply_id(), UNBOND + 7);
+            assert_eq!(ReplyMsg::Withdraw { id: 7 }.to_reply_id(), WITHDRAW + 7);
+        }
+    }
+}
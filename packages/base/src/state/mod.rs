@@ -1,9 +1,12 @@
 pub mod astroport_exchange_handler;
 pub mod bond_providers;
 pub mod core;
+pub mod deposit_bond_provider;
 pub mod factory;
 pub mod hook_tester;
+pub mod ibc_hook_bonder;
 pub mod lsm_share_bond_provider;
+pub mod lst_swap_bond_provider;
 pub mod mirror;
 pub mod native_bond_provider;
 pub mod price_provider;
@@ -13,6 +16,7 @@ pub mod pump;
 pub mod puppeteer;
 pub mod puppeteer_initia;
 pub mod redemption_rate_adapter;
+pub mod remote_governance;
 pub mod rewards_manager;
 pub mod splitter;
 pub mod strategy;
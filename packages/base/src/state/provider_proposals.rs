@@ -2,7 +2,7 @@ use cosmwasm_schema::cw_serde;
 
 use cosmwasm_std::Decimal;
 use cw_storage_plus::{Item, Map};
-use neutron_sdk::interchain_queries::v045::types::{Proposal, ProposalVote};
+use neutron_sdk::interchain_queries::v045::types::{Proposal, WeightedVoteOption};
 use optfield::optfield;
 
 #[cw_serde]
@@ -31,6 +31,24 @@ pub struct Metrics {
     pub last_proposal: u64,
 }
 
+#[cw_serde]
+pub struct ValidatorParticipation {
+    pub voter: String,
+    pub voted: u64,
+    pub weighted: u64, // votes split between several options
+    pub yes: Decimal,
+    pub abstain: Decimal,
+    pub no: Decimal,
+    pub no_with_veto: Decimal,
+    pub participation: Decimal,
+}
+
+#[cw_serde]
+pub struct ParticipationResponse {
+    pub proposals: Vec<u64>,
+    pub validators: Vec<ValidatorParticipation>,
+}
+
 pub const PROPOSALS_REPLY_ID: u64 = 1;
 
 pub const QUERY_ID: Item<u64> = Item::new("query_id");
@@ -38,4 +56,12 @@ pub const QUERY_ID: Item<u64> = Item::new("query_id");
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const ACTIVE_PROPOSALS: Item<Vec<u64>> = Item::new("active_proposals");
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
-pub const PROPOSALS_VOTES: Map<u64, Vec<ProposalVote>> = Map::new("proposals_votes");
+// legacy storage of the latest votes snapshot per proposal, moved to VOTES on migration
+pub const PROPOSALS_VOTES: Map<
+    u64,
+    Vec<neutron_sdk::interchain_queries::v045::types::ProposalVote>,
+> = Map::new("proposals_votes");
+// one record per (proposal, validator), empty options mean the validator didn't vote
+pub const VOTES: Map<(u64, &str), Vec<WeightedVoteOption>> = Map::new("votes");
+// finished proposals already reported to the validators set, value is the spam flag
+pub const FINISHED_PROPOSALS: Map<u64, bool> = Map::new("finished_proposals");
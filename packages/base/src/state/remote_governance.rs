@@ -0,0 +1,90 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_storage_plus::{Item, Map};
+use drop_puppeteer_base::peripheral_hook::{VoteOption, WeightedVoteOption};
+use optfield::optfield;
+
+#[cw_serde]
+pub struct CommitteeMember {
+    pub address: String,
+    pub power: Uint128,
+}
+
+#[cw_serde]
+pub enum VoterSet {
+    /// dAsset holders vote with the dAsset they lock until the voting ends
+    Holders {},
+    /// only the listed members vote, each with a fixed power
+    Committee { members: Vec<CommitteeMember> },
+}
+
+#[optfield(pub ConfigOptional, attrs)]
+#[cw_serde]
+pub struct Config {
+    pub puppeteer_address: Addr,
+    pub provider_proposals_address: Addr,
+    pub dasset_denom: String,
+    pub voter_set: VoterSet,
+    /// seconds before the end of the voting period when the aggregated vote may be sent
+    pub submission_window: u64,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Tally {
+    pub yes: Uint128,
+    pub abstain: Uint128,
+    pub no: Uint128,
+    pub no_with_veto: Uint128,
+}
+
+impl Tally {
+    pub fn get_mut(&mut self, option: VoteOption) -> &mut Uint128 {
+        match option {
+            VoteOption::Yes => &mut self.yes,
+            VoteOption::Abstain => &mut self.abstain,
+            VoteOption::No => &mut self.no,
+            VoteOption::NoWithVeto => &mut self.no_with_veto,
+        }
+    }
+
+    pub fn options(&self) -> Vec<(VoteOption, Uint128)> {
+        vec![
+            (VoteOption::Yes, self.yes),
+            (VoteOption::Abstain, self.abstain),
+            (VoteOption::No, self.no),
+            (VoteOption::NoWithVeto, self.no_with_veto),
+        ]
+    }
+}
+
+#[cw_serde]
+pub struct Ballot {
+    pub power: Uint128,
+    pub options: Vec<WeightedVoteOption>,
+    /// dAsset locked with the ballot, returned as is whatever the config is on unlock
+    pub locked: Option<Coin>,
+}
+
+#[cw_serde]
+pub enum SubmissionStatus {
+    InProgress,
+    /// may be submitted again to reflect votes cast after the submission
+    Submitted,
+    /// may be submitted again until the failed submissions limit is reached
+    Failed,
+}
+
+#[cw_serde]
+pub struct ProposalVoting {
+    pub voting_end_time: u64,
+    pub tally: Tally,
+    pub submission_status: Option<SubmissionStatus>,
+    pub submitted_options: Vec<WeightedVoteOption>,
+    pub failed_submissions: u32,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PROPOSALS: Map<u64, ProposalVoting> = Map::new("proposals");
+// (proposal_id, voter) → ballot
+pub const BALLOTS: Map<(u64, &str), Ballot> = Map::new("ballots");
@@ -7,6 +7,7 @@ pub struct ConfigOptional {
     pub stats_contract: Option<String>,
     pub provider_proposals_contract: Option<String>,
     pub val_ref_contract: Option<String>,
+    pub puppeteer_contract: Option<String>,
 }
 
 #[cw_serde]
@@ -14,6 +15,7 @@ pub struct Config {
     pub stats_contract: Addr,
     pub provider_proposals_contract: Option<Addr>,
     pub val_ref_contract: Option<Addr>,
+    pub puppeteer_contract: Option<Addr>, // source of delegations to release draining validators
 }
 
 #[cw_serde]
@@ -55,9 +57,55 @@ pub struct ValidatorInfoDeprecated {
     pub total_voted_proposals: u64,
 }
 
+#[cw_serde]
+pub struct ScoringPolicy {
+    pub base_weight: u64, // weight of a validator with the perfect score
+    pub min_uptime: Decimal,
+    pub uptime_coefficient: Decimal,
+    pub commission_coefficient: Decimal,
+    pub commission_max_age: u64, // blocks since the commission was last seen in range
+    pub voting_coefficient: Decimal,
+    pub min_voting_participation: Decimal,
+    pub jail_penalty: Decimal, // score share lost per jail
+    pub max_jailed_number: u64,
+}
+
+#[cw_serde]
+pub struct WeightRamp {
+    pub from_weight: u64,
+    pub to_weight: u64,
+    pub start: u64,    // seconds
+    pub duration: u64, // seconds
+}
+
+impl WeightRamp {
+    /// Weight interpolated at `now`, `None` once the ramp is over
+    pub fn weight_at(&self, now: u64) -> Option<u64> {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed >= self.duration {
+            return None;
+        }
+        let progress = |delta: u64| {
+            (u128::from(delta) * u128::from(elapsed) / u128::from(self.duration)) as u64
+        };
+        Some(if self.to_weight >= self.from_weight {
+            self.from_weight + progress(self.to_weight - self.from_weight)
+        } else {
+            self.from_weight - progress(self.from_weight - self.to_weight)
+        })
+    }
+}
+
 pub const CONFIG: Item<Config> = Item::new("config_v2");
+pub const SCORING_POLICY: Item<ScoringPolicy> = Item::new("scoring_policy");
 pub const VALIDATORS_SET: Map<&str, ValidatorInfo> = Map::new("validators_set_v2");
 pub const VALIDATORS_LIST_CACHE: Item<Vec<ValidatorInfo>> = Item::new("validators_list_v2");
+pub const WEIGHT_RAMPS: Map<&str, WeightRamp> = Map::new("weight_ramps");
+// removed validators stay in the set with zero weight until their delegation is moved away,
+// value is the removal time
+pub const DRAINING_VALIDATORS: Map<&str, u64> = Map::new("draining_validators");
+// proposals already counted in validators voting stats
+pub const VOTING_PROPOSALS: Map<u64, bool> = Map::new("voting_proposals");
 
 pub const CONFIG_DEPRECATED: Item<ConfigDeprecated> = Item::new("config");
 pub const VALIDATORS_SET_DEPRECATED: Map<String, ValidatorInfoDeprecated> =
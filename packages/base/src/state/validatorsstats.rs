@@ -13,6 +13,20 @@ pub struct Config {
     pub owner: Addr,
 }
 
+#[cw_serde]
+pub struct PushConfig {
+    pub validators_set_contract: Addr,
+    pub min_interval: u64, // seconds between pushes of validators info
+}
+
+#[cw_serde]
+pub struct StatsParams {
+    pub min_commission: Decimal,
+    pub max_commission: Decimal,
+    pub uptime_window: u64, // remote blocks the uptime is calculated over
+    pub missed_blocks_retention: u64, // max missed blocks records kept per validator
+}
+
 #[cw_serde]
 pub struct KVQueryIds {
     pub signing_info_id: Option<String>,
@@ -39,24 +53,34 @@ pub struct State {
 }
 
 #[cw_serde]
-pub struct ValidatorMissedBlocksForPeriod {
-    pub address: String,
+pub struct MissedBlocks {
+    pub remote_height: u64,
+    pub timestamp: u64,
     pub missed_blocks: u64,
 }
 
+// bounds of the per-validator missed blocks ring buffer, `start` is the oldest record
 #[cw_serde]
-pub struct MissedBlocks {
-    pub remote_height: u64,
-    pub timestamp: u64,
-    pub validators: Vec<ValidatorMissedBlocksForPeriod>,
+#[derive(Default)]
+pub struct MissedBlocksCursor {
+    pub start: u64,
+    pub end: u64,
 }
 
 pub const VALIDATOR_PROFILE_REPLY_ID: u64 = 1;
 pub const SIGNING_INFO_REPLY_ID: u64 = 2;
+pub const PUSH_VALIDATORS_INFO_REPLY_ID: u64 = 3;
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const MISSED_BLOCKS: Item<Vec<MissedBlocks>> = Item::new("missed_blocks");
+pub const STATS_PARAMS: Item<StatsParams> = Item::new("stats_params");
+pub const MISSED_BLOCKS: Map<(&str, u64), MissedBlocks> = Map::new("validator_missed_blocks");
+pub const MISSED_BLOCKS_CURSOR: Map<&str, MissedBlocksCursor> =
+    Map::new("validator_missed_blocks_cursor");
 pub const STATE_MAP: Map<String, ValidatorState> = Map::new("state_map");
 pub const VALIDATOR_PROFILE_QUERY_ID: Item<u64> = Item::new("validator_profile_query_id");
 pub const SIGNING_INFO_QUERY_ID: Item<u64> = Item::new("signin_info_query_id");
 pub const VALCONS_TO_VALOPER: Map<String, String> = Map::new("valcons_to_valoper");
+pub const PUSH_CONFIG: Item<PushConfig> = Item::new("push_config");
+pub const LAST_PUSH: Item<u64> = Item::new("last_push");
+// highest remote height of the ICQ results processed so far
+pub const LAST_REMOTE_HEIGHT: Item<u64> = Item::new("last_remote_height");
@@ -1,6 +1,6 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
@@ -11,3 +11,20 @@ pub struct Config {
 pub type Cw721ReceiveMsg = cw721::Cw721ReceiveMsg;
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MarketConfig {
+    // annual rate used to discount the expected payout of not yet released vouchers
+    pub discount_rate: Decimal,
+}
+
+pub const MARKET_CONFIG: Item<MarketConfig> = Item::new("market_config");
+
+#[cw_serde]
+pub struct VoucherListing {
+    pub seller: Addr,
+    pub price: Uint128,
+}
+
+pub const VOUCHER_LISTINGS: Map<&str, VoucherListing> = Map::new("voucher_listings");
@@ -27,6 +27,9 @@ pub enum ContractError {
     #[error("ICA is already registered")]
     IcaAlreadyRegistered {},
 
+    #[error("ICA is not timed out")]
+    IcaNotTimedOut {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -36,6 +39,9 @@ pub enum ContractError {
     #[error("Invalid remote denom")]
     InvalidRemoteDenom,
 
+    #[error("Queued transaction {id} not found")]
+    QueuedTxNotFound { id: u64 },
+
     #[error("{0}")]
     OwnershipError(#[from] OwnershipError),
 
@@ -1,17 +1,17 @@
 use crate::{
     error::{ContractError, ContractResult},
     msg::ExecuteMsg,
-    peripheral_hook::Transaction,
+    peripheral_hook::{ReceiverExecuteMsg, ResponseHookErrorMsg, ResponseHookMsg, Transaction},
     r#trait::PuppeteerReconstruct,
-    state::{BaseConfig, PuppeteerBase, TxState, TxStateStatus, ICA_ID, LOCAL_DENOM},
+    state::{BaseConfig, PuppeteerBase, QueuedTx, TxState, TxStateStatus, ICA_ID, LOCAL_DENOM},
 };
 use cosmwasm_std::{
-    attr, ensure_eq, CosmosMsg, CustomQuery, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, SubMsg,
+    attr, ensure, ensure_eq, to_json_binary, BankMsg, Coin, CosmosMsg, CustomQuery, Deps, DepsMut,
+    Env, MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
-use drop_helpers::answer::response;
+use drop_helpers::{answer::response, ica::IcaState};
 use neutron_sdk::{
-    bindings::{msg::NeutronMsg, query::NeutronQuery},
+    bindings::{msg::NeutronMsg, query::NeutronQuery, types::ProtobufAny},
     interchain_queries::v045::new_register_transfers_query_msg,
     NeutronError, NeutronResult,
 };
@@ -44,6 +44,9 @@ where
     ) -> ContractResult<Response<NeutronMsg>> {
         match msg {
             ExecuteMsg::RegisterICA {} => self.execute_register_ica(deps, info),
+            ExecuteMsg::RecoverICA { register_fee } => {
+                self.execute_recover_ica(deps, info, register_fee)
+            }
             ExecuteMsg::RegisterQuery {} => self.register_transfers_query(deps),
         }
     }
@@ -104,6 +107,184 @@ where
         Ok(SubMsg::reply_on_success(msg, payload_id))
     }
 
+    pub fn enqueue_tx(
+        &self,
+        storage: &mut dyn Storage,
+        msgs: Vec<ProtobufAny>,
+        transaction: Transaction,
+        reply_to: String,
+        reply_id: u64,
+    ) -> StdResult<u64> {
+        let id = self.tx_queue_next_id.may_load(storage)?.unwrap_or_default();
+        self.tx_queue.save(
+            storage,
+            id,
+            &QueuedTx {
+                id,
+                msgs,
+                transaction,
+                reply_to,
+                reply_id,
+            },
+        )?;
+        self.tx_queue_next_id.save(storage, &(id + 1))?;
+        Ok(id)
+    }
+
+    /// Returns the oldest queued transaction if there is no transaction in flight.
+    pub fn peek_queued_tx(&self, storage: &dyn Storage) -> StdResult<Option<QueuedTx>> {
+        let tx_state = self.tx_state.may_load(storage)?.unwrap_or_default();
+        if tx_state.status != TxStateStatus::Idle {
+            return Ok(None);
+        }
+        self.tx_queue
+            .range(storage, None, None, Order::Ascending)
+            .next()
+            .transpose()
+            .map(|next| next.map(|(_, tx)| tx))
+    }
+
+    /// Takes the oldest queued transaction if there is no transaction in flight.
+    pub fn pop_queued_tx(&self, storage: &mut dyn Storage) -> StdResult<Option<QueuedTx>> {
+        let next = self.peek_queued_tx(storage)?;
+        if let Some(tx) = &next {
+            self.tx_queue.remove(storage, tx.id);
+        }
+        Ok(next)
+    }
+
+    /// Removes a queued transaction. Only the owner or the contract the
+    /// transaction replies to is allowed to cancel it. When the owner cancels
+    /// it, the reply_to contract gets an error hook so it does not wait forever.
+    pub fn cancel_queued_tx<X>(
+        &self,
+        deps: DepsMut<NeutronQuery>,
+        info: MessageInfo,
+        id: u64,
+    ) -> ContractResult<Response<X>> {
+        let queued_tx = self
+            .tx_queue
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::QueuedTxNotFound { id })?;
+        ensure!(
+            info.sender == queued_tx.reply_to || cw_ownable::is_owner(deps.storage, &info.sender)?,
+            ContractError::Unauthorized {}
+        );
+        self.tx_queue.remove(deps.storage, id);
+        let mut msgs = vec![];
+        if !queued_tx.reply_to.is_empty() && info.sender != queued_tx.reply_to {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: queued_tx.reply_to.clone(),
+                msg: to_json_binary(&ReceiverExecuteMsg::PeripheralHook(ResponseHookMsg::Error(
+                    ResponseHookErrorMsg {
+                        transaction: queued_tx.transaction,
+                        details: "Cancelled".to_string(),
+                    },
+                )))?,
+                funds: vec![],
+            }));
+        }
+        Ok(response(
+            "cancel-queued",
+            "puppeteer-base",
+            vec![
+                attr("action", "cancel_queued"),
+                attr("id", id.to_string()),
+                attr("reply_to", queued_tx.reply_to),
+            ],
+        )
+        .add_messages(msgs))
+    }
+
+    /// Returns the ICA address. While the ICA is being recovered after a timeout
+    /// the previously known address is returned, as recovery keeps it unchanged.
+    pub fn get_ica_address(&self, storage: &dyn Storage) -> StdResult<String> {
+        match self.ica.load(storage)? {
+            IcaState::Timeout | IcaState::InProgress => {
+                match self
+                    .ica_health
+                    .may_load(storage)?
+                    .and_then(|h| h.ica_address)
+                {
+                    Some(ica_address) => Ok(ica_address),
+                    None => self.ica.get_address(storage),
+                }
+            }
+            _ => self.ica.get_address(storage),
+        }
+    }
+
+    /// Marks the ICA as timed out and remembers its address so it can be
+    /// checked once the account is registered again.
+    pub fn set_ica_timeout(&self, storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+        let mut ica_health = self.ica_health.may_load(storage)?.unwrap_or_default();
+        if let IcaState::Registered { ica_address, .. } = self.ica.load(storage)? {
+            ica_health.ica_address = Some(ica_address);
+        }
+        ica_health.timeout_count += 1;
+        ica_health.last_timeout_height = Some(height);
+        self.ica_health.save(storage, &ica_health)?;
+        self.ica.set_timeout(storage)
+    }
+
+    /// Re-registers a timed out ICA. Anyone may pay the registration fee,
+    /// everything attached above it is sent back to the caller.
+    fn execute_recover_ica(
+        &self,
+        deps: DepsMut<NeutronQuery>,
+        info: MessageInfo,
+        register_fee: Uint128,
+    ) -> ContractResult<Response<NeutronMsg>> {
+        ensure!(
+            self.ica.load(deps.storage)? == IcaState::Timeout,
+            ContractError::IcaNotTimedOut {}
+        );
+        let paid = info
+            .funds
+            .iter()
+            .find(|f| f.denom == LOCAL_DENOM)
+            .map(|f| f.amount)
+            .unwrap_or_default();
+        ensure!(
+            !register_fee.is_zero() && paid >= register_fee,
+            ContractError::InvalidFunds {
+                reason: format!("register fee {}{} is not paid", register_fee, LOCAL_DENOM)
+            }
+        );
+        let config = self.config.load(deps.storage)?;
+        let attrs = vec![
+            attr("connection_id", config.connection_id()),
+            attr("ica_id", ICA_ID),
+            attr("register_fee", format!("{}{}", register_fee, LOCAL_DENOM)),
+        ];
+        let register_msg = self.ica.register(
+            deps.storage,
+            config.connection_id(),
+            ICA_ID,
+            Coin::new(register_fee.u128(), LOCAL_DENOM),
+        )?;
+        let refund: Vec<Coin> = info
+            .funds
+            .into_iter()
+            .map(|mut f| {
+                if f.denom == LOCAL_DENOM {
+                    f.amount -= register_fee;
+                }
+                f
+            })
+            .filter(|f| !f.amount.is_zero())
+            .collect();
+        let mut response =
+            response("recover-ica", "puppeteer-base", attrs).add_message(register_msg);
+        if !refund.is_empty() {
+            response = response.add_message(BankMsg::Send {
+                to_address: info.sender.into_string(),
+                amount: refund,
+            });
+        }
+        Ok(response)
+    }
+
     fn execute_register_ica(
         &self,
         deps: DepsMut<NeutronQuery>,
@@ -6,6 +6,11 @@ use schemars::JsonSchema;
 pub enum ExecuteMsg {
     RegisterICA {},
     RegisterQuery {},
+    /// Registers the ICA again after a channel timeout. Anyone may call it,
+    /// untrn attached above the `register_fee` is refunded.
+    RecoverICA {
+        register_fee: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -39,6 +44,10 @@ where
     Extension { msg: E },
     #[returns(crate::state::TxState)]
     TxState {},
+    #[returns(Vec<crate::state::QueuedTx>)]
+    TxQueue {},
+    #[returns(crate::state::IcaHealth)]
+    IcaHealth {},
 }
 
 #[cw_serde]
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 
 use crate::{
     msg::TransferReadyBatchesMsg,
@@ -73,6 +73,17 @@ pub enum Transaction {
         validator: String,
         denom: String,
         amount: u128,
+        /// the minted LSM share, known once the host chain acked the transaction
+        share: Option<cosmwasm_std::Coin>,
+        /// id of the caller's request, echoed back as is
+        request_id: Option<u64>,
+    },
+    TransferShare {
+        interchain_account_id: String,
+        share: cosmwasm_std::Coin,
+        channel_id: String,
+        receiver: String,
+        request_id: Option<u64>,
     },
     RedeemShares {
         items: Vec<RedeemShareItem>,
@@ -101,6 +112,26 @@ pub enum Transaction {
         interchain_account_id: String,
         rewards_withdraw_address: String,
     },
+    VoteWeighted {
+        interchain_account_id: String,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+}
+
+#[cw_serde]
+#[derive(Copy, Eq, PartialOrd, Ord)]
+pub enum VoteOption {
+    Yes,
+    Abstain,
+    No,
+    NoWithVeto,
+}
+
+#[cw_serde]
+pub struct WeightedVoteOption {
+    pub option: VoteOption,
+    pub weight: Decimal,
 }
 
 #[cw_serde]
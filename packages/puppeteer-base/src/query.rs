@@ -1,4 +1,4 @@
-use cosmwasm_std::{to_json_binary, Binary, Deps, Env, StdError};
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Order, StdError, StdResult};
 use neutron_sdk::bindings::query::NeutronQuery;
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
@@ -7,7 +7,7 @@ use crate::{
     error::{ContractError, ContractResult},
     msg::QueryMsg,
     r#trait::PuppeteerReconstruct,
-    state::{BaseConfig, PuppeteerBase, Transfer},
+    state::{BaseConfig, PuppeteerBase, QueuedTx, Transfer},
 };
 
 impl<'a, T, U, Z> PuppeteerBase<'a, T, U, Z>
@@ -26,6 +26,8 @@ where
             QueryMsg::Config {} => self.query_config(deps),
             QueryMsg::Ica {} => self.query_ica(deps),
             QueryMsg::TxState {} => self.query_tx_state(deps),
+            QueryMsg::TxQueue {} => self.query_tx_queue(deps),
+            QueryMsg::IcaHealth {} => self.query_ica_health(deps),
             QueryMsg::Transactions {} => self.query_transactions(deps),
             QueryMsg::Extension { msg } => Err(ContractError::Std(StdError::generic_err(format!(
                 "Unsupported query message: {:?}",
@@ -57,4 +59,18 @@ where
         let tx_state = self.tx_state.may_load(deps.storage)?.unwrap_or_default();
         Ok(to_json_binary(&tx_state)?)
     }
+
+    fn query_ica_health(&self, deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+        let ica_health = self.ica_health.may_load(deps.storage)?.unwrap_or_default();
+        Ok(to_json_binary(&ica_health)?)
+    }
+
+    fn query_tx_queue(&self, deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+        let tx_queue = self
+            .tx_queue
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| res.map(|(_, tx)| tx))
+            .collect::<StdResult<Vec<QueuedTx>>>()?;
+        Ok(to_json_binary(&tx_queue)?)
+    }
 }
@@ -2,7 +2,7 @@ use crate::{
     r#trait::PuppeteerReconstruct,
     state::{BaseConfig, PuppeteerBase, TxState, TxStateStatus},
 };
-use cosmwasm_std::{attr, DepsMut, Reply, Response, StdError, StdResult};
+use cosmwasm_std::{attr, DepsMut, Order, Reply, Response, StdError, StdResult, SubMsgResult};
 use drop_helpers::{answer::response, query_id::get_query_id};
 use neutron_sdk::bindings::msg::{MsgIbcTransferResponse, MsgSubmitTxResponse};
 use serde::{de::DeserializeOwned, Serialize};
@@ -100,6 +100,30 @@ where
         Ok(response("sudo-tx-payload-received", "puppeteer-base", atts))
     }
 
+    /// Handles the submission of a queued transaction sent along with an ack.
+    /// The transaction leaves the queue only once it is submitted, a failed
+    /// submission keeps it queued for the next call instead of failing the ack.
+    pub fn submit_queued_tx_reply(&self, deps: DepsMut, msg: Reply) -> StdResult<Response> {
+        if let SubMsgResult::Err(details) = msg.result {
+            self.tx_state.save(deps.storage, &TxState::default())?;
+            self.in_flight_tx.remove(deps.storage);
+            return Ok(response(
+                "submit-queued-failed",
+                "puppeteer-base",
+                vec![attr("details", details)],
+            ));
+        }
+        let submitted = self
+            .tx_queue
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        if let Some(id) = submitted {
+            self.tx_queue.remove(deps.storage, id);
+        }
+        self.submit_tx_reply(deps, msg)
+    }
+
     pub fn submit_ibc_transfer_reply(&self, deps: DepsMut, msg: Reply) -> StdResult<Response> {
         let resp: MsgIbcTransferResponse = serde_json_wasm::from_slice(
             msg.result
@@ -2,7 +2,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Timestamp, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
 use drop_helpers::ica::Ica;
-use neutron_sdk::interchain_queries::v045::types::UnbondingEntry;
+use neutron_sdk::{bindings::types::ProtobufAny, interchain_queries::v045::types::UnbondingEntry};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{peripheral_hook::Transaction, r#trait::PuppeteerReconstruct};
@@ -18,6 +18,11 @@ where
     pub recipient_transfers: Item<'a, Vec<Transfer>>,
     pub transfer_channel_id: Item<'a, String>,
     pub tx_state: Item<'a, TxState>,
+    pub tx_queue: Map<'a, u64, QueuedTx>,
+    pub tx_queue_next_id: Item<'a, u64>,
+    // the queued transaction waiting for its ack, put back into the queue on timeout
+    pub in_flight_tx: Item<'a, QueuedTx>,
+    pub ica_health: Item<'a, IcaHealth>,
     pub kv_queries: Map<'a, u64, U>,
     pub last_complete_delegations_and_balances_key: Item<'a, u64>,
     pub delegations_and_balances: Map<'a, &'a u64, BalancesAndDelegationsState<Z>>,
@@ -50,6 +55,10 @@ where
             ica: Ica::new("ica"),
             recipient_transfers: Item::new("transfers"),
             tx_state: Item::new("sudo_payload"),
+            tx_queue: Map::new("tx_queue"),
+            tx_queue_next_id: Item::new("tx_queue_next_id"),
+            in_flight_tx: Item::new("in_flight_tx"),
+            ica_health: Item::new("ica_health"),
             transfer_channel_id: Item::new("transfer_channel_id"),
             kv_queries: Map::new("kv_queries"),
             last_complete_delegations_and_balances_key: Item::new(
@@ -133,6 +142,24 @@ pub struct TxState {
     pub reply_to: Option<String>,
 }
 
+#[cw_serde]
+#[derive(Default)]
+pub struct IcaHealth {
+    pub ica_address: Option<String>,
+    pub timeout_count: u64,
+    pub last_timeout_height: Option<u64>,
+    pub last_recovery_height: Option<u64>,
+}
+
+#[cw_serde]
+pub struct QueuedTx {
+    pub id: u64,
+    pub msgs: Vec<ProtobufAny>,
+    pub transaction: Transaction,
+    pub reply_to: String,
+    pub reply_id: u64,
+}
+
 #[cw_serde]
 pub struct UnbondingDelegation {
     pub validator_address: String,
@@ -175,6 +202,7 @@ pub mod reply_msg {
     pub const KV_UNBONDING_DELEGATIONS_LOWER_BOUND: u64 = 5 << OFFSET;
     pub const KV_UNBONDING_DELEGATIONS_UPPER_BOUND: u64 =
         KV_UNBONDING_DELEGATIONS_LOWER_BOUND + u16::MAX as u64;
+    pub const SUBMIT_QUEUED: u64 = 6 << OFFSET;
 
     #[cosmwasm_schema::cw_serde]
     pub enum ReplyMsg {
@@ -183,6 +211,7 @@ pub mod reply_msg {
         KvDelegationsAndBalance { i: u16 },
         KvNonNativeRewardsBalances,
         KvUnbondingDelegations { validator_index: u16 },
+        SubmitQueued,
     }
 
     impl ReplyMsg {
@@ -197,6 +226,7 @@ pub mod reply_msg {
                 ReplyMsg::KvUnbondingDelegations { validator_index } => {
                     KV_UNBONDING_DELEGATIONS_LOWER_BOUND | *validator_index as u64
                 }
+                ReplyMsg::SubmitQueued => SUBMIT_QUEUED,
             }
         }
 
@@ -205,6 +235,7 @@ pub mod reply_msg {
                 SUDO_PAYLOAD => Self::SudoPayload,
                 IBC_TRANSFER => Self::IbcTransfer,
                 KV_NON_NATIVE_REWARDS_BALANCES => Self::KvNonNativeRewardsBalances,
+                SUBMIT_QUEUED => Self::SubmitQueued,
                 i @ KV_DELEGATIONS_AND_BALANCE_LOWER_BOUND
                     ..=KV_DELEGATIONS_AND_BALANCE_UPPER_BOUND => {
                     Self::KvDelegationsAndBalance { i: i as u16 }
@@ -226,12 +257,17 @@ pub mod reply_msg {
         fn enum_variant_from_reply_id() {
             assert_eq!(ReplyMsg::from_reply_id(SUDO_PAYLOAD), ReplyMsg::SudoPayload);
             assert_eq!(ReplyMsg::from_reply_id(IBC_TRANSFER), ReplyMsg::IbcTransfer);
+            assert_eq!(
+                ReplyMsg::from_reply_id(SUBMIT_QUEUED),
+                ReplyMsg::SubmitQueued
+            );
         }
 
         #[test]
         fn enum_variant_to_reply_id() {
             assert_eq!(ReplyMsg::SudoPayload.to_reply_id(), SUDO_PAYLOAD);
             assert_eq!(ReplyMsg::IbcTransfer.to_reply_id(), IBC_TRANSFER);
+            assert_eq!(ReplyMsg::SubmitQueued.to_reply_id(), SUBMIT_QUEUED);
         }
 
         mod kv_unbonding_delegations_from_reply_id {
@@ -163,7 +163,7 @@ where
     pub fn sudo_open_ack(
         &self,
         deps: DepsMut<NeutronQuery>,
-        _env: Env,
+        env: Env,
         port_id: String,
         channel_id: String,
         _counterparty_channel_id: String,
@@ -172,6 +172,20 @@ where
         let parsed_version: Result<OpenAckVersion, _> =
             serde_json_wasm::from_str(counterparty_version.as_str());
         if let Ok(parsed_version) = parsed_version {
+            let mut ica_health = self.ica_health.may_load(deps.storage)?.unwrap_or_default();
+            if let Some(expected) = ica_health.ica_address.clone() {
+                if expected != parsed_version.address {
+                    return Err(NeutronError::Std(StdError::generic_err(format!(
+                        "recovered ICA address {} does not match the stored one {}",
+                        parsed_version.address, expected
+                    ))));
+                }
+            }
+            if ica_health.last_timeout_height > ica_health.last_recovery_height {
+                ica_health.last_recovery_height = Some(env.block.height);
+            }
+            ica_health.ica_address = Some(parsed_version.address.clone());
+            self.ica_health.save(deps.storage, &ica_health)?;
             self.ica
                 .set_address(deps.storage, parsed_version.address, port_id, channel_id)?;
             Ok(Response::default())